@@ -0,0 +1,21 @@
+-- 玩家账号：密码凭据与会话令牌。
+-- 密码使用 pgcrypto 的 bcrypt（crypt + gen_salt('bf')）散列，令牌只保存 blake3 摘要。
+ALTER TABLE players ADD COLUMN IF NOT EXISTS is_guest BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE TABLE IF NOT EXISTS player_credentials (
+    player_id UUID PRIMARY KEY REFERENCES players(id) ON DELETE CASCADE,
+    password_hash TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS player_auth_tokens (
+    token_hash CHAR(64) PRIMARY KEY,
+    player_id UUID NOT NULL REFERENCES players(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS player_auth_tokens_player_id_idx ON player_auth_tokens(player_id);
+CREATE INDEX IF NOT EXISTS player_auth_tokens_expires_at_idx ON player_auth_tokens(expires_at);
@@ -0,0 +1,37 @@
+-- 排行榜：会话记录通关关卡与用时，视图取每位玩家每个角色的最好成绩。
+ALTER TABLE game_sessions ADD COLUMN IF NOT EXISTS cleared_level VARCHAR(32);
+ALTER TABLE game_sessions ADD COLUMN IF NOT EXISTS clear_time REAL;
+
+CREATE INDEX IF NOT EXISTS game_sessions_score_idx
+    ON game_sessions(character_type, score DESC)
+    WHERE end_time IS NOT NULL;
+CREATE INDEX IF NOT EXISTS game_sessions_clear_time_idx
+    ON game_sessions(cleared_level, character_type, clear_time)
+    WHERE clear_time IS NOT NULL;
+
+-- 每位玩家、每个角色的最高分
+CREATE OR REPLACE VIEW leaderboard_best_scores AS
+SELECT DISTINCT ON (s.player_id, s.character_type)
+    s.player_id,
+    p.username,
+    s.character_type,
+    s.score::DOUBLE PRECISION AS value,
+    s.end_time AS achieved_at
+FROM game_sessions s
+JOIN players p ON p.id = s.player_id
+WHERE s.end_time IS NOT NULL AND s.score > 0
+ORDER BY s.player_id, s.character_type, s.score DESC, s.end_time;
+
+-- 每位玩家、每个角色、每个关卡的最快通关
+CREATE OR REPLACE VIEW leaderboard_fastest_clears AS
+SELECT DISTINCT ON (s.player_id, s.character_type, s.cleared_level)
+    s.player_id,
+    p.username,
+    s.character_type,
+    s.cleared_level,
+    s.clear_time::DOUBLE PRECISION AS value,
+    s.start_time AS achieved_at
+FROM game_sessions s
+JOIN players p ON p.id = s.player_id
+WHERE s.clear_time IS NOT NULL
+ORDER BY s.player_id, s.character_type, s.cleared_level, s.clear_time, s.start_time;
@@ -0,0 +1,7 @@
+-- 账号登记的存档签名密钥（trust on first use，每个账号只保存第一次登记的密钥）。
+-- 服务器需要用同一把对称密钥重算 MAC 才能校验上传的存档，因此保存密钥本身（十六进制）。
+CREATE TABLE IF NOT EXISTS player_save_keys (
+    player_id UUID PRIMARY KEY REFERENCES players(id) ON DELETE CASCADE,
+    signing_key CHAR(64) NOT NULL,
+    registered_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
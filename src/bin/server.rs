@@ -1,49 +1,507 @@
 use bevy::app::ScheduleRunnerPlugin;
 use bevy::prelude::*;
-use emiyashiro::plugins::server::{NetworkChannels, ServerRuntimePlugin};
-use emiyashiro::protocol::{GamePacket, PlayerAction};
+use emiyashiro::plugins::server::{ConnectionEvent, NetworkChannels, ServerRuntimePlugin};
+use emiyashiro::protocol::{
+    GamePacket, LeaderboardPage, LeaderboardQuery, LobbyEvent, PlayerAction, sanitize_chat_text,
+};
+use emiyashiro::resources::GameplayTuning;
+use emiyashiro::systems::accounts::{
+    AUTH_REQUESTS_PER_CONNECTION, AccountError, AccountIdentity, AuthRateLimiter, AuthRequest,
+    AuthenticatedAccount, session_token_from_handshake,
+};
+use emiyashiro::systems::admin::{AdminQueries, ConnectionRegistry, DEFAULT_ADMIN_ADDRESS};
+use emiyashiro::systems::admin_http::{AdminState, run_admin_server};
+use emiyashiro::systems::metrics::MetricsRegistry;
+use emiyashiro::systems::save_signing::{SaveKeyRegistry, SaveSigningKey, verify_upload};
+use emiyashiro::systems::session_recording::SessionRecorder;
 use futures_util::{SinkExt, StreamExt};
 use std::collections::HashMap;
 use std::error::Error;
+use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::mpsc;
-use tokio_tungstenite::accept_async;
+use tokio_tungstenite::accept_hdr_async;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
 
 type WsMessage = tokio_tungstenite::tungstenite::Message;
 type ClientMessageSender = mpsc::UnboundedSender<WsMessage>;
 type ClientSenderMap = HashMap<u64, ClientMessageSender>;
 type SharedClients = Arc<Mutex<ClientSenderMap>>;
+type ConnectionEventSender = mpsc::UnboundedSender<ConnectionEvent>;
+
+const SERVER_ADDRESS: &str = "127.0.0.1:8080";
+/// WebSocket ping interval used to measure per-connection RTT
+const RTT_PROBE_INTERVAL: Duration = Duration::from_secs(5);
+
+const LEADERBOARD_UNAVAILABLE: &str = "Leaderboards are unavailable on this server";
+const ONLINE_SAVES_UNAVAILABLE: &str = "Online saves are unavailable on this server";
+#[cfg(feature = "server")]
+const SAVE_KEYS_UNAVAILABLE: &str = "Save verification is temporarily unavailable";
+/// Largest accepted save upload (uncompressed JSON)
+const MAX_UPLOADED_SAVE_BYTES: usize = 256 * 1024;
+/// Longest kept save name (stored with a prefix in a `VARCHAR(100)` column)
+const MAX_UPLOADED_SAVE_NAME_CHARS: usize = 64;
+/// Keeps uploaded copies apart from the server-built online autosave
+#[cfg(feature = "server")]
+const UPLOADED_SAVE_PREFIX: &str = "upload:";
+
+/// Accounts, leaderboards and cluster presence shared by all connection tasks
+/// (database/Redis-backed with the `server` feature).
+#[derive(Clone, Default)]
+struct OnlineServices {
+    server_id: String,
+    /// Fan-out to this server's clients (same channel as ECS broadcasts)
+    packets: Option<mpsc::UnboundedSender<GamePacket>>,
+    /// Live connections as seen by the admin API
+    connections: ConnectionRegistry,
+    /// Per-IP throttle for login, registration and guest creation
+    auth_limiter: AuthRateLimiter,
+    /// Save-signing keys registered by accounts (a cache of `player_save_keys` when the
+    /// database is available), used to verify uploaded saves
+    save_keys: SaveKeyRegistry,
+    #[cfg(feature = "server")]
+    saves: Option<emiyashiro::systems::save_worker::SavePublisher>,
+    #[cfg(feature = "server")]
+    database: Option<Arc<emiyashiro::database::Database>>,
+    #[cfg(feature = "server")]
+    leaderboard: Option<Arc<emiyashiro::database::leaderboard::LeaderboardService>>,
+    #[cfg(feature = "server")]
+    presence: Option<Arc<emiyashiro::database::presence::PresenceService>>,
+}
+
+impl OnlineServices {
+    /// Delivers a chat/lobby packet to local clients and the rest of the cluster.
+    async fn announce(&self, packet: GamePacket) {
+        if let Some(packets) = &self.packets {
+            let _ = packets.send(packet.clone());
+        }
+        self.publish_cluster(packet).await;
+    }
+
+    /// Connection is now bound to `identity`: mark it online and tell the lobby.
+    async fn account_online(&self, client_id: u64, identity: &AccountIdentity) {
+        self.track_online(client_id, identity).await;
+        self.announce(GamePacket::Lobby(LobbyEvent::PlayerOnline {
+            username: identity.username.clone(),
+            server_id: self.server_id.clone(),
+        }))
+        .await;
+    }
+
+    /// Connection closed or switched accounts.
+    async fn account_offline(&self, identity: &AccountIdentity) {
+        if self.track_offline(identity).await {
+            self.announce(GamePacket::Lobby(LobbyEvent::PlayerOffline {
+                username: identity.username.clone(),
+                server_id: self.server_id.clone(),
+            }))
+            .await;
+        }
+    }
+
+    #[cfg(feature = "server")]
+    async fn publish_cluster(&self, packet: GamePacket) {
+        if let Some(presence) = &self.presence
+            && let Err(error) = presence.publish(packet).await
+        {
+            warn!("Cluster publish failed: {error}");
+        }
+    }
+
+    #[cfg(not(feature = "server"))]
+    async fn publish_cluster(&self, _packet: GamePacket) {}
+
+    #[cfg(feature = "server")]
+    async fn track_online(&self, client_id: u64, identity: &AccountIdentity) {
+        if let Some(presence) = &self.presence
+            && let Err(error) = presence.player_online(client_id, identity).await
+        {
+            warn!(
+                "Failed to record presence for {}: {error}",
+                identity.username
+            );
+        }
+    }
+
+    #[cfg(not(feature = "server"))]
+    async fn track_online(&self, _client_id: u64, _identity: &AccountIdentity) {}
+
+    /// Returns `false` when the player has already come online on another server.
+    #[cfg(feature = "server")]
+    async fn track_offline(&self, identity: &AccountIdentity) -> bool {
+        let Some(presence) = &self.presence else {
+            return true;
+        };
+        presence
+            .player_offline(identity)
+            .await
+            .unwrap_or_else(|error| {
+                warn!(
+                    "Failed to clear presence for {}: {error}",
+                    identity.username
+                );
+                true
+            })
+    }
+
+    #[cfg(not(feature = "server"))]
+    async fn track_offline(&self, _identity: &AccountIdentity) -> bool {
+        true
+    }
+
+    #[cfg(feature = "server")]
+    async fn authenticate(
+        &self,
+        request: AuthRequest,
+    ) -> Result<AuthenticatedAccount, AccountError> {
+        match &self.database {
+            Some(database) => database.authenticate(request).await,
+            None => Err(AccountError::Unavailable),
+        }
+    }
+
+    #[cfg(not(feature = "server"))]
+    async fn authenticate(
+        &self,
+        _request: AuthRequest,
+    ) -> Result<AuthenticatedAccount, AccountError> {
+        Err(AccountError::Unavailable)
+    }
+
+    #[cfg(feature = "server")]
+    async fn leaderboard_page(
+        &self,
+        query: LeaderboardQuery,
+        limit: u16,
+        player_id: Option<uuid::Uuid>,
+    ) -> Result<LeaderboardPage, String> {
+        let Some(leaderboard) = &self.leaderboard else {
+            return Err(LEADERBOARD_UNAVAILABLE.to_string());
+        };
+        leaderboard
+            .page(query, limit, player_id)
+            .await
+            .map_err(|error| {
+                warn!("Leaderboard query failed: {error}");
+                LEADERBOARD_UNAVAILABLE.to_string()
+            })
+    }
+
+    #[cfg(not(feature = "server"))]
+    async fn leaderboard_page(
+        &self,
+        _query: LeaderboardQuery,
+        _limit: u16,
+        _player_id: Option<uuid::Uuid>,
+    ) -> Result<LeaderboardPage, String> {
+        Err(LEADERBOARD_UNAVAILABLE.to_string())
+    }
+
+    /// Registers an account's save key (the first key wins); stored in Postgres when the
+    /// database is available so it survives restarts and is shared across the cluster.
+    #[cfg(feature = "server")]
+    async fn register_save_key(
+        &self,
+        player_id: uuid::Uuid,
+        key: SaveSigningKey,
+    ) -> Result<bool, &'static str> {
+        let Some(database) = &self.database else {
+            return Ok(self.save_keys.register(player_id, key));
+        };
+        match database.register_save_key(player_id, &key).await {
+            Ok(registered) => {
+                if registered {
+                    self.save_keys.register(player_id, key);
+                }
+                Ok(registered)
+            }
+            Err(error) => {
+                warn!("Failed to register save key for player {player_id}: {error}");
+                Err(SAVE_KEYS_UNAVAILABLE)
+            }
+        }
+    }
+
+    #[cfg(not(feature = "server"))]
+    async fn register_save_key(
+        &self,
+        player_id: uuid::Uuid,
+        key: SaveSigningKey,
+    ) -> Result<bool, &'static str> {
+        Ok(self.save_keys.register(player_id, key))
+    }
+
+    /// The account's registered save key; database lookups are cached since keys never change.
+    #[cfg(feature = "server")]
+    async fn save_key(
+        &self,
+        player_id: uuid::Uuid,
+    ) -> Result<Option<SaveSigningKey>, &'static str> {
+        if let Some(key) = self.save_keys.key_for(player_id) {
+            return Ok(Some(key));
+        }
+        let Some(database) = &self.database else {
+            return Ok(None);
+        };
+        match database.save_key(player_id).await {
+            Ok(key) => {
+                if let Some(key) = &key {
+                    self.save_keys.register(player_id, key.clone());
+                }
+                Ok(key)
+            }
+            Err(error) => {
+                warn!("Failed to look up save key for player {player_id}: {error}");
+                Err(SAVE_KEYS_UNAVAILABLE)
+            }
+        }
+    }
+
+    #[cfg(not(feature = "server"))]
+    async fn save_key(
+        &self,
+        player_id: uuid::Uuid,
+    ) -> Result<Option<SaveSigningKey>, &'static str> {
+        Ok(self.save_keys.key_for(player_id))
+    }
+
+    #[cfg(feature = "server")]
+    fn store_uploaded_save(
+        &self,
+        player_id: uuid::Uuid,
+        name: &str,
+        game_data: serde_json::Value,
+    ) -> Result<(), String> {
+        let Some(saves) = &self.saves else {
+            return Err(ONLINE_SAVES_UNAVAILABLE.to_string());
+        };
+        let task = emiyashiro::systems::save_worker::SaveGameTask {
+            player_id,
+            save_name: format!("{UPLOADED_SAVE_PREFIX}{name}"),
+            game_data,
+            saved_at: chrono::Utc::now(),
+        };
+        saves.enqueue(&task).map_err(|error| {
+            warn!("Failed to queue uploaded save for player {player_id}: {error}");
+            ONLINE_SAVES_UNAVAILABLE.to_string()
+        })
+    }
+
+    #[cfg(not(feature = "server"))]
+    fn store_uploaded_save(
+        &self,
+        _player_id: uuid::Uuid,
+        _name: &str,
+        _game_data: serde_json::Value,
+    ) -> Result<(), String> {
+        Err(ONLINE_SAVES_UNAVAILABLE.to_string())
+    }
+}
+
+/// `server dead-letters list|replay [limit]`: inspect or replay dead-lettered saves.
+#[cfg(feature = "server")]
+async fn run_dead_letter_admin(args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+    use emiyashiro::systems::save_worker::{
+        SAVE_DEAD_LETTER_QUEUE, inspect_dead_letters, replay_dead_letters,
+    };
+    const DEAD_LETTER_USAGE: &str = "usage: server dead-letters <list|replay> [limit]";
+    const DEFAULT_DEAD_LETTER_LIMIT: usize = 20;
+
+    let limit = match args.get(1) {
+        Some(limit) => limit.parse::<usize>().map_err(|_| DEAD_LETTER_USAGE)?,
+        None => DEFAULT_DEAD_LETTER_LIMIT,
+    };
+    match args.first().map(String::as_str) {
+        Some("list") => {
+            let entries = inspect_dead_letters(limit).await?;
+            println!(
+                "{} dead-lettered save(s) in {SAVE_DEAD_LETTER_QUEUE}",
+                entries.len()
+            );
+            for entry in entries {
+                let player = entry
+                    .player_id
+                    .map_or_else(|| "<unparsable>".to_string(), |id| id.to_string());
+                println!(
+                    "player={player} save={} reason={} attempts={} bytes={} error={}",
+                    entry.save_name.as_deref().unwrap_or("-"),
+                    entry.reason,
+                    entry.attempts,
+                    entry.payload_bytes,
+                    entry.last_error,
+                );
+            }
+        }
+        Some("replay") => {
+            let replay = replay_dead_letters(limit).await?;
+            println!(
+                "replayed {} save(s); {} malformed save(s) left in {SAVE_DEAD_LETTER_QUEUE}",
+                replay.replayed, replay.skipped_malformed
+            );
+        }
+        _ => return Err(DEAD_LETTER_USAGE.into()),
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "server"))]
+async fn run_dead_letter_admin(_args: &[String]) -> Result<(), Box<dyn Error + Send + Sync>> {
+    Err("dead-letter tooling requires the `server` feature".into())
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("dead-letters") {
+        return run_dead_letter_admin(&args[1..]).await;
+    }
+
     info!("Starting G-Engine Server...");
 
     let (action_tx, action_rx) = mpsc::unbounded_channel::<(u64, PlayerAction)>();
     let (broadcast_tx, mut broadcast_rx) = mpsc::unbounded_channel::<GamePacket>();
+    let (connection_tx, connection_rx) = mpsc::unbounded_channel::<ConnectionEvent>();
 
+    let server_id = std::env::var("SERVER_ID")
+        .ok()
+        .filter(|id| !id.trim().is_empty())
+        .unwrap_or_else(|| format!("server-{}", &uuid::Uuid::new_v4().simple().to_string()[..8]));
+    info!("Server id: {}", server_id);
+
+    let connections = ConnectionRegistry::default();
     #[cfg(feature = "server")]
-    {
-        let database = emiyashiro::database::Database::new().await?;
+    let (services, session_recorder) = {
+        let database = Arc::new(emiyashiro::database::Database::new().await?);
+        let leaderboard = Arc::new(emiyashiro::database::leaderboard::LeaderboardService::new(
+            database.clone(),
+        ));
         let pool = database.pool.clone();
 
         tokio::spawn(async move {
             emiyashiro::systems::save_worker::run_save_worker(pool).await;
         });
-    }
+
+        let saves = emiyashiro::systems::save_worker::SavePublisher::spawn_from_env()
+            .map_err(|error| warn!("Online saves are disabled: {}", error))
+            .ok();
+
+        let (record_tx, record_rx) = mpsc::unbounded_channel();
+        tokio::spawn(emiyashiro::systems::session_writer::run_session_writer(
+            database.clone(),
+            leaderboard.clone(),
+            saves.clone(),
+            record_rx,
+        ));
+
+        let presence = emiyashiro::database::presence::PresenceService::connect(
+            server_id.clone(),
+            SERVER_ADDRESS.to_string(),
+            Default::default(),
+        )
+        .map(Arc::new);
+
+        (
+            OnlineServices {
+                server_id: server_id.clone(),
+                packets: Some(broadcast_tx.clone()),
+                connections: connections.clone(),
+                auth_limiter: AuthRateLimiter::default(),
+                save_keys: SaveKeyRegistry::default(),
+                saves,
+                database: Some(database),
+                leaderboard: Some(leaderboard),
+                presence,
+            },
+            SessionRecorder::with_sink(record_tx),
+        )
+    };
+    #[cfg(not(feature = "server"))]
+    let (services, session_recorder) = (
+        OnlineServices {
+            server_id: server_id.clone(),
+            packets: Some(broadcast_tx.clone()),
+            connections: connections.clone(),
+            auth_limiter: AuthRateLimiter::default(),
+            save_keys: SaveKeyRegistry::default(),
+        },
+        SessionRecorder::default(),
+    );
+    // 击杀与清场分数由服务器按本地计分表计算，不信任客户端
+    let session_recorder = session_recorder.with_scoring(GameplayTuning::load_from_disk().scoring);
 
     let clients: SharedClients = Arc::new(Mutex::new(HashMap::new()));
     let clients_clone = clients.clone();
-    let addr = "127.0.0.1:8080";
-    let listener = TcpListener::bind(addr).await?;
-    info!("WebSocket server listening on: {}", addr);
+
+    #[cfg(feature = "server")]
+    let presence = services.presence.clone();
+    #[cfg(feature = "server")]
+    if let Some(presence) = presence.clone() {
+        // 心跳：续期服务器登记与本服务器玩家的在线记录
+        let heartbeat_presence = presence.clone();
+        let heartbeat_clients = clients.clone();
+        tokio::spawn(async move {
+            let mut interval =
+                tokio::time::interval(heartbeat_presence.config().heartbeat_interval);
+            loop {
+                interval.tick().await;
+                let player_count = heartbeat_clients
+                    .lock()
+                    .map(|clients| clients.len() as u32)
+                    .unwrap_or(0);
+                if let Err(error) = heartbeat_presence.heartbeat(player_count).await {
+                    warn!("Cluster heartbeat failed: {error}");
+                }
+            }
+        });
+
+        // 其他服务器的聊天/大厅事件转发给本服务器的客户端
+        let mut cluster_rx = presence.subscribe();
+        let cluster_tx = broadcast_tx.clone();
+        tokio::spawn(async move {
+            while let Some(packet) = cluster_rx.recv().await {
+                let _ = cluster_tx.send(packet);
+            }
+        });
+    }
+
+    let (admin_tx, admin_queries) = AdminQueries::channel();
+    let metrics = MetricsRegistry::default();
+    let admin_address = std::env::var("ADMIN_ADDRESS")
+        .ok()
+        .filter(|address| !address.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_ADMIN_ADDRESS.to_string());
+    match TcpListener::bind(&admin_address).await {
+        Ok(admin_listener) => {
+            info!("Admin API listening on: {}", admin_address);
+            tokio::spawn(run_admin_server(
+                admin_listener,
+                AdminState {
+                    connections: connections.clone(),
+                    queries: admin_tx,
+                    broadcast: broadcast_tx.clone(),
+                    metrics: metrics.clone(),
+                    token: std::env::var("ADMIN_TOKEN")
+                        .ok()
+                        .filter(|token| !token.is_empty()),
+                },
+            ));
+        }
+        Err(error) => warn!(
+            "Admin API disabled, cannot bind {}: {}",
+            admin_address, error
+        ),
+    }
+
+    let listener = TcpListener::bind(SERVER_ADDRESS).await?;
+    info!("WebSocket server listening on: {}", SERVER_ADDRESS);
 
     tokio::spawn(async move {
         let mut client_id_counter: u64 = 0;
 
         loop {
-            let (stream, _) = match listener.accept().await {
+            let (stream, address) = match listener.accept().await {
                 Ok(connection) => connection,
                 Err(error) => {
                     error!("WebSocket accept failed: {error}");
@@ -54,10 +512,20 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
             let client_id = client_id_counter;
             let clients_inner = clients_clone.clone();
             let action_tx_inner = action_tx.clone();
+            let connection_tx_inner = connection_tx.clone();
+            let services_inner = services.clone();
 
             tokio::spawn(async move {
-                if let Err(error) =
-                    handle_connection(stream, client_id, clients_inner, action_tx_inner).await
+                if let Err(error) = handle_connection(
+                    stream,
+                    address,
+                    client_id,
+                    clients_inner,
+                    action_tx_inner,
+                    connection_tx_inner,
+                    services_inner,
+                )
+                .await
                 {
                     warn!("Client {client_id} connection failed: {error}");
                 }
@@ -113,25 +581,55 @@ async fn main() -> Result<(), Box<dyn Error + Send + Sync>> {
     #[cfg(feature = "server")]
     app.add_plugins(emiyashiro::database::redis::RedisPlugin);
 
+    app.insert_resource(session_recorder);
+    app.insert_resource(admin_queries);
+    app.insert_resource(metrics);
     app.add_plugins(ServerRuntimePlugin {
         channels: NetworkChannels {
             action_rx: Arc::new(Mutex::new(action_rx)),
+            connection_rx: Arc::new(Mutex::new(connection_rx)),
             broadcast_tx,
         },
     });
 
     app.run();
+
+    #[cfg(feature = "server")]
+    if let Some(presence) = presence
+        && let Err(error) = presence.deregister().await
+    {
+        warn!("Failed to deregister from the cluster: {error}");
+    }
     Ok(())
 }
 
+// 握手回调的签名由 tungstenite 规定，错误类型就是完整的 HTTP 响应
+#[allow(clippy::result_large_err)]
 async fn handle_connection(
     stream: TcpStream,
+    address: SocketAddr,
     client_id: u64,
     clients: SharedClients,
     action_tx: mpsc::UnboundedSender<(u64, PlayerAction)>,
+    connection_tx: ConnectionEventSender,
+    services: OnlineServices,
 ) -> Result<(), Box<dyn Error + Send + Sync>> {
-    let ws_stream = accept_async(stream).await?;
-    info!("New client connected: {}", client_id);
+    let mut handshake_token = None;
+    let ws_stream = accept_hdr_async(
+        stream,
+        |request: &Request, response: Response| -> Result<Response, ErrorResponse> {
+            let authorization = request
+                .headers()
+                .get("authorization")
+                .and_then(|value| value.to_str().ok());
+            handshake_token = session_token_from_handshake(authorization, request.uri().query());
+            Ok(response)
+        },
+    )
+    .await?;
+    info!("New client connected: {} ({})", client_id, address);
+    let kick = services.connections.register(client_id, address);
+    let connected_at = Instant::now();
 
     let (mut write, mut read) = ws_stream.split();
     let (out_tx, mut out_rx) = mpsc::unbounded_channel::<WsMessage>();
@@ -148,27 +646,115 @@ async fn handle_connection(
         id: client_id,
         message: "Connected to G-Engine Server".to_string(),
     };
-    match bincode::serde::encode_to_vec(&welcome, bincode::config::standard()) {
-        Ok(binary) => {
-            let _ = out_tx.send(WsMessage::Binary(binary.into()));
-        }
-        Err(error) => {
-            warn!("Failed to serialize welcome packet: {}", error);
-        }
+    send_packet(&out_tx, &welcome);
+
+    let mut account: Option<AccountIdentity> = None;
+    if let Some(token) = handshake_token
+        && let Some(identity) = authenticate_connection(
+            client_id,
+            AuthRequest::SessionToken(token),
+            &services,
+            &out_tx,
+            &connection_tx,
+        )
+        .await
+    {
+        bind_account(client_id, &mut account, identity, &services).await;
     }
 
     if let Ok(mut clients_guard) = clients.lock() {
         clients_guard.insert(client_id, out_tx.clone());
     }
 
-    while let Some(msg) = read.next().await {
+    let mut auth_requests = 0u32;
+    let mut rtt_probe = tokio::time::interval(RTT_PROBE_INTERVAL);
+    loop {
+        let msg = tokio::select! {
+            msg = read.next() => match msg {
+                Some(msg) => msg,
+                None => break,
+            },
+            () = kick.notified() => {
+                info!("Client {} kicked by admin", client_id);
+                let _ = out_tx.send(WsMessage::Close(None));
+                break;
+            }
+            _ = rtt_probe.tick() => {
+                // 载荷是发送时刻（连接建立后的微秒数），Pong 原样带回
+                let sent_micros = connected_at.elapsed().as_micros() as u64;
+                let _ = out_tx.send(WsMessage::Ping(sent_micros.to_be_bytes().to_vec().into()));
+                continue;
+            }
+        };
         match msg {
             Ok(WsMessage::Binary(bin)) => {
                 if let Ok((action, _)) = bincode::serde::decode_from_slice::<PlayerAction, _>(
                     &bin,
                     bincode::config::standard(),
                 ) {
-                    let _ = action_tx.send((client_id, action));
+                    if let Some(request) = AuthRequest::from_action(&action) {
+                        // 每个连接与每个 IP 的账号请求都限量，防止批量创建游客账号或猜密码
+                        auth_requests = auth_requests.saturating_add(1);
+                        let throttled = if auth_requests > AUTH_REQUESTS_PER_CONNECTION {
+                            Err(AccountError::RateLimited)
+                        } else {
+                            services
+                                .auth_limiter
+                                .check(address.ip(), &request, Instant::now())
+                        };
+                        if let Err(error) = throttled {
+                            reject_auth(client_id, &out_tx, &error);
+                        } else if let Some(identity) = authenticate_connection(
+                            client_id,
+                            request,
+                            &services,
+                            &out_tx,
+                            &connection_tx,
+                        )
+                        .await
+                        {
+                            bind_account(client_id, &mut account, identity, &services).await;
+                        }
+                    } else if let PlayerAction::Chat { text } = action {
+                        // 只有已登录的连接可以发言，消息发往整个集群
+                        if let Some(identity) = &account
+                            && let Some(text) = sanitize_chat_text(&text)
+                        {
+                            services
+                                .announce(GamePacket::Chat {
+                                    from: identity.username.clone(),
+                                    text,
+                                })
+                                .await;
+                        }
+                    } else if let PlayerAction::RequestLeaderboard { query, limit } = action {
+                        let player_id = account.as_ref().map(|identity| identity.player_id);
+                        let reply = match services.leaderboard_page(query, limit, player_id).await {
+                            Ok(page) => GamePacket::Leaderboard(page),
+                            Err(reason) => GamePacket::LeaderboardUnavailable { reason },
+                        };
+                        send_packet(&out_tx, &reply);
+                    } else if let PlayerAction::RegisterSaveKey { key } = action {
+                        if let Some(reply) =
+                            register_save_key(account.as_ref(), &key, &services).await
+                        {
+                            send_packet(&out_tx, &reply);
+                        }
+                    } else if let PlayerAction::UploadSave { name, save_json } = action {
+                        let reply =
+                            upload_save(account.as_ref(), &name, &save_json, &services).await;
+                        send_packet(&out_tx, &reply);
+                    } else {
+                        let _ = action_tx.send((client_id, action));
+                    }
+                }
+            }
+            Ok(WsMessage::Pong(payload)) => {
+                if let Ok(bytes) = <[u8; 8]>::try_from(payload.as_ref()) {
+                    let sent = Duration::from_micros(u64::from_be_bytes(bytes));
+                    services
+                        .connections
+                        .record_rtt(client_id, connected_at.elapsed().saturating_sub(sent));
                 }
             }
             Ok(WsMessage::Close(_)) => break,
@@ -177,11 +763,154 @@ async fn handle_connection(
     }
 
     info!("Client disconnected: {}", client_id);
+    services.connections.unregister(client_id);
     if let Ok(mut clients_guard) = clients.lock() {
         clients_guard.remove(&client_id);
     }
+    let _ = connection_tx.send(ConnectionEvent::Disconnected { client_id });
+    if let Some(identity) = &account {
+        services.account_offline(identity).await;
+    }
 
     drop(out_tx);
     let _ = writer_handle.await;
     Ok(())
 }
+
+/// Records a (re)authenticated account on this connection, taking the previous one offline.
+async fn bind_account(
+    client_id: u64,
+    account: &mut Option<AccountIdentity>,
+    identity: AccountIdentity,
+    services: &OnlineServices,
+) {
+    match account.take() {
+        Some(previous) if previous.player_id == identity.player_id => {}
+        Some(previous) => {
+            services.account_offline(&previous).await;
+            services.account_online(client_id, &identity).await;
+        }
+        None => services.account_online(client_id, &identity).await,
+    }
+    services
+        .connections
+        .set_username(client_id, Some(identity.username.clone()));
+    *account = Some(identity);
+}
+
+/// Runs one authentication request and replies to this connection only.
+async fn authenticate_connection(
+    client_id: u64,
+    request: AuthRequest,
+    services: &OnlineServices,
+    out_tx: &ClientMessageSender,
+    connection_tx: &ConnectionEventSender,
+) -> Option<AccountIdentity> {
+    match services.authenticate(request).await {
+        Ok(AuthenticatedAccount {
+            identity,
+            session_token,
+        }) => {
+            let _ = connection_tx.send(ConnectionEvent::Authenticated {
+                client_id,
+                account: identity.clone(),
+            });
+            send_packet(
+                out_tx,
+                &GamePacket::AuthAccepted {
+                    account: identity.clone(),
+                    session_token,
+                },
+            );
+            Some(identity)
+        }
+        Err(error) => {
+            reject_auth(client_id, out_tx, &error);
+            None
+        }
+    }
+}
+
+fn reject_auth(client_id: u64, out_tx: &ClientMessageSender, error: &AccountError) {
+    info!("Client {client_id} authentication rejected: {error}");
+    send_packet(
+        out_tx,
+        &GamePacket::AuthRejected {
+            reason: error.to_string(),
+        },
+    );
+}
+
+/// Registers the install key of a signed-in client; replies only when refused.
+async fn register_save_key(
+    account: Option<&AccountIdentity>,
+    key: &str,
+    services: &OnlineServices,
+) -> Option<GamePacket> {
+    let reason = match (account, SaveSigningKey::from_hex(key)) {
+        (None, _) => "Sign in before registering a save key",
+        (Some(_), None) => "Malformed save key",
+        (Some(identity), Some(key)) => {
+            match services.register_save_key(identity.player_id, key).await {
+                Ok(true) => return None,
+                Ok(false) => "This account already registered a different save key",
+                Err(reason) => reason,
+            }
+        }
+    };
+    Some(GamePacket::SaveRejected {
+        reason: reason.to_string(),
+    })
+}
+
+/// Verifies an uploaded save against the account's registered key before storing it;
+/// unsigned, foreign-key and edited saves never reach online state.
+async fn upload_save(
+    account: Option<&AccountIdentity>,
+    name: &str,
+    save_json: &str,
+    services: &OnlineServices,
+) -> GamePacket {
+    let rejected = |reason: &str| GamePacket::SaveRejected {
+        reason: reason.to_string(),
+    };
+    let Some(identity) = account else {
+        return rejected("Sign in before uploading saves");
+    };
+    if save_json.len() > MAX_UPLOADED_SAVE_BYTES {
+        return rejected("Save is too large to upload");
+    }
+    let key = match services.save_key(identity.player_id).await {
+        Ok(Some(key)) => key,
+        Ok(None) => return rejected("No save key is registered for this account"),
+        Err(reason) => return rejected(reason),
+    };
+    if let Err(reason) = verify_upload(&key, save_json) {
+        info!("Rejected save upload from {}: {reason}", identity.username);
+        return rejected(reason);
+    }
+    let Ok(game_data) = serde_json::from_str::<serde_json::Value>(save_json) else {
+        return rejected("Save is not valid JSON");
+    };
+
+    let name: String = name
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_UPLOADED_SAVE_NAME_CHARS)
+        .collect();
+    match services.store_uploaded_save(identity.player_id, &name, game_data) {
+        Ok(()) => GamePacket::SaveAccepted { name },
+        Err(reason) => GamePacket::SaveRejected { reason },
+    }
+}
+
+fn send_packet(out_tx: &ClientMessageSender, packet: &GamePacket) {
+    match bincode::serde::encode_to_vec(packet, bincode::config::standard()) {
+        Ok(binary) => {
+            let _ = out_tx.send(WsMessage::Binary(binary.into()));
+        }
+        Err(error) => {
+            warn!("Failed to serialize packet: {}", error);
+        }
+    }
+}
@@ -15,6 +15,9 @@ use bevy::prelude::*;
 /// * `Jumping` - 跳跃动画
 /// * `Crouching` - 蹲下动画
 /// * `Landing` - 着陆动画
+/// * `Guarding` - 格挡动画
+/// * `Parrying` - 弹反动画
+/// * `Dodging` - 闪避动画
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Deserialize)]
 pub enum AnimationType {
     Idle,
@@ -23,6 +26,9 @@ pub enum AnimationType {
     Jumping,
     Crouching,
     Landing,
+    Guarding,
+    Parrying,
+    Dodging,
 }
 
 impl AnimationType {
@@ -33,7 +39,10 @@ impl AnimationType {
             AnimationType::Idle
             | AnimationType::Jumping
             | AnimationType::Crouching
-            | AnimationType::Landing => SpriteSheetKind::Core,
+            | AnimationType::Landing
+            | AnimationType::Guarding
+            | AnimationType::Parrying
+            | AnimationType::Dodging => SpriteSheetKind::Core,
         }
     }
 }
@@ -45,7 +54,19 @@ pub enum SpriteSheetKind {
     Attacking,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
 pub enum AttackAnimationStyle {
     #[default]
     Normal,
@@ -148,6 +169,50 @@ impl AttackAnimationStyle {
             _ => None,
         }
     }
+
+    /// Reference board 招式所属的招式族（不带行号的样式），例如 `HeavyRefRow(3)` → `HeavyRef`
+    pub fn reference_family(self) -> Option<Self> {
+        match self {
+            AttackAnimationStyle::GroundLight | AttackAnimationStyle::GroundLightRow(_) => {
+                Some(AttackAnimationStyle::GroundLight)
+            }
+            AttackAnimationStyle::AirCombo | AttackAnimationStyle::AirComboRow(_) => {
+                Some(AttackAnimationStyle::AirCombo)
+            }
+            AttackAnimationStyle::HeavyRef | AttackAnimationStyle::HeavyRefRow(_) => {
+                Some(AttackAnimationStyle::HeavyRef)
+            }
+            AttackAnimationStyle::UltimateRef | AttackAnimationStyle::UltimateRefRow(_) => {
+                Some(AttackAnimationStyle::UltimateRef)
+            }
+            AttackAnimationStyle::MobilityRef | AttackAnimationStyle::MobilityRefRow(_) => {
+                Some(AttackAnimationStyle::MobilityRef)
+            }
+            AttackAnimationStyle::NinjutsuRef | AttackAnimationStyle::NinjutsuRefRow(_) => {
+                Some(AttackAnimationStyle::NinjutsuRef)
+            }
+            AttackAnimationStyle::WeaponProjRef | AttackAnimationStyle::WeaponProjRefRow(_) => {
+                Some(AttackAnimationStyle::WeaponProjRef)
+            }
+            _ => None,
+        }
+    }
+
+    /// 同一招式族中指定行号的样式
+    pub fn with_reference_row(self, row: u8) -> Option<Self> {
+        match self.reference_family()? {
+            AttackAnimationStyle::GroundLight => Some(AttackAnimationStyle::GroundLightRow(row)),
+            AttackAnimationStyle::AirCombo => Some(AttackAnimationStyle::AirComboRow(row)),
+            AttackAnimationStyle::HeavyRef => Some(AttackAnimationStyle::HeavyRefRow(row)),
+            AttackAnimationStyle::UltimateRef => Some(AttackAnimationStyle::UltimateRefRow(row)),
+            AttackAnimationStyle::MobilityRef => Some(AttackAnimationStyle::MobilityRefRow(row)),
+            AttackAnimationStyle::NinjutsuRef => Some(AttackAnimationStyle::NinjutsuRefRow(row)),
+            AttackAnimationStyle::WeaponProjRef => {
+                Some(AttackAnimationStyle::WeaponProjRefRow(row))
+            }
+            _ => None,
+        }
+    }
 }
 
 #[derive(Component, Debug, Clone)]
@@ -2,16 +2,20 @@
 
 use bevy::prelude::*;
 
+use crate::{components::Velocity, resources::EnemyPhysicsTuning};
+
 /// 敌人标记组件
 #[derive(Component, Debug)]
 pub struct Enemy;
 
-/// 敌人类型
-#[derive(Component, Debug, Clone, Copy, PartialEq)]
+/// 敌人类型（同时用于在线击杀上报，服务器据此查表计分）
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum EnemyType {
     Slime,             // 史莱姆敌人
     Familiar,          // 使魔
     EnemyHeroicSpirit, // 敌方英灵
+    Shadow,            // 影：穿过地形并抓取玩家
+    BlackMud,          // 黑泥：贴地蠕行，接触时持续吸取生命
 }
 
 /// 敌人状态
@@ -27,15 +31,7 @@ pub struct EnemyState {
     pub base_speed: f32,     // 基础移动速度
     pub contact_damage: f32, // 接触伤害
     pub hover_phase: f32,    // 浮空类敌人的相位
-    pub attack_cooldown: f32,
-    pub ranged_cooldown: f32,
-    pub ranged_windup_timer: f32,
-    pub pending_ranged_shot: bool,
-    pub ranged_shot_direction: Vec2,
     pub hit_stun_timer: f32,
-    pub dash_charge_timer: f32,
-    pub dash_active_timer: f32,
-    pub dash_direction: f32,
 }
 
 impl Default for EnemyState {
@@ -51,15 +47,7 @@ impl Default for EnemyState {
             base_speed: 55.0,
             contact_damage: 12.0,
             hover_phase: 0.0,
-            attack_cooldown: 0.0,
-            ranged_cooldown: 0.0,
-            ranged_windup_timer: 0.0,
-            pending_ranged_shot: false,
-            ranged_shot_direction: Vec2::ZERO,
             hit_stun_timer: 0.0,
-            dash_charge_timer: 0.0,
-            dash_active_timer: 0.0,
-            dash_direction: 1.0,
         }
     }
 }
@@ -78,15 +66,7 @@ impl EnemyState {
             base_speed: 55.0,
             contact_damage: 12.0,
             hover_phase: 0.0,
-            attack_cooldown: 0.0,
-            ranged_cooldown: 0.0,
-            ranged_windup_timer: 0.0,
-            pending_ranged_shot: false,
-            ranged_shot_direction: Vec2::ZERO,
             hit_stun_timer: 0.0,
-            dash_charge_timer: 0.0,
-            dash_active_timer: 0.0,
-            dash_direction: 1.0,
         }
     }
 
@@ -118,20 +98,212 @@ impl EnemyState {
     }
 
     pub fn tick_timers(&mut self, delta_secs: f32) {
-        self.attack_cooldown = (self.attack_cooldown - delta_secs).max(0.0);
-        self.ranged_cooldown = (self.ranged_cooldown - delta_secs).max(0.0);
-        self.ranged_windup_timer = (self.ranged_windup_timer - delta_secs).max(0.0);
         self.hit_stun_timer = (self.hit_stun_timer - delta_secs).max(0.0);
-        self.dash_charge_timer = (self.dash_charge_timer - delta_secs).max(0.0);
-        self.dash_active_timer = (self.dash_active_timer - delta_secs).max(0.0);
     }
 
+    /// 受击硬直；正在进行的预警/冲刺由行为系统在下一帧转入 [`BehaviorNode::Stunned`] 时打断
     pub fn apply_hit_stun(&mut self, duration: f32) {
         self.hit_stun_timer = self.hit_stun_timer.max(duration);
-        self.dash_charge_timer = 0.0;
-        self.dash_active_timer = 0.0;
-        self.ranged_windup_timer = 0.0;
-        self.pending_ranged_shot = false;
-        self.ranged_shot_direction = Vec2::ZERO;
+    }
+}
+
+/// 可复用的敌人行为节点。各原型的状态机只是在这些节点之间按行为配置跳转，
+/// 见 `systems::enemy_behavior::EnemyBehaviorProfile`。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BehaviorNode {
+    /// 在巡逻区间内来回移动
+    #[default]
+    Patrol,
+    /// 追向玩家（可保持水平偏移）
+    Chase,
+    /// 出招前摇：锁定方向并给出视觉预警
+    Telegraph,
+    /// 沿锁定方向高速冲刺
+    Dash,
+    /// 沿锁定方向发射飞弹，之后立即回到空闲节点
+    Shoot,
+    /// 已越过玩家的非编排敌人离场，不再出招
+    Retreat,
+    /// 受击硬直，打断预警与冲刺
+    Stunned,
+    /// 抓住玩家，期间玩家无法移动
+    Grab,
+}
+
+impl BehaviorNode {
+    /// 调试层显示的名字
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Patrol => "patrol",
+            Self::Chase => "chase",
+            Self::Telegraph => "telegraph",
+            Self::Dash => "dash",
+            Self::Shoot => "shoot",
+            Self::Retreat => "retreat",
+            Self::Stunned => "stunned",
+            Self::Grab => "grab",
+        }
+    }
+}
+
+/// 敌人行为状态机的运行时状态，所有原型共用同一组计时器
+#[derive(Component, Debug, Clone, Default)]
+pub struct EnemyBrain {
+    pub node: BehaviorNode,
+    /// 计时节点（预警、冲刺）剩余时间
+    pub node_timer: f32,
+    /// 出招完成后的冷却
+    pub attack_cooldown: f32,
+    /// 进入预警时锁定的出招方向
+    pub aim: Vec2,
+}
+
+impl EnemyBrain {
+    pub fn enter(&mut self, node: BehaviorNode, duration: f32) {
+        self.node = node;
+        self.node_timer = duration.max(0.0);
+    }
+
+    pub fn tick(&mut self, delta_secs: f32) {
+        self.node_timer = (self.node_timer - delta_secs).max(0.0);
+        self.attack_cooldown = (self.attack_cooldown - delta_secs).max(0.0);
+    }
+
+    /// 是否处于预警或出招中
+    pub fn is_attacking(&self) -> bool {
+        matches!(
+            self.node,
+            BehaviorNode::Telegraph | BehaviorNode::Dash | BehaviorNode::Shoot | BehaviorNode::Grab
+        )
+    }
+}
+
+/// 贴地敌人的受击物理状态：被挑空后受重力下落，落在地形或锚点高度上。
+/// 速度沿用实体的 [`Velocity`]。
+#[derive(Component, Debug, Clone, Default)]
+pub struct EnemyBody {
+    pub airborne: bool,
+    /// 本次浮空中被命中的次数
+    pub juggle_hits: u32,
+    /// 本次浮空中撞墙反弹的次数
+    pub wall_bounces: u32,
+    /// 站在高于锚点的平台上时的落脚高度
+    pub rest_y: Option<f32>,
+}
+
+impl EnemyBody {
+    /// 按招式的击退与上挑速度设置受击速度：`launch_y > 0` 把敌人挑空，
+    /// 浮空中的命中按 `knockback_y` 追击（逐次衰减），地面上的普通命中只水平击退
+    pub fn take_hit(
+        &mut self,
+        velocity: &mut Velocity,
+        knockback: Vec2,
+        launch_y: f32,
+        tuning: &EnemyPhysicsTuning,
+    ) {
+        velocity.x = knockback.x * tuning.knockback_scale_x;
+        if launch_y > 0.0 {
+            if !self.airborne {
+                self.juggle_hits = 0;
+                self.wall_bounces = 0;
+            }
+            self.airborne = true;
+            velocity.y = launch_y * tuning.juggle_decay.powi(self.juggle_hits as i32);
+            self.juggle_hits += 1;
+        } else if self.airborne {
+            velocity.y = knockback.y
+                * tuning.air_knockback_scale_y
+                * tuning.juggle_decay.powi(self.juggle_hits as i32);
+            self.juggle_hits += 1;
+        } else {
+            velocity.y = 0.0;
+        }
+    }
+
+    pub fn land(&mut self, rest_y: Option<f32>) {
+        self.airborne = false;
+        self.juggle_hits = 0;
+        self.wall_bounces = 0;
+        self.rest_y = rest_y;
+    }
+}
+
+/// 被敌人抓住的玩家：无法移动与起跳，抓取者松手、硬直或死亡时移除
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Grabbed {
+    pub by: Entity,
+}
+
+/// Boss 当前执行的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BossAction {
+    #[default]
+    Idle,
+    /// 冲刺前的蓄力预警
+    DashCharge,
+    Dashing,
+    Barrage,
+    /// 召唤场地危害后的施法硬直
+    Casting,
+}
+
+/// Boss 运行时状态。Boss 同时带有 [`Enemy`]/[`EnemyState`]，受伤与击杀走普通敌人管线，
+/// 行为由 boss 系统按当前阶段的招式驱动，不参与普通巡逻 AI。
+#[derive(Component, Debug, Clone, Default)]
+pub struct Boss {
+    pub arena: i32,
+    pub phase: usize,
+    /// 当前阶段招式表中正在执行的招式
+    pub active_pattern: usize,
+    /// 下一次出招使用的招式序号（对招式表取模）
+    pub next_pattern: usize,
+    pub pattern_cooldown: f32,
+    pub transition_timer: f32,
+    pub action: BossAction,
+    pub action_timer: f32,
+    /// 当前招式剩余的冲刺段数/弹幕波数
+    pub repeats_left: u32,
+    /// 本段冲刺锁定的水平方向
+    pub dash_direction: f32,
+}
+
+impl Boss {
+    pub fn new(arena: i32) -> Self {
+        Self {
+            arena,
+            ..Self::default()
+        }
+    }
+
+    /// 进入新阶段：打断当前招式并进入阶段切换硬直
+    pub fn enter_phase(&mut self, phase: usize, transition_secs: f32) {
+        self.phase = phase;
+        self.next_pattern = 0;
+        self.transition_timer = transition_secs.max(0.0);
+        self.pattern_cooldown = 0.0;
+        self.finish_pattern(0.0);
+    }
+
+    pub fn finish_pattern(&mut self, cooldown: f32) {
+        self.action = BossAction::Idle;
+        self.action_timer = 0.0;
+        self.repeats_left = 0;
+        self.pattern_cooldown = self.pattern_cooldown.max(cooldown);
+    }
+}
+
+/// Boss 召唤的地面光柱：预警阶段无伤害，生效阶段对玩家造成一次伤害
+#[derive(Component, Debug, Clone)]
+pub struct BossHazard {
+    pub telegraph_timer: f32,
+    pub active_timer: f32,
+    pub damage: f32,
+    pub width: f32,
+    pub has_hit: bool,
+}
+
+impl BossHazard {
+    pub fn is_active(&self) -> bool {
+        self.telegraph_timer <= 0.0 && self.active_timer > 0.0
     }
 }
@@ -0,0 +1,161 @@
+//! 逐帧判定数据（hitbox / hurtbox）
+//!
+//! 与 `assets/animations/hf_shirou.ron` 放在一起的 `hf_shirou_frames.ron` 描述每个攻击样式
+//! 在哪些动画帧上有攻击判定框，以及各动画帧的受击框。帧号是动画片段内的位置
+//! （即 `SpriteAnimation::current_frame`），不是图集索引。
+use crate::components::animation::{AnimationType, AttackAnimationStyle};
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+
+fn unit_scale() -> f32 {
+    1.0
+}
+
+fn unit_scale_pair() -> (f32, f32) {
+    (1.0, 1.0)
+}
+
+/// 一个判定框。偏移以角色中心为原点，x 随朝向翻转。
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct FrameBox {
+    pub offset: (f32, f32),
+    pub size: (f32, f32),
+    /// 攻击框的伤害倍率（乘在招式伤害上），受击框忽略
+    #[serde(default = "unit_scale")]
+    pub damage_scale: f32,
+    /// 攻击框的击退倍率 (x, y)，受击框忽略
+    #[serde(default = "unit_scale_pair")]
+    pub knockback_scale: (f32, f32),
+}
+
+impl FrameBox {
+    /// 世界坐标下的判定矩形
+    pub fn world_rect(&self, center: Vec2, facing: f32) -> Rect {
+        let offset = Vec2::new(self.offset.0 * facing.signum(), self.offset.1);
+        Rect::from_center_size(center + offset, Vec2::new(self.size.0, self.size.1))
+    }
+}
+
+/// 在 `frames` 闭区间内生效的一组判定框
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct FrameWindow {
+    pub frames: (usize, usize),
+    pub boxes: Vec<FrameBox>,
+}
+
+impl FrameWindow {
+    pub fn contains(&self, frame: usize) -> bool {
+        (self.frames.0..=self.frames.1).contains(&frame)
+    }
+}
+
+/// 一个攻击样式的逐帧判定
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct AttackFrameData {
+    pub hitboxes: Vec<FrameWindow>,
+    /// 攻击期间替换默认受击框；为空时沿用 `FrameDataMap::hurtboxes`
+    pub hurtboxes: Vec<FrameWindow>,
+}
+
+/// 角色的全部逐帧判定数据
+#[derive(Resource, Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct FrameDataMap {
+    pub attacks: BTreeMap<AttackAnimationStyle, AttackFrameData>,
+    /// 按动画类型的受击框；没有配置的动画使用角色的 `CollisionBox`
+    pub hurtboxes: HashMap<AnimationType, Vec<FrameWindow>>,
+}
+
+fn active_boxes(windows: &[FrameWindow], frame: usize) -> impl Iterator<Item = &FrameBox> {
+    windows
+        .iter()
+        .filter(move |window| window.contains(frame))
+        .flat_map(|window| window.boxes.iter())
+}
+
+impl FrameDataMap {
+    /// 该攻击样式是否按帧判定
+    pub fn has_hitboxes(&self, style: AttackAnimationStyle) -> bool {
+        self.attacks
+            .get(&style)
+            .is_some_and(|attack| !attack.hitboxes.is_empty())
+    }
+
+    /// 攻击样式在某一帧生效的攻击框
+    pub fn hitboxes_at(
+        &self,
+        style: AttackAnimationStyle,
+        frame: usize,
+    ) -> impl Iterator<Item = &FrameBox> {
+        let windows = self
+            .attacks
+            .get(&style)
+            .map_or(&[][..], |attack| attack.hitboxes.as_slice());
+        active_boxes(windows, frame)
+    }
+
+    /// 当前帧的受击框；返回 `None` 时使用 `CollisionBox`
+    pub fn hurtboxes_at(
+        &self,
+        animation: &AnimationType,
+        attack_style: Option<AttackAnimationStyle>,
+        frame: usize,
+    ) -> Option<Vec<FrameBox>> {
+        let attack_windows = attack_style
+            .filter(|_| *animation == AnimationType::Attacking)
+            .and_then(|style| self.attacks.get(&style))
+            .map(|attack| attack.hurtboxes.as_slice())
+            .filter(|windows| !windows.is_empty());
+        let windows =
+            attack_windows.or_else(|| self.hurtboxes.get(animation).map(Vec::as_slice))?;
+        let boxes: Vec<FrameBox> = active_boxes(windows, frame).copied().collect();
+        (!boxes.is_empty()).then_some(boxes)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let mut errors = Vec::new();
+        let mut check = |owner: String, windows: &[FrameWindow]| {
+            for window in windows {
+                if window.frames.0 > window.frames.1 {
+                    errors.push(format!("{owner}: 帧区间 {:?} 起点大于终点", window.frames));
+                }
+                if window.boxes.is_empty() {
+                    errors.push(format!("{owner}: 帧区间 {:?} 没有判定框", window.frames));
+                }
+                for frame_box in &window.boxes {
+                    let finite = [
+                        frame_box.offset.0,
+                        frame_box.offset.1,
+                        frame_box.damage_scale,
+                        frame_box.knockback_scale.0,
+                        frame_box.knockback_scale.1,
+                    ]
+                    .iter()
+                    .all(|value| value.is_finite());
+                    let positive_size = [frame_box.size.0, frame_box.size.1]
+                        .iter()
+                        .all(|value| value.is_finite() && *value > 0.0);
+                    if !finite || !positive_size || frame_box.damage_scale < 0.0 {
+                        errors.push(format!("{owner}: 判定框 {frame_box:?} 数值无效"));
+                    }
+                }
+            }
+        };
+
+        for (style, attack) in &self.attacks {
+            check(format!("{style:?} hitboxes"), &attack.hitboxes);
+            check(format!("{style:?} hurtboxes"), &attack.hurtboxes);
+        }
+        for (animation, windows) in &self.hurtboxes {
+            check(format!("{animation:?} hurtboxes"), windows);
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; "))
+        }
+    }
+}
@@ -42,6 +42,8 @@ impl Default for SkyLevelRuntime {
 pub struct SkyEncounterState {
     pub active_arena: Option<i32>,
     pub completed_arenas: std::collections::HashSet<i32>,
+    /// Arenas where the player took hostile damage while the encounter was active.
+    pub damaged_arenas: std::collections::HashSet<i32>,
 }
 
 #[derive(Component, Debug, Default, Clone, Copy)]
@@ -154,6 +156,8 @@ pub enum SkyEnemyKind {
     Slime,
     Familiar,
     HeroicSpirit,
+    Shadow,
+    BlackMud,
 }
 
 #[derive(Component, Debug, Default, Clone, Copy)]
@@ -173,6 +177,8 @@ impl SkyEnemySpawn {
         {
             "Familiar" => SkyEnemyKind::Familiar,
             "HeroicSpirit" => SkyEnemyKind::HeroicSpirit,
+            "Shadow" => SkyEnemyKind::Shadow,
+            "BlackMud" => SkyEnemyKind::BlackMud,
             _ => SkyEnemyKind::Slime,
         };
 
@@ -201,6 +207,41 @@ pub struct SkyEnemySpawnBundle {
     pub grid_coords: GridCoords,
 }
 
+/// Authored boss placement. The boss joins its arena like any encounter enemy,
+/// so the arena (and the goal behind it) only opens once the boss falls.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SkyBoss {
+    pub arena: i32,
+    pub health_multiplier: f32,
+    pub patrol_range: f32,
+}
+
+impl SkyBoss {
+    fn from_entity(entity: &EntityInstance) -> Self {
+        Self {
+            arena: entity.get_int_field("arena").copied().unwrap_or_default(),
+            health_multiplier: entity
+                .get_float_field("healthMultiplier")
+                .copied()
+                .unwrap_or(1.0)
+                .max(0.25),
+            patrol_range: entity
+                .get_float_field("patrolRange")
+                .copied()
+                .unwrap_or(640.0)
+                .max(0.0),
+        }
+    }
+}
+
+#[derive(Default, Bundle, LdtkEntity)]
+pub struct SkyBossBundle {
+    #[with(SkyBoss::from_entity)]
+    pub boss: SkyBoss,
+    #[grid_coords]
+    pub grid_coords: GridCoords,
+}
+
 #[derive(Component, Debug, Default, Clone, Copy)]
 pub struct SkyEncounterEnemy {
     pub arena: i32,
@@ -6,6 +6,7 @@ pub mod animation;
 pub mod animation_data;
 pub mod audio;
 pub mod enemy;
+pub mod frame_data;
 pub mod health;
 pub mod level;
 pub mod network;
@@ -13,6 +14,7 @@ pub mod physics;
 pub mod player;
 pub mod projectile;
 pub mod shirou;
+pub mod status;
 pub mod ui;
 
 // 对外导出常用组件。
@@ -20,10 +22,12 @@ pub use animation::*;
 pub use animation_data::*;
 pub use audio::*;
 pub use enemy::*;
+pub use frame_data::*;
 pub use health::*;
 pub use level::*;
 pub use physics::*;
 pub use player::*;
 pub use projectile::*;
 pub use shirou::*;
+pub use status::*;
 pub use ui::*;
@@ -205,6 +205,44 @@ impl DamageInvulnerability {
     }
 }
 
+/// 玩家防御动作：按住格挡、短窗口弹反、带无敌帧的闪避。
+///
+/// 各计时器按秒倒数，由 `defense::update_player_defense` 推进；
+/// 弹反与闪避期间格挡不生效。
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct PlayerDefense {
+    pub guarding: bool,
+    pub parry_remaining: f32,
+    pub parry_cooldown: f32,
+    pub dodge_remaining: f32,
+    pub dodge_cooldown: f32,
+}
+
+impl PlayerDefense {
+    pub fn is_parrying(&self) -> bool {
+        self.parry_remaining > 0.0
+    }
+
+    pub fn is_dodging(&self) -> bool {
+        self.dodge_remaining > 0.0
+    }
+
+    pub fn is_guarding(&self) -> bool {
+        self.guarding && !self.is_parrying() && !self.is_dodging()
+    }
+
+    pub fn tick(&mut self, delta_secs: f32) {
+        self.parry_remaining = (self.parry_remaining - delta_secs).max(0.0);
+        self.parry_cooldown = (self.parry_cooldown - delta_secs).max(0.0);
+        self.dodge_remaining = (self.dodge_remaining - delta_secs).max(0.0);
+        self.dodge_cooldown = (self.dodge_cooldown - delta_secs).max(0.0);
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
 /// 攻击动作短时动量保护。
 ///
 /// 用于让 dash、替身、墙面上冲等动作的位移承诺保留几个关键帧，
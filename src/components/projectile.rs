@@ -7,7 +7,7 @@ use bevy::prelude::*;
 pub struct Projectile;
 
 /// 投射物类型
-#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum ProjectileType {
     MagicWave, // 法波
     Fireball,  // 火球
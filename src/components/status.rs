@@ -0,0 +1,183 @@
+//! 状态异常组件（灼烧、减速、眩晕、中毒）
+//!
+//! 玩家与敌人共用同一个 [`StatusEffects`] 组件；叠加规则、持续时间与跳伤数值来自
+//! `config/gameplay_tuning.ron` 的 `status_effects` 段。
+
+use bevy::prelude::*;
+
+use crate::resources::{StackingRule, StatusEffectTuning, StatusRule};
+
+/// 单帧内每种状态最多结算的跳伤次数；超长帧积压的其余跳伤直接丢弃
+const MAX_TICKS_PER_UPDATE: f32 = 8.0;
+
+/// 状态异常种类
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum StatusKind {
+    /// 灼烧：周期跳伤
+    Burn,
+    /// 减速：降低移动速度
+    Slow,
+    /// 眩晕：玩家无法移动与跳跃，敌人进入硬直
+    Stun,
+    /// 中毒：可叠层的周期跳伤
+    Poison,
+}
+
+impl StatusKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            StatusKind::Burn => "BURN",
+            StatusKind::Slow => "SLOW",
+            StatusKind::Stun => "STUN",
+            StatusKind::Poison => "POISON",
+        }
+    }
+
+    /// 精灵染色与 HUD 文字使用的颜色
+    pub fn tint(self) -> Color {
+        match self {
+            StatusKind::Burn => Color::srgb(1.0, 0.45, 0.18),
+            StatusKind::Slow => Color::srgb(0.45, 0.70, 1.0),
+            StatusKind::Stun => Color::srgb(1.0, 0.95, 0.40),
+            StatusKind::Poison => Color::srgb(0.62, 0.30, 0.85),
+        }
+    }
+}
+
+/// 单个生效中的状态异常
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusKind,
+    pub stacks: u32,
+    /// 剩余持续时间（秒）
+    pub remaining: f32,
+    /// 距下一次跳伤的时间（秒）
+    pub tick_timer: f32,
+}
+
+/// 实体身上的全部状态异常，每种至多一条
+#[derive(Component, Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct StatusEffects {
+    pub effects: Vec<StatusEffect>,
+}
+
+impl StatusEffects {
+    /// 按 `rule` 的叠加规则施加 `stacks` 层状态
+    pub fn apply(&mut self, kind: StatusKind, stacks: u32, rule: &StatusRule) {
+        let stacks = stacks.max(1);
+        let max_stacks = rule.max_stacks.max(1);
+        let duration = rule.duration_secs.max(0.0);
+        let Some(effect) = self.effects.iter_mut().find(|effect| effect.kind == kind) else {
+            self.effects.push(StatusEffect {
+                kind,
+                stacks: stacks.min(max_stacks),
+                remaining: duration,
+                tick_timer: rule.tick_interval_secs.max(0.0),
+            });
+            return;
+        };
+
+        match rule.stacking {
+            StackingRule::Refresh => {
+                effect.stacks = effect.stacks.max(stacks).min(max_stacks);
+                effect.remaining = effect.remaining.max(duration);
+            }
+            StackingRule::Intensity => {
+                effect.stacks = (effect.stacks + stacks).min(max_stacks);
+                effect.remaining = effect.remaining.max(duration);
+            }
+            StackingRule::Extend => {
+                effect.stacks = effect.stacks.max(stacks).min(max_stacks);
+                effect.remaining = (effect.remaining + duration).min(rule.max_duration_secs);
+            }
+        }
+    }
+
+    pub fn get(&self, kind: StatusKind) -> Option<&StatusEffect> {
+        self.effects.iter().find(|effect| effect.kind == kind)
+    }
+
+    pub fn has(&self, kind: StatusKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    pub fn is_stunned(&self) -> bool {
+        self.has(StatusKind::Stun)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.effects.is_empty()
+    }
+
+    /// 减速后的移动速度倍率
+    pub fn speed_multiplier(&self, tuning: &StatusEffectTuning) -> f32 {
+        self.get(StatusKind::Slow)
+            .map(|effect| (1.0 - tuning.slow.slow_per_stack * effect.stacks as f32).clamp(0.2, 1.0))
+            .unwrap_or(1.0)
+    }
+
+    /// 推进计时器，返回本帧的跳伤总量并移除到期的状态
+    pub fn tick(&mut self, delta_secs: f32, tuning: &StatusEffectTuning) -> f32 {
+        let mut damage = 0.0;
+        for effect in &mut self.effects {
+            let rule = tuning.rule(effect.kind);
+            effect.remaining -= delta_secs;
+            let interval = rule.tick_interval_secs;
+            if interval <= 0.0 || rule.damage_per_stack <= 0.0 {
+                continue;
+            }
+            effect.tick_timer -= delta_secs;
+            if !effect.tick_timer.is_finite() {
+                effect.tick_timer = interval;
+                continue;
+            }
+            if effect.tick_timer > 0.0 {
+                continue;
+            }
+            // 按欠下的时间一次算出跳数（封顶），不逐跳循环
+            let ticks = ((-effect.tick_timer / interval).floor() + 1.0).min(MAX_TICKS_PER_UPDATE);
+            damage += rule.damage_per_stack * effect.stacks as f32 * ticks;
+            effect.tick_timer += interval * ticks;
+            if effect.tick_timer <= 0.0 {
+                effect.tick_timer = interval;
+            }
+        }
+        self.effects.retain(|effect| effect.remaining > 0.0);
+        damage
+    }
+
+    /// 读档时按调参收敛存档里的状态：每种至多一条，层数、剩余时间与跳伤计时
+    /// 都限制在规则允许的范围内，非法数值的状态直接丢弃
+    pub fn restored(&self, tuning: &StatusEffectTuning) -> Self {
+        let mut restored = Self::default();
+        for effect in &self.effects {
+            if restored.has(effect.kind) || !effect.remaining.is_finite() || effect.remaining <= 0.0
+            {
+                continue;
+            }
+            let rule = tuning.rule(effect.kind);
+            let max_remaining = rule.duration_secs.max(rule.max_duration_secs).max(0.0);
+            let interval = rule.tick_interval_secs.max(0.0);
+            let tick_timer = if effect.tick_timer.is_finite() {
+                effect.tick_timer.clamp(0.0, interval)
+            } else {
+                interval
+            };
+            restored.effects.push(StatusEffect {
+                kind: effect.kind,
+                stacks: effect.stacks.clamp(1, rule.max_stacks.max(1)),
+                remaining: effect.remaining.min(max_remaining),
+                tick_timer,
+            });
+        }
+        restored.effects.retain(|effect| effect.remaining > 0.0);
+        restored
+    }
+
+    /// 剩余时间最长的状态，决定精灵染色
+    pub fn dominant(&self) -> Option<&StatusEffect> {
+        self.effects
+            .iter()
+            .max_by(|a, b| a.remaining.total_cmp(&b.remaining))
+    }
+}
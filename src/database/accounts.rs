@@ -0,0 +1,217 @@
+use super::Database;
+use crate::systems::accounts::{
+    AccountError, AccountIdentity, AuthRequest, AuthenticatedAccount, SESSION_TOKEN_TTL_DAYS,
+    generate_session_token, guest_username, hash_session_token, validate_password,
+    validate_username,
+};
+use crate::systems::save_signing::SaveSigningKey;
+use sqlx::Row;
+use uuid::Uuid;
+
+fn storage_error(error: sqlx::Error) -> AccountError {
+    bevy::log::warn!("Account storage error: {error}");
+    AccountError::Unavailable
+}
+
+impl Database {
+    /// 处理一次认证请求；握手令牌有效时沿用原令牌
+    pub async fn authenticate(
+        &self,
+        request: AuthRequest,
+    ) -> Result<AuthenticatedAccount, AccountError> {
+        match request {
+            AuthRequest::SessionToken(session_token) => {
+                let identity = self.player_for_session_token(&session_token).await?;
+                Ok(AuthenticatedAccount {
+                    identity,
+                    session_token,
+                })
+            }
+            AuthRequest::Register { username, password } => {
+                self.register_player(&username, &password).await
+            }
+            AuthRequest::Login { username, password } => {
+                self.login_player(&username, &password).await
+            }
+            AuthRequest::Guest => self.create_guest_player().await,
+        }
+    }
+
+    /// 注册账号：创建玩家并保存 bcrypt 密码散列
+    pub async fn register_player(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<AuthenticatedAccount, AccountError> {
+        validate_username(username)?;
+        validate_password(password)?;
+
+        let mut transaction = self.pool.begin().await.map_err(storage_error)?;
+        // 已被 get_or_create_player 占用的用户名同样视为已注册
+        let row = sqlx::query(
+            "INSERT INTO players (username) VALUES ($1) ON CONFLICT (username) DO NOTHING RETURNING id",
+        )
+        .bind(username)
+        .fetch_optional(&mut *transaction)
+        .await
+        .map_err(storage_error)?
+        .ok_or(AccountError::UsernameTaken)?;
+        let player_id: Uuid = row.get("id");
+
+        sqlx::query(
+            "INSERT INTO player_credentials (player_id, password_hash) VALUES ($1, crypt($2, gen_salt('bf', 10)))",
+        )
+        .bind(player_id)
+        .bind(password)
+        .execute(&mut *transaction)
+        .await
+        .map_err(storage_error)?;
+        transaction.commit().await.map_err(storage_error)?;
+
+        let identity = AccountIdentity {
+            player_id,
+            username: username.to_string(),
+            is_guest: false,
+        };
+        self.issue_session_token(identity).await
+    }
+
+    /// 用户名 + 密码登录；用户名不存在与密码错误返回同一错误
+    pub async fn login_player(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> Result<AuthenticatedAccount, AccountError> {
+        let row = sqlx::query(
+            r#"
+            SELECT p.id, p.username, p.is_guest
+            FROM players p
+            JOIN player_credentials c ON c.player_id = p.id
+            WHERE p.username = $1 AND c.password_hash = crypt($2, c.password_hash)
+            "#,
+        )
+        .bind(username)
+        .bind(password)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(storage_error)?
+        .ok_or(AccountError::InvalidCredentials)?;
+
+        let identity = AccountIdentity {
+            player_id: row.get("id"),
+            username: row.get("username"),
+            is_guest: row.get("is_guest"),
+        };
+        self.issue_session_token(identity).await
+    }
+
+    /// 创建游客账号（无密码，只能凭会话令牌找回）
+    pub async fn create_guest_player(&self) -> Result<AuthenticatedAccount, AccountError> {
+        let username = guest_username();
+        let row = sqlx::query(
+            "INSERT INTO players (username, is_guest) VALUES ($1, TRUE) RETURNING id, username",
+        )
+        .bind(&username)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(storage_error)?;
+
+        let identity = AccountIdentity {
+            player_id: row.get("id"),
+            username: row.get("username"),
+            is_guest: true,
+        };
+        self.issue_session_token(identity).await
+    }
+
+    /// 为账号签发新的会话令牌
+    pub async fn issue_session_token(
+        &self,
+        identity: AccountIdentity,
+    ) -> Result<AuthenticatedAccount, AccountError> {
+        let session_token = generate_session_token();
+        sqlx::query(
+            r#"
+            INSERT INTO player_auth_tokens (token_hash, player_id, expires_at)
+            VALUES ($1, $2, NOW() + make_interval(days => $3))
+            "#,
+        )
+        .bind(hash_session_token(&session_token))
+        .bind(identity.player_id)
+        .bind(SESSION_TOKEN_TTL_DAYS as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(storage_error)?;
+
+        Ok(AuthenticatedAccount {
+            identity,
+            session_token,
+        })
+    }
+
+    /// 根据握手携带的会话令牌找到账号，并顺延令牌有效期
+    pub async fn player_for_session_token(
+        &self,
+        session_token: &str,
+    ) -> Result<AccountIdentity, AccountError> {
+        let row = sqlx::query(
+            r#"
+            UPDATE player_auth_tokens t
+            SET last_used_at = NOW(), expires_at = NOW() + make_interval(days => $2)
+            FROM players p
+            WHERE t.token_hash = $1 AND t.expires_at > NOW() AND p.id = t.player_id
+            RETURNING p.id, p.username, p.is_guest
+            "#,
+        )
+        .bind(hash_session_token(session_token))
+        .bind(SESSION_TOKEN_TTL_DAYS as i32)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(storage_error)?
+        .ok_or(AccountError::InvalidToken)?;
+
+        Ok(AccountIdentity {
+            player_id: row.get("id"),
+            username: row.get("username"),
+            is_guest: row.get("is_guest"),
+        })
+    }
+
+    /// 注销会话令牌
+    pub async fn revoke_session_token(&self, session_token: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM player_auth_tokens WHERE token_hash = $1")
+            .bind(hash_session_token(session_token))
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// 为账号登记存档签名密钥；首次登记的密钥生效，返回账号登记的是否就是这把密钥
+    pub async fn register_save_key(
+        &self,
+        player_id: Uuid,
+        key: &SaveSigningKey,
+    ) -> Result<bool, sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO player_save_keys (player_id, signing_key)
+            VALUES ($1, $2)
+            ON CONFLICT (player_id) DO NOTHING
+            "#,
+        )
+        .bind(player_id)
+        .bind(key.to_hex())
+        .execute(&self.pool)
+        .await?;
+        Ok(self.save_key(player_id).await?.as_ref() == Some(key))
+    }
+
+    /// 账号登记的存档签名密钥
+    pub async fn save_key(&self, player_id: Uuid) -> Result<Option<SaveSigningKey>, sqlx::Error> {
+        let row = sqlx::query("SELECT signing_key FROM player_save_keys WHERE player_id = $1")
+            .bind(player_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.and_then(|row| SaveSigningKey::from_hex(row.get::<&str, _>("signing_key"))))
+    }
+}
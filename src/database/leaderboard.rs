@@ -0,0 +1,405 @@
+//! 排行榜
+//!
+//! 排名来自 `leaderboard_best_scores` / `leaderboard_fastest_clears` 两个视图；
+//! 每个榜单的前 [`CACHE_DEPTH`] 名缓存在 Redis 有序集合 `leaderboard:{kind}:{character}`，
+//! 玩家名与角色放在同名 `:meta` 哈希里。缓存按 TTL 过期，会话写入新成绩时主动失效。
+
+use super::Database;
+use super::redis::SharedRedisConnection;
+use crate::protocol::{LeaderboardEntry, LeaderboardKind, LeaderboardPage, LeaderboardQuery};
+use crate::systems::session_recording::SKY_CITY_LEVEL_ID;
+use redis::aio::MultiplexedConnection;
+use sqlx::{AssertSqlSafe, Row};
+use std::{env, error::Error, sync::Arc};
+use uuid::Uuid;
+
+/// 单次请求最多返回的条目
+pub const MAX_LEADERBOARD_LIMIT: u16 = 100;
+/// 缓存的榜单深度
+pub const CACHE_DEPTH: i64 = 1000;
+const CACHE_TTL_SECS: i64 = 60;
+/// 不区分角色的总榜在缓存键中的名称
+const ALL_CHARACTERS_KEY: &str = "all";
+
+type LeaderboardResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// 带玩家 id 的排名行（玩家 id 不发给客户端）
+#[derive(Debug, Clone)]
+pub struct RankedRow {
+    pub player_id: Uuid,
+    pub entry: LeaderboardEntry,
+}
+
+const BEST_SCORE_CTE: &str = r#"
+    WITH best AS (
+        SELECT DISTINCT ON (player_id) player_id, username, character_type, value, achieved_at
+        FROM leaderboard_best_scores
+        WHERE ($1::TEXT IS NULL OR character_type = $1)
+        ORDER BY player_id, value DESC, achieved_at
+    ), ranked AS (
+        SELECT *, ROW_NUMBER() OVER (ORDER BY value DESC, achieved_at) AS rank FROM best
+    )
+"#;
+
+const FASTEST_CLEAR_CTE: &str = r#"
+    WITH best AS (
+        SELECT DISTINCT ON (player_id) player_id, username, character_type, value, achieved_at
+        FROM leaderboard_fastest_clears
+        WHERE ($1::TEXT IS NULL OR character_type = $1) AND cleared_level = $2
+        ORDER BY player_id, value, achieved_at
+    ), ranked AS (
+        SELECT *, ROW_NUMBER() OVER (ORDER BY value, achieved_at) AS rank FROM best
+    )
+"#;
+
+/// 榜单的 CTE 与其占用的参数个数（只由静态片段拼接，参数一律绑定）
+fn ranked_cte(kind: LeaderboardKind) -> (&'static str, usize) {
+    match kind {
+        LeaderboardKind::BestScore => (BEST_SCORE_CTE, 1),
+        LeaderboardKind::FastestClear => (FASTEST_CLEAR_CTE, 2),
+    }
+}
+
+fn ranked_row(row: &sqlx::postgres::PgRow) -> RankedRow {
+    RankedRow {
+        player_id: row.get("player_id"),
+        entry: LeaderboardEntry {
+            rank: row.get::<i64, _>("rank") as u32,
+            username: row.get("username"),
+            character: row.get("character_type"),
+            value: row.get("value"),
+        },
+    }
+}
+
+fn cache_key(kind: LeaderboardKind, character: Option<&str>) -> String {
+    let kind = match kind {
+        LeaderboardKind::BestScore => "best_score",
+        LeaderboardKind::FastestClear => "fastest_clear",
+    };
+    format!(
+        "leaderboard:{}:{}",
+        kind,
+        character.unwrap_or(ALL_CHARACTERS_KEY)
+    )
+}
+
+impl Database {
+    /// 榜单前 `limit` 名（同分按达成时间先后）
+    pub async fn leaderboard_rows(
+        &self,
+        query: &LeaderboardQuery,
+        limit: i64,
+    ) -> Result<Vec<RankedRow>, sqlx::Error> {
+        let (cte, used) = ranked_cte(query.kind);
+        let sql = format!(
+            "{cte} SELECT player_id, username, character_type, value, rank FROM ranked ORDER BY rank LIMIT ${}",
+            used + 1
+        );
+        let mut statement = sqlx::query(AssertSqlSafe(sql)).bind(query.character.as_deref());
+        if query.kind == LeaderboardKind::FastestClear {
+            statement = statement.bind(SKY_CITY_LEVEL_ID);
+        }
+        let rows = statement.bind(limit).fetch_all(&self.pool).await?;
+
+        Ok(rows.iter().map(ranked_row).collect())
+    }
+
+    /// 某位玩家在榜单中的名次
+    pub async fn leaderboard_rank(
+        &self,
+        query: &LeaderboardQuery,
+        player_id: Uuid,
+    ) -> Result<Option<RankedRow>, sqlx::Error> {
+        let (cte, used) = ranked_cte(query.kind);
+        let sql = format!(
+            "{cte} SELECT player_id, username, character_type, value, rank FROM ranked WHERE player_id = ${}",
+            used + 1
+        );
+        let mut statement = sqlx::query(AssertSqlSafe(sql)).bind(query.character.as_deref());
+        if query.kind == LeaderboardKind::FastestClear {
+            statement = statement.bind(SKY_CITY_LEVEL_ID);
+        }
+        let row = statement.bind(player_id).fetch_optional(&self.pool).await?;
+
+        Ok(row.as_ref().map(ranked_row))
+    }
+
+    /// 会话中途上报的角色
+    pub async fn set_session_character(
+        &self,
+        session_id: Uuid,
+        character_type: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE game_sessions SET character_type = $2 WHERE id = $1")
+            .bind(session_id)
+            .bind(character_type)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// 记录通关；同一会话多次通关只保留最快一次
+    pub async fn record_level_clear(
+        &self,
+        session_id: Uuid,
+        level_id: &str,
+        clear_time: f32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            UPDATE game_sessions
+            SET cleared_level = $2, clear_time = LEAST(COALESCE(clear_time, $3), $3)
+            WHERE id = $1
+            "#,
+        )
+        .bind(session_id)
+        .bind(level_id)
+        .bind(clear_time)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+/// Postgres 排行榜查询 + Redis 有序集合缓存
+pub struct LeaderboardService {
+    database: Arc<Database>,
+    /// 缓存读写共用一条多路复用连接，出错后下次使用时重连
+    cache: Option<SharedRedisConnection>,
+}
+
+impl LeaderboardService {
+    /// 缓存地址读取 `REDIS_URL`；地址无效时直接查 Postgres
+    pub fn new(database: Arc<Database>) -> Self {
+        let redis_url =
+            env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let cache = match redis::Client::open(redis_url) {
+            Ok(client) => Some(SharedRedisConnection::new(client)),
+            Err(error) => {
+                bevy::log::warn!("Leaderboard cache disabled: {error}");
+                None
+            }
+        };
+        Self { database, cache }
+    }
+
+    /// 读取一页排行榜以及请求者自己的名次
+    pub async fn page(
+        &self,
+        query: LeaderboardQuery,
+        limit: u16,
+        player_id: Option<Uuid>,
+    ) -> LeaderboardResult<LeaderboardPage> {
+        let limit = limit.clamp(1, MAX_LEADERBOARD_LIMIT) as usize;
+
+        if let Some(cache) = &self.cache {
+            match self.cached_page(cache, &query, limit, player_id).await {
+                Ok(Some(page)) => return Ok(page),
+                Ok(None) => {}
+                Err(error) => {
+                    cache.reset();
+                    bevy::log::warn!("Leaderboard cache read failed: {error}");
+                }
+            }
+        }
+
+        let rows = self.database.leaderboard_rows(&query, CACHE_DEPTH).await?;
+        if let Some(cache) = &self.cache
+            && let Err(error) = fill_cache(cache, &query, &rows).await
+        {
+            bevy::log::warn!("Leaderboard cache fill failed: {error}");
+        }
+
+        let own_entry = match player_id {
+            Some(player_id) => match rows.iter().find(|row| row.player_id == player_id) {
+                Some(row) => Some(row.entry.clone()),
+                None if rows.len() as i64 >= CACHE_DEPTH => self
+                    .database
+                    .leaderboard_rank(&query, player_id)
+                    .await?
+                    .map(|row| row.entry),
+                None => None,
+            },
+            None => None,
+        };
+
+        Ok(LeaderboardPage {
+            entries: rows.into_iter().take(limit).map(|row| row.entry).collect(),
+            query,
+            own_entry,
+        })
+    }
+
+    /// 有新成绩写入时丢弃该角色榜与总榜的缓存
+    pub async fn invalidate(&self, kind: LeaderboardKind, character_type: &str) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        let mut pipeline = redis::pipe();
+        for key in [cache_key(kind, Some(character_type)), cache_key(kind, None)] {
+            pipeline
+                .cmd("DEL")
+                .arg(&key)
+                .arg(format!("{key}:meta"))
+                .ignore();
+        }
+
+        if let Err(error) = cache.query_pipeline::<()>(&pipeline).await {
+            bevy::log::warn!("Leaderboard cache invalidation failed: {error}");
+        }
+    }
+
+    /// 命中缓存时返回整页；缓存不存在时返回 `None`。出错时由调用方丢弃共享连接
+    async fn cached_page(
+        &self,
+        cache: &SharedRedisConnection,
+        query: &LeaderboardQuery,
+        limit: usize,
+        player_id: Option<Uuid>,
+    ) -> LeaderboardResult<Option<LeaderboardPage>> {
+        let mut connection = cache.get().await?;
+        let key = cache_key(query.kind, query.character.as_deref());
+        let cached_len: i64 = redis::cmd("ZCARD")
+            .arg(&key)
+            .query_async(&mut connection)
+            .await?;
+        if cached_len == 0 {
+            return Ok(None);
+        }
+
+        let descending = query.kind == LeaderboardKind::BestScore;
+        let mut range = redis::cmd("ZRANGE");
+        range.arg(&key).arg(0).arg(limit as i64 - 1);
+        if descending {
+            range.arg("REV");
+        }
+        let members: Vec<(String, f64)> =
+            range.arg("WITHSCORES").query_async(&mut connection).await?;
+
+        let mut ranked: Vec<(String, u32, f64)> = members
+            .into_iter()
+            .enumerate()
+            .map(|(index, (member, value))| (member, index as u32 + 1, value))
+            .collect();
+
+        // 自己的名次与分数一次往返取回，随整页一起查玩家信息
+        let own_cached = match player_id {
+            Some(player_id) => {
+                let member = player_id.to_string();
+                let (rank, value): (Option<u32>, Option<f64>) = redis::pipe()
+                    .cmd(if descending { "ZREVRANK" } else { "ZRANK" })
+                    .arg(&key)
+                    .arg(&member)
+                    .cmd("ZSCORE")
+                    .arg(&key)
+                    .arg(&member)
+                    .query_async(&mut connection)
+                    .await?;
+                rank.zip(value)
+                    .map(|(rank, value)| (member, rank + 1, value))
+            }
+            None => None,
+        };
+        let has_own_cached = own_cached.is_some();
+        ranked.extend(own_cached);
+
+        let mut entries = cached_entries(&mut connection, &key, ranked).await?;
+        let own_entry = match player_id {
+            Some(_) if has_own_cached => entries.pop(),
+            // 缓存只保存前 CACHE_DEPTH 名，更靠后的名次回落到 Postgres
+            Some(player_id) if cached_len >= CACHE_DEPTH => self
+                .database
+                .leaderboard_rank(query, player_id)
+                .await?
+                .map(|row| row.entry),
+            _ => None,
+        };
+
+        Ok(Some(LeaderboardPage {
+            query: query.clone(),
+            entries,
+            own_entry,
+        }))
+    }
+}
+
+/// 用一次 HMGET 取回一组缓存名次（成员、名次、分数）对应的玩家名与角色
+async fn cached_entries(
+    connection: &mut MultiplexedConnection,
+    key: &str,
+    ranked: Vec<(String, u32, f64)>,
+) -> LeaderboardResult<Vec<LeaderboardEntry>> {
+    if ranked.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut hmget = redis::cmd("HMGET");
+    hmget.arg(format!("{key}:meta"));
+    for (member, ..) in &ranked {
+        hmget.arg(member);
+    }
+    let metas: Vec<Option<String>> = hmget.query_async(connection).await?;
+
+    Ok(ranked
+        .into_iter()
+        .zip(metas)
+        .map(|((_, rank, value), meta)| {
+            let (username, character) = meta
+                .and_then(|meta| serde_json::from_str::<(String, String)>(&meta).ok())
+                .unwrap_or_default();
+            LeaderboardEntry {
+                rank,
+                username,
+                character,
+                value,
+            }
+        })
+        .collect())
+}
+
+async fn fill_cache(
+    cache: &SharedRedisConnection,
+    query: &LeaderboardQuery,
+    rows: &[RankedRow],
+) -> LeaderboardResult<()> {
+    if rows.is_empty() {
+        return Ok(());
+    }
+
+    let key = cache_key(query.kind, query.character.as_deref());
+    let meta_key = format!("{key}:meta");
+    let mut pipeline = redis::pipe();
+    pipeline
+        .atomic()
+        .cmd("DEL")
+        .arg(&key)
+        .arg(&meta_key)
+        .ignore();
+    for row in rows {
+        let member = row.player_id.to_string();
+        let meta = serde_json::to_string(&(&row.entry.username, &row.entry.character))?;
+        pipeline
+            .cmd("ZADD")
+            .arg(&key)
+            .arg(row.entry.value)
+            .arg(&member)
+            .ignore()
+            .cmd("HSET")
+            .arg(&meta_key)
+            .arg(&member)
+            .arg(meta)
+            .ignore();
+    }
+    pipeline
+        .cmd("EXPIRE")
+        .arg(&key)
+        .arg(CACHE_TTL_SECS)
+        .ignore()
+        .cmd("EXPIRE")
+        .arg(&meta_key)
+        .arg(CACHE_TTL_SECS)
+        .ignore();
+    cache.query_pipeline::<()>(&pipeline).await?;
+    Ok(())
+}
@@ -3,9 +3,50 @@ use sqlx::{PgPool, postgres::PgPoolOptions};
 #[cfg(feature = "server")]
 use std::{env, error::Error, time::Duration};
 
+/// 手写 SQL 的执行入口：启用 `sqlx-checked` 时展开为 `sqlx::query!`，编译期按迁移后的
+/// 表结构校验语句、参数与返回类型（构建需要 `DATABASE_URL` 或仓库内的 `.sqlx/` 离线数据）；
+/// 否则展开为运行时 `sqlx::query` 并逐个 `bind`。两种展开的 SQL 是同一个字面量。
+macro_rules! checked_query {
+    ($sql:literal $(, $arg:expr)* $(,)?) => {{
+        #[cfg(feature = "sqlx-checked")]
+        let query = ::sqlx::query!($sql $(, $arg)*);
+        #[cfg(not(feature = "sqlx-checked"))]
+        let query = ::sqlx::query($sql)$(.bind($arg))*;
+        query
+    }};
+}
+
+/// [`checked_query!`] 的 `query_as` 版本，运行时展开依赖目标类型的 `FromRow`
+macro_rules! checked_query_as {
+    ($out:path, $sql:literal $(, $arg:expr)* $(,)?) => {{
+        #[cfg(feature = "sqlx-checked")]
+        let query = ::sqlx::query_as!($out, $sql $(, $arg)*);
+        #[cfg(not(feature = "sqlx-checked"))]
+        let query = ::sqlx::query_as::<_, $out>($sql)$(.bind($arg))*;
+        query
+    }};
+}
+
+/// [`checked_query!`] 的单列版本，运行时展开的列类型由调用处推断
+macro_rules! checked_query_scalar {
+    ($sql:literal $(, $arg:expr)* $(,)?) => {{
+        #[cfg(feature = "sqlx-checked")]
+        let query = ::sqlx::query_scalar!($sql $(, $arg)*);
+        #[cfg(not(feature = "sqlx-checked"))]
+        let query = ::sqlx::query_scalar($sql)$(.bind($arg))*;
+        query
+    }};
+}
+
+pub(crate) use checked_query;
+
+pub mod accounts;
+pub mod leaderboard;
 pub mod models;
 pub mod operations;
 
+#[cfg(feature = "server")]
+pub mod presence;
 #[cfg(feature = "server")]
 pub mod redis;
 
@@ -25,8 +66,12 @@ impl Database {
             .connect(&database_url)
             .await?;
 
-        sqlx::migrate!("./migrations").run(&pool).await?;
+        Ok(Self::with_pool(pool).await?)
+    }
 
+    /// 在已有连接池上执行版本化迁移（测试工具用它连接临时 schema）。
+    pub async fn with_pool(pool: PgPool) -> Result<Self, sqlx::migrate::MigrateError> {
+        sqlx::migrate!("./migrations").run(&pool).await?;
         Ok(Self { pool })
     }
 }
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 /// 玩家模型
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct Player {
     pub id: Uuid,
     pub username: String,
@@ -12,7 +12,7 @@ pub struct Player {
 }
 
 /// 游戏会话模型
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct GameSession {
     pub id: Uuid,
     pub player_id: Uuid,
@@ -38,7 +38,7 @@ pub struct PlayerAction {
 }
 
 /// 存档模型
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct SaveGame {
     pub id: Uuid,
     pub player_id: Uuid,
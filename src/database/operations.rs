@@ -1,5 +1,6 @@
 use super::{Database, models::*};
-use sqlx::Row;
+use crate::systems::session_recording::RecordedAction;
+use sqlx::{Postgres, QueryBuilder};
 use uuid::Uuid;
 
 impl Database {
@@ -11,36 +12,24 @@ impl Database {
         }
 
         // 创建新玩家
-        let row = sqlx::query(
-            "INSERT INTO players (username) VALUES ($1) RETURNING id, username, created_at, updated_at"
+        checked_query_as!(
+            Player,
+            "INSERT INTO players (username) VALUES ($1) RETURNING id, username, created_at, updated_at",
+            username
         )
-        .bind(username)
         .fetch_one(&self.pool)
-        .await?;
-
-        Ok(Player {
-            id: row.get("id"),
-            username: row.get("username"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        })
+        .await
     }
 
     /// 根据用户名获取玩家
     pub async fn get_player_by_username(&self, username: &str) -> Result<Player, sqlx::Error> {
-        let row = sqlx::query(
+        checked_query_as!(
+            Player,
             "SELECT id, username, created_at, updated_at FROM players WHERE username = $1",
+            username
         )
-        .bind(username)
         .fetch_one(&self.pool)
-        .await?;
-
-        Ok(Player {
-            id: row.get("id"),
-            username: row.get("username"),
-            created_at: row.get("created_at"),
-            updated_at: row.get("updated_at"),
-        })
+        .await
     }
 
     /// 创建游戏会话
@@ -49,29 +38,18 @@ impl Database {
         player_id: Uuid,
         character_type: &str,
     ) -> Result<GameSession, sqlx::Error> {
-        let row = sqlx::query(
+        checked_query_as!(
+            GameSession,
             r#"
-            INSERT INTO game_sessions (player_id, character_type) 
-            VALUES ($1, $2) 
+            INSERT INTO game_sessions (player_id, character_type)
+            VALUES ($1, $2)
             RETURNING id, player_id, character_type, start_time, end_time, distance_traveled, jump_count, play_time, score
-            "#
+            "#,
+            player_id,
+            character_type
         )
-        .bind(player_id)
-        .bind(character_type)
         .fetch_one(&self.pool)
-        .await?;
-
-        Ok(GameSession {
-            id: row.get("id"),
-            player_id: row.get("player_id"),
-            character_type: row.get("character_type"),
-            start_time: row.get("start_time"),
-            end_time: row.get("end_time"),
-            distance_traveled: row.get("distance_traveled"),
-            jump_count: row.get("jump_count"),
-            play_time: row.get("play_time"),
-            score: row.get("score"),
-        })
+        .await
     }
 
     /// 更新游戏会话
@@ -83,18 +61,18 @@ impl Database {
         play_time: f32,
         score: i32,
     ) -> Result<(), sqlx::Error> {
-        sqlx::query(
+        checked_query!(
             r#"
-            UPDATE game_sessions 
+            UPDATE game_sessions
             SET distance_traveled = $2, jump_count = $3, play_time = $4, score = $5, end_time = NOW()
             WHERE id = $1
-            "#
+            "#,
+            session_id,
+            distance,
+            jumps,
+            play_time,
+            score
         )
-        .bind(session_id)
-        .bind(distance)
-        .bind(jumps)
-        .bind(play_time)
-        .bind(score)
         .execute(&self.pool)
         .await?;
 
@@ -111,23 +89,48 @@ impl Database {
     ) -> Result<(), sqlx::Error> {
         let (pos_x, pos_y) = player_pos.unwrap_or((0.0, 0.0));
 
-        sqlx::query(
+        checked_query!(
             r#"
             INSERT INTO player_actions (session_id, action_type, action_data, player_position_x, player_position_y)
             VALUES ($1, $2, $3, $4, $5)
-            "#
+            "#,
+            session_id,
+            action_type,
+            action_data,
+            pos_x,
+            pos_y
         )
-        .bind(session_id)
-        .bind(action_type)
-        .bind(action_data)
-        .bind(pos_x)
-        .bind(pos_y)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
+    /// 批量记录玩家操作（一条 INSERT 写入整批）
+    pub async fn log_player_actions(
+        &self,
+        actions: &[(Uuid, RecordedAction)],
+    ) -> Result<(), sqlx::Error> {
+        if actions.is_empty() {
+            return Ok(());
+        }
+
+        let mut builder = QueryBuilder::<Postgres>::new(
+            "INSERT INTO player_actions (session_id, action_type, action_data, timestamp, player_position_x, player_position_y) ",
+        );
+        builder.push_values(actions, |mut row, (session_id, action)| {
+            row.push_bind(*session_id)
+                .push_bind(action.action_type)
+                .push_bind(action.action_data.clone())
+                .push_bind(action.timestamp)
+                .push_bind(action.position.map(|(x, _)| x))
+                .push_bind(action.position.map(|(_, y)| y));
+        });
+        builder.build().execute(&self.pool).await?;
+
+        Ok(())
+    }
+
     /// 保存游戏存档
     pub async fn save_game(
         &self,
@@ -138,22 +141,20 @@ impl Database {
         let game_data_json =
             serde_json::to_value(game_data).map_err(|error| sqlx::Error::Encode(error.into()))?;
 
-        let row = sqlx::query(
+        checked_query_scalar!(
             r#"
             INSERT INTO save_games (player_id, save_name, game_data)
             VALUES ($1, $2, $3)
-            ON CONFLICT (player_id, save_name) 
+            ON CONFLICT (player_id, save_name)
             DO UPDATE SET game_data = $3, updated_at = NOW()
             RETURNING id
             "#,
+            player_id,
+            save_name,
+            game_data_json
         )
-        .bind(player_id)
-        .bind(save_name)
-        .bind(game_data_json)
         .fetch_one(&self.pool)
-        .await?;
-
-        Ok(row.get("id"))
+        .await
     }
 
     /// 加载游戏存档
@@ -162,14 +163,14 @@ impl Database {
         player_id: Uuid,
         save_name: &str,
     ) -> Result<GameData, sqlx::Error> {
-        let row =
-            sqlx::query("SELECT game_data FROM save_games WHERE player_id = $1 AND save_name = $2")
-                .bind(player_id)
-                .bind(save_name)
-                .fetch_one(&self.pool)
-                .await?;
-
-        let game_data_json: serde_json::Value = row.get("game_data");
+        let game_data_json: serde_json::Value = checked_query_scalar!(
+            "SELECT game_data FROM save_games WHERE player_id = $1 AND save_name = $2",
+            player_id,
+            save_name
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
         let game_data = serde_json::from_value(game_data_json)
             .map_err(|error| sqlx::Error::Decode(error.into()))?;
 
@@ -178,25 +179,12 @@ impl Database {
 
     /// 获取玩家的所有存档
     pub async fn get_player_saves(&self, player_id: Uuid) -> Result<Vec<SaveGame>, sqlx::Error> {
-        let rows = sqlx::query(
-            "SELECT id, player_id, save_name, game_data, created_at, updated_at FROM save_games WHERE player_id = $1 ORDER BY updated_at DESC"
+        checked_query_as!(
+            SaveGame,
+            "SELECT id, player_id, save_name, game_data, created_at, updated_at FROM save_games WHERE player_id = $1 ORDER BY updated_at DESC",
+            player_id
         )
-        .bind(player_id)
         .fetch_all(&self.pool)
-        .await?;
-
-        let saves = rows
-            .into_iter()
-            .map(|row| SaveGame {
-                id: row.get("id"),
-                player_id: row.get("player_id"),
-                save_name: row.get("save_name"),
-                game_data: row.get("game_data"),
-                created_at: row.get("created_at"),
-                updated_at: row.get("updated_at"),
-            })
-            .collect();
-
-        Ok(saves)
+        .await
     }
 }
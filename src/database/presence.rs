@@ -0,0 +1,811 @@
+//! 玩家在线状态与多服务器广播
+//!
+//! Redis 键：
+//! - `presence:{player_id}`：玩家当前所在的服务器（JSON [`PresenceEntry`]），带 TTL，
+//!   由所在服务器的心跳续期；
+//! - `server:{server_id}`：服务器登记信息（JSON [`ServerInfo`]），心跳续期，进程崩溃后自动过期；
+//! - `server:{server_id}:players`：该服务器上的玩家 id 集合；
+//! - `servers`：登记过的服务器 id 集合，读取时顺带清理已过期的成员。
+//!
+//! 跨服务器的聊天与大厅事件走 pub/sub 频道 `cluster:chat` / `cluster:lobby`，
+//! 消息带上发送方 `server_id`，各服务器只把其他实例发出的消息转发给本地客户端。
+//! 所有命令共用一条多路复用连接（断开后自动重连）；测试用进程内的 `LocalPresence` 代替 Redis。
+
+use super::redis::SharedRedisConnection;
+use crate::protocol::GamePacket;
+use crate::systems::accounts::AccountIdentity;
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::env;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+pub const CHAT_CHANNEL: &str = "cluster:chat";
+pub const LOBBY_CHANNEL: &str = "cluster:lobby";
+const SERVERS_KEY: &str = "servers";
+const SUBSCRIBE_RETRY: Duration = Duration::from_secs(2);
+
+pub type PresenceResult<T> = Result<T, redis::RedisError>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct PresenceConfig {
+    /// 玩家在线记录的 TTL（所在服务器停止心跳后多久视为离线）
+    pub presence_ttl: Duration,
+    /// 服务器登记的 TTL
+    pub server_ttl: Duration,
+    /// 心跳间隔，应明显小于两个 TTL
+    pub heartbeat_interval: Duration,
+}
+
+impl Default for PresenceConfig {
+    fn default() -> Self {
+        Self {
+            presence_ttl: Duration::from_secs(30),
+            server_ttl: Duration::from_secs(15),
+            heartbeat_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// 玩家目录中的一条在线记录
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresenceEntry {
+    pub player_id: Uuid,
+    pub username: String,
+    pub server_id: String,
+    pub client_id: u64,
+}
+
+/// 服务器登记信息
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub server_id: String,
+    pub address: String,
+    pub player_count: u32,
+    pub started_at: DateTime<Utc>,
+}
+
+/// pub/sub 频道上的消息
+#[derive(Debug, Serialize, Deserialize)]
+struct ClusterMessage {
+    origin: String,
+    packet: GamePacket,
+}
+
+fn presence_key(player_id: Uuid) -> String {
+    format!("presence:{player_id}")
+}
+
+fn server_key(server_id: &str) -> String {
+    format!("server:{server_id}")
+}
+
+fn server_players_key(server_id: &str) -> String {
+    format!("server:{server_id}:players")
+}
+
+/// 可以跨服务器转发的数据包所属频道
+fn cluster_channel(packet: &GamePacket) -> Option<&'static str> {
+    match packet {
+        GamePacket::Chat { .. } => Some(CHAT_CHANNEL),
+        GamePacket::Lobby(_) => Some(LOBBY_CHANNEL),
+        _ => None,
+    }
+}
+
+/// 解析频道消息；本服务器自己发出的消息返回 `None`（发送时已在本地投递）
+fn decode_cluster_message(own_server_id: &str, payload: &str) -> Option<GamePacket> {
+    match serde_json::from_str::<ClusterMessage>(payload) {
+        Ok(message) if message.origin != own_server_id => Some(message.packet),
+        Ok(_) => None,
+        Err(error) => {
+            bevy::log::warn!("Ignoring malformed cluster message: {error}");
+            None
+        }
+    }
+}
+
+/// 进程内的 Redis 替身，只在测试中使用
+#[cfg(test)]
+mod local {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Arc, Mutex, MutexGuard};
+    use std::time::{Duration, Instant};
+    use tokio::sync::{broadcast, mpsc};
+
+    use super::decode_cluster_message;
+    use crate::protocol::GamePacket;
+
+    /// 同一个实例可被多个 `PresenceService` 共享，模拟多台服务器
+    #[derive(Clone)]
+    pub struct LocalPresence {
+        state: Arc<Mutex<LocalState>>,
+        channel: broadcast::Sender<String>,
+    }
+
+    #[derive(Default)]
+    struct LocalState {
+        values: HashMap<String, (String, Instant)>,
+        sets: HashMap<String, (HashSet<String>, Option<Instant>)>,
+    }
+
+    impl Default for LocalPresence {
+        fn default() -> Self {
+            let (channel, _) = broadcast::channel(256);
+            Self {
+                state: Arc::default(),
+                channel,
+            }
+        }
+    }
+
+    impl LocalPresence {
+        fn lock(&self) -> MutexGuard<'_, LocalState> {
+            self.state
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+        }
+
+        pub(super) fn set_px(&self, key: &str, value: &str, ttl: Duration) {
+            self.lock()
+                .values
+                .insert(key.to_string(), (value.to_string(), Instant::now() + ttl));
+        }
+
+        pub(super) fn get_many(&self, keys: &[String]) -> Vec<Option<String>> {
+            let mut state = self.lock();
+            let now = Instant::now();
+            state.values.retain(|_, (_, expires_at)| *expires_at > now);
+            keys.iter()
+                .map(|key| state.values.get(key).map(|(value, _)| value.clone()))
+                .collect()
+        }
+
+        pub(super) fn del(&self, keys: &[String]) {
+            let mut state = self.lock();
+            for key in keys {
+                state.values.remove(key);
+                state.sets.remove(key);
+            }
+        }
+
+        pub(super) fn pexpire(&self, keys: &[String], ttl: Duration) {
+            let mut state = self.lock();
+            let expires_at = Instant::now() + ttl;
+            for key in keys {
+                if let Some((_, value_expiry)) = state.values.get_mut(key) {
+                    *value_expiry = expires_at;
+                }
+                if let Some((_, set_expiry)) = state.sets.get_mut(key) {
+                    *set_expiry = Some(expires_at);
+                }
+            }
+        }
+
+        /// 取未过期的集合；过期的集合直接删除
+        fn live_set<'a>(state: &'a mut LocalState, key: &str) -> Option<&'a mut HashSet<String>> {
+            let now = Instant::now();
+            if state
+                .sets
+                .get(key)
+                .is_some_and(|(_, expiry)| expiry.is_some_and(|expiry| expiry <= now))
+            {
+                state.sets.remove(key);
+            }
+            state.sets.get_mut(key).map(|(members, _)| members)
+        }
+
+        pub(super) fn sadd(&self, key: &str, member: &str) {
+            let mut state = self.lock();
+            match Self::live_set(&mut state, key) {
+                Some(members) => {
+                    members.insert(member.to_string());
+                }
+                None => {
+                    let members = HashSet::from([member.to_string()]);
+                    state.sets.insert(key.to_string(), (members, None));
+                }
+            }
+        }
+
+        pub(super) fn srem(&self, key: &str, members: &[String]) {
+            let mut state = self.lock();
+            if let Some(set) = Self::live_set(&mut state, key) {
+                for member in members {
+                    set.remove(member);
+                }
+            }
+        }
+
+        pub(super) fn smembers(&self, key: &str) -> Vec<String> {
+            let mut state = self.lock();
+            Self::live_set(&mut state, key)
+                .map(|members| members.iter().cloned().collect())
+                .unwrap_or_default()
+        }
+
+        pub(super) fn publish(&self, payload: String) {
+            // 没有订阅者时发送失败，与 Redis PUBLISH 返回 0 一致
+            let _ = self.channel.send(payload);
+        }
+
+        /// 在后台任务中把频道消息转发给 `tx`，与 Redis 订阅一样跳过本服务器发出的消息
+        pub(super) fn forward_messages(
+            &self,
+            server_id: String,
+            tx: mpsc::UnboundedSender<GamePacket>,
+        ) {
+            let mut messages = self.channel.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    match messages.recv().await {
+                        Ok(payload) => {
+                            if let Some(packet) = decode_cluster_message(&server_id, &payload)
+                                && tx.send(packet).is_err()
+                            {
+                                return;
+                            }
+                        }
+                        Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                        Err(broadcast::error::RecvError::Closed) => return,
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+pub use local::LocalPresence;
+
+enum PresenceBackend {
+    Redis(SharedRedisConnection),
+    #[cfg(test)]
+    Local(LocalPresence),
+}
+
+impl PresenceBackend {
+    async fn set_px(&self, key: &str, value: &str, ttl: Duration) -> PresenceResult<()> {
+        match self {
+            PresenceBackend::Redis(redis) => {
+                redis
+                    .query(
+                        redis::cmd("SET")
+                            .arg(key)
+                            .arg(value)
+                            .arg("PX")
+                            .arg(ttl.as_millis() as u64),
+                    )
+                    .await
+            }
+            #[cfg(test)]
+            PresenceBackend::Local(store) => {
+                store.set_px(key, value, ttl);
+                Ok(())
+            }
+        }
+    }
+
+    async fn get_many(&self, keys: &[String]) -> PresenceResult<Vec<Option<String>>> {
+        if keys.is_empty() {
+            return Ok(Vec::new());
+        }
+        match self {
+            PresenceBackend::Redis(redis) => redis.query(redis::cmd("MGET").arg(keys)).await,
+            #[cfg(test)]
+            PresenceBackend::Local(store) => Ok(store.get_many(keys)),
+        }
+    }
+
+    async fn del(&self, keys: &[String]) -> PresenceResult<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        match self {
+            PresenceBackend::Redis(redis) => redis.query(redis::cmd("DEL").arg(keys)).await,
+            #[cfg(test)]
+            PresenceBackend::Local(store) => {
+                store.del(keys);
+                Ok(())
+            }
+        }
+    }
+
+    async fn pexpire(&self, keys: &[String], ttl: Duration) -> PresenceResult<()> {
+        if keys.is_empty() {
+            return Ok(());
+        }
+        match self {
+            PresenceBackend::Redis(redis) => {
+                let mut pipeline = redis::pipe();
+                for key in keys {
+                    pipeline
+                        .cmd("PEXPIRE")
+                        .arg(key)
+                        .arg(ttl.as_millis() as u64)
+                        .ignore();
+                }
+                redis.query_pipeline(&pipeline).await
+            }
+            #[cfg(test)]
+            PresenceBackend::Local(store) => {
+                store.pexpire(keys, ttl);
+                Ok(())
+            }
+        }
+    }
+
+    async fn sadd(&self, key: &str, member: &str) -> PresenceResult<()> {
+        match self {
+            PresenceBackend::Redis(redis) => {
+                redis.query(redis::cmd("SADD").arg(key).arg(member)).await
+            }
+            #[cfg(test)]
+            PresenceBackend::Local(store) => {
+                store.sadd(key, member);
+                Ok(())
+            }
+        }
+    }
+
+    async fn srem(&self, key: &str, members: &[String]) -> PresenceResult<()> {
+        if members.is_empty() {
+            return Ok(());
+        }
+        match self {
+            PresenceBackend::Redis(redis) => {
+                redis.query(redis::cmd("SREM").arg(key).arg(members)).await
+            }
+            #[cfg(test)]
+            PresenceBackend::Local(store) => {
+                store.srem(key, members);
+                Ok(())
+            }
+        }
+    }
+
+    async fn smembers(&self, key: &str) -> PresenceResult<Vec<String>> {
+        match self {
+            PresenceBackend::Redis(redis) => redis.query(redis::cmd("SMEMBERS").arg(key)).await,
+            #[cfg(test)]
+            PresenceBackend::Local(store) => Ok(store.smembers(key)),
+        }
+    }
+
+    async fn publish(&self, channel: &str, payload: String) -> PresenceResult<()> {
+        match self {
+            PresenceBackend::Redis(redis) => {
+                redis
+                    .query(redis::cmd("PUBLISH").arg(channel).arg(payload))
+                    .await
+            }
+            #[cfg(test)]
+            PresenceBackend::Local(store) => {
+                store.publish(payload);
+                Ok(())
+            }
+        }
+    }
+}
+
+/// 本服务器在集群中的登记、玩家目录与跨服务器广播
+pub struct PresenceService {
+    server_id: String,
+    address: String,
+    started_at: DateTime<Utc>,
+    config: PresenceConfig,
+    backend: PresenceBackend,
+}
+
+impl PresenceService {
+    /// 使用 `REDIS_URL`；地址无效时返回 `None`（单机运行）
+    pub fn connect(server_id: String, address: String, config: PresenceConfig) -> Option<Self> {
+        let redis_url =
+            env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        match redis::Client::open(redis_url) {
+            Ok(client) => Some(Self::with_backend(
+                server_id,
+                address,
+                config,
+                PresenceBackend::Redis(SharedRedisConnection::new(client)),
+            )),
+            Err(error) => {
+                bevy::log::warn!("Cluster presence disabled: {error}");
+                None
+            }
+        }
+    }
+
+    /// 使用进程内替身（测试）
+    #[cfg(test)]
+    pub fn local(
+        store: &LocalPresence,
+        server_id: String,
+        address: String,
+        config: PresenceConfig,
+    ) -> Self {
+        Self::with_backend(
+            server_id,
+            address,
+            config,
+            PresenceBackend::Local(store.clone()),
+        )
+    }
+
+    fn with_backend(
+        server_id: String,
+        address: String,
+        config: PresenceConfig,
+        backend: PresenceBackend,
+    ) -> Self {
+        Self {
+            server_id,
+            address,
+            started_at: Utc::now(),
+            config,
+            backend,
+        }
+    }
+
+    pub fn server_id(&self) -> &str {
+        &self.server_id
+    }
+
+    pub fn config(&self) -> PresenceConfig {
+        self.config
+    }
+
+    /// 登记/续期本服务器，并续期本服务器上所有玩家的在线记录
+    pub async fn heartbeat(&self, player_count: u32) -> PresenceResult<()> {
+        let info = ServerInfo {
+            server_id: self.server_id.clone(),
+            address: self.address.clone(),
+            player_count,
+            started_at: self.started_at,
+        };
+        let info = serde_json::to_string(&info).unwrap_or_default();
+        self.backend
+            .set_px(&server_key(&self.server_id), &info, self.config.server_ttl)
+            .await?;
+        self.backend.sadd(SERVERS_KEY, &self.server_id).await?;
+
+        let players_key = server_players_key(&self.server_id);
+        let mut keys: Vec<String> = self
+            .backend
+            .smembers(&players_key)
+            .await?
+            .iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .map(presence_key)
+            .collect();
+        keys.push(players_key);
+        self.backend.pexpire(&keys, self.config.presence_ttl).await
+    }
+
+    /// 正常退出时注销本服务器，并让其上的玩家立即离线
+    pub async fn deregister(&self) -> PresenceResult<()> {
+        let players_key = server_players_key(&self.server_id);
+        let player_ids: Vec<Uuid> = self
+            .backend
+            .smembers(&players_key)
+            .await?
+            .iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .collect();
+        let keys: Vec<String> = player_ids.iter().copied().map(presence_key).collect();
+        let entries = self.backend.get_many(&keys).await?;
+        let owned: Vec<String> = keys
+            .into_iter()
+            .zip(entries)
+            .filter(|(_, entry)| {
+                entry
+                    .as_deref()
+                    .and_then(|entry| serde_json::from_str::<PresenceEntry>(entry).ok())
+                    .is_some_and(|entry| entry.server_id == self.server_id)
+            })
+            .map(|(key, _)| key)
+            .collect();
+
+        self.backend.del(&owned).await?;
+        self.backend
+            .del(&[server_key(&self.server_id), players_key])
+            .await?;
+        self.backend
+            .srem(SERVERS_KEY, std::slice::from_ref(&self.server_id))
+            .await
+    }
+
+    /// 玩家在本服务器上线（同一玩家在其他服务器的旧记录被覆盖）
+    pub async fn player_online(
+        &self,
+        client_id: u64,
+        identity: &AccountIdentity,
+    ) -> PresenceResult<()> {
+        let entry = PresenceEntry {
+            player_id: identity.player_id,
+            username: identity.username.clone(),
+            server_id: self.server_id.clone(),
+            client_id,
+        };
+        let entry = serde_json::to_string(&entry).unwrap_or_default();
+        self.backend
+            .set_px(
+                &presence_key(identity.player_id),
+                &entry,
+                self.config.presence_ttl,
+            )
+            .await?;
+        let players_key = server_players_key(&self.server_id);
+        self.backend
+            .sadd(&players_key, &identity.player_id.to_string())
+            .await?;
+        self.backend
+            .pexpire(&[players_key], self.config.presence_ttl)
+            .await
+    }
+
+    /// 玩家从本服务器离线；返回 `false` 表示玩家已在其他服务器重新上线，
+    /// 此时只清理本服务器的集合
+    pub async fn player_offline(&self, identity: &AccountIdentity) -> PresenceResult<bool> {
+        self.backend
+            .srem(
+                &server_players_key(&self.server_id),
+                &[identity.player_id.to_string()],
+            )
+            .await?;
+
+        let owned = self
+            .lookup(identity.player_id)
+            .await?
+            .is_some_and(|entry| entry.server_id == self.server_id);
+        if owned {
+            self.backend
+                .del(&[presence_key(identity.player_id)])
+                .await?;
+        }
+        Ok(owned)
+    }
+
+    /// 查询玩家当前所在的服务器
+    pub async fn lookup(&self, player_id: Uuid) -> PresenceResult<Option<PresenceEntry>> {
+        let entries = self.backend.get_many(&[presence_key(player_id)]).await?;
+        Ok(entries
+            .into_iter()
+            .flatten()
+            .find_map(|entry| serde_json::from_str(&entry).ok()))
+    }
+
+    /// 心跳未过期的服务器；顺带从 `servers` 集合清理已过期的成员
+    pub async fn live_servers(&self) -> PresenceResult<Vec<ServerInfo>> {
+        let server_ids = self.backend.smembers(SERVERS_KEY).await?;
+        let keys: Vec<String> = server_ids.iter().map(|id| server_key(id)).collect();
+        let infos = self.backend.get_many(&keys).await?;
+
+        let mut stale = Vec::new();
+        let mut servers = Vec::new();
+        for (server_id, info) in server_ids.into_iter().zip(infos) {
+            match info.and_then(|info| serde_json::from_str::<ServerInfo>(&info).ok()) {
+                Some(info) => servers.push(info),
+                None => stale.push(server_id),
+            }
+        }
+        self.backend.srem(SERVERS_KEY, &stale).await?;
+        servers.sort_by(|a, b| a.server_id.cmp(&b.server_id));
+        Ok(servers)
+    }
+
+    /// 整个集群的在线玩家目录（按用户名排序）
+    pub async fn online_players(&self) -> PresenceResult<Vec<PresenceEntry>> {
+        let mut players = Vec::new();
+        let mut seen = HashSet::new();
+        for server in self.live_servers().await? {
+            let keys: Vec<String> = self
+                .backend
+                .smembers(&server_players_key(&server.server_id))
+                .await?
+                .iter()
+                .filter_map(|id| Uuid::parse_str(id).ok())
+                .map(presence_key)
+                .collect();
+            for entry in self.backend.get_many(&keys).await?.into_iter().flatten() {
+                let Ok(entry) = serde_json::from_str::<PresenceEntry>(&entry) else {
+                    continue;
+                };
+                // 玩家换了服务器后旧服务器集合里可能还留着它
+                if entry.server_id == server.server_id && seen.insert(entry.player_id) {
+                    players.push(entry);
+                }
+            }
+        }
+        players.sort_by(|a, b| a.username.cmp(&b.username));
+        Ok(players)
+    }
+
+    /// 把聊天/大厅数据包发给集群中的其他服务器；其他数据包不跨服务器转发
+    pub async fn publish(&self, packet: GamePacket) -> PresenceResult<()> {
+        let Some(channel) = cluster_channel(&packet) else {
+            return Ok(());
+        };
+        let message = ClusterMessage {
+            origin: self.server_id.clone(),
+            packet,
+        };
+        let payload = serde_json::to_string(&message).unwrap_or_default();
+        self.backend.publish(channel, payload).await
+    }
+
+    /// 订阅集群频道，只转发其他服务器发出的数据包；Redis 连接断开后自动重连
+    pub fn subscribe(&self) -> mpsc::UnboundedReceiver<GamePacket> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let server_id = self.server_id.clone();
+
+        match &self.backend {
+            PresenceBackend::Redis(redis) => {
+                // pub/sub 需要独占连接，不能复用命令用的多路复用连接
+                let client = redis.client().clone();
+                tokio::spawn(async move {
+                    loop {
+                        if let Err(error) = forward_redis_messages(&client, &server_id, &tx).await {
+                            bevy::log::warn!("Cluster subscription lost: {error}");
+                        }
+                        if tx.is_closed() {
+                            return;
+                        }
+                        tokio::time::sleep(SUBSCRIBE_RETRY).await;
+                    }
+                });
+            }
+            #[cfg(test)]
+            PresenceBackend::Local(store) => store.forward_messages(server_id, tx),
+        }
+        rx
+    }
+}
+
+/// 转发 Redis 频道消息，直到连接断开或接收端关闭
+async fn forward_redis_messages(
+    client: &redis::Client,
+    server_id: &str,
+    tx: &mpsc::UnboundedSender<GamePacket>,
+) -> PresenceResult<()> {
+    let mut pubsub = client.get_async_pubsub().await?;
+    pubsub.subscribe(&[CHAT_CHANNEL, LOBBY_CHANNEL]).await?;
+    let mut messages = pubsub.on_message();
+    while let Some(message) = messages.next().await {
+        let payload: String = message.get_payload()?;
+        if let Some(packet) = decode_cluster_message(server_id, &payload)
+            && tx.send(packet).is_err()
+        {
+            return Ok(());
+        }
+    }
+    Err(redis::RedisError::from((
+        redis::ErrorKind::Io,
+        "Redis pub/sub connection closed",
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::sanitize_chat_text;
+
+    fn runtime() -> tokio::runtime::Runtime {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("test runtime")
+    }
+
+    fn identity(username: &str) -> AccountIdentity {
+        AccountIdentity {
+            player_id: Uuid::new_v4(),
+            username: username.to_string(),
+            is_guest: false,
+        }
+    }
+
+    fn server(store: &LocalPresence, id: &str, config: PresenceConfig) -> PresenceService {
+        PresenceService::local(store, id.to_string(), format!("{id}:8080"), config)
+    }
+
+    #[test]
+    fn servers_share_one_player_directory() {
+        runtime().block_on(async {
+            let store = LocalPresence::default();
+            let alpha = server(&store, "alpha", PresenceConfig::default());
+            let beta = server(&store, "beta", PresenceConfig::default());
+            let rin = identity("rin");
+            let shirou = identity("shirou");
+
+            alpha.heartbeat(1).await.unwrap();
+            beta.heartbeat(1).await.unwrap();
+            alpha.player_online(1, &rin).await.unwrap();
+            beta.player_online(7, &shirou).await.unwrap();
+
+            let directory = beta.online_players().await.unwrap();
+            let names: Vec<_> = directory
+                .iter()
+                .map(|entry| entry.username.as_str())
+                .collect();
+            assert_eq!(names, ["rin", "shirou"]);
+            assert_eq!(
+                beta.lookup(rin.player_id)
+                    .await
+                    .unwrap()
+                    .map(|entry| entry.server_id),
+                Some("alpha".to_string())
+            );
+
+            // 玩家转到 beta 后，alpha 上迟到的离线不能把新记录删掉
+            beta.player_online(8, &rin).await.unwrap();
+            assert!(!alpha.player_offline(&rin).await.unwrap());
+            assert_eq!(
+                alpha
+                    .lookup(rin.player_id)
+                    .await
+                    .unwrap()
+                    .map(|entry| entry.server_id),
+                Some("beta".to_string())
+            );
+            assert_eq!(alpha.online_players().await.unwrap().len(), 2);
+            assert!(beta.player_offline(&shirou).await.unwrap());
+            assert_eq!(beta.lookup(shirou.player_id).await.unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn crashed_server_and_its_players_expire_without_heartbeats() {
+        runtime().block_on(async {
+            let store = LocalPresence::default();
+            let short = PresenceConfig {
+                presence_ttl: Duration::from_millis(40),
+                server_ttl: Duration::from_millis(40),
+                heartbeat_interval: Duration::from_millis(10),
+            };
+            let crashed = server(&store, "crashed", short);
+            let alive = server(&store, "alive", PresenceConfig::default());
+            crashed.heartbeat(1).await.unwrap();
+            crashed.player_online(1, &identity("sakura")).await.unwrap();
+            alive.heartbeat(0).await.unwrap();
+            assert_eq!(alive.live_servers().await.unwrap().len(), 2);
+
+            tokio::time::sleep(Duration::from_millis(60)).await;
+            let servers = alive.live_servers().await.unwrap();
+            assert_eq!(servers.len(), 1);
+            assert_eq!(servers[0].server_id, "alive");
+            assert!(alive.online_players().await.unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn cluster_messages_reach_other_servers_only() {
+        runtime().block_on(async {
+            let store = LocalPresence::default();
+            let alpha = server(&store, "alpha", PresenceConfig::default());
+            let beta = server(&store, "beta", PresenceConfig::default());
+            let mut alpha_rx = alpha.subscribe();
+            let mut beta_rx = beta.subscribe();
+
+            let chat = GamePacket::Chat {
+                from: "rin".to_string(),
+                text: sanitize_chat_text("  gandr\u{7}  ").expect("non-empty chat"),
+            };
+            alpha.publish(chat).await.unwrap();
+            alpha.publish(GamePacket::Pong(1)).await.unwrap();
+
+            let received = tokio::time::timeout(Duration::from_secs(1), beta_rx.recv())
+                .await
+                .expect("beta receives chat");
+            assert!(matches!(
+                received,
+                Some(GamePacket::Chat { from, text }) if from == "rin" && text == "gandr"
+            ));
+            assert!(alpha_rx.try_recv().is_err());
+            assert!(beta_rx.try_recv().is_err());
+        });
+    }
+}
@@ -1,15 +1,90 @@
+//! 服务器实时状态的 Redis 存储
+//!
+//! 键结构（所有键都带过期时间，进程崩溃后自动清理）：
+//!
+//! | 键 | 类型 | 字段 | TTL |
+//! |---|---|---|---|
+//! | `player:{network_id}:state` | hash | `x` `y` `vx` `vy`（f32）、`updated_at`（Unix 毫秒） | [`PLAYER_STATE_TTL_SECS`] |
+//! | `presence:{player_id}` 等 | 见 [`super::presence`] | | |
+//! | `leaderboard:{kind}:{character}` | 见 [`super::leaderboard`] | | |
+//!
+//! 玩家断线时立即删除其 `player:*:state`；写入只在位置变化或接近过期时发生。
+//!
+//! 写入在 Tokio 任务中通过多路复用连接批量执行，失败按指数退避重试
+//! （`REDIS_WRITE_RETRY_MAX` / `REDIS_WRITE_RETRY_BACKOFF_MS` / `REDIS_WRITE_RETRY_BACKOFF_MAX_MS`）。
+
 use bevy::prelude::*;
-use redis::{Client, Commands, Connection, RedisError};
+use redis::aio::MultiplexedConnection;
+use redis::{Client, FromRedisValue, RedisError};
+use std::collections::HashMap;
 use std::env;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
 use std::time::Duration;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+/// `player:{network_id}:state` 的过期时间
+pub const PLAYER_STATE_TTL_SECS: u64 = 10;
+
+pub fn player_state_key(network_id: u64) -> String {
+    format!("player:{network_id}:state")
+}
+
+/// `player:{network_id}:state` 哈希的内容
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerStateRecord {
+    pub x: f32,
+    pub y: f32,
+    pub vx: f32,
+    pub vy: f32,
+    /// 写入时间（Unix 毫秒）
+    pub updated_at: i64,
+}
+
+impl PlayerStateRecord {
+    fn to_fields(self) -> [(&'static str, String); 5] {
+        [
+            ("x", self.x.to_string()),
+            ("y", self.y.to_string()),
+            ("vx", self.vx.to_string()),
+            ("vy", self.vy.to_string()),
+            ("updated_at", self.updated_at.to_string()),
+        ]
+    }
+
+    /// 从 `HGETALL` 结果解析；缺字段或格式错误时返回 `None`
+    fn from_fields(fields: &HashMap<String, String>) -> Option<Self> {
+        let float = |name: &str| fields.get(name)?.parse::<f32>().ok();
+        Some(Self {
+            x: float("x")?,
+            y: float("y")?,
+            vx: float("vx")?,
+            vy: float("vy")?,
+            updated_at: fields.get("updated_at")?.parse().ok()?,
+        })
+    }
+}
+
+/// 写入队列中的一条操作
+#[derive(Debug, Clone, PartialEq)]
+pub enum RedisWrite {
+    PlayerState {
+        network_id: u64,
+        state: PlayerStateRecord,
+    },
+    RemovePlayer {
+        network_id: u64,
+    },
+}
+
+/// 写入队列容量（批次数）；队列满时直接丢弃新批次，不阻塞 ECS
+const WRITE_QUEUE_CAPACITY: usize = 128;
 
 #[derive(Clone, Debug)]
 struct RedisWriteConfig {
     max_retries: u32,
     retry_backoff_ms: u64,
+    retry_backoff_max_ms: u64,
 }
 
 impl RedisWriteConfig {
@@ -22,11 +97,153 @@ impl RedisWriteConfig {
             .ok()
             .and_then(|v| v.parse::<u64>().ok())
             .unwrap_or(20);
+        let retry_backoff_max_ms = env::var("REDIS_WRITE_RETRY_BACKOFF_MAX_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .unwrap_or(1_000);
         Self {
             max_retries,
             retry_backoff_ms,
+            retry_backoff_max_ms,
+        }
+    }
+
+    /// 第 `attempt` 次失败后的等待时间：`base * 2^attempt`，不超过上限
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64.checked_shl(attempt).unwrap_or(u64::MAX);
+        Duration::from_millis(
+            self.retry_backoff_ms
+                .saturating_mul(factor)
+                .min(self.retry_backoff_max_ms),
+        )
+    }
+}
+
+/// 惰性建立并复用的多路复用连接；出错后丢弃，下次使用时重连
+struct RedisConnection {
+    client: Client,
+    connection: Option<MultiplexedConnection>,
+}
+
+impl RedisConnection {
+    fn new(client: Client) -> Self {
+        Self {
+            client,
+            connection: None,
+        }
+    }
+
+    async fn get(&mut self) -> Result<&mut MultiplexedConnection, RedisError> {
+        let connection = match self.connection.take() {
+            Some(connection) => connection,
+            None => self.client.get_multiplexed_async_connection().await?,
+        };
+        Ok(self.connection.insert(connection))
+    }
+
+    async fn write_batch(&mut self, writes: &[RedisWrite]) -> Result<(), RedisError> {
+        let pipeline = write_pipeline(writes);
+        let result = pipeline.query_async::<()>(self.get().await?).await;
+        if result.is_err() {
+            self.connection = None;
+        }
+        result
+    }
+}
+
+/// 多个任务共享的多路复用连接（在线状态、排行榜缓存使用）
+///
+/// 首次使用时建立，之后各任务克隆同一个连接句柄并发发送命令；
+/// 命令出错后丢弃连接，下一次使用时重连。
+#[derive(Clone)]
+pub(crate) struct SharedRedisConnection {
+    client: Client,
+    connection: Arc<Mutex<Option<MultiplexedConnection>>>,
+}
+
+impl SharedRedisConnection {
+    pub(crate) fn new(client: Client) -> Self {
+        Self {
+            client,
+            connection: Arc::default(),
+        }
+    }
+
+    pub(crate) fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// 取共享连接的句柄；没有可用连接时新建一条
+    pub(crate) async fn get(&self) -> Result<MultiplexedConnection, RedisError> {
+        if let Some(connection) = self.slot().as_ref() {
+            return Ok(connection.clone());
         }
+        // 建立连接时不持锁；并发建立的多条连接只保留最后一条
+        let connection = self.client.get_multiplexed_async_connection().await?;
+        *self.slot() = Some(connection.clone());
+        Ok(connection)
     }
+
+    /// 命令失败后丢弃连接，下一次 [`Self::get`] 重连
+    pub(crate) fn reset(&self) {
+        *self.slot() = None;
+    }
+
+    pub(crate) async fn query<T: FromRedisValue>(
+        &self,
+        command: &redis::Cmd,
+    ) -> Result<T, RedisError> {
+        let result = command.query_async(&mut self.get().await?).await;
+        if result.is_err() {
+            self.reset();
+        }
+        result
+    }
+
+    pub(crate) async fn query_pipeline<T: FromRedisValue>(
+        &self,
+        pipeline: &redis::Pipeline,
+    ) -> Result<T, RedisError> {
+        let result = pipeline.query_async(&mut self.get().await?).await;
+        if result.is_err() {
+            self.reset();
+        }
+        result
+    }
+
+    fn slot(&self) -> MutexGuard<'_, Option<MultiplexedConnection>> {
+        self.connection
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+fn write_pipeline(writes: &[RedisWrite]) -> redis::Pipeline {
+    let mut pipeline = redis::pipe();
+    for write in writes {
+        match write {
+            RedisWrite::PlayerState { network_id, state } => {
+                let key = player_state_key(*network_id);
+                pipeline.cmd("HSET").arg(&key);
+                for (field, value) in state.to_fields() {
+                    pipeline.arg(field).arg(value);
+                }
+                pipeline.ignore();
+                pipeline
+                    .cmd("EXPIRE")
+                    .arg(&key)
+                    .arg(PLAYER_STATE_TTL_SECS)
+                    .ignore();
+            }
+            RedisWrite::RemovePlayer { network_id } => {
+                pipeline
+                    .cmd("DEL")
+                    .arg(player_state_key(*network_id))
+                    .ignore();
+            }
+        }
+    }
+    pipeline
 }
 
 #[derive(Clone, Default)]
@@ -48,73 +265,71 @@ pub struct RedisWriteMetricsSnapshot {
     pub estimated_pending_batches: u64,
 }
 
+/// Redis 写入入口：ECS 只把批次放进有界队列，实际写入在 Tokio 任务中完成，
+/// 主调度里不会出现阻塞的 Redis 调用。
 #[derive(Resource)]
 pub struct RedisManager {
-    client: Client,
-    write_tx: SyncSender<Vec<(String, String)>>,
+    /// 读取用的共享连接（写入任务有自己的连接）
+    reads: SharedRedisConnection,
+    write_tx: mpsc::Sender<Vec<RedisWrite>>,
     metrics: RedisWriteMetrics,
 }
 
 impl RedisManager {
+    /// 需要在 Tokio 运行时内调用（写入任务在当前运行时上启动）
     pub fn new() -> Result<Self, RedisError> {
         let redis_url =
             env::var("REDIS_URL").unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
         let client = Client::open(redis_url)?;
+        let runtime = tokio::runtime::Handle::try_current().map_err(|_| {
+            RedisError::from((
+                redis::ErrorKind::Io,
+                "Redis write worker requires a Tokio runtime",
+            ))
+        })?;
         let config = RedisWriteConfig::from_env();
         let metrics = RedisWriteMetrics::default();
 
-        let (write_tx, write_rx) = mpsc::sync_channel::<Vec<(String, String)>>(128);
-        let worker_client = client.clone();
-        let worker_metrics = metrics.clone();
-        let worker_config = config.clone();
-        std::thread::Builder::new()
-            .name("redis-write-worker".to_string())
-            .spawn(move || {
-                Self::run_write_worker(worker_client, write_rx, worker_config, worker_metrics)
-            })
-            .map_err(|_| {
-                RedisError::from((
-                    redis::ErrorKind::Io,
-                    "Failed to spawn Redis write worker thread",
-                ))
-            })?;
+        let (write_tx, write_rx) = mpsc::channel::<Vec<RedisWrite>>(WRITE_QUEUE_CAPACITY);
+        runtime.spawn(Self::run_write_worker(
+            RedisConnection::new(client.clone()),
+            write_rx,
+            config,
+            metrics.clone(),
+        ));
 
         Ok(Self {
-            client,
+            reads: SharedRedisConnection::new(client),
             write_tx,
             metrics,
         })
     }
 
-    fn run_write_worker(
-        client: Client,
-        write_rx: Receiver<Vec<(String, String)>>,
+    async fn run_write_worker(
+        mut connection: RedisConnection,
+        mut write_rx: mpsc::Receiver<Vec<RedisWrite>>,
         config: RedisWriteConfig,
         metrics: RedisWriteMetrics,
     ) {
-        let mut connection = Self::connect(&client);
-        while let Ok(batch) = write_rx.recv() {
+        while let Some(batch) = write_rx.recv().await {
             if batch.is_empty() {
                 continue;
             }
 
             let mut success = false;
             for attempt in 0..=config.max_retries {
-                if connection.is_none() {
-                    connection = Self::connect(&client);
-                }
-
-                if let Some(conn) = connection.as_mut() {
-                    if Self::write_batch(conn, &batch).is_ok() {
+                match connection.write_batch(&batch).await {
+                    Ok(()) => {
                         success = true;
                         break;
                     }
-                    connection = None;
-                }
-
-                if attempt < config.max_retries {
-                    metrics.retry_attempts.fetch_add(1, Ordering::Relaxed);
-                    std::thread::sleep(Duration::from_millis(config.retry_backoff_ms));
+                    Err(error) if attempt == config.max_retries => {
+                        bevy::log::error!("Redis batch write failed: {error}");
+                    }
+                    Err(_) => {
+                        metrics.retry_attempts.fetch_add(1, Ordering::Relaxed);
+                        tokio::time::sleep(config.backoff(attempt)).await;
+                    }
                 }
             }
 
@@ -126,48 +341,14 @@ impl RedisManager {
         }
     }
 
-    fn connect(client: &Client) -> Option<Connection> {
-        match client.get_connection() {
-            Ok(connection) => Some(connection),
-            Err(error) => {
-                bevy::log::error!("Redis worker failed to connect: {error}");
-                None
-            }
-        }
-    }
-
-    fn write_batch(
-        connection: &mut Connection,
-        entries: &[(String, String)],
-    ) -> Result<(), RedisError> {
-        let mut pipeline = redis::pipe();
-        for (key, value) in entries {
-            pipeline.cmd("SET").arg(key).arg(value).ignore();
-        }
-        pipeline.query::<()>(connection)
-    }
-
-    fn set_many_sync(&self, entries: &[(String, String)]) -> Result<(), RedisError> {
-        if entries.is_empty() {
-            return Ok(());
-        }
-
-        let mut connection = self.client.get_connection()?;
-        Self::write_batch(&mut connection, entries)
-    }
-
-    pub fn set_key(&self, key: &str, value: &str) -> Result<(), RedisError> {
-        self.set_many_sync(&[(key.to_string(), value.to_string())])
-    }
-
     /// Queue batched writes to a background worker to avoid main-thread blocking.
     /// Returns an error if queue is full/disconnected; caller should rely on worker retries.
-    pub fn set_many(&self, entries: &[(String, String)]) -> Result<(), RedisError> {
-        if entries.is_empty() {
+    pub fn write_many(&self, writes: Vec<RedisWrite>) -> Result<(), RedisError> {
+        if writes.is_empty() {
             return Ok(());
         }
 
-        match self.write_tx.try_send(entries.to_vec()) {
+        match self.write_tx.try_send(writes) {
             Ok(()) => {
                 self.metrics.queued_batches.fetch_add(1, Ordering::Relaxed);
                 Ok(())
@@ -179,7 +360,7 @@ impl RedisManager {
                     "Redis write queue is full",
                 )))
             }
-            Err(TrySendError::Disconnected(_)) => {
+            Err(TrySendError::Closed(_)) => {
                 self.metrics.dropped_batches.fetch_add(1, Ordering::Relaxed);
                 Err(RedisError::from((
                     redis::ErrorKind::Io,
@@ -189,9 +370,16 @@ impl RedisManager {
         }
     }
 
-    pub fn get_key(&self, key: &str) -> Result<String, RedisError> {
-        let mut con = self.client.get_connection()?;
-        con.get(key)
+    /// 读取玩家实时状态；键不存在（已断线或过期）时返回 `None`
+    pub async fn get_player_state(
+        &self,
+        network_id: u64,
+    ) -> Result<Option<PlayerStateRecord>, RedisError> {
+        let fields: HashMap<String, String> = self
+            .reads
+            .query(redis::cmd("HGETALL").arg(player_state_key(network_id)))
+            .await?;
+        Ok(PlayerStateRecord::from_fields(&fields))
     }
 
     pub fn metrics_snapshot(&self) -> RedisWriteMetricsSnapshot {
@@ -228,3 +416,44 @@ impl Plugin for RedisPlugin {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_retries_back_off_exponentially_up_to_the_cap() {
+        let config = RedisWriteConfig {
+            max_retries: 8,
+            retry_backoff_ms: 20,
+            retry_backoff_max_ms: 300,
+        };
+        let delays: Vec<u64> = (0..6)
+            .map(|attempt| config.backoff(attempt).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, [20, 40, 80, 160, 300, 300]);
+        assert_eq!(config.backoff(200), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn player_state_roundtrips_through_hash_fields() {
+        let state = PlayerStateRecord {
+            x: 120.5,
+            y: -3.25,
+            vx: 4.0,
+            vy: 0.0,
+            updated_at: 1_760_000_000_000,
+        };
+        let fields: HashMap<String, String> = state
+            .to_fields()
+            .into_iter()
+            .map(|(field, value)| (field.to_string(), value))
+            .collect();
+        assert_eq!(PlayerStateRecord::from_fields(&fields), Some(state));
+
+        let mut missing = fields;
+        missing.remove("vy");
+        assert_eq!(PlayerStateRecord::from_fields(&missing), None);
+        assert_eq!(player_state_key(42), "player:42:state");
+    }
+}
@@ -1,4 +1,5 @@
-use crate::resources::CompleteGameState;
+use crate::components::{EnemyType, StatusKind};
+use crate::resources::{CompleteGameState, SaveThumbnail};
 use bevy::prelude::*;
 
 /// Event to request saving the game state to a file with the given name.
@@ -6,12 +7,28 @@ use bevy::prelude::*;
 pub struct StartSaveGame {
     pub save_name: String,
     pub state: CompleteGameState,
+    pub thumbnail: Option<SaveThumbnail>,
+}
+
+/// Fired after a manual save reached disk; `save_json` is the file's JSON before compression.
+#[derive(Message, Debug, Clone)]
+pub struct SaveWritten {
+    pub save_name: String,
+    pub save_json: String,
 }
 
 /// Event to request loading the game state from the given file path.
 #[derive(Message, Clone)]
 pub struct StartLoadGame {
     pub file_path: String,
+    /// Restore the slot from its newest valid rolling backup instead of reading it.
+    pub restore_from_backup: bool,
+}
+
+/// Fired when the player reaches a new sky-level checkpoint.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct CheckpointActivated {
+    pub checkpoint_id: i32,
 }
 
 /// Gameplay damage event routed through a single hit pipeline.
@@ -32,9 +49,72 @@ pub struct CameraImpulseEvent {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DamageSource {
     Projectile,
-    Knife,
+    /// Knife hit; `combo_chain` counts consecutive attacks in the current combo window.
+    Knife {
+        combo_chain: u32,
+    },
     EnemyContact,
     EnemyProjectile,
     Fall,
     ShroudDrain,
+    /// Black mud draining the player while they stand in it; bypasses guard and i-frames.
+    MudDrain,
+    /// Periodic burn/poison damage from `StatusEffects`; bypasses guard and i-frames.
+    StatusTick,
+}
+
+/// Applies `stacks` of a status effect to `target`, following the kind's stacking rule.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct StatusEffectEvent {
+    pub target: Entity,
+    pub kind: StatusKind,
+    pub stacks: u32,
+}
+
+/// Fired by the damage pipeline when an enemy's health reaches zero.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct EnemyDefeated {
+    pub enemy: Entity,
+    pub enemy_type: EnemyType,
+    /// Combo chain of the finishing knife hit; 0 for ranged kills.
+    pub combo_chain: u32,
+}
+
+/// Fired when a sky-level arena is cleared.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ArenaCleared {
+    pub arena: i32,
+    /// The player took no hostile damage while the arena was active.
+    pub no_damage: bool,
+}
+
+/// Points added to the run's combat score.
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ScoreAwarded {
+    pub points: u32,
+    pub reason: ScoreReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreReason {
+    Kill {
+        enemy_type: EnemyType,
+        combo_chain: u32,
+    },
+    NoDamageClear {
+        arena: i32,
+    },
+}
+
+/// Short on-screen notice, e.g. a config file that failed to (re)load.
+#[derive(Message, Debug, Clone)]
+pub struct ShowToast {
+    pub text: String,
+    pub kind: ToastKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastKind {
+    Info,
+    Error,
 }
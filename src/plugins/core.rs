@@ -3,11 +3,11 @@ use bevy::prelude::*;
 use crate::{
     asset_paths,
     components::SpriteAnimationSheets,
-    events::{CameraImpulseEvent, DamageEvent},
-    events::{StartLoadGame, StartSaveGame},
+    events::{ArenaCleared, CameraImpulseEvent, CheckpointActivated, DamageEvent},
+    events::{EnemyDefeated, ScoreAwarded, StatusEffectEvent},
+    events::{SaveWritten, ShowToast, StartLoadGame, StartSaveGame},
     resources::{
         AudioSettings, AudioStateManager, GameAssets, GameStats, PauseManager, SaveFileManager,
-        SaveManager,
     },
     states::CharacterSelection,
     states::GameState,
@@ -26,16 +26,39 @@ impl Plugin for CorePlugin {
         app.init_state::<GameState>()
             .insert_resource(Time::<Fixed>::from_hz(60.0))
             .add_message::<StartSaveGame>()
+            .add_message::<SaveWritten>()
             .add_message::<StartLoadGame>()
             .add_message::<DamageEvent>()
             .add_message::<CameraImpulseEvent>()
+            .add_message::<CheckpointActivated>()
+            .add_message::<EnemyDefeated>()
+            .add_message::<ArenaCleared>()
+            .add_message::<StatusEffectEvent>()
+            .add_message::<ScoreAwarded>()
+            .add_message::<ShowToast>()
+            .init_asset::<systems::hot_reload::GameplayTuningAsset>()
+            .init_asset::<systems::hot_reload::MoveListAsset>()
+            .init_asset::<systems::hot_reload::AnimationProfileAsset>()
+            .init_asset::<systems::hot_reload::FrameDataAsset>()
+            .register_asset_loader(systems::hot_reload::RonConfigLoader::<
+                systems::hot_reload::GameplayTuningAsset,
+            >::default())
+            .register_asset_loader(systems::hot_reload::RonConfigLoader::<
+                systems::hot_reload::MoveListAsset,
+            >::default())
+            .register_asset_loader(systems::hot_reload::RonConfigLoader::<
+                systems::hot_reload::AnimationProfileAsset,
+            >::default())
+            .register_asset_loader(systems::hot_reload::RonConfigLoader::<
+                systems::hot_reload::FrameDataAsset,
+            >::default())
             .init_resource::<CharacterSelection>()
             .init_resource::<GameStats>()
             .init_resource::<AudioSettings>()
             .init_resource::<systems::settings_ui::VolumeControlState>()
             .init_resource::<systems::audio::AudioManager>()
-            .init_resource::<SaveManager>()
             .init_resource::<SaveFileManager>()
+            .init_resource::<systems::save::AutosaveState>()
             .init_resource::<PauseManager>()
             .init_resource::<AudioStateManager>()
             .init_resource::<systems::input::GameInput>()
@@ -52,13 +75,25 @@ impl Plugin for CorePlugin {
             .init_resource::<systems::sprite_animation::AnimationRuntimeConfig>()
             .init_resource::<systems::camera::CameraShakeState>()
             .init_resource::<systems::combat::HitStopState>()
+            .init_resource::<systems::hitboxes::HitboxDebugOverlay>()
             .add_systems(
                 Startup,
                 (
                     systems::setup::load_gameplay_tuning,
+                    systems::save_signing::load_install_signing_key,
                     setup_game_resources,
                     setup_animation_data,
-                    systems::save::load_game,
+                    systems::hot_reload::load_config_assets,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    systems::hot_reload::apply_gameplay_tuning_reloads,
+                    systems::hot_reload::apply_move_list_reloads,
+                    systems::hot_reload::apply_animation_profile_reloads,
+                    systems::hot_reload::apply_frame_data_reloads,
+                    systems::hot_reload::report_config_load_failures,
                 ),
             );
     }
@@ -279,4 +314,5 @@ fn setup_game_resources(
 fn setup_animation_data(mut commands: Commands) {
     let animation_data = systems::sprite_animation::load_animation_data();
     commands.insert_resource(animation_data);
+    commands.insert_resource(systems::hitboxes::load_frame_data());
 }
@@ -35,6 +35,7 @@ impl Plugin for GameplayPlugin {
                 (
                     systems::input::update_game_input,
                     systems::player::update_player_facing_from_input,
+                    systems::defense::update_player_defense,
                     systems::shirou::handle_shroud_input,
                     systems::combat::player_knife_attack,
                     systems::combat::player_shoot_projectile,
@@ -48,6 +49,7 @@ impl Plugin for GameplayPlugin {
                 Update,
                 (
                     systems::player::update_game_stats,
+                    systems::scoring::award_combat_score,
                     systems::enemy::spawn_enemies,
                     systems::enemy::cleanup_dead_enemies,
                     systems::enemy::cleanup_offscreen_enemies,
@@ -62,6 +64,14 @@ impl Plugin for GameplayPlugin {
                 Update,
                 systems::combat::maintain_hit_stop_timescale.in_set(GameSystemSet::GameLogic),
             )
+            .add_systems(
+                Update,
+                (
+                    systems::hitboxes::toggle_hitbox_overlay.run_if(in_state(GameState::Playing)),
+                    systems::hitboxes::draw_hitbox_overlay,
+                )
+                    .chain(),
+            )
             .add_systems(
                 Update,
                 (
@@ -87,16 +97,20 @@ impl Plugin for GameplayPlugin {
                     )
                         .chain(),
                     (
-                        systems::enemy::enemy_patrol_ai,
-                        systems::enemy::enemy_ranged_attack,
+                        systems::enemy_behavior::enemy_behavior_ai,
+                        systems::boss::boss_ai,
+                        systems::boss::update_boss_hazards,
                         systems::combat::update_projectiles,
                         systems::combat::update_enemy_projectiles,
+                        systems::defense::resolve_parries,
                         systems::combat::projectile_enemy_collision,
                         systems::combat::knife_enemy_collision,
                         systems::combat::enemy_projectile_player_collision,
                         systems::combat::player_enemy_collision,
                         systems::death::check_player_fall_death,
                         systems::shirou::shroud_health_drain,
+                        systems::status_effects::apply_status_effect_events,
+                        systems::status_effects::tick_status_effects,
                         systems::combat::apply_damage_events,
                     )
                         .chain(),
@@ -1,13 +1,18 @@
 use bevy::prelude::*;
 
+use crate::states::GameState;
 use crate::systems::{
     interfaces::GameSystemSet,
+    metrics::MetricsRegistry,
+    metrics_overlay::{export_client_metrics, toggle_metrics_overlay, update_metrics_overlay},
     network::{
-        ClientPredictionConfig, MyNetworkId, NetworkConfig, NetworkEntityMap,
-        NetworkLifecycleState, NetworkReconnectState, NetworkResource, NetworkSnapshotState,
-        apply_server_corrections, auto_reconnect_network, handle_network_events,
-        interpolate_positions, send_heartbeat_ping_system, send_ping_system, setup_network,
-        update_network_status,
+        ChatLog, ClientPredictionConfig, LeaderboardState, LocalAccount, MyNetworkId,
+        NetworkConfig, NetworkEntityMap, NetworkLifecycleState, NetworkReconnectState,
+        NetworkResource, NetworkSnapshotState, apply_server_corrections, auto_reconnect_network,
+        handle_network_events, interpolate_positions, report_checkpoint_milestones,
+        report_death_milestone, report_run_started, report_score_milestones,
+        report_selected_character, report_victory_milestone, send_heartbeat_ping_system,
+        send_ping_system, setup_network, update_network_status, upload_saved_games,
     },
 };
 
@@ -22,8 +27,12 @@ impl Plugin for NetcodePlugin {
             .init_resource::<NetworkReconnectState>()
             .init_resource::<NetworkEntityMap>()
             .init_resource::<MyNetworkId>()
+            .init_resource::<LocalAccount>()
+            .init_resource::<LeaderboardState>()
+            .init_resource::<ChatLog>()
             .init_resource::<NetworkSnapshotState>()
             .init_resource::<NetworkLifecycleState>()
+            .init_resource::<MetricsRegistry>()
             .add_systems(Startup, setup_network)
             .add_systems(
                 Update,
@@ -34,10 +43,40 @@ impl Plugin for NetcodePlugin {
                     apply_server_corrections,
                     send_ping_system,
                     send_heartbeat_ping_system,
+                    report_checkpoint_milestones,
+                    report_score_milestones,
+                    upload_saved_games,
                     interpolate_positions,
                 )
                     .chain()
                     .in_set(GameSystemSet::GameLogic),
-            );
+            )
+            .add_systems(
+                Update,
+                (
+                    export_client_metrics,
+                    toggle_metrics_overlay,
+                    update_metrics_overlay,
+                )
+                    .chain(),
+            )
+            // 从主菜单或读档表进入游戏才算新的一局，暂停后继续不算
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Menu,
+                    entered: GameState::Playing,
+                },
+                report_run_started,
+            )
+            .add_systems(
+                OnTransition {
+                    exited: GameState::LoadTable,
+                    entered: GameState::Playing,
+                },
+                report_run_started,
+            )
+            .add_systems(OnEnter(GameState::Playing), report_selected_character)
+            .add_systems(OnEnter(GameState::GameOver), report_death_milestone)
+            .add_systems(OnEnter(GameState::Victory), report_victory_milestone);
     }
 }
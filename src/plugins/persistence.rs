@@ -12,10 +12,14 @@ impl Plugin for PersistencePlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
             Update,
-            systems::save::auto_save_system
+            systems::save::autosave_system
                 .in_set(GameSystemSet::Persistence)
                 .run_if(in_state(GameState::Playing)),
         )
+        .add_systems(
+            Update,
+            systems::save::poll_autosave_tasks.in_set(GameSystemSet::Persistence),
+        )
         .add_systems(
             Update,
             (
@@ -46,6 +50,13 @@ impl Plugin for PersistencePlugin {
                 .in_set(GameSystemSet::Input)
                 .run_if(in_state(GameState::Playing).or_else(in_state(GameState::Paused))),
         )
+        .add_systems(
+            Update,
+            systems::save_thumbnail::capture_pause_thumbnail
+                .after(systems::pause_save::handle_pause_input)
+                .in_set(GameSystemSet::Persistence)
+                .run_if(in_state(GameState::Playing)),
+        )
         .add_systems(
             Update,
             systems::pause_save::restore_paused_state
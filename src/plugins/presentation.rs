@@ -34,6 +34,7 @@ impl Plugin for PresentationPlugin {
             (
                 systems::player::sync_player_sprite_facing,
                 systems::enemy::update_enemy_telegraph_visuals,
+                systems::boss::update_boss_visuals,
                 systems::audio::trigger_audio_effects,
                 systems::combat::animate_projectile_visuals,
                 systems::combat::animate_attack_reference_action_vfx,
@@ -43,6 +44,14 @@ impl Plugin for PresentationPlugin {
                 .in_set(GameSystemSet::Animation)
                 .run_if(in_state(GameState::Playing)),
         )
+        .add_systems(
+            Update,
+            systems::status_effects::update_status_effect_visuals
+                .after(systems::enemy::update_enemy_telegraph_visuals)
+                .after(systems::boss::update_boss_visuals)
+                .in_set(GameSystemSet::Animation)
+                .run_if(in_state(GameState::Playing)),
+        )
         .add_systems(
             Update,
             (
@@ -7,22 +7,59 @@ use crate::components::ai::BotController;
 use crate::components::network::NetworkId;
 use crate::components::physics::Velocity;
 use crate::components::player::{Player, PlayerInputState};
-use crate::protocol::{GamePacket, InputEventKind, PlayerAction};
+use crate::protocol::{GamePacket, InputEventKind, MilestoneKind, PlayerAction};
 use crate::resources::GameConfig;
+use crate::systems::accounts::AccountIdentity;
+use crate::systems::admin::answer_admin_queries;
 use crate::systems::ai::bot_control_system;
+use crate::systems::metrics::{
+    MetricsRegistry, TickTimer, begin_tick_timing, end_tick_timing, time_stage,
+};
+use crate::systems::session_recording::{SessionActionKind, SessionRecorder};
 #[cfg(feature = "server")]
 use crate::systems::sync_redis::sync_transform_to_redis;
 
 type ActionReceiver = mpsc::UnboundedReceiver<(u64, PlayerAction)>;
+type ConnectionEventReceiver = mpsc::UnboundedReceiver<ConnectionEvent>;
+
+/// Connection lifecycle reported by the Tokio network tasks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// The connection authenticated (handshake token, login, register or guest).
+    Authenticated {
+        client_id: u64,
+        account: AccountIdentity,
+    },
+    Disconnected {
+        client_id: u64,
+    },
+}
+
+/// A connection closed; its entity (network id == `client_id`) stays for session resume.
+#[derive(Message, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientDisconnected {
+    pub client_id: u64,
+}
 
 /// Cross-runtime channels used by the server:
 /// Tokio network tasks push input actions into ECS and receive snapshots from ECS.
 #[derive(Resource, Clone)]
 pub struct NetworkChannels {
     pub action_rx: Arc<Mutex<ActionReceiver>>,
+    pub connection_rx: Arc<Mutex<ConnectionEventReceiver>>,
     pub broadcast_tx: mpsc::UnboundedSender<GamePacket>,
 }
 
+/// Maps live connections (`client_id`) to the `players.id` they authenticated as.
+#[derive(Resource, Default)]
+pub struct ConnectionAccounts(pub HashMap<u64, AccountIdentity>);
+
+impl ConnectionAccounts {
+    pub fn player_id(&self, client_id: u64) -> Option<uuid::Uuid> {
+        self.0.get(&client_id).map(|account| account.player_id)
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct ClientEntityMap(pub HashMap<u64, Entity>);
 
@@ -55,28 +92,120 @@ impl Plugin for ServerRuntimePlugin {
         app.insert_resource(self.channels.clone())
             .insert_resource(Time::<Fixed>::from_hz(60.0))
             .init_resource::<ClientEntityMap>()
+            .init_resource::<ConnectionAccounts>()
+            .init_resource::<SessionRecorder>()
             .init_resource::<ServerTick>()
             .init_resource::<ClientInputSequence>()
             .init_resource::<SnapshotStateCache>()
             .init_resource::<SnapshotBandwidthMetrics>()
+            .init_resource::<MetricsRegistry>()
+            .init_resource::<TickTimer>()
+            .add_message::<ClientDisconnected>()
             .add_systems(Startup, setup_bots)
+            .add_systems(FixedFirst, begin_tick_timing)
             .add_systems(
                 FixedUpdate,
                 (
                     increment_tick,
+                    process_connection_events,
+                    time_stage("process_connection_events"),
                     process_network_events,
+                    time_stage("process_network_events"),
                     bot_control_system,
+                    time_stage("bot_control_system"),
                     server_physics_system,
+                    time_stage("server_physics_system"),
                     broadcast_snapshot_system,
+                    time_stage("broadcast_snapshot_system"),
                 )
                     .chain(),
-            );
+            )
+            .add_systems(FixedLast, (end_tick_timing, export_server_metrics).chain())
+            // 运维查询只在 bin 插入 `AdminQueries` 后才有数据
+            .add_systems(Update, answer_admin_queries);
 
         #[cfg(feature = "server")]
         app.add_systems(FixedUpdate, sync_transform_to_redis);
     }
 }
 
+/// 把服务端已有的统计写入指标注册表（运维接口 `/metrics` 读取）
+fn export_server_metrics(
+    registry: Res<MetricsRegistry>,
+    tick: Res<ServerTick>,
+    bandwidth: Res<SnapshotBandwidthMetrics>,
+    entities: Query<(), With<NetworkId>>,
+    #[cfg(feature = "server")] redis: Option<Res<crate::database::redis::RedisManager>>,
+) {
+    registry.set_counter(
+        "gengine_server_ticks",
+        "Fixed-update ticks since start",
+        &[],
+        tick.0 as f64,
+    );
+    registry.set_gauge(
+        "gengine_entities",
+        "Networked entities in the world",
+        &[],
+        entities.iter().count() as f64,
+    );
+    for (kind, bytes, count) in [
+        (
+            "full",
+            bandwidth.full_snapshot_bytes,
+            bandwidth.full_snapshot_count,
+        ),
+        (
+            "delta",
+            bandwidth.delta_snapshot_bytes,
+            bandwidth.delta_snapshot_count,
+        ),
+    ] {
+        registry.set_counter(
+            "gengine_snapshot_bytes",
+            "Encoded snapshot bytes broadcast",
+            &[("kind", kind)],
+            bytes as f64,
+        );
+        registry.set_counter(
+            "gengine_snapshots",
+            "Snapshots broadcast",
+            &[("kind", kind)],
+            count as f64,
+        );
+    }
+
+    #[cfg(feature = "server")]
+    if let Some(redis) = redis {
+        let metrics = redis.metrics_snapshot();
+        for (result, batches) in [
+            ("queued", metrics.queued_batches),
+            ("processed", metrics.processed_batches),
+            ("dropped", metrics.dropped_batches),
+            ("failed", metrics.failed_batches),
+        ] {
+            registry.set_counter(
+                "gengine_redis_write_batches",
+                "Redis write batches by outcome",
+                &[("result", result)],
+                batches as f64,
+            );
+        }
+        registry.set_counter(
+            "gengine_redis_write_retries",
+            "Redis write retry attempts",
+            &[],
+            metrics.retry_attempts as f64,
+        );
+        registry.set_gauge(
+            "gengine_redis_write_pending_batches",
+            "Estimated Redis write batches waiting in the queue",
+            &[],
+            metrics.estimated_pending_batches as f64,
+        );
+    }
+}
+
 fn increment_tick(mut tick: ResMut<ServerTick>) {
     tick.0 = tick.0.wrapping_add(1);
 }
@@ -92,13 +221,54 @@ fn setup_bots(mut commands: Commands) {
     ));
 }
 
+fn process_connection_events(
+    channels: Res<NetworkChannels>,
+    mut accounts: ResMut<ConnectionAccounts>,
+    mut recorder: ResMut<SessionRecorder>,
+    client_map: Res<ClientEntityMap>,
+    transforms: Query<&Transform>,
+    mut disconnected: MessageWriter<ClientDisconnected>,
+) {
+    let position_of = |client_id: u64| {
+        client_map
+            .0
+            .get(&client_id)
+            .and_then(|entity| transforms.get(*entity).ok())
+            .map(|transform| transform.translation)
+    };
+
+    let mut rx = match channels.connection_rx.lock() {
+        Ok(receiver) => receiver,
+        Err(_) => return,
+    };
+
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            ConnectionEvent::Authenticated { client_id, account } => {
+                info!(
+                    "Client {} authenticated as {} ({})",
+                    client_id, account.username, account.player_id
+                );
+                recorder.start(client_id, account.player_id, position_of(client_id));
+                accounts.0.insert(client_id, account);
+            }
+            ConnectionEvent::Disconnected { client_id } => {
+                recorder.disconnect(client_id, position_of(client_id));
+                accounts.0.remove(&client_id);
+                disconnected.write(ClientDisconnected { client_id });
+            }
+        }
+    }
+}
+
 fn process_network_events(
     mut commands: Commands,
     channels: Res<NetworkChannels>,
     mut client_map: ResMut<ClientEntityMap>,
-    mut input_query: Query<&mut PlayerInputState>,
+    mut input_query: Query<(&mut PlayerInputState, &Transform)>,
     mut net_id_query: Query<&mut NetworkId>,
     mut sequence_state: ResMut<ClientInputSequence>,
+    mut recorder: ResMut<SessionRecorder>,
 ) {
     let mut rx = match channels.action_rx.lock() {
         Ok(receiver) => receiver,
@@ -137,7 +307,7 @@ fn process_network_events(
                 }
 
                 let entity = ensure_entity(&mut commands, &mut client_map, client_id);
-                if let Ok(mut input) = input_query.get_mut(entity) {
+                if let Ok((mut input, _)) = input_query.get_mut(entity) {
                     input.move_x = x;
                     input.move_y = y;
                 }
@@ -148,15 +318,53 @@ fn process_network_events(
                 }
 
                 let entity = ensure_entity(&mut commands, &mut client_map, client_id);
+                let position = input_query
+                    .get(entity)
+                    .ok()
+                    .map(|(_, transform)| transform.translation);
                 match kind {
                     InputEventKind::Jump => {
-                        if let Ok(mut input) = input_query.get_mut(entity) {
-                            input.jump_pressed = true;
+                        // 只有站在地面上（且本帧还没有待执行的跳跃）时跳跃才生效并计分，
+                        // 空中连按既不起跳也不计入跳跃次数
+                        let jumped =
+                            input_query
+                                .get_mut(entity)
+                                .is_ok_and(|(mut input, transform)| {
+                                    let can_jump = !input.jump_pressed && is_grounded(transform);
+                                    input.jump_pressed |= can_jump;
+                                    can_jump
+                                });
+                        if jumped {
+                            recorder.record(client_id, SessionActionKind::Jump, position);
                         }
                     }
-                    InputEventKind::Attack => {}
+                    InputEventKind::Attack => {
+                        recorder.record(client_id, SessionActionKind::Attack, position);
+                    }
+                }
+            }
+            PlayerAction::Milestone { kind } => {
+                let position = client_map
+                    .0
+                    .get(&client_id)
+                    .and_then(|entity| input_query.get(*entity).ok())
+                    .map(|(_, transform)| transform.translation);
+                if let MilestoneKind::RunStarted { character } = kind {
+                    recorder.start_run(client_id, character.as_str(), position);
                 }
+                recorder.record(client_id, SessionActionKind::Milestone(kind), position);
             }
+            PlayerAction::SelectCharacter { character } => {
+                recorder.select_character(client_id, character.as_str());
+            }
+            // 排行榜查询由连接任务直接回复，聊天由连接任务发往整个集群
+            PlayerAction::RequestLeaderboard { .. } | PlayerAction::Chat { .. } => {}
+            // 存档密钥登记与上传由连接任务按账号校验
+            PlayerAction::RegisterSaveKey { .. } | PlayerAction::UploadSave { .. } => {}
+            // 账号请求由连接任务直接处理，结果经 ConnectionEvent 送达
+            PlayerAction::Register { .. }
+            | PlayerAction::Login { .. }
+            | PlayerAction::GuestLogin => {}
         }
     }
 }
@@ -206,7 +414,9 @@ fn server_physics_system(
         velocity.x = input.move_x * GameConfig::MOVE_SPEED;
 
         if input.jump_pressed {
-            velocity.y = GameConfig::JUMP_VELOCITY;
+            if is_grounded(&transform) {
+                velocity.y = GameConfig::JUMP_VELOCITY;
+            }
             input.jump_pressed = false;
         }
 
@@ -323,14 +533,17 @@ fn has_meaningful_delta(
         || previous.animation_state != current.animation_state
 }
 
+/// 服务器模拟中没有平台，只有地面高度算作着地
+fn is_grounded(transform: &Transform) -> bool {
+    transform.translation.y <= GameConfig::GROUND_LEVEL + 0.5
+}
+
 fn determine_animation_state(
     velocity: &Velocity,
     input: &PlayerInputState,
     transform: &Transform,
 ) -> String {
-    let is_grounded = transform.translation.y <= GameConfig::GROUND_LEVEL + 0.5;
-
-    if !is_grounded {
+    if !is_grounded(transform) {
         if velocity.y > 0.0 {
             "Jump".to_string()
         } else {
@@ -350,6 +563,7 @@ fn determine_animation_state(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::systems::session_recording::SessionRecord;
 
     fn spawn_networked_player(app: &mut App, network_id: u64, x: f32) -> Entity {
         app.world_mut()
@@ -363,12 +577,141 @@ mod tests {
             .id()
     }
 
+    #[test]
+    fn session_scores_milestones_from_server_table_with_limits() {
+        use crate::protocol::MilestoneKind;
+        use crate::resources::ScoringTuning;
+
+        let scoring = ScoringTuning::default();
+        let (record_tx, mut record_rx) = mpsc::unbounded_channel::<SessionRecord>();
+        let mut recorder = SessionRecorder::with_sink(record_tx).with_scoring(scoring.clone());
+        recorder.start(9, uuid::Uuid::new_v4(), None);
+
+        let milestone = |kind| SessionActionKind::Milestone(kind);
+        for _ in 0..2 {
+            recorder.record(
+                9,
+                milestone(MilestoneKind::NoDamageClear { arena: 1 }),
+                None,
+            );
+        }
+        recorder.record(
+            9,
+            milestone(MilestoneKind::NoDamageClear { arena: -4 }),
+            None,
+        );
+        for _ in 0..40 {
+            recorder.record(
+                9,
+                milestone(MilestoneKind::Kill {
+                    enemy: crate::components::EnemyType::EnemyHeroicSpirit,
+                    combo_chain: u32::MAX,
+                }),
+                None,
+            );
+        }
+        let summary = recorder.finish(9, None).expect("session should be active");
+
+        let awarded: Vec<u64> = std::iter::from_fn(|| record_rx.try_recv().ok())
+            .filter_map(|record| match record {
+                SessionRecord::Action { action, .. } => action
+                    .action_data
+                    .and_then(|data| data.get("points").and_then(|points| points.as_u64())),
+                _ => None,
+            })
+            .collect();
+        let kill_points =
+            (scoring.heroic_spirit_points as f32 * scoring.max_combo_multiplier).round() as u64;
+        // 同一竞技场只计一次，非法编号不计分；击杀按计分表封顶，超出限速窗口的不计分
+        assert_eq!(&awarded[..3], &[scoring.no_damage_arena_bonus as u64, 0, 0]);
+        assert!(awarded[3..23].iter().all(|points| *points == kill_points));
+        assert!(awarded[23..].iter().all(|points| *points == 0));
+        assert_eq!(
+            summary.score as u64,
+            scoring.no_damage_arena_bonus as u64 + kill_points * 20
+        );
+    }
+
+    #[test]
+    fn run_started_closes_previous_run_and_restarts_counters() {
+        use crate::states::CharacterType;
+
+        let (record_tx, mut record_rx) = mpsc::unbounded_channel::<SessionRecord>();
+        let mut recorder = SessionRecorder::with_sink(record_tx);
+        let player_id = uuid::Uuid::new_v4();
+        recorder.start(3, player_id, None);
+        for _ in 0..3 {
+            recorder.record(3, SessionActionKind::Jump, None);
+        }
+
+        recorder.start_run(3, CharacterType::Sakura.as_str(), None);
+        recorder.record(3, SessionActionKind::Jump, None);
+        let second_run = recorder.finish(3, None).expect("new run should be active");
+        assert_eq!(second_run.jump_count, 1);
+
+        let records: Vec<SessionRecord> =
+            std::iter::from_fn(|| record_rx.try_recv().ok()).collect();
+        let ended: Vec<i32> = records
+            .iter()
+            .filter_map(|record| match record {
+                SessionRecord::End { summary, .. } => Some(summary.jump_count),
+                _ => None,
+            })
+            .collect();
+        // 每一局单独成一条会话：第一局的跳跃不会累加进第二局
+        assert_eq!(ended, [3, 1]);
+        assert!(records.iter().any(|record| matches!(
+            record,
+            SessionRecord::Start { player_id: id, character_type, .. }
+                if *id == player_id && character_type == CharacterType::Sakura.as_str()
+        )));
+    }
+
+    #[test]
+    fn server_physics_only_jumps_from_the_ground() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_systems(Update, server_physics_system);
+
+        let grounded = spawn_networked_player(&mut app, 1, 0.0);
+        let airborne = spawn_networked_player(&mut app, 2, 0.0);
+        app.world_mut()
+            .get_mut::<Transform>(airborne)
+            .expect("airborne player should have a transform")
+            .translation
+            .y = GameConfig::GROUND_LEVEL + 80.0;
+        for entity in [grounded, airborne] {
+            app.world_mut()
+                .get_mut::<PlayerInputState>(entity)
+                .expect("player should have input state")
+                .jump_pressed = true;
+        }
+        app.update();
+
+        let vertical_speed = |app: &App, entity| {
+            app.world()
+                .get::<Velocity>(entity)
+                .expect("player should have velocity")
+                .y
+        };
+        assert!(vertical_speed(&app, grounded) > 0.0);
+        assert!(vertical_speed(&app, airborne) <= 0.0);
+        assert!(
+            !app.world()
+                .get::<PlayerInputState>(airborne)
+                .expect("player should have input state")
+                .jump_pressed
+        );
+    }
+
     #[test]
     fn resume_session_reuses_previous_entity_and_despawns_duplicate() {
         let (action_tx, action_rx) = mpsc::unbounded_channel::<(u64, PlayerAction)>();
         let (broadcast_tx, _broadcast_rx) = mpsc::unbounded_channel::<GamePacket>();
+        let (_connection_tx, connection_rx) = mpsc::unbounded_channel::<ConnectionEvent>();
         let channels = NetworkChannels {
             action_rx: Arc::new(Mutex::new(action_rx)),
+            connection_rx: Arc::new(Mutex::new(connection_rx)),
             broadcast_tx,
         };
 
@@ -377,6 +720,7 @@ mod tests {
             .insert_resource(channels)
             .init_resource::<ClientEntityMap>()
             .init_resource::<ClientInputSequence>()
+            .init_resource::<SessionRecorder>()
             .add_systems(Update, process_network_events);
 
         let resumed_entity = spawn_networked_player(&mut app, 10, 0.0);
@@ -413,8 +757,10 @@ mod tests {
     fn snapshot_broadcast_uses_full_then_delta_and_records_bandwidth_metrics() {
         let (_action_tx, action_rx) = mpsc::unbounded_channel::<(u64, PlayerAction)>();
         let (broadcast_tx, mut broadcast_rx) = mpsc::unbounded_channel::<GamePacket>();
+        let (_connection_tx, connection_rx) = mpsc::unbounded_channel::<ConnectionEvent>();
         let channels = NetworkChannels {
             action_rx: Arc::new(Mutex::new(action_rx)),
+            connection_rx: Arc::new(Mutex::new(connection_rx)),
             broadcast_tx,
         };
 
@@ -424,7 +770,11 @@ mod tests {
             .insert_resource(ServerTick(1))
             .init_resource::<SnapshotStateCache>()
             .init_resource::<SnapshotBandwidthMetrics>()
-            .add_systems(Update, broadcast_snapshot_system);
+            .init_resource::<MetricsRegistry>()
+            .add_systems(
+                Update,
+                (broadcast_snapshot_system, export_server_metrics).chain(),
+            );
 
         let entity = spawn_networked_player(&mut app, 1, 0.0);
 
@@ -464,14 +814,24 @@ mod tests {
         assert_eq!(metrics.delta_snapshot_count, 1);
         assert!(metrics.full_snapshot_bytes > 0);
         assert!(metrics.delta_snapshot_bytes > 0);
+
+        let registry = app.world().resource::<MetricsRegistry>();
+        assert_eq!(
+            registry.value("gengine_snapshots", &[("kind", "delta")]),
+            Some(1.0)
+        );
+        assert_eq!(registry.value("gengine_server_ticks", &[]), Some(2.0));
+        assert_eq!(registry.value("gengine_entities", &[]), Some(1.0));
     }
 
     #[test]
     fn process_network_events_spawns_player_at_configured_ground_level() {
         let (action_tx, action_rx) = mpsc::unbounded_channel::<(u64, PlayerAction)>();
         let (broadcast_tx, _broadcast_rx) = mpsc::unbounded_channel::<GamePacket>();
+        let (_connection_tx, connection_rx) = mpsc::unbounded_channel::<ConnectionEvent>();
         let channels = NetworkChannels {
             action_rx: Arc::new(Mutex::new(action_rx)),
+            connection_rx: Arc::new(Mutex::new(connection_rx)),
             broadcast_tx,
         };
 
@@ -480,6 +840,7 @@ mod tests {
             .insert_resource(channels)
             .init_resource::<ClientEntityMap>()
             .init_resource::<ClientInputSequence>()
+            .init_resource::<SessionRecorder>()
             .add_systems(Update, process_network_events);
 
         action_tx
@@ -517,4 +878,193 @@ mod tests {
             "spawned player x should match configured start position"
         );
     }
+
+    #[test]
+    fn connection_events_map_clients_to_player_accounts() {
+        let (_action_tx, action_rx) = mpsc::unbounded_channel::<(u64, PlayerAction)>();
+        let (connection_tx, connection_rx) = mpsc::unbounded_channel::<ConnectionEvent>();
+        let (broadcast_tx, _broadcast_rx) = mpsc::unbounded_channel::<GamePacket>();
+        let channels = NetworkChannels {
+            action_rx: Arc::new(Mutex::new(action_rx)),
+            connection_rx: Arc::new(Mutex::new(connection_rx)),
+            broadcast_tx,
+        };
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(channels)
+            .init_resource::<ConnectionAccounts>()
+            .init_resource::<ClientEntityMap>()
+            .init_resource::<SessionRecorder>()
+            .add_message::<ClientDisconnected>()
+            .add_systems(Update, process_connection_events);
+
+        let account = AccountIdentity {
+            player_id: uuid::Uuid::from_u128(7),
+            username: "shirou".to_string(),
+            is_guest: false,
+        };
+        connection_tx
+            .send(ConnectionEvent::Authenticated {
+                client_id: 3,
+                account: account.clone(),
+            })
+            .expect("auth event should be enqueued");
+        app.update();
+
+        let accounts = app.world().resource::<ConnectionAccounts>();
+        assert_eq!(accounts.player_id(3), Some(account.player_id));
+
+        connection_tx
+            .send(ConnectionEvent::Disconnected { client_id: 3 })
+            .expect("disconnect event should be enqueued");
+        app.update();
+
+        let accounts = app.world().resource::<ConnectionAccounts>();
+        assert_eq!(accounts.player_id(3), None);
+        let disconnected: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<ClientDisconnected>>()
+            .drain()
+            .collect();
+        assert_eq!(disconnected, [ClientDisconnected { client_id: 3 }]);
+    }
+
+    #[test]
+    fn authenticated_session_records_actions_and_closes_with_summary() {
+        let (action_tx, action_rx) = mpsc::unbounded_channel::<(u64, PlayerAction)>();
+        let (connection_tx, connection_rx) = mpsc::unbounded_channel::<ConnectionEvent>();
+        let (broadcast_tx, _broadcast_rx) = mpsc::unbounded_channel::<GamePacket>();
+        let (record_tx, mut record_rx) = mpsc::unbounded_channel::<SessionRecord>();
+        let channels = NetworkChannels {
+            action_rx: Arc::new(Mutex::new(action_rx)),
+            connection_rx: Arc::new(Mutex::new(connection_rx)),
+            broadcast_tx,
+        };
+
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(channels)
+            .insert_resource(SessionRecorder::with_sink(record_tx))
+            .init_resource::<ConnectionAccounts>()
+            .init_resource::<ClientEntityMap>()
+            .init_resource::<ClientInputSequence>()
+            .add_message::<ClientDisconnected>()
+            .add_systems(
+                Update,
+                (process_connection_events, process_network_events).chain(),
+            );
+
+        let entity = spawn_networked_player(&mut app, 5, GameConfig::PLAYER_START_POS.x);
+        app.world_mut()
+            .resource_mut::<ClientEntityMap>()
+            .0
+            .insert(5, entity);
+
+        // 未认证连接的输入不会被记录
+        action_tx
+            .send((
+                5,
+                PlayerAction::InputEvent {
+                    sequence: 1,
+                    kind: InputEventKind::Jump,
+                },
+            ))
+            .expect("jump should be enqueued");
+        app.update();
+        assert!(record_rx.try_recv().is_err());
+        // 该测试不运行物理步，手动消耗这次起跳，玩家仍站在地面上
+        app.world_mut()
+            .get_mut::<PlayerInputState>(entity)
+            .expect("player should have input state")
+            .jump_pressed = false;
+
+        let player_id = uuid::Uuid::from_u128(5);
+        connection_tx
+            .send(ConnectionEvent::Authenticated {
+                client_id: 5,
+                account: AccountIdentity {
+                    player_id,
+                    username: "sakura".to_string(),
+                    is_guest: false,
+                },
+            })
+            .expect("auth event should be enqueued");
+        // 同一帧内的第二次跳跃发生在起跳之后，不计入跳跃次数
+        for (sequence, kind) in [
+            (2, InputEventKind::Jump),
+            (3, InputEventKind::Jump),
+            (4, InputEventKind::Attack),
+        ] {
+            action_tx
+                .send((5, PlayerAction::InputEvent { sequence, kind }))
+                .expect("input event should be enqueued");
+        }
+        action_tx
+            .send((
+                5,
+                PlayerAction::Milestone {
+                    kind: crate::protocol::MilestoneKind::Checkpoint { id: 2 },
+                },
+            ))
+            .expect("milestone should be enqueued");
+        action_tx
+            .send((
+                5,
+                PlayerAction::Milestone {
+                    kind: crate::protocol::MilestoneKind::Kill {
+                        enemy: crate::components::EnemyType::Slime,
+                        combo_chain: 3,
+                    },
+                },
+            ))
+            .expect("kill milestone should be enqueued");
+        app.update();
+
+        if let Some(mut transform) = app.world_mut().get_mut::<Transform>(entity) {
+            transform.translation.x += 12.0;
+        }
+        connection_tx
+            .send(ConnectionEvent::Disconnected { client_id: 5 })
+            .expect("disconnect event should be enqueued");
+        app.update();
+
+        let records: Vec<SessionRecord> =
+            std::iter::from_fn(|| record_rx.try_recv().ok()).collect();
+        assert!(matches!(
+            &records[0],
+            SessionRecord::Start { client_id: 5, player_id: id, .. } if *id == player_id
+        ));
+        let action_types: Vec<&str> = records
+            .iter()
+            .filter_map(|record| match record {
+                SessionRecord::Action { action, .. } => Some(action.action_type),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(action_types, vec!["jump", "attack", "checkpoint", "kill"]);
+
+        let checkpoints: Vec<Option<i32>> = records
+            .iter()
+            .filter_map(|record| match record {
+                SessionRecord::Save {
+                    player_id: id,
+                    snapshot,
+                } if *id == player_id => Some(snapshot.checkpoint_id),
+                _ => None,
+            })
+            .collect();
+        // 检查点存档一次，断线前再存一次（保留最近的检查点）
+        assert_eq!(checkpoints, vec![Some(2), Some(2)]);
+
+        let Some(SessionRecord::End { summary, .. }) = records.last() else {
+            panic!("disconnect should close the session");
+        };
+        assert_eq!(summary.jump_count, 1);
+        assert!((summary.distance_traveled - 12.0).abs() < 1e-3);
+        assert_eq!(
+            summary.score,
+            (summary.distance_traveled * 10.0) as i32 + 50 + 150
+        );
+    }
 }
@@ -27,6 +27,7 @@ impl Plugin for SkyLevelPlugin {
             .register_ldtk_entity::<SkyClimbAnchorBundle>("ClimbAnchor")
             .register_ldtk_entity::<SkyCheckpointBundle>("Checkpoint")
             .register_ldtk_entity::<SkyEnemySpawnBundle>("EnemySpawn")
+            .register_ldtk_entity::<SkyBossBundle>("SkyBoss")
             .register_ldtk_entity::<SkyCombatGateBundle>("CombatGate")
             .register_ldtk_entity::<SkyGoalBundle>("Goal")
             .register_ldtk_entity::<SkyBackdropBundle>("Backdrop")
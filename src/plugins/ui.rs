@@ -19,6 +19,7 @@ impl Plugin for UiPlugin {
                 systems::sky_level::cleanup_sky_level,
                 systems::audio::stop_game_music,
                 systems::settings_ui::cleanup_settings_overlay,
+                systems::leaderboard_ui::cleanup_leaderboard_overlay,
                 systems::menu::setup_menu,
             )
                 .chain(),
@@ -33,7 +34,7 @@ impl Plugin for UiPlugin {
                     .chain(),
                 systems::menu::handle_load_button,
                 systems::menu::handle_menu_settings_button,
-                systems::save::handle_save_button_click,
+                systems::leaderboard_ui::handle_menu_leaderboard_button,
                 systems::menu::cover_fade_animation,
                 systems::menu::update_menu_cover_layout.after(systems::menu::cover_fade_animation),
                 systems::visual_effects::button_hover_effect,
@@ -46,13 +47,22 @@ impl Plugin for UiPlugin {
             (
                 systems::menu::cleanup_menu,
                 systems::settings_ui::cleanup_settings_overlay,
+                systems::leaderboard_ui::cleanup_leaderboard_overlay,
                 systems::audio::stop_menu_music,
             ),
         )
+        .add_systems(
+            Update,
+            (systems::toast::show_toasts, systems::toast::expire_toasts).chain(),
+        )
         .add_systems(OnEnter(GameState::Playing), systems::ui::setup_game_hud)
         .add_systems(
             Update,
-            systems::ui::update_game_hud
+            (
+                systems::ui::update_game_hud,
+                systems::ui::update_status_hud,
+                systems::ui::update_boss_health_bar,
+            )
                 .in_set(GameSystemSet::UI)
                 .run_if(in_state(GameState::Playing)),
         )
@@ -69,6 +79,21 @@ impl Plugin for UiPlugin {
                 .run_if(any_with_component::<crate::systems::settings_ui::SettingsOverlayRoot>)
                 .run_if(in_state(GameState::Menu).or_else(in_state(GameState::Paused))),
         )
+        .add_systems(
+            Update,
+            (
+                systems::leaderboard_ui::handle_leaderboard_filter_buttons,
+                systems::leaderboard_ui::update_leaderboard_display,
+                systems::leaderboard_ui::handle_leaderboard_back_button,
+                systems::leaderboard_ui::close_leaderboard_overlay_on_escape,
+            )
+                .chain()
+                .in_set(GameSystemSet::UI)
+                .run_if(
+                    any_with_component::<crate::systems::leaderboard_ui::LeaderboardOverlayRoot>,
+                )
+                .run_if(in_state(GameState::Menu)),
+        )
         .add_systems(
             OnEnter(GameState::Paused),
             (
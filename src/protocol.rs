@@ -1,6 +1,10 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::components::EnemyType;
+use crate::states::CharacterType;
+use crate::systems::accounts::AccountIdentity;
+
 /// Network packet sent from Server to Client
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum GamePacket {
@@ -21,6 +25,80 @@ pub enum GamePacket {
     Message(String),
     /// Pong response to Ping
     Pong(u64),
+    /// Connection is now bound to an account; keep the token for the next handshake
+    AuthAccepted {
+        account: AccountIdentity,
+        session_token: String,
+    },
+    /// Register/login/token authentication failed
+    AuthRejected { reason: String },
+    /// Requested leaderboard page
+    Leaderboard(LeaderboardPage),
+    /// Leaderboard request could not be served (offline database/cache)
+    LeaderboardUnavailable { reason: String },
+    /// Chat line from a player on any server of the cluster
+    Chat { from: String, text: String },
+    /// Player joined/left somewhere in the cluster
+    Lobby(LobbyEvent),
+    /// Save-key registration or save upload was refused
+    SaveRejected { reason: String },
+    /// Uploaded save passed signature verification and was stored online
+    SaveAccepted { name: String },
+}
+
+/// Longest chat line relayed by the server, in characters
+pub const MAX_CHAT_CHARS: usize = 200;
+
+/// Strips control characters and surrounding whitespace and truncates to [`MAX_CHAT_CHARS`];
+/// returns `None` for lines that end up empty
+pub fn sanitize_chat_text(text: &str) -> Option<String> {
+    let cleaned: String = text
+        .trim()
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_CHAT_CHARS)
+        .collect();
+    let cleaned = cleaned.trim_end();
+    (!cleaned.is_empty()).then(|| cleaned.to_string())
+}
+
+/// Cluster-wide lobby notifications
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum LobbyEvent {
+    PlayerOnline { username: String, server_id: String },
+    PlayerOffline { username: String, server_id: String },
+}
+
+/// Leaderboard ranking
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LeaderboardKind {
+    /// Highest session score (higher is better)
+    BestScore,
+    /// Fastest sky-city clear time in seconds (lower is better)
+    FastestClear,
+}
+
+/// Which board to browse; `character: None` ranks across all characters
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct LeaderboardQuery {
+    pub kind: LeaderboardKind,
+    pub character: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub rank: u32,
+    pub username: String,
+    pub character: String,
+    pub value: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LeaderboardPage {
+    pub query: LeaderboardQuery,
+    pub entries: Vec<LeaderboardEntry>,
+    /// Requesting player's own entry, when authenticated and ranked
+    pub own_entry: Option<LeaderboardEntry>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
@@ -29,6 +107,32 @@ pub enum InputEventKind {
     Attack,
 }
 
+/// Gameplay milestones only the client simulates, reported for session records
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum MilestoneKind {
+    Death,
+    Checkpoint {
+        id: i32,
+    },
+    Victory {
+        clear_time_ms: u32,
+    },
+    /// Enemy killed; the server scores it from its own tuning table
+    Kill {
+        enemy: EnemyType,
+        combo_chain: u32,
+    },
+    /// Sky-level arena cleared without taking hostile damage
+    NoDamageClear {
+        arena: i32,
+    },
+    /// A new run began (new game or loaded save); the server closes the previous run's
+    /// session and opens a fresh one
+    RunStarted {
+        character: CharacterType,
+    },
+}
+
 /// Player input sent from Client to Server
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum PlayerAction {
@@ -40,6 +144,25 @@ pub enum PlayerAction {
     InputState { sequence: u32, x: f32, y: f32 },
     /// Instant event input (edge-triggered)
     InputEvent { sequence: u32, kind: InputEventKind },
+    /// Create a password account and bind this connection to it
+    Register { username: String, password: String },
+    /// Log into an existing password account
+    Login { username: String, password: String },
+    /// Bind this connection to a fresh guest account
+    GuestLogin,
+    /// Client-side milestone (death, checkpoint, victory)
+    Milestone { kind: MilestoneKind },
+    /// Character chosen for the current run
+    SelectCharacter { character: CharacterType },
+    /// Fetch the top `limit` entries of a leaderboard plus the caller's own rank
+    RequestLeaderboard { query: LeaderboardQuery, limit: u16 },
+    /// Chat line for every player in the cluster (authenticated connections only)
+    Chat { text: String },
+    /// Register this install's save-signing key (hex) with the signed-in account
+    RegisterSaveKey { key: String },
+    /// Upload a locally written save (its JSON) as an online copy; only saves signed
+    /// with the account's registered key are accepted
+    UploadSave { name: String, save_json: String },
 }
 
 /// Serializable player state for snapshots
@@ -1,6 +1,17 @@
 use crate::components::SpriteAnimationSheets;
 use bevy::prelude::*;
 use std::io::ErrorKind;
+use std::sync::Arc;
+
+pub mod boss;
+pub mod move_list;
+pub mod scoring;
+pub mod status_effects;
+
+pub use boss::{BossPattern, BossPhaseTuning, BossTuning};
+pub use move_list::MoveListTuning;
+pub use scoring::{CombatRecord, RunGrade, ScoringTuning};
+pub use status_effects::{StackingRule, StatusEffectTuning, StatusRule};
 
 // Vec3 序列化支持
 mod vec3_serde {
@@ -62,18 +73,50 @@ impl GameConfig {
 }
 
 /// Gameplay tuning loaded from disk for faster iteration and balancing.
-#[derive(Resource, Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+///
+/// The move list lives in its own file (`MoveListTuning::FILE_PATH`) and is loaded alongside.
+#[derive(Resource, Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct GameplayTuning {
     pub knife: KnifeCombatTuning,
+    pub defense: DefenseTuning,
+    pub scoring: ScoringTuning,
     pub enemies: EnemyDirectorTuning,
+    pub boss: BossTuning,
+    pub status_effects: StatusEffectTuning,
     pub camera_feedback: CameraFeedbackTuning,
+    pub autosave: AutosaveTuning,
+    #[serde(skip, default = "MoveListTuning::builtin")]
+    pub moves: Arc<MoveListTuning>,
+}
+
+impl Default for GameplayTuning {
+    fn default() -> Self {
+        Self {
+            knife: KnifeCombatTuning::default(),
+            defense: DefenseTuning::default(),
+            scoring: ScoringTuning::default(),
+            enemies: EnemyDirectorTuning::default(),
+            boss: BossTuning::default(),
+            status_effects: StatusEffectTuning::default(),
+            camera_feedback: CameraFeedbackTuning::default(),
+            autosave: AutosaveTuning::default(),
+            moves: MoveListTuning::builtin(),
+        }
+    }
 }
 
 impl GameplayTuning {
     pub const FILE_PATH: &'static str = "assets/config/gameplay_tuning.ron";
 
     pub fn load_from_disk() -> Self {
+        Self {
+            moves: MoveListTuning::load_from_disk(),
+            ..Self::load_tuning_file()
+        }
+    }
+
+    fn load_tuning_file() -> Self {
         let file_content = match std::fs::read_to_string(Self::FILE_PATH) {
             Ok(content) => content,
             Err(error) => {
@@ -123,6 +166,43 @@ impl Default for KnifeCombatTuning {
     }
 }
 
+/// 玩家防御：C 按住格挡、F 弹反、E 闪避。
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DefenseTuning {
+    /// 格挡时实际承受的伤害比例（削血）
+    pub guard_chip_ratio: f32,
+    pub parry_window_secs: f32,
+    pub parry_cooldown_secs: f32,
+    /// 弹反命中近身敌人时施加的硬直
+    pub parry_stun_secs: f32,
+    /// 弹反成功后的无敌时间，吞掉同一帧的接触伤害
+    pub parry_invulnerability_secs: f32,
+    /// 被反弹的敌方飞弹速度倍率
+    pub parry_reflect_speed_scale: f32,
+    pub dodge_duration_secs: f32,
+    pub dodge_iframe_secs: f32,
+    pub dodge_speed: f32,
+    pub dodge_cooldown_secs: f32,
+}
+
+impl Default for DefenseTuning {
+    fn default() -> Self {
+        Self {
+            guard_chip_ratio: 0.2,
+            parry_window_secs: 0.18,
+            parry_cooldown_secs: 0.5,
+            parry_stun_secs: 0.6,
+            parry_invulnerability_secs: 0.2,
+            parry_reflect_speed_scale: 1.25,
+            dodge_duration_secs: 0.32,
+            dodge_iframe_secs: 0.24,
+            dodge_speed: 420.0,
+            dodge_cooldown_secs: 0.6,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct EnemyDirectorTuning {
@@ -133,9 +213,15 @@ pub struct EnemyDirectorTuning {
     pub slime: EnemyArchetypeTuning,
     pub familiar: EnemyArchetypeTuning,
     pub heroic_spirit: EnemyArchetypeTuning,
+    pub shadow: EnemyArchetypeTuning,
+    /// 黑泥的 `contact_damage` 是玩家站在泥中时每秒被吸取的生命
+    pub black_mud: EnemyArchetypeTuning,
     pub slime_behavior: SlimeBehaviorTuning,
     pub familiar_behavior: FamiliarBehaviorTuning,
     pub heroic_spirit_behavior: HeroicSpiritBehaviorTuning,
+    pub shadow_behavior: ShadowBehaviorTuning,
+    pub black_mud_behavior: BlackMudBehaviorTuning,
+    pub physics: EnemyPhysicsTuning,
 }
 
 impl Default for EnemyDirectorTuning {
@@ -166,9 +252,26 @@ impl Default for EnemyDirectorTuning {
                 contact_damage: 19.0,
                 spawn_y_offset: 30.0,
             },
+            shadow: EnemyArchetypeTuning {
+                health: 9,
+                patrol_range: 320.0,
+                base_speed: 104.0,
+                contact_damage: 15.0,
+                spawn_y_offset: 40.0,
+            },
+            black_mud: EnemyArchetypeTuning {
+                health: 12,
+                patrol_range: 220.0,
+                base_speed: 26.0,
+                contact_damage: 9.0,
+                spawn_y_offset: 12.0,
+            },
             slime_behavior: SlimeBehaviorTuning::default(),
             familiar_behavior: FamiliarBehaviorTuning::default(),
             heroic_spirit_behavior: HeroicSpiritBehaviorTuning::default(),
+            shadow_behavior: ShadowBehaviorTuning::default(),
+            black_mud_behavior: BlackMudBehaviorTuning::default(),
+            physics: EnemyPhysicsTuning::default(),
         }
     }
 }
@@ -179,6 +282,8 @@ pub struct EnemySpawnWeights {
     pub slime: f32,
     pub familiar: f32,
     pub heroic_spirit: f32,
+    pub shadow: f32,
+    pub black_mud: f32,
 }
 
 impl Default for EnemySpawnWeights {
@@ -187,6 +292,8 @@ impl Default for EnemySpawnWeights {
             slime: 0.44,
             familiar: 0.33,
             heroic_spirit: 0.23,
+            shadow: 0.12,
+            black_mud: 0.10,
         }
     }
 }
@@ -218,6 +325,8 @@ impl Default for EnemyArchetypeTuning {
 pub struct SlimeBehaviorTuning {
     pub engage_distance: f32,
     pub burst_speed_multiplier: f32,
+    /// 扑击持续时间
+    pub burst_secs: f32,
     pub burst_cooldown_secs: f32,
 }
 
@@ -226,6 +335,7 @@ impl Default for SlimeBehaviorTuning {
         Self {
             engage_distance: 88.0,
             burst_speed_multiplier: 1.95,
+            burst_secs: 0.18,
             burst_cooldown_secs: 1.25,
         }
     }
@@ -281,6 +391,101 @@ impl Default for HeroicSpiritBehaviorTuning {
     }
 }
 
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ShadowBehaviorTuning {
+    pub grab_trigger_distance: f32,
+    pub lunge_windup_secs: f32,
+    pub lunge_secs: f32,
+    pub lunge_speed_multiplier: f32,
+    /// 抓住玩家后保持的时间
+    pub grab_hold_secs: f32,
+    pub grab_cooldown_secs: f32,
+    /// 穿过地形时偏离锚点的最大高度
+    pub phase_range: f32,
+    pub phase_alpha: f32,
+}
+
+impl Default for ShadowBehaviorTuning {
+    fn default() -> Self {
+        Self {
+            grab_trigger_distance: 170.0,
+            lunge_windup_secs: 0.45,
+            lunge_secs: 0.35,
+            lunge_speed_multiplier: 2.8,
+            grab_hold_secs: 1.1,
+            grab_cooldown_secs: 2.6,
+            phase_range: 160.0,
+            phase_alpha: 0.55,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BlackMudBehaviorTuning {
+    pub surge_trigger_distance: f32,
+    pub surge_windup_secs: f32,
+    pub surge_secs: f32,
+    pub surge_speed_multiplier: f32,
+    pub surge_cooldown_secs: f32,
+}
+
+impl Default for BlackMudBehaviorTuning {
+    fn default() -> Self {
+        Self {
+            surge_trigger_distance: 180.0,
+            surge_windup_secs: 0.6,
+            surge_secs: 0.7,
+            surge_speed_multiplier: 2.4,
+            surge_cooldown_secs: 3.0,
+        }
+    }
+}
+
+/// 贴地敌人的受击物理：击退、挑空、浮空追击与撞墙反弹。
+/// 浮空（Hover）与穿行（Phase）类敌人不受重力影响。
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct EnemyPhysicsTuning {
+    pub gravity: f32,
+    pub max_fall_speed: f32,
+    /// 招式 `knockback_x` 换算为敌人水平速度的倍率
+    pub knockback_scale_x: f32,
+    /// 浮空中被命中时，招式 `knockback_y` 换算为竖直速度的倍率
+    pub air_knockback_scale_y: f32,
+    /// 同一次浮空中每多一次命中，竖直速度再乘以该系数，避免无限浮空
+    pub juggle_decay: f32,
+    /// 落地滑行时每秒的水平速度衰减
+    pub ground_friction: f32,
+    pub air_drag: f32,
+    /// 撞墙时水平速度不低于该值才会反弹
+    pub wall_bounce_min_speed: f32,
+    pub wall_restitution: f32,
+    /// 一次浮空中最多反弹的次数
+    pub max_wall_bounces: u32,
+    /// 被击飞后落地的倒地硬直
+    pub landing_stun_secs: f32,
+}
+
+impl Default for EnemyPhysicsTuning {
+    fn default() -> Self {
+        Self {
+            gravity: 1500.0,
+            max_fall_speed: 900.0,
+            knockback_scale_x: 2.0,
+            air_knockback_scale_y: 4.0,
+            juggle_decay: 0.85,
+            ground_friction: 10.0,
+            air_drag: 1.5,
+            wall_bounce_min_speed: 160.0,
+            wall_restitution: 0.55,
+            max_wall_bounces: 2,
+            landing_stun_secs: 0.18,
+        }
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 #[serde(default)]
 pub struct CameraFeedbackTuning {
@@ -299,6 +504,27 @@ impl Default for CameraFeedbackTuning {
     }
 }
 
+/// 自动存档：在 `saves/` 下轮换写入 `autosave_1..=slot_count` 槽位。
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct AutosaveTuning {
+    pub enabled: bool,
+    pub interval_secs: f32,
+    pub slot_count: u8,
+    pub save_on_checkpoint: bool,
+}
+
+impl Default for AutosaveTuning {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_secs: 30.0,
+            slot_count: 3,
+            save_on_checkpoint: true,
+        }
+    }
+}
+
 /// 全局资源句柄。
 ///
 /// 角色动画的纹理、图集布局和帧数必须成套使用，因此 HF 士郎只保存一个
@@ -363,6 +589,21 @@ pub struct GameStats {
     pub distance_traveled: f32,
     pub jump_count: u32,
     pub play_time: f32,
+    pub combat: CombatRecord,
+    /// 本局从未通过本机签名校验的存档继续：成绩只留在本地，不上报服务器
+    pub untrusted_save: bool,
+}
+
+impl GameStats {
+    /// 本局总分：距离、跳跃与战斗得分
+    pub fn score(&self) -> u32 {
+        scoring::base_run_score(self.distance_traveled, self.jump_count)
+            .saturating_add(self.combat.points)
+    }
+
+    pub fn grade(&self, tuning: &ScoringTuning) -> RunGrade {
+        RunGrade::from_score(self.score(), &tuning.grade_thresholds)
+    }
 }
 
 fn default_save_file_version() -> String {
@@ -377,6 +618,17 @@ pub struct SaveFileData {
     pub metadata: SaveFileMetadata,
     pub game_state: CompleteGameState,
     pub checksum: String,
+    /// 可选的安装密钥签名，见 `systems::save_signing`；旧存档没有该字段
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<SaveSignature>,
+}
+
+/// 存档的 keyed MAC 签名
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SaveSignature {
+    pub algorithm: String,
+    pub key_id: String,
+    pub mac: String,
 }
 
 impl SaveFileData {
@@ -386,12 +638,18 @@ impl SaveFileData {
             metadata,
             game_state,
             checksum: String::new(),
+            signature: None,
         };
         // Calculate checksum after creating the struct
-        data.checksum = Self::calculate_checksum_for(&data);
+        data.refresh_checksum();
         data
     }
 
+    /// 内容变动后重新计算校验和
+    pub fn refresh_checksum(&mut self) {
+        self.checksum = Self::calculate_checksum_for(self);
+    }
+
     fn calculate_checksum_for(data: &SaveFileData) -> String {
         use crate::systems::shared_utils::calculate_checksum;
 
@@ -421,18 +679,26 @@ impl SaveFileData {
             return true;
         }
 
-        let Some(payload) = serialized_with_blank_checksum(serialized, &self.checksum) else {
+        let Some(payload) = serialized_with_blank_field(serialized, "checksum", &self.checksum)
+        else {
             return false;
         };
         crate::systems::shared_utils::calculate_checksum(payload.as_bytes()) == self.checksum
     }
 }
 
-fn serialized_with_blank_checksum(serialized: &str, expected: &str) -> Option<String> {
-    let key_start = serialized.rfind("\"checksum\"")?;
-    let after_key = &serialized[key_start + "\"checksum\"".len()..];
+/// 把序列化文本中最后一个 `field` 字段的字符串值留空，其余字节保持原样；
+/// 字段的值必须等于 `expected`
+pub(crate) fn serialized_with_blank_field(
+    serialized: &str,
+    field: &str,
+    expected: &str,
+) -> Option<String> {
+    let quoted_key = format!("\"{field}\"");
+    let key_start = serialized.rfind(&quoted_key)?;
+    let after_key = &serialized[key_start + quoted_key.len()..];
     let colon_offset = after_key.find(':')?;
-    let after_colon = key_start + "\"checksum\"".len() + colon_offset + 1;
+    let after_colon = key_start + quoted_key.len() + colon_offset + 1;
     let value_start = serialized[after_colon..].find('"')? + after_colon;
     let value_end = serialized[value_start + 1..].find('"')? + value_start + 1;
 
@@ -446,22 +712,6 @@ fn serialized_with_blank_checksum(serialized: &str, expected: &str) -> Option<St
     Some(payload)
 }
 
-/// 存档管理资源
-#[derive(Resource, Default)]
-pub struct SaveManager {
-    pub current_save: Option<SaveFileData>,
-    pub save_file_path: String,
-}
-
-impl SaveManager {
-    pub fn new() -> Self {
-        Self {
-            current_save: None,
-            save_file_path: "save_data.json".to_string(),
-        }
-    }
-}
-
 /// 完整游戏状态 - 用于暂停存档系统
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct CompleteGameState {
@@ -484,6 +734,16 @@ pub struct CompleteGameState {
     pub distance_traveled: f32,
     pub jump_count: u32,
     pub play_time: f32,
+    /// 击杀、连段与清场成绩（旧存档没有该字段）
+    #[serde(default)]
+    pub combat: CombatRecord,
+    /// 玩家身上的状态异常（旧存档没有该字段）
+    #[serde(default)]
+    pub player_status_effects: crate::components::StatusEffects,
+    /// 这一局曾从未签名、他人签名或无法校验的存档读入；该标记随签名一起保存，
+    /// 再次存档也无法洗掉（值为 `false` 时不写出，旧存档的签名负载保持不变）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub untrusted_save: bool,
 
     // Character selection and player count
     pub selected_character: crate::states::CharacterType,
@@ -552,6 +812,9 @@ impl Default for CompleteGameState {
             distance_traveled: 0.0,
             jump_count: 0,
             play_time: 0.0,
+            combat: CombatRecord::default(),
+            player_status_effects: crate::components::StatusEffects::default(),
+            untrusted_save: false,
             selected_character: crate::states::CharacterType::Shirou,
             player_count: PlayerCount::Single,
             music_position: 0.0,
@@ -568,6 +831,8 @@ impl Default for CompleteGameState {
 pub struct SaveFileManager {
     pub save_directory: String,
     pub save_files: Vec<SaveFileMetadata>,
+    /// 扫描时未通过校验的存档路径（仍然列出，便于从备份恢复）
+    pub damaged_save_paths: Vec<String>,
     pub current_save_name: Option<String>,
     pub selected_save_index: Option<usize>,
 }
@@ -577,6 +842,7 @@ impl SaveFileManager {
         Self {
             save_directory: "saves".to_string(),
             save_files: Vec::new(),
+            damaged_save_paths: Vec::new(),
             current_save_name: None,
             selected_save_index: None,
         }
@@ -602,11 +868,20 @@ pub struct SaveFileMetadata {
     pub selected_character: crate::states::CharacterType,
 }
 
+/// 存档缩略图 - 暂停瞬间截取并缩小的游戏画面（sRGB RGBA8）
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SaveThumbnail {
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
 /// 暂停管理器
 #[derive(Resource, Default)]
 pub struct PauseManager {
     pub is_paused: bool,
     pub preserved_state: Option<CompleteGameState>,
+    pub preserved_thumbnail: Option<SaveThumbnail>,
     pub pause_timestamp: Option<std::time::Instant>,
 }
 
@@ -615,6 +890,7 @@ impl PauseManager {
         Self {
             is_paused: false,
             preserved_state: None,
+            preserved_thumbnail: None,
             pause_timestamp: None,
         }
     }
@@ -622,17 +898,21 @@ impl PauseManager {
     pub fn pause_game(&mut self, state: CompleteGameState) {
         self.is_paused = true;
         self.preserved_state = Some(state);
+        // 缩略图由截图回调异步填入，旧暂停的画面不能沿用。
+        self.preserved_thumbnail = None;
         self.pause_timestamp = Some(std::time::Instant::now());
     }
 
     pub fn clear_pause_state(&mut self) {
         self.is_paused = false;
         self.preserved_state = None;
+        self.preserved_thumbnail = None;
         self.pause_timestamp = None;
     }
 
     pub fn resume_game(&mut self) -> Option<CompleteGameState> {
         let preserved_state = self.preserved_state.take();
+        self.preserved_thumbnail = None;
         self.is_paused = false;
         self.pause_timestamp = None;
         preserved_state
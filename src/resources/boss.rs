@@ -0,0 +1,206 @@
+//! Boss 战调参：按血量阈值切换的阶段，每个阶段有自己的移动/伤害倍率与招式轮换。
+//!
+//! 数值来自 `config/gameplay_tuning.ron` 的 `boss` 段，支持热重载；Boss 运行时只保存
+//! 阶段与招式的索引（见 [`Boss`](crate::components::Boss)），每帧回查这里的参数。
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BossTuning {
+    /// 血条上显示的名字
+    pub name: String,
+    pub health: i32,
+    pub base_speed: f32,
+    pub contact_damage: f32,
+    /// 切换阶段时的硬直（不出招、不移动）
+    pub phase_transition_secs: f32,
+    /// 按 `health_threshold` 从高到低排列
+    pub phases: Vec<BossPhaseTuning>,
+}
+
+impl Default for BossTuning {
+    fn default() -> Self {
+        Self {
+            name: "Windheart Guardian".to_string(),
+            health: 60,
+            base_speed: 150.0,
+            contact_damage: 22.0,
+            phase_transition_secs: 1.0,
+            phases: vec![
+                BossPhaseTuning {
+                    health_threshold: 1.0,
+                    speed_multiplier: 1.0,
+                    damage_multiplier: 1.0,
+                    pattern_cooldown_secs: 1.6,
+                    patterns: vec![
+                        BossPattern::DashChain {
+                            dashes: 2,
+                            charge_secs: 0.35,
+                            dash_secs: 0.28,
+                            speed_multiplier: 3.0,
+                        },
+                        BossPattern::ProjectileBarrage {
+                            volleys: 2,
+                            projectiles: 3,
+                            spread_degrees: 30.0,
+                            interval_secs: 0.5,
+                            projectile_speed: 260.0,
+                            projectile_damage: 12.0,
+                        },
+                    ],
+                },
+                BossPhaseTuning {
+                    health_threshold: 0.66,
+                    speed_multiplier: 1.15,
+                    damage_multiplier: 1.15,
+                    pattern_cooldown_secs: 1.2,
+                    patterns: vec![
+                        BossPattern::ProjectileBarrage {
+                            volleys: 3,
+                            projectiles: 5,
+                            spread_degrees: 50.0,
+                            interval_secs: 0.45,
+                            projectile_speed: 280.0,
+                            projectile_damage: 13.0,
+                        },
+                        BossPattern::DashChain {
+                            dashes: 3,
+                            charge_secs: 0.3,
+                            dash_secs: 0.26,
+                            speed_multiplier: 3.2,
+                        },
+                        BossPattern::ArenaHazard {
+                            pillars: 3,
+                            spacing: 160.0,
+                            width: 56.0,
+                            telegraph_secs: 0.8,
+                            active_secs: 0.5,
+                            damage: 26.0,
+                        },
+                    ],
+                },
+                BossPhaseTuning {
+                    health_threshold: 0.33,
+                    speed_multiplier: 1.3,
+                    damage_multiplier: 1.3,
+                    pattern_cooldown_secs: 0.8,
+                    patterns: vec![
+                        BossPattern::DashChain {
+                            dashes: 4,
+                            charge_secs: 0.25,
+                            dash_secs: 0.24,
+                            speed_multiplier: 3.4,
+                        },
+                        BossPattern::ArenaHazard {
+                            pillars: 5,
+                            spacing: 128.0,
+                            width: 56.0,
+                            telegraph_secs: 0.65,
+                            active_secs: 0.5,
+                            damage: 28.0,
+                        },
+                        BossPattern::ProjectileBarrage {
+                            volleys: 4,
+                            projectiles: 7,
+                            spread_degrees: 70.0,
+                            interval_secs: 0.4,
+                            projectile_speed: 300.0,
+                            projectile_damage: 14.0,
+                        },
+                    ],
+                },
+            ],
+        }
+    }
+}
+
+impl BossTuning {
+    /// 给定剩余血量比例对应的阶段：取阈值仍不低于该比例的最后一个阶段
+    pub fn phase_for_health(&self, health_fraction: f32) -> usize {
+        self.phases
+            .iter()
+            .rposition(|phase| health_fraction <= phase.health_threshold)
+            .unwrap_or(0)
+    }
+
+    pub fn phase(&self, index: usize) -> Option<&BossPhaseTuning> {
+        self.phases.get(index)
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct BossPhaseTuning {
+    /// 血量比例降到该值及以下时进入本阶段
+    pub health_threshold: f32,
+    pub speed_multiplier: f32,
+    pub damage_multiplier: f32,
+    /// 两个招式之间的间隔
+    pub pattern_cooldown_secs: f32,
+    /// 按顺序轮换的招式
+    pub patterns: Vec<BossPattern>,
+}
+
+impl Default for BossPhaseTuning {
+    fn default() -> Self {
+        Self {
+            health_threshold: 1.0,
+            speed_multiplier: 1.0,
+            damage_multiplier: 1.0,
+            pattern_cooldown_secs: 1.5,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// 脚本化招式
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum BossPattern {
+    /// 连续蓄力冲刺，每段冲刺前重新瞄准玩家
+    DashChain {
+        dashes: u32,
+        charge_secs: f32,
+        dash_secs: f32,
+        speed_multiplier: f32,
+    },
+    /// 朝玩家发射扇形弹幕
+    ProjectileBarrage {
+        volleys: u32,
+        projectiles: u32,
+        spread_degrees: f32,
+        interval_secs: f32,
+        projectile_speed: f32,
+        projectile_damage: f32,
+    },
+    /// 以玩家为中心在竞技场地面召唤一排光柱，预警后生效
+    ArenaHazard {
+        pillars: u32,
+        spacing: f32,
+        width: f32,
+        telegraph_secs: f32,
+        active_secs: f32,
+        damage: f32,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn phases_follow_descending_health_thresholds() {
+        let tuning = BossTuning::default();
+
+        assert_eq!(tuning.phase_for_health(1.0), 0);
+        assert_eq!(tuning.phase_for_health(0.67), 0);
+        assert_eq!(tuning.phase_for_health(0.66), 1);
+        assert_eq!(tuning.phase_for_health(0.2), 2);
+        assert_eq!(tuning.phase_for_health(0.0), 2);
+
+        let empty = BossTuning {
+            phases: Vec::new(),
+            ..BossTuning::default()
+        };
+        assert_eq!(empty.phase_for_health(0.5), 0);
+        assert!(empty.phase(0).is_none());
+    }
+}
@@ -0,0 +1,633 @@
+//! 招式表：近战与投射招式的数值，按 [`AttackAnimationStyle`] 组织
+//!
+//! 数据来自 `assets/config/move_list.ron`，启动时读取并校验；同一个文件在编译时内置，
+//! 文件缺失、解析失败或校验不通过时回退到内置版本。
+
+use crate::asset_paths;
+use crate::components::{AttackAnimationStyle, ProjectileType};
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::sync::{Arc, OnceLock};
+
+type Size = (f32, f32);
+type Offset = (f32, f32, f32);
+type Rgba = (f32, f32, f32, f32);
+
+const BUILTIN_MOVE_LIST: &str = include_str!("../../assets/config/move_list.ron");
+
+/// 对单个数值的一步调整
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum StatOp {
+    Set(f32),
+    Add(f32),
+    Scale(f32),
+    AtLeast(f32),
+    AtMost(f32),
+}
+
+impl StatOp {
+    pub fn apply(self, value: f32) -> f32 {
+        match self {
+            StatOp::Set(x) => x,
+            StatOp::Add(x) => value + x,
+            StatOp::Scale(x) => value * x,
+            StatOp::AtLeast(x) => value.max(x),
+            StatOp::AtMost(x) => value.min(x),
+        }
+    }
+
+    fn operand(self) -> f32 {
+        match self {
+            StatOp::Set(x)
+            | StatOp::Add(x)
+            | StatOp::Scale(x)
+            | StatOp::AtLeast(x)
+            | StatOp::AtMost(x) => x,
+        }
+    }
+}
+
+/// 对宽高的一步调整
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum SizeOp {
+    Set(f32, f32),
+    Scale(f32, f32),
+}
+
+impl SizeOp {
+    pub fn apply(self, (width, height): Size) -> Size {
+        match self {
+            SizeOp::Set(w, h) => (w, h),
+            SizeOp::Scale(w, h) => (width * w, height * h),
+        }
+    }
+}
+
+/// 一次近战出招的完整数值
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MovePreset {
+    pub damage: f32,
+    pub cooldown: f32,
+    pub windup_secs: f32,
+    pub animation_duration_secs: f32,
+    pub lifetime: f32,
+    pub hitbox_size: Size,
+    pub x_offset: f32,
+    pub y_offset: f32,
+    pub crouch_y_offset: f32,
+    pub slash_render_size: Size,
+    pub slash_color: Rgba,
+    pub knockback_x: f32,
+    pub knockback_y: f32,
+    /// 挑空速度（像素/秒）：大于 0 的招式把贴地敌人挑到空中，0 表示不挑空
+    #[serde(default)]
+    pub launch_y: f32,
+    pub hit_stop_secs: f32,
+}
+
+/// 叠加在上一层结果上的调整；未写的字段保持不变
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct MoveModifier {
+    pub damage: Vec<StatOp>,
+    pub cooldown: Vec<StatOp>,
+    pub windup_secs: Vec<StatOp>,
+    pub animation_duration_secs: Vec<StatOp>,
+    pub lifetime: Vec<StatOp>,
+    pub hitbox_size: Vec<SizeOp>,
+    pub x_offset: Vec<StatOp>,
+    pub y_offset: Vec<StatOp>,
+    pub crouch_y_offset: Vec<StatOp>,
+    pub slash_render_size: Vec<SizeOp>,
+    pub slash_color: Option<Rgba>,
+    pub knockback_x: Vec<StatOp>,
+    pub knockback_y: Vec<StatOp>,
+    pub launch_y: Vec<StatOp>,
+    pub hit_stop_secs: Vec<StatOp>,
+}
+
+fn apply_stat(ops: &[StatOp], value: f32) -> f32 {
+    ops.iter().fold(value, |value, op| op.apply(value))
+}
+
+fn apply_size(ops: &[SizeOp], value: Size) -> Size {
+    ops.iter().fold(value, |value, op| op.apply(value))
+}
+
+impl MoveModifier {
+    pub fn apply(&self, preset: MovePreset) -> MovePreset {
+        MovePreset {
+            damage: apply_stat(&self.damage, preset.damage),
+            cooldown: apply_stat(&self.cooldown, preset.cooldown),
+            windup_secs: apply_stat(&self.windup_secs, preset.windup_secs),
+            animation_duration_secs: apply_stat(
+                &self.animation_duration_secs,
+                preset.animation_duration_secs,
+            ),
+            lifetime: apply_stat(&self.lifetime, preset.lifetime),
+            hitbox_size: apply_size(&self.hitbox_size, preset.hitbox_size),
+            x_offset: apply_stat(&self.x_offset, preset.x_offset),
+            y_offset: apply_stat(&self.y_offset, preset.y_offset),
+            crouch_y_offset: apply_stat(&self.crouch_y_offset, preset.crouch_y_offset),
+            slash_render_size: apply_size(&self.slash_render_size, preset.slash_render_size),
+            slash_color: self.slash_color.unwrap_or(preset.slash_color),
+            knockback_x: apply_stat(&self.knockback_x, preset.knockback_x),
+            knockback_y: apply_stat(&self.knockback_y, preset.knockback_y),
+            launch_y: apply_stat(&self.launch_y, preset.launch_y),
+            hit_stop_secs: apply_stat(&self.hit_stop_secs, preset.hit_stop_secs),
+        }
+    }
+
+    fn stat_ops(&self) -> [(&'static str, &[StatOp]); 12] {
+        [
+            ("damage", &self.damage),
+            ("cooldown", &self.cooldown),
+            ("windup_secs", &self.windup_secs),
+            ("animation_duration_secs", &self.animation_duration_secs),
+            ("lifetime", &self.lifetime),
+            ("x_offset", &self.x_offset),
+            ("y_offset", &self.y_offset),
+            ("crouch_y_offset", &self.crouch_y_offset),
+            ("knockback_x", &self.knockback_x),
+            ("knockback_y", &self.knockback_y),
+            ("launch_y", &self.launch_y),
+            ("hit_stop_secs", &self.hit_stop_secs),
+        ]
+    }
+}
+
+/// 投射类招式发出的投射物
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ProjectileDefinition {
+    pub kind: ProjectileType,
+    pub damage: i32,
+    pub speed: f32,
+    pub lifetime: f32,
+    pub collision_size: Size,
+    pub core_size: Size,
+    pub core_color: Rgba,
+    pub aura_size: Size,
+    pub aura_color: Rgba,
+    pub accent_size: Size,
+    pub accent_color: Rgba,
+    pub aura_offset: Offset,
+    pub accent_offset: Offset,
+    pub initial_rotation: f32,
+    pub accent_rotation: f32,
+    pub cooldown: f32,
+    pub pulse_speed: f32,
+    pub pulse_amount: f32,
+    pub spin_speed: f32,
+}
+
+/// 招式族（如 `HeavyRef`）：行轮换顺序、族内共用的调整与冷却下限
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct MoveFamily {
+    /// 连续按键时依次使用的行
+    pub rows: Vec<u8>,
+    /// Overedge 模组激活时依次使用的行
+    pub overedge_rows: Vec<u8>,
+    /// 族内没有单独设置冷却下限的行使用此值
+    pub cooldown_floor_secs: Option<f32>,
+    pub preset: MoveModifier,
+}
+
+/// 单个招式（具体的行或 Overedge 招式）
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct MoveDefinition {
+    pub preset: MoveModifier,
+    /// 出招后到下一次出招的最短间隔
+    pub cooldown_floor_secs: Option<f32>,
+    /// 覆盖由精灵表帧数推算的动画时长
+    pub animation_duration_secs: Option<f32>,
+    pub projectile: Option<ProjectileDefinition>,
+}
+
+/// 完整招式表
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct MoveListTuning {
+    /// 按连击段取基础数值，超出部分使用最后一段
+    pub combo_steps: Vec<MovePreset>,
+    pub overedge: MoveModifier,
+    #[serde(default)]
+    pub families: BTreeMap<AttackAnimationStyle, MoveFamily>,
+    #[serde(default)]
+    pub moves: BTreeMap<AttackAnimationStyle, MoveDefinition>,
+}
+
+impl MoveListTuning {
+    pub const FILE_PATH: &'static str = "assets/config/move_list.ron";
+
+    /// 编译时内置的招式表
+    pub fn builtin() -> Arc<Self> {
+        static BUILTIN: OnceLock<Arc<MoveListTuning>> = OnceLock::new();
+        BUILTIN
+            .get_or_init(|| {
+                Arc::new(ron::from_str(BUILTIN_MOVE_LIST).expect("built-in move list should parse"))
+            })
+            .clone()
+    }
+
+    pub fn load_from_disk() -> Arc<Self> {
+        let file_content = match std::fs::read_to_string(Self::FILE_PATH) {
+            Ok(content) => content,
+            Err(error) => {
+                if error.kind() != ErrorKind::NotFound {
+                    warn!("Failed to read move list '{}': {}", Self::FILE_PATH, error);
+                }
+                return Self::builtin();
+            }
+        };
+
+        match Self::parse(&file_content) {
+            Ok(moves) => {
+                crate::debug_log!("Loaded move list from {}", Self::FILE_PATH);
+                Arc::new(moves)
+            }
+            Err(errors) => {
+                for error in errors {
+                    warn!("Invalid move list '{}': {}", Self::FILE_PATH, error);
+                }
+                warn!("Falling back to the built-in move list.");
+                Self::builtin()
+            }
+        }
+    }
+
+    /// 解析并校验招式表
+    pub fn parse(content: &str) -> Result<Self, Vec<String>> {
+        let moves: Self = ron::from_str(content).map_err(|error| vec![error.to_string()])?;
+        moves.validate()?;
+        Ok(moves)
+    }
+
+    /// 某招式族的行轮换顺序
+    pub fn family_rows(&self, family: AttackAnimationStyle, overedge: bool) -> &[u8] {
+        self.families.get(&family).map_or(&[], |family| {
+            if overedge {
+                &family.overedge_rows
+            } else {
+                &family.rows
+            }
+        })
+    }
+
+    /// 连击段、Overedge、招式族、具体招式逐层叠加后的数值
+    pub fn preset_for(&self, step: u8, overedge: bool, style: AttackAnimationStyle) -> MovePreset {
+        let index = usize::from(step.saturating_sub(1)).min(self.combo_steps.len() - 1);
+        let mut preset = self.combo_steps[index];
+        if overedge {
+            preset = self.overedge.apply(preset);
+        }
+        if let Some(family) = style
+            .reference_family()
+            .and_then(|family| self.families.get(&family))
+        {
+            preset = family.preset.apply(preset);
+        }
+        if let Some(definition) = self.moves.get(&style) {
+            preset = definition.preset.apply(preset);
+        }
+        preset
+    }
+
+    /// 招式自身的冷却下限；具体行没有设置时使用招式族的值
+    pub fn cooldown_floor(&self, style: AttackAnimationStyle) -> Option<f32> {
+        if let Some(floor) = self
+            .moves
+            .get(&style)
+            .and_then(|definition| definition.cooldown_floor_secs)
+        {
+            return Some(floor);
+        }
+        style.reference_row()?;
+        self.families
+            .get(&style.reference_family()?)?
+            .cooldown_floor_secs
+    }
+
+    pub fn animation_duration(&self, style: AttackAnimationStyle) -> Option<f32> {
+        self.moves.get(&style)?.animation_duration_secs
+    }
+
+    pub fn projectile(&self, style: AttackAnimationStyle) -> Option<&ProjectileDefinition> {
+        self.moves.get(&style)?.projectile.as_ref()
+    }
+
+    /// 检查招式表；返回全部问题而不是第一个
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+
+        if self.combo_steps.is_empty() {
+            errors.push("combo_steps must contain at least one step".to_string());
+        }
+        for (index, preset) in self.combo_steps.iter().enumerate() {
+            check_preset(&format!("combo_steps[{index}]"), preset, &mut errors);
+        }
+        check_modifier("overedge", &self.overedge, &mut errors);
+
+        for (style, family) in &self.families {
+            let path = format!("families[{style:?}]");
+            if style.reference_family() != Some(*style) {
+                errors.push(format!("{path}: not a reference move family"));
+                continue;
+            }
+            let sheet_rows = reference_sheet_rows(*style);
+            for (field, rows) in [
+                ("rows", &family.rows),
+                ("overedge_rows", &family.overedge_rows),
+            ] {
+                if rows.is_empty() {
+                    errors.push(format!("{path}.{field}: must list at least one row"));
+                }
+                for row in rows {
+                    if !(1..=sheet_rows).contains(row) {
+                        errors.push(format!(
+                            "{path}.{field}: row {row} is outside the sprite sheet (1..={sheet_rows})"
+                        ));
+                    }
+                }
+            }
+            check_non_negative(
+                &format!("{path}.cooldown_floor_secs"),
+                family.cooldown_floor_secs,
+                &mut errors,
+            );
+            check_modifier(&format!("{path}.preset"), &family.preset, &mut errors);
+        }
+
+        for (style, definition) in &self.moves {
+            let path = format!("moves[{style:?}]");
+            match (style.reference_family(), style.reference_row()) {
+                (Some(family), Some(row)) => {
+                    let sheet_rows = reference_sheet_rows(family);
+                    if !(1..=sheet_rows).contains(&row) {
+                        errors.push(format!(
+                            "{path}: row {row} is outside the sprite sheet (1..={sheet_rows})"
+                        ));
+                    }
+                }
+                _ if is_overedge_move(*style) => {}
+                _ => errors.push(format!(
+                    "{path}: moves must be a reference board row or an Overedge move"
+                )),
+            }
+            check_non_negative(
+                &format!("{path}.cooldown_floor_secs"),
+                definition.cooldown_floor_secs,
+                &mut errors,
+            );
+            check_non_negative(
+                &format!("{path}.animation_duration_secs"),
+                definition.animation_duration_secs,
+                &mut errors,
+            );
+            check_modifier(&format!("{path}.preset"), &definition.preset, &mut errors);
+            if let Some(projectile) = &definition.projectile {
+                check_projectile(&format!("{path}.projectile"), projectile, &mut errors);
+            }
+        }
+
+        // 叠加后的数值也必须合法（例如 Add 不能把判定框减成负数）
+        if errors.is_empty() {
+            let styles = self.families.keys().chain(self.moves.keys());
+            for style in styles {
+                for step in 1..=self.combo_steps.len() as u8 {
+                    for overedge in [false, true] {
+                        let path = format!("{style:?} (combo step {step}, overedge {overedge})");
+                        check_preset(&path, &self.preset_for(step, overedge, *style), &mut errors);
+                    }
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn reference_sheet_rows(family: AttackAnimationStyle) -> u8 {
+    let rows = match family {
+        AttackAnimationStyle::GroundLight => asset_paths::REFERENCE_BOARD_GROUND_LIGHT_ROWS,
+        AttackAnimationStyle::AirCombo => asset_paths::REFERENCE_BOARD_AIR_COMBO_ROWS,
+        AttackAnimationStyle::HeavyRef => asset_paths::REFERENCE_BOARD_HEAVY_ROWS,
+        AttackAnimationStyle::UltimateRef => asset_paths::REFERENCE_BOARD_ULTIMATE_ROWS,
+        AttackAnimationStyle::MobilityRef => asset_paths::REFERENCE_BOARD_MOBILITY_ROWS,
+        AttackAnimationStyle::NinjutsuRef => asset_paths::REFERENCE_BOARD_NINJUTSU_ROWS,
+        AttackAnimationStyle::WeaponProjRef => asset_paths::REFERENCE_BOARD_WEAPON_PROJ_ROWS,
+        _ => 0,
+    };
+    rows as u8
+}
+
+fn is_overedge_move(style: AttackAnimationStyle) -> bool {
+    style.is_overedge_light()
+        || matches!(
+            style,
+            AttackAnimationStyle::OveredgeRelease | AttackAnimationStyle::OveredgeHeavy
+        )
+}
+
+fn check_non_negative(path: &str, value: Option<f32>, errors: &mut Vec<String>) {
+    if let Some(value) = value
+        && !(value.is_finite() && value >= 0.0)
+    {
+        errors.push(format!("{path}: must be a finite, non-negative number"));
+    }
+}
+
+fn check_color(path: &str, (r, g, b, a): Rgba, errors: &mut Vec<String>) {
+    if ![r, g, b, a]
+        .into_iter()
+        .all(|channel| (0.0..=1.0).contains(&channel))
+    {
+        errors.push(format!("{path}: color channels must be within 0.0..=1.0"));
+    }
+}
+
+fn check_size(path: &str, (width, height): Size, errors: &mut Vec<String>) {
+    check_non_negative(&format!("{path}.0"), Some(width), errors);
+    check_non_negative(&format!("{path}.1"), Some(height), errors);
+}
+
+fn check_preset(path: &str, preset: &MovePreset, errors: &mut Vec<String>) {
+    for (field, value) in [
+        ("damage", preset.damage),
+        ("cooldown", preset.cooldown),
+        ("windup_secs", preset.windup_secs),
+        ("animation_duration_secs", preset.animation_duration_secs),
+        ("lifetime", preset.lifetime),
+        ("launch_y", preset.launch_y),
+        ("hit_stop_secs", preset.hit_stop_secs),
+    ] {
+        check_non_negative(&format!("{path}.{field}"), Some(value), errors);
+    }
+    for (field, value) in [
+        ("x_offset", preset.x_offset),
+        ("y_offset", preset.y_offset),
+        ("crouch_y_offset", preset.crouch_y_offset),
+        ("knockback_x", preset.knockback_x),
+        ("knockback_y", preset.knockback_y),
+    ] {
+        if !value.is_finite() {
+            errors.push(format!("{path}.{field}: must be a finite number"));
+        }
+    }
+    check_size(&format!("{path}.hitbox_size"), preset.hitbox_size, errors);
+    check_size(
+        &format!("{path}.slash_render_size"),
+        preset.slash_render_size,
+        errors,
+    );
+    check_color(&format!("{path}.slash_color"), preset.slash_color, errors);
+}
+
+fn check_modifier(path: &str, modifier: &MoveModifier, errors: &mut Vec<String>) {
+    for (field, ops) in modifier.stat_ops() {
+        if ops.iter().any(|op| !op.operand().is_finite()) {
+            errors.push(format!("{path}.{field}: operands must be finite numbers"));
+        }
+    }
+    for (field, ops) in [
+        ("hitbox_size", &modifier.hitbox_size),
+        ("slash_render_size", &modifier.slash_render_size),
+    ] {
+        if ops.iter().any(|op| {
+            let (SizeOp::Set(w, h) | SizeOp::Scale(w, h)) = *op;
+            !(w.is_finite() && h.is_finite())
+        }) {
+            errors.push(format!("{path}.{field}: operands must be finite numbers"));
+        }
+    }
+    if let Some(color) = modifier.slash_color {
+        check_color(&format!("{path}.slash_color"), color, errors);
+    }
+}
+
+fn check_projectile(path: &str, projectile: &ProjectileDefinition, errors: &mut Vec<String>) {
+    if projectile.damage < 0 {
+        errors.push(format!("{path}.damage: must not be negative"));
+    }
+    for (field, value) in [
+        ("speed", projectile.speed),
+        ("lifetime", projectile.lifetime),
+    ] {
+        if !(value.is_finite() && value > 0.0) {
+            errors.push(format!("{path}.{field}: must be a positive number"));
+        }
+    }
+    check_non_negative(
+        &format!("{path}.cooldown"),
+        Some(projectile.cooldown),
+        errors,
+    );
+    for (field, size) in [
+        ("collision_size", projectile.collision_size),
+        ("core_size", projectile.core_size),
+        ("aura_size", projectile.aura_size),
+        ("accent_size", projectile.accent_size),
+    ] {
+        check_size(&format!("{path}.{field}"), size, errors);
+    }
+    for (field, color) in [
+        ("core_color", projectile.core_color),
+        ("aura_color", projectile.aura_color),
+        ("accent_color", projectile.accent_color),
+    ] {
+        check_color(&format!("{path}.{field}"), color, errors);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_move_list_is_valid() {
+        let moves = MoveListTuning::builtin();
+        assert_eq!(moves.validate(), Ok(()));
+        assert_eq!(moves.combo_steps.len(), 3);
+        assert_eq!(
+            moves.family_rows(AttackAnimationStyle::HeavyRef, true),
+            &[3, 5, 4]
+        );
+        assert_eq!(
+            moves.family_rows(AttackAnimationStyle::AdvanceRef, false),
+            &[] as &[u8]
+        );
+    }
+
+    #[test]
+    fn presets_stack_combo_step_overedge_family_and_row() {
+        let moves = MoveListTuning::builtin();
+
+        // 第 2 段 + Overedge + 重攻击族 + 第 3 行
+        let preset = moves.preset_for(2, true, AttackAnimationStyle::HeavyRefRow(3));
+        let overedge_damage = 8.0 * 1.25;
+        assert_eq!(preset.damage, overedge_damage * 1.45 * 1.18);
+        assert_eq!(
+            preset.cooldown,
+            (0.26f32 - 0.03).max(0.14).max(0.42).max(0.48)
+        );
+        assert_eq!(preset.hitbox_size, (174.0, 44.0));
+        assert_eq!(preset.slash_color, (1.0, 0.30, 0.24, 0.34));
+
+        let backstep = moves.preset_for(1, false, AttackAnimationStyle::MobilityRefRow(3));
+        assert!(backstep.knockback_x < 0.0);
+
+        assert_eq!(
+            moves.cooldown_floor(AttackAnimationStyle::NinjutsuRefRow(4)),
+            Some(0.50)
+        );
+        assert_eq!(
+            moves.cooldown_floor(AttackAnimationStyle::NinjutsuRefRow(2)),
+            Some(0.44)
+        );
+        assert_eq!(moves.cooldown_floor(AttackAnimationStyle::HeavyRef), None);
+        assert_eq!(
+            moves
+                .projectile(AttackAnimationStyle::NinjutsuRefRow(1))
+                .map(|projectile| projectile.kind),
+            Some(ProjectileType::Fireball)
+        );
+    }
+
+    #[test]
+    fn invalid_move_lists_report_every_problem() {
+        let mut moves = (*MoveListTuning::builtin()).clone();
+        moves
+            .families
+            .get_mut(&AttackAnimationStyle::UltimateRef)
+            .unwrap()
+            .rows
+            .push(4);
+        moves
+            .moves
+            .insert(AttackAnimationStyle::GroundLight, MoveDefinition::default());
+        moves
+            .moves
+            .get_mut(&AttackAnimationStyle::AirComboRow(1))
+            .unwrap()
+            .cooldown_floor_secs = Some(-1.0);
+
+        let errors = moves.validate().expect_err("invalid move list should fail");
+        assert_eq!(errors.len(), 3, "{errors:?}");
+        assert!(errors[0].contains("families[UltimateRef].rows: row 4"));
+
+        // 叠加后判定框变成负数
+        let mut moves = (*MoveListTuning::builtin()).clone();
+        moves.overedge.hitbox_size = vec![SizeOp::Scale(-1.0, 1.0)];
+        assert!(moves.validate().is_err());
+
+        let errors = MoveListTuning::parse("(combo_steps: [], overedge: ())")
+            .expect_err("empty combo steps should fail");
+        assert_eq!(errors, vec!["combo_steps must contain at least one step"]);
+        assert!(MoveListTuning::parse("(combo_steps: [").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! 战斗计分：击杀按敌人类型给分并乘以连段倍率，竞技场无伤清场另有奖励，
+//! 总分（距离 + 跳跃 + 战斗）决定本局评级。
+//!
+//! 计分数值来自 `config/gameplay_tuning.ron` 的 `scoring` 段；运行时的累计结果保存在
+//! [`GameStats::combat`](crate::resources::GameStats) 中，随存档与在线会话记录一起保存。
+
+use crate::components::EnemyType;
+
+/// 距离与跳跃部分的分数（单机存档与在线会话使用同一公式）
+pub fn base_run_score(distance_traveled: f32, jump_count: u32) -> u32 {
+    ((distance_traveled.max(0.0) * 10.0) as u32).saturating_add(jump_count.saturating_mul(50))
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct ScoringTuning {
+    pub slime_points: u32,
+    pub familiar_points: u32,
+    pub heroic_spirit_points: u32,
+    pub shadow_points: u32,
+    pub black_mud_points: u32,
+    /// 连段每多一段增加的倍率
+    pub combo_bonus_per_step: f32,
+    pub max_combo_multiplier: f32,
+    /// 竞技场从开门到清场都没有受到敌方伤害时的奖励
+    pub no_damage_arena_bonus: u32,
+    pub grade_thresholds: GradeThresholds,
+}
+
+impl Default for ScoringTuning {
+    fn default() -> Self {
+        Self {
+            slime_points: 100,
+            familiar_points: 150,
+            heroic_spirit_points: 400,
+            shadow_points: 300,
+            black_mud_points: 250,
+            combo_bonus_per_step: 0.25,
+            max_combo_multiplier: 3.0,
+            no_damage_arena_bonus: 1500,
+            grade_thresholds: GradeThresholds::default(),
+        }
+    }
+}
+
+impl ScoringTuning {
+    pub fn kill_points(&self, enemy_type: EnemyType) -> u32 {
+        match enemy_type {
+            EnemyType::Slime => self.slime_points,
+            EnemyType::Familiar => self.familiar_points,
+            EnemyType::EnemyHeroicSpirit => self.heroic_spirit_points,
+            EnemyType::Shadow => self.shadow_points,
+            EnemyType::BlackMud => self.black_mud_points,
+        }
+    }
+
+    /// 连段长度对应的倍率；没有连段（远程击杀等）时为 1
+    pub fn combo_multiplier(&self, combo_chain: u32) -> f32 {
+        let extra_steps = combo_chain.saturating_sub(1) as f32;
+        (1.0 + extra_steps * self.combo_bonus_per_step.max(0.0))
+            .clamp(1.0, self.max_combo_multiplier.max(1.0))
+    }
+}
+
+/// 各评级需要的最低总分
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct GradeThresholds {
+    pub c: u32,
+    pub b: u32,
+    pub a: u32,
+    pub s: u32,
+}
+
+impl Default for GradeThresholds {
+    fn default() -> Self {
+        Self {
+            c: 2_000,
+            b: 6_000,
+            a: 12_000,
+            s: 24_000,
+        }
+    }
+}
+
+/// 本局评级
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Default,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+pub enum RunGrade {
+    #[default]
+    D,
+    C,
+    B,
+    A,
+    S,
+}
+
+impl RunGrade {
+    pub fn from_score(score: u32, thresholds: &GradeThresholds) -> Self {
+        if score >= thresholds.s {
+            Self::S
+        } else if score >= thresholds.a {
+            Self::A
+        } else if score >= thresholds.b {
+            Self::B
+        } else if score >= thresholds.c {
+            Self::C
+        } else {
+            Self::D
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::D => "D",
+            Self::C => "C",
+            Self::B => "B",
+            Self::A => "A",
+            Self::S => "S",
+        }
+    }
+}
+
+/// 一局的战斗成绩
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct CombatRecord {
+    /// 击杀与清场奖励的总分
+    pub points: u32,
+    pub kills: u32,
+    pub best_combo: u32,
+    pub no_damage_clears: u32,
+}
+
+impl CombatRecord {
+    /// 记录一次击杀，返回本次获得的分数
+    pub fn record_kill(
+        &mut self,
+        enemy_type: EnemyType,
+        combo_chain: u32,
+        tuning: &ScoringTuning,
+    ) -> u32 {
+        let points = (tuning.kill_points(enemy_type) as f32 * tuning.combo_multiplier(combo_chain))
+            .round() as u32;
+        self.points = self.points.saturating_add(points);
+        self.kills = self.kills.saturating_add(1);
+        self.best_combo = self.best_combo.max(combo_chain);
+        points
+    }
+
+    pub fn record_no_damage_clear(&mut self, tuning: &ScoringTuning) {
+        self.points = self.points.saturating_add(tuning.no_damage_arena_bonus);
+        self.no_damage_clears = self.no_damage_clears.saturating_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kills_scale_with_combo_and_grades_follow_thresholds() {
+        let tuning = ScoringTuning::default();
+        let mut record = CombatRecord::default();
+
+        assert_eq!(record.record_kill(EnemyType::Slime, 0, &tuning), 100);
+        assert_eq!(record.record_kill(EnemyType::Slime, 3, &tuning), 150);
+        assert_eq!(
+            record.record_kill(EnemyType::EnemyHeroicSpirit, 50, &tuning),
+            (400.0 * tuning.max_combo_multiplier) as u32
+        );
+        assert_eq!(record.kills, 3);
+        assert_eq!(record.best_combo, 50);
+
+        record.record_no_damage_clear(&tuning);
+        assert_eq!(record.no_damage_clears, 1);
+        assert_eq!(record.points, 100 + 150 + 1200 + 1500);
+
+        let thresholds = &tuning.grade_thresholds;
+        assert_eq!(RunGrade::from_score(0, thresholds), RunGrade::D);
+        assert_eq!(RunGrade::from_score(thresholds.b, thresholds), RunGrade::B);
+        assert_eq!(RunGrade::from_score(u32::MAX, thresholds), RunGrade::S);
+    }
+
+    #[test]
+    fn base_run_score_saturates_instead_of_overflowing() {
+        assert_eq!(base_run_score(12.5, 3), 125 + 150);
+        assert_eq!(base_run_score(-5.0, 0), 0);
+        assert_eq!(base_run_score(100.0, u32::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn heavens_feel_archetypes_have_their_own_kill_points() {
+        let tuning = ScoringTuning::default();
+
+        assert_eq!(tuning.kill_points(EnemyType::Shadow), tuning.shadow_points);
+        assert_eq!(
+            tuning.kill_points(EnemyType::BlackMud),
+            tuning.black_mud_points
+        );
+        assert!(tuning.shadow_points > tuning.familiar_points);
+    }
+}
@@ -0,0 +1,124 @@
+//! 状态异常调参：每种状态的叠加规则、持续时间与跳伤，以及各来源施加的层数。
+//!
+//! 数值来自 `config/gameplay_tuning.ron` 的 `status_effects` 段。
+
+use crate::components::StatusKind;
+
+/// 已有同种状态时再次施加的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum StackingRule {
+    /// 层数取较大值，持续时间刷新
+    Refresh,
+    /// 层数累加（不超过上限），持续时间刷新
+    Intensity,
+    /// 层数不变，持续时间累加（不超过 `max_duration_secs`）
+    Extend,
+}
+
+/// 单种状态的规则
+#[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct StatusRule {
+    pub stacking: StackingRule,
+    pub duration_secs: f32,
+    /// 仅 [`StackingRule::Extend`] 使用
+    pub max_duration_secs: f32,
+    pub max_stacks: u32,
+    /// 跳伤间隔，0 表示不跳伤
+    pub tick_interval_secs: f32,
+    pub damage_per_stack: f32,
+    /// 每层减少的移动速度比例
+    pub slow_per_stack: f32,
+}
+
+impl Default for StatusRule {
+    fn default() -> Self {
+        Self {
+            stacking: StackingRule::Refresh,
+            duration_secs: 1.0,
+            max_duration_secs: 1.0,
+            max_stacks: 1,
+            tick_interval_secs: 0.0,
+            damage_per_stack: 0.0,
+            slow_per_stack: 0.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct StatusEffectTuning {
+    pub burn: StatusRule,
+    pub slow: StatusRule,
+    pub stun: StatusRule,
+    pub poison: StatusRule,
+    /// 火球命中敌人施加的灼烧层数
+    pub fireball_burn_stacks: u32,
+    /// Overedge 命中敌人施加的眩晕层数
+    pub overedge_stun_stacks: u32,
+    /// 敌方飞弹命中玩家施加的中毒层数
+    pub enemy_projectile_poison_stacks: u32,
+    /// 危险地块每帧施加的灼烧层数
+    pub hazard_burn_stacks: u32,
+    /// 接触黑泥时施加的减速层数
+    pub black_mud_slow_stacks: u32,
+}
+
+impl Default for StatusEffectTuning {
+    fn default() -> Self {
+        Self {
+            burn: StatusRule {
+                stacking: StackingRule::Refresh,
+                duration_secs: 3.0,
+                max_duration_secs: 3.0,
+                max_stacks: 1,
+                tick_interval_secs: 0.5,
+                damage_per_stack: 3.0,
+                slow_per_stack: 0.0,
+            },
+            slow: StatusRule {
+                stacking: StackingRule::Refresh,
+                duration_secs: 1.2,
+                max_duration_secs: 1.2,
+                max_stacks: 2,
+                tick_interval_secs: 0.0,
+                damage_per_stack: 0.0,
+                slow_per_stack: 0.3,
+            },
+            stun: StatusRule {
+                stacking: StackingRule::Extend,
+                duration_secs: 0.4,
+                max_duration_secs: 1.2,
+                max_stacks: 1,
+                tick_interval_secs: 0.0,
+                damage_per_stack: 0.0,
+                slow_per_stack: 0.0,
+            },
+            poison: StatusRule {
+                stacking: StackingRule::Intensity,
+                duration_secs: 6.0,
+                max_duration_secs: 6.0,
+                max_stacks: 5,
+                tick_interval_secs: 1.0,
+                damage_per_stack: 1.5,
+                slow_per_stack: 0.0,
+            },
+            fireball_burn_stacks: 1,
+            overedge_stun_stacks: 1,
+            enemy_projectile_poison_stacks: 1,
+            hazard_burn_stacks: 1,
+            black_mud_slow_stacks: 1,
+        }
+    }
+}
+
+impl StatusEffectTuning {
+    pub fn rule(&self, kind: StatusKind) -> StatusRule {
+        match kind {
+            StatusKind::Burn => self.burn,
+            StatusKind::Slow => self.slow,
+            StatusKind::Stun => self.stun,
+            StatusKind::Poison => self.poison,
+        }
+    }
+}
@@ -22,7 +22,7 @@ pub struct CharacterSelection {
 }
 
 /// 角色类型
-#[derive(Debug, Clone, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub enum CharacterType {
     #[default]
     #[serde(alias = "Shirou1")]
@@ -32,6 +32,14 @@ pub enum CharacterType {
 }
 
 impl CharacterType {
+    /// 稳定的角色标识（与序列化名称一致），用于会话记录与排行榜
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CharacterType::Shirou => "Shirou",
+            CharacterType::Sakura => "Sakura",
+        }
+    }
+
     pub fn get_texture_path(&self) -> &'static str {
         match self {
             CharacterType::Shirou => crate::asset_paths::IMAGE_HF_SHIROU_IDLE,
@@ -0,0 +1,370 @@
+//! 玩家账号
+//!
+//! 账号与 `players` 表一一对应：注册/登录使用用户名 + 密码，也可以直接领取游客账号。
+//! 认证成功后服务器签发会话令牌，客户端重连时在 WebSocket 握手里携带
+//! （`Authorization: Bearer <token>` 或 `?token=<token>`），服务器据此把连接映射到
+//! `players.id`。数据库只保存令牌的 blake3 摘要，泄露的数据库无法直接冒充玩家。
+//! 客户端把令牌写入 [`SESSION_TOKEN_PATH`]，下次启动继续使用同一账号。
+//!
+//! 登录、注册与游客创建按来源 IP 限流（[`AuthRateLimiter`]），握手令牌不计入。
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io;
+use std::net::IpAddr;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+use crate::protocol::PlayerAction;
+use crate::systems::shared_utils::atomic_write_file;
+
+/// 会话令牌有效期（天）
+pub const SESSION_TOKEN_TTL_DAYS: i64 = 30;
+/// 游客账号用户名前缀，注册时不允许使用
+pub const GUEST_USERNAME_PREFIX: &str = "guest_";
+/// 客户端保存会话令牌的文件（纯文本，不会被存档扫描当作存档）
+pub const SESSION_TOKEN_PATH: &str = "saves/session.token";
+
+/// 账号请求限流窗口
+pub const AUTH_RATE_WINDOW: Duration = Duration::from_secs(10 * 60);
+/// 同一 IP 每个窗口内允许的登录/注册尝试次数
+pub const CREDENTIAL_ATTEMPTS_PER_WINDOW: u32 = 10;
+/// 同一 IP 每个窗口内允许创建的游客账号数
+pub const GUEST_ACCOUNTS_PER_WINDOW: u32 = 3;
+/// 单个连接最多发起的账号请求数，超过后需要重新连接
+pub const AUTH_REQUESTS_PER_CONNECTION: u32 = 5;
+
+const USERNAME_MIN_LEN: usize = 3;
+const USERNAME_MAX_LEN: usize = 32;
+const PASSWORD_MIN_LEN: usize = 8;
+const PASSWORD_MAX_LEN: usize = 72;
+
+/// 已认证连接对应的账号
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountIdentity {
+    pub player_id: Uuid,
+    pub username: String,
+    pub is_guest: bool,
+}
+
+/// 认证成功的结果：账号与会话令牌（明文只发给该连接的客户端）
+#[derive(Debug, Clone)]
+pub struct AuthenticatedAccount {
+    pub identity: AccountIdentity,
+    pub session_token: String,
+}
+
+/// 一次认证请求：握手令牌或客户端发来的账号操作
+#[derive(Debug, Clone)]
+pub enum AuthRequest {
+    SessionToken(String),
+    Register { username: String, password: String },
+    Login { username: String, password: String },
+    Guest,
+}
+
+impl AuthRequest {
+    /// 账号类 `PlayerAction` 转为认证请求，其他操作返回 `None`
+    pub fn from_action(action: &PlayerAction) -> Option<Self> {
+        match action {
+            PlayerAction::Register { username, password } => Some(Self::Register {
+                username: username.trim().to_string(),
+                password: password.clone(),
+            }),
+            PlayerAction::Login { username, password } => Some(Self::Login {
+                username: username.trim().to_string(),
+                password: password.clone(),
+            }),
+            PlayerAction::GuestLogin => Some(Self::Guest),
+            _ => None,
+        }
+    }
+}
+
+/// 账号操作失败原因（`Display` 文本会原样发给客户端）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountError {
+    InvalidUsername,
+    WeakPassword,
+    UsernameTaken,
+    InvalidCredentials,
+    InvalidToken,
+    RateLimited,
+    Unavailable,
+}
+
+impl std::fmt::Display for AccountError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountError::InvalidUsername => write!(
+                f,
+                "Username must be {}-{} characters of A-Z, 0-9, _ or -",
+                USERNAME_MIN_LEN, USERNAME_MAX_LEN
+            ),
+            AccountError::WeakPassword => write!(
+                f,
+                "Password must be {}-{} characters",
+                PASSWORD_MIN_LEN, PASSWORD_MAX_LEN
+            ),
+            AccountError::UsernameTaken => write!(f, "Username is already taken"),
+            AccountError::InvalidCredentials => write!(f, "Wrong username or password"),
+            AccountError::InvalidToken => write!(f, "Session expired, please log in again"),
+            AccountError::RateLimited => {
+                write!(f, "Too many account requests, please try again later")
+            }
+            AccountError::Unavailable => write!(f, "Accounts are unavailable on this server"),
+        }
+    }
+}
+
+impl std::error::Error for AccountError {}
+
+/// 校验注册用户名
+pub fn validate_username(username: &str) -> Result<(), AccountError> {
+    let valid_length = (USERNAME_MIN_LEN..=USERNAME_MAX_LEN).contains(&username.len());
+    let valid_chars = username
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    let reserved = username
+        .to_ascii_lowercase()
+        .starts_with(GUEST_USERNAME_PREFIX);
+
+    if valid_length && valid_chars && !reserved {
+        Ok(())
+    } else {
+        Err(AccountError::InvalidUsername)
+    }
+}
+
+/// 校验注册密码（bcrypt 只使用前 72 字节，更长的密码直接拒绝）
+pub fn validate_password(password: &str) -> Result<(), AccountError> {
+    if (PASSWORD_MIN_LEN..=PASSWORD_MAX_LEN).contains(&password.len()) {
+        Ok(())
+    } else {
+        Err(AccountError::WeakPassword)
+    }
+}
+
+/// 生成游客用户名
+pub fn guest_username() -> String {
+    let suffix: [u8; 6] = rand::random();
+    let hex: String = suffix.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("{}{}", GUEST_USERNAME_PREFIX, hex)
+}
+
+/// 生成新的会话令牌（32 字节随机数的十六进制）
+pub fn generate_session_token() -> String {
+    let bytes: [u8; 32] = rand::random();
+    blake3::Hash::from_bytes(bytes).to_hex().to_string()
+}
+
+/// 令牌在数据库中的存储形式
+pub fn hash_session_token(token: &str) -> String {
+    blake3::hash(token.trim().as_bytes()).to_hex().to_string()
+}
+
+/// 从握手请求中取出会话令牌：优先 `Authorization: Bearer`，其次 URL 查询参数 `token`
+pub fn session_token_from_handshake(
+    authorization: Option<&str>,
+    query: Option<&str>,
+) -> Option<String> {
+    let from_header = authorization
+        .and_then(|value| value.trim().strip_prefix("Bearer "))
+        .map(str::trim);
+    let from_query = query.and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+    });
+
+    from_header
+        .or(from_query)
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+}
+
+/// 账号请求计数的分组
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum AuthRateBucket {
+    Credentials,
+    Guest,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct AuthRateWindow {
+    started_at: Instant,
+    count: u32,
+}
+
+/// 账号请求限流：按来源 IP 分别统计登录/注册与游客创建次数（固定窗口）
+#[derive(Clone, Default)]
+pub struct AuthRateLimiter {
+    windows: Arc<Mutex<HashMap<(IpAddr, AuthRateBucket), AuthRateWindow>>>,
+}
+
+impl AuthRateLimiter {
+    /// 记一次请求；本窗口内该 IP 的额度用完时返回 [`AccountError::RateLimited`]
+    pub fn check(
+        &self,
+        address: IpAddr,
+        request: &AuthRequest,
+        now: Instant,
+    ) -> Result<(), AccountError> {
+        let (bucket, limit) = match request {
+            AuthRequest::SessionToken(_) => return Ok(()),
+            AuthRequest::Register { .. } | AuthRequest::Login { .. } => {
+                (AuthRateBucket::Credentials, CREDENTIAL_ATTEMPTS_PER_WINDOW)
+            }
+            AuthRequest::Guest => (AuthRateBucket::Guest, GUEST_ACCOUNTS_PER_WINDOW),
+        };
+
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        windows.retain(|_, window| now.duration_since(window.started_at) < AUTH_RATE_WINDOW);
+        let window = windows.entry((address, bucket)).or_insert(AuthRateWindow {
+            started_at: now,
+            count: 0,
+        });
+        if window.count >= limit {
+            return Err(AccountError::RateLimited);
+        }
+        window.count += 1;
+        Ok(())
+    }
+}
+
+/// 读取客户端保存的会话令牌；文件不存在或为空时返回 `None`
+pub fn load_session_token(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty())
+}
+
+/// 保存会话令牌；`None` 删除已保存的令牌
+pub fn store_session_token(path: &Path, token: Option<&str>) -> io::Result<()> {
+    match token {
+        Some(token) => atomic_write_file(path, token.as_bytes()),
+        None => match std::fs::remove_file(path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// 客户端连接地址：有令牌时附加 `token` 查询参数（浏览器 WebSocket 无法设置请求头）
+pub fn server_url_with_token(server_url: &str, token: Option<&str>) -> String {
+    match token {
+        Some(token) if !token.is_empty() => {
+            let separator = if server_url.contains('?') { '&' } else { '?' };
+            format!("{}{}token={}", server_url, separator, token)
+        }
+        _ => server_url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registration_rejects_reserved_and_malformed_usernames() {
+        assert!(validate_username("Shirou_01").is_ok());
+        assert_eq!(validate_username("ab"), Err(AccountError::InvalidUsername));
+        assert_eq!(
+            validate_username("Guest_abc"),
+            Err(AccountError::InvalidUsername)
+        );
+        assert_eq!(
+            validate_username("has space"),
+            Err(AccountError::InvalidUsername)
+        );
+        assert_eq!(validate_password("short"), Err(AccountError::WeakPassword));
+        assert!(validate_password("unlimited blade works").is_ok());
+    }
+
+    #[test]
+    fn handshake_token_prefers_bearer_header_over_query() {
+        assert_eq!(
+            session_token_from_handshake(Some("Bearer abc"), Some("token=def")),
+            Some("abc".to_string())
+        );
+        assert_eq!(
+            session_token_from_handshake(None, Some("room=1&token=def")),
+            Some("def".to_string())
+        );
+        assert_eq!(session_token_from_handshake(Some("Basic xyz"), None), None);
+        assert_eq!(session_token_from_handshake(None, Some("token=")), None);
+    }
+
+    #[test]
+    fn only_account_actions_become_auth_requests() {
+        assert!(matches!(
+            AuthRequest::from_action(&PlayerAction::Login {
+                username: " rin ".to_string(),
+                password: "gandr-shot".to_string(),
+            }),
+            Some(AuthRequest::Login { username, .. }) if username == "rin"
+        ));
+        assert!(matches!(
+            AuthRequest::from_action(&PlayerAction::GuestLogin),
+            Some(AuthRequest::Guest)
+        ));
+        assert!(AuthRequest::from_action(&PlayerAction::Ping(1)).is_none());
+    }
+
+    #[test]
+    fn rate_limiter_caps_guest_and_credential_requests_per_address() {
+        let limiter = AuthRateLimiter::default();
+        let address: IpAddr = "10.0.0.1".parse().expect("ip");
+        let other: IpAddr = "10.0.0.2".parse().expect("ip");
+        let now = Instant::now();
+
+        for _ in 0..GUEST_ACCOUNTS_PER_WINDOW {
+            assert!(limiter.check(address, &AuthRequest::Guest, now).is_ok());
+        }
+        assert_eq!(
+            limiter.check(address, &AuthRequest::Guest, now),
+            Err(AccountError::RateLimited)
+        );
+        assert!(limiter.check(other, &AuthRequest::Guest, now).is_ok());
+        let login = AuthRequest::Login {
+            username: "rin".to_string(),
+            password: "gandr-shot".to_string(),
+        };
+        assert!(limiter.check(address, &login, now).is_ok());
+        assert!(
+            limiter
+                .check(address, &AuthRequest::SessionToken("t".to_string()), now)
+                .is_ok()
+        );
+
+        let later = now + AUTH_RATE_WINDOW;
+        assert!(limiter.check(address, &AuthRequest::Guest, later).is_ok());
+    }
+
+    #[test]
+    fn session_token_file_roundtrips_and_clears() {
+        let path =
+            std::env::temp_dir().join(format!("emiyashiro-session-token-{}.token", Uuid::new_v4()));
+        assert_eq!(load_session_token(&path), None);
+        store_session_token(&path, Some("abc123")).expect("store token");
+        assert_eq!(load_session_token(&path).as_deref(), Some("abc123"));
+        store_session_token(&path, None).expect("clear token");
+        assert_eq!(load_session_token(&path), None);
+        store_session_token(&path, None).expect("clearing twice is fine");
+    }
+
+    #[test]
+    fn session_tokens_are_unique_and_stored_hashed() {
+        let token = generate_session_token();
+        assert_eq!(token.len(), 64);
+        assert_ne!(token, generate_session_token());
+        assert_ne!(hash_session_token(&token), token);
+        assert_eq!(
+            server_url_with_token("ws://127.0.0.1:8080", Some(&token)),
+            format!("ws://127.0.0.1:8080?token={}", token)
+        );
+    }
+}
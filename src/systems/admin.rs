@@ -0,0 +1,259 @@
+//! 服务器运维接口的共享部分
+//!
+//! - [`ConnectionRegistry`]：连接任务登记地址、账号与 RTT，运维接口据此列出或踢出连接
+//! - [`AdminQueries`]：运维接口向 ECS 请求世界状态，由 [`answer_admin_queries`] 在主调度中应答
+//!
+//! 运行指标来自共享的 [`MetricsRegistry`](crate::systems::metrics::MetricsRegistry)，
+//! 不经过 ECS 查询。
+//!
+//! HTTP/WebSocket 服务见 `admin_http`。
+
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, mpsc, oneshot};
+
+use crate::components::ai::BotController;
+use crate::components::network::NetworkId;
+use crate::components::physics::Velocity;
+use crate::plugins::server::{ConnectionAccounts, ServerTick};
+
+/// 运维接口默认只监听本机
+pub const DEFAULT_ADMIN_ADDRESS: &str = "127.0.0.1:9090";
+
+struct ConnectionEntry {
+    address: SocketAddr,
+    connected_at: Instant,
+    username: Option<String>,
+    rtt: Option<Duration>,
+    kick: Arc<Notify>,
+}
+
+/// 一条连接的运维视图
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ConnectionSummary {
+    pub client_id: u64,
+    pub address: String,
+    pub username: Option<String>,
+    pub connected_secs: f64,
+    pub rtt_ms: Option<f64>,
+}
+
+/// 当前 WebSocket 连接表（连接任务写入，运维接口读取）
+#[derive(Clone, Default)]
+pub struct ConnectionRegistry(Arc<Mutex<HashMap<u64, ConnectionEntry>>>);
+
+impl ConnectionRegistry {
+    fn with_entries<T>(&self, f: impl FnOnce(&mut HashMap<u64, ConnectionEntry>) -> T) -> T {
+        let mut entries = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        f(&mut entries)
+    }
+
+    /// 登记新连接；返回的 `Notify` 在运维接口踢出该连接时被唤醒
+    pub fn register(&self, client_id: u64, address: SocketAddr) -> Arc<Notify> {
+        let kick = Arc::new(Notify::new());
+        self.with_entries(|entries| {
+            entries.insert(
+                client_id,
+                ConnectionEntry {
+                    address,
+                    connected_at: Instant::now(),
+                    username: None,
+                    rtt: None,
+                    kick: kick.clone(),
+                },
+            )
+        });
+        kick
+    }
+
+    pub fn unregister(&self, client_id: u64) {
+        self.with_entries(|entries| entries.remove(&client_id));
+    }
+
+    pub fn set_username(&self, client_id: u64, username: Option<String>) {
+        self.with_entries(|entries| {
+            if let Some(entry) = entries.get_mut(&client_id) {
+                entry.username = username;
+            }
+        });
+    }
+
+    pub fn record_rtt(&self, client_id: u64, rtt: Duration) {
+        self.with_entries(|entries| {
+            if let Some(entry) = entries.get_mut(&client_id) {
+                entry.rtt = Some(rtt);
+            }
+        });
+    }
+
+    /// 通知连接任务断开；连接不存在时返回 `false`
+    pub fn kick(&self, client_id: u64) -> bool {
+        self.with_entries(|entries| entries.get(&client_id).map(|entry| entry.kick.clone()))
+            .map(|kick| kick.notify_one())
+            .is_some()
+    }
+
+    /// 按 client id 排序的连接列表
+    pub fn list(&self) -> Vec<ConnectionSummary> {
+        let mut connections: Vec<ConnectionSummary> = self.with_entries(|entries| {
+            entries
+                .iter()
+                .map(|(client_id, entry)| ConnectionSummary {
+                    client_id: *client_id,
+                    address: entry.address.to_string(),
+                    username: entry.username.clone(),
+                    connected_secs: entry.connected_at.elapsed().as_secs_f64(),
+                    rtt_ms: entry.rtt.map(|rtt| rtt.as_micros() as f64 / 1000.0),
+                })
+                .collect()
+        });
+        connections.sort_by_key(|connection| connection.client_id);
+        connections
+    }
+}
+
+/// 世界中的一个联网实体
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorldEntity {
+    pub network_id: u64,
+    pub position: [f32; 2],
+    pub velocity: [f32; 2],
+    pub bot: bool,
+    pub username: Option<String>,
+}
+
+/// 世界状态转储
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WorldDump {
+    pub tick: u64,
+    pub entities: Vec<WorldEntity>,
+}
+
+/// 运维接口发给 ECS 的查询
+pub enum AdminQuery {
+    World(oneshot::Sender<WorldDump>),
+}
+
+/// 运维查询的接收端（服务端插入后 [`answer_admin_queries`] 才会工作）
+#[derive(Resource, Clone)]
+pub struct AdminQueries(pub Arc<Mutex<mpsc::UnboundedReceiver<AdminQuery>>>);
+
+impl AdminQueries {
+    pub fn channel() -> (mpsc::UnboundedSender<AdminQuery>, Self) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (tx, Self(Arc::new(Mutex::new(rx))))
+    }
+}
+
+/// 应答运维接口的世界状态查询
+pub fn answer_admin_queries(
+    queries: Option<Res<AdminQueries>>,
+    tick: Res<ServerTick>,
+    accounts: Res<ConnectionAccounts>,
+    entities: Query<(
+        &NetworkId,
+        &Transform,
+        Option<&Velocity>,
+        Has<BotController>,
+    )>,
+) {
+    let Some(queries) = queries else {
+        return;
+    };
+    let Ok(mut receiver) = queries.0.lock() else {
+        return;
+    };
+
+    while let Ok(query) = receiver.try_recv() {
+        match query {
+            AdminQuery::World(reply) => {
+                let mut dump = WorldDump {
+                    tick: tick.0,
+                    entities: entities
+                        .iter()
+                        .map(|(network_id, transform, velocity, bot)| WorldEntity {
+                            network_id: network_id.0,
+                            position: [transform.translation.x, transform.translation.y],
+                            velocity: velocity.map_or([0.0, 0.0], |v| [v.x, v.y]),
+                            bot,
+                            username: accounts
+                                .0
+                                .get(&network_id.0)
+                                .map(|account| account.username.clone()),
+                        })
+                        .collect(),
+                };
+                dump.entities.sort_by_key(|entity| entity.network_id);
+                let _ = reply.send(dump);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_tracks_accounts_rtt_and_kicks() {
+        let registry = ConnectionRegistry::default();
+        let address: SocketAddr = "127.0.0.1:50000".parse().unwrap();
+        let kick = registry.register(7, address);
+        registry.register(3, address);
+        registry.set_username(7, Some("rin".to_string()));
+        registry.record_rtt(7, Duration::from_millis(40));
+
+        let connections = registry.list();
+        assert_eq!(
+            connections
+                .iter()
+                .map(|connection| connection.client_id)
+                .collect::<Vec<_>>(),
+            vec![3, 7]
+        );
+        assert_eq!(connections[1].username.as_deref(), Some("rin"));
+        assert_eq!(connections[1].rtt_ms, Some(40.0));
+
+        assert!(registry.kick(7));
+        assert!(!registry.kick(99));
+        let notified = kick.notified();
+        futures_lite::future::block_on(notified);
+
+        registry.unregister(7);
+        assert_eq!(registry.list().len(), 1);
+    }
+
+    #[test]
+    fn world_queries_are_answered_from_the_ecs() {
+        let (tx, queries) = AdminQueries::channel();
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(queries)
+            .insert_resource(ServerTick(42))
+            .init_resource::<ConnectionAccounts>()
+            .add_systems(Update, answer_admin_queries);
+        app.world_mut().spawn((
+            NetworkId(9999),
+            Transform::from_xyz(100.0, -200.0, 0.0),
+            Velocity::zero(),
+            BotController::default(),
+        ));
+        app.world_mut()
+            .spawn((NetworkId(1), Transform::from_xyz(5.0, 6.0, 0.0)));
+
+        let (world_tx, mut world_rx) = oneshot::channel();
+        tx.send(AdminQuery::World(world_tx)).unwrap();
+        app.update();
+
+        let dump = world_rx.try_recv().expect("world query should be answered");
+        assert_eq!(dump.tick, 42);
+        assert_eq!(dump.entities.len(), 2);
+        assert_eq!(dump.entities[0].network_id, 1);
+        assert_eq!(dump.entities[0].velocity, [0.0, 0.0]);
+        assert!(dump.entities[1].bot);
+    }
+}
@@ -0,0 +1,493 @@
+//! 服务器运维 HTTP/WebSocket 接口
+//!
+//! 监听独立端口（默认 [`DEFAULT_ADMIN_ADDRESS`]，由 `ADMIN_ADDRESS` 覆盖）。
+//! 设置 `ADMIN_TOKEN` 后每个请求都需要 `Authorization: Bearer <token>`。
+//!
+//! | 请求 | 说明 |
+//! |------|------|
+//! | `GET /connections` | 连接列表（JSON，含地址、账号与 RTT） |
+//! | `POST /connections/{id}/kick` | 断开指定连接 |
+//! | `POST /broadcast` | 请求体作为 `GamePacket::Message` 广播给所有客户端 |
+//! | `GET /world` | 当前世界状态（JSON） |
+//! | `GET /metrics` | OpenMetrics 文本格式指标 |
+//! | `GET /ws` | WebSocket：每条文本消息是一条命令（`connections`、`kick <id>`、`broadcast <text>`、`world`、`metrics`） |
+//!
+//! [`DEFAULT_ADMIN_ADDRESS`]: crate::systems::admin::DEFAULT_ADMIN_ADDRESS
+
+use futures_util::{SinkExt, StreamExt};
+use std::error::Error;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, oneshot};
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::tungstenite::handshake::derive_accept_key;
+use tokio_tungstenite::tungstenite::protocol::Role;
+
+use crate::protocol::GamePacket;
+use crate::systems::admin::{AdminQuery, ConnectionRegistry};
+use crate::systems::metrics::{MetricsRegistry, OPENMETRICS_CONTENT_TYPE};
+
+const MAX_HEADER_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+/// ECS 每帧应答查询，超过该时间视为主循环卡住
+const QUERY_TIMEOUT: Duration = Duration::from_secs(2);
+
+type AdminResult<T> = Result<T, Box<dyn Error + Send + Sync>>;
+
+/// 运维接口访问服务器状态所需的句柄
+#[derive(Clone)]
+pub struct AdminState {
+    pub connections: ConnectionRegistry,
+    pub queries: mpsc::UnboundedSender<AdminQuery>,
+    pub broadcast: mpsc::UnboundedSender<GamePacket>,
+    pub metrics: MetricsRegistry,
+    pub token: Option<String>,
+}
+
+/// 一条运维命令（HTTP 路由与 WebSocket 文本命令共用）
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AdminCommand {
+    Connections,
+    Kick(u64),
+    Broadcast(String),
+    World,
+    Metrics,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AdminReply {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl AdminReply {
+    fn json(status: u16, body: String) -> Self {
+        Self {
+            status,
+            content_type: "application/json",
+            body,
+        }
+    }
+
+    fn text(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            content_type: "text/plain; charset=utf-8",
+            body: body.into(),
+        }
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+type HttpHeaders = Vec<(String, String)>;
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    headers: HttpHeaders,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn is_websocket_upgrade(&self) -> bool {
+        self.header("upgrade")
+            .is_some_and(|value| value.eq_ignore_ascii_case("websocket"))
+    }
+}
+
+/// 解析请求行与头部；`head` 不含结尾的空行
+fn parse_request_head(head: &str) -> Option<(String, String, HttpHeaders)> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let path = target.split('?').next().unwrap_or(target).to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect();
+    Some((method, path, headers))
+}
+
+/// HTTP 路由到运维命令；无法路由时直接给出错误回复
+fn route(method: &str, path: &str, body: &[u8]) -> Result<AdminCommand, AdminReply> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let command = match segments.as_slice() {
+        ["connections"] => (method == "GET").then_some(AdminCommand::Connections),
+        ["connections", id, "kick"] => {
+            let id = id
+                .parse()
+                .map_err(|_| AdminReply::text(400, "invalid client id"))?;
+            (method == "POST").then_some(AdminCommand::Kick(id))
+        }
+        ["broadcast"] => {
+            let text = String::from_utf8(body.to_vec())
+                .map_err(|_| AdminReply::text(400, "broadcast body must be UTF-8"))?;
+            (method == "POST").then_some(AdminCommand::Broadcast(text))
+        }
+        ["world"] => (method == "GET").then_some(AdminCommand::World),
+        ["metrics"] => (method == "GET").then_some(AdminCommand::Metrics),
+        _ => return Err(AdminReply::text(404, "not found")),
+    };
+    command.ok_or_else(|| AdminReply::text(405, "method not allowed"))
+}
+
+/// WebSocket 文本命令
+fn parse_ws_command(line: &str) -> Result<AdminCommand, String> {
+    let line = line.trim();
+    let (name, argument) = line.split_once(' ').unwrap_or((line, ""));
+    match name {
+        "connections" => Ok(AdminCommand::Connections),
+        "kick" => argument
+            .trim()
+            .parse()
+            .map(AdminCommand::Kick)
+            .map_err(|_| "usage: kick <client id>".to_string()),
+        "broadcast" => Ok(AdminCommand::Broadcast(argument.to_string())),
+        "world" => Ok(AdminCommand::World),
+        "metrics" => Ok(AdminCommand::Metrics),
+        _ => Err(format!("unknown command: {name}")),
+    }
+}
+
+async fn query_ecs<T>(
+    state: &AdminState,
+    make: impl FnOnce(oneshot::Sender<T>) -> AdminQuery,
+) -> Option<T> {
+    let (reply_tx, reply_rx) = oneshot::channel();
+    state.queries.send(make(reply_tx)).ok()?;
+    tokio::time::timeout(QUERY_TIMEOUT, reply_rx)
+        .await
+        .ok()?
+        .ok()
+}
+
+/// 连接数与每个连接的 RTT 在抓取时写入注册表
+fn export_connection_metrics(metrics: &MetricsRegistry, connections: &ConnectionRegistry) {
+    let connections = connections.list();
+    metrics.set_gauge(
+        "gengine_connected_clients",
+        "Open WebSocket connections",
+        &[],
+        connections.len() as f64,
+    );
+    let client_ids: Vec<String> = connections
+        .iter()
+        .map(|connection| connection.client_id.to_string())
+        .collect();
+    let labels: Vec<[(&str, &str); 1]> = client_ids
+        .iter()
+        .map(|client_id| [("client_id", client_id.as_str())])
+        .collect();
+    let samples: Vec<(&[(&str, &str)], f64)> = connections
+        .iter()
+        .zip(&labels)
+        .filter_map(|(connection, labels)| {
+            connection
+                .rtt_ms
+                .map(|rtt_ms| (labels.as_slice(), rtt_ms / 1000.0))
+        })
+        .collect();
+    metrics.replace_gauges(
+        "gengine_client_rtt_seconds",
+        "Last measured WebSocket ping round trip per client",
+        &samples,
+    );
+}
+
+async fn execute(state: &AdminState, command: AdminCommand) -> AdminReply {
+    let unavailable = || AdminReply::text(503, "server runtime did not answer");
+    match command {
+        AdminCommand::Connections => match serde_json::to_string(&state.connections.list()) {
+            Ok(body) => AdminReply::json(200, body),
+            Err(error) => AdminReply::text(500, error.to_string()),
+        },
+        AdminCommand::Kick(client_id) => {
+            if state.connections.kick(client_id) {
+                AdminReply::json(200, format!("{{\"kicked\":{client_id}}}"))
+            } else {
+                AdminReply::text(404, format!("client {client_id} is not connected"))
+            }
+        }
+        AdminCommand::Broadcast(text) => {
+            let text = text.trim();
+            if text.is_empty() {
+                return AdminReply::text(400, "broadcast text is empty");
+            }
+            match state.broadcast.send(GamePacket::Message(text.to_string())) {
+                Ok(()) => AdminReply::json(200, "{\"broadcast\":true}".to_string()),
+                Err(_) => unavailable(),
+            }
+        }
+        AdminCommand::World => match query_ecs(state, AdminQuery::World).await {
+            Some(dump) => match serde_json::to_string(&dump) {
+                Ok(body) => AdminReply::json(200, body),
+                Err(error) => AdminReply::text(500, error.to_string()),
+            },
+            None => unavailable(),
+        },
+        AdminCommand::Metrics => {
+            export_connection_metrics(&state.metrics, &state.connections);
+            AdminReply {
+                status: 200,
+                content_type: OPENMETRICS_CONTENT_TYPE,
+                body: state.metrics.render(),
+            }
+        }
+    }
+}
+
+/// 运行运维接口，直到监听出错
+pub async fn run_admin_server(listener: TcpListener, state: AdminState) {
+    loop {
+        let (stream, address) = match listener.accept().await {
+            Ok(connection) => connection,
+            Err(error) => {
+                bevy::log::error!("Admin accept failed: {error}");
+                continue;
+            }
+        };
+        let state = state.clone();
+        tokio::spawn(async move {
+            if let Err(error) = handle_admin_connection(stream, &state).await {
+                bevy::log::warn!("Admin request from {address} failed: {error}");
+            }
+        });
+    }
+}
+
+async fn read_request(stream: &mut TcpStream) -> AdminResult<Option<HttpRequest>> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0_u8; 4096];
+    let header_end = loop {
+        if let Some(position) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            break position;
+        }
+        if buffer.len() > MAX_HEADER_BYTES {
+            return Err("request headers too large".into());
+        }
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(None);
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    };
+
+    let head = std::str::from_utf8(&buffer[..header_end])?;
+    let Some((method, path, headers)) = parse_request_head(head) else {
+        return Err("malformed request line".into());
+    };
+    let mut request = HttpRequest {
+        method,
+        path,
+        headers,
+        body: buffer[header_end + 4..].to_vec(),
+    };
+
+    let content_length: usize = request
+        .header("content-length")
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return Err("request body too large".into());
+    }
+    while request.body.len() < content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        request.body.extend_from_slice(&chunk[..read]);
+    }
+    request.body.truncate(content_length);
+    Ok(Some(request))
+}
+
+async fn write_reply(stream: &mut TcpStream, reply: &AdminReply) -> AdminResult<()> {
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        reply.status,
+        reason_phrase(reply.status),
+        reply.content_type,
+        reply.body.len(),
+        reply.body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+async fn handle_admin_connection(mut stream: TcpStream, state: &AdminState) -> AdminResult<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+
+    if let Some(token) = &state.token {
+        let authorized = request
+            .header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|candidate| candidate == token);
+        if !authorized {
+            return write_reply(&mut stream, &AdminReply::text(401, "missing admin token")).await;
+        }
+    }
+
+    if request.path == "/ws" && request.is_websocket_upgrade() {
+        let Some(key) = request.header("sec-websocket-key") else {
+            return write_reply(&mut stream, &AdminReply::text(400, "missing websocket key")).await;
+        };
+        let accept = derive_accept_key(key.as_bytes());
+        let response = format!(
+            "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {accept}\r\n\r\n"
+        );
+        stream.write_all(response.as_bytes()).await?;
+        let socket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+        return serve_admin_socket(socket, state).await;
+    }
+
+    let reply = match route(&request.method, &request.path, &request.body) {
+        Ok(command) => execute(state, command).await,
+        Err(reply) => reply,
+    };
+    write_reply(&mut stream, &reply).await
+}
+
+async fn serve_admin_socket(
+    mut socket: WebSocketStream<TcpStream>,
+    state: &AdminState,
+) -> AdminResult<()> {
+    while let Some(message) = socket.next().await {
+        match message? {
+            WsMessage::Text(line) => {
+                let body = match parse_ws_command(line.as_str()) {
+                    Ok(command) => execute(state, command).await.body,
+                    Err(error) => error,
+                };
+                socket.send(WsMessage::Text(body.into())).await?;
+            }
+            WsMessage::Close(_) => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_routes_map_to_commands() {
+        assert_eq!(
+            route("GET", "/connections", b""),
+            Ok(AdminCommand::Connections)
+        );
+        assert_eq!(
+            route("POST", "/connections/12/kick", b""),
+            Ok(AdminCommand::Kick(12))
+        );
+        assert_eq!(
+            route(
+                "POST",
+                "/broadcast",
+                "server restarts in 5 minutes".as_bytes()
+            ),
+            Ok(AdminCommand::Broadcast(
+                "server restarts in 5 minutes".to_string()
+            ))
+        );
+        assert_eq!(route("GET", "/metrics", b""), Ok(AdminCommand::Metrics));
+        assert_eq!(route("GET", "/broadcast", b"").unwrap_err().status, 405);
+        assert_eq!(
+            route("POST", "/connections/abc/kick", b"")
+                .unwrap_err()
+                .status,
+            400
+        );
+        assert_eq!(route("GET", "/nope", b"").unwrap_err().status, 404);
+    }
+
+    #[test]
+    fn request_head_and_ws_commands_parse() {
+        let (method, path, headers) = parse_request_head(
+            "GET /world?pretty=1 HTTP/1.1\r\nHost: localhost\r\nAuthorization: Bearer secret",
+        )
+        .expect("request head should parse");
+        assert_eq!((method.as_str(), path.as_str()), ("GET", "/world"));
+        assert_eq!(headers[1], ("Authorization".into(), "Bearer secret".into()));
+
+        assert_eq!(parse_ws_command("kick 4"), Ok(AdminCommand::Kick(4)));
+        assert_eq!(
+            parse_ws_command("broadcast hello all"),
+            Ok(AdminCommand::Broadcast("hello all".to_string()))
+        );
+        assert!(parse_ws_command("kick").is_err());
+        assert!(parse_ws_command("reboot").is_err());
+    }
+
+    #[test]
+    fn broadcast_and_kick_act_on_server_handles() {
+        let (broadcast_tx, mut broadcast_rx) = mpsc::unbounded_channel();
+        let (queries, _queries_rx) = mpsc::unbounded_channel();
+        let state = AdminState {
+            connections: ConnectionRegistry::default(),
+            queries,
+            broadcast: broadcast_tx,
+            metrics: MetricsRegistry::default(),
+            token: None,
+        };
+        let kick = state
+            .connections
+            .register(3, "127.0.0.1:40000".parse().unwrap());
+
+        futures_lite::future::block_on(async {
+            let reply = execute(&state, AdminCommand::Broadcast("  hi  ".to_string())).await;
+            assert_eq!(reply.status, 200);
+            assert!(matches!(
+                broadcast_rx.try_recv(),
+                Ok(GamePacket::Message(text)) if text == "hi"
+            ));
+            assert_eq!(
+                execute(&state, AdminCommand::Broadcast(" ".to_string()))
+                    .await
+                    .status,
+                400
+            );
+
+            assert_eq!(execute(&state, AdminCommand::Kick(3)).await.status, 200);
+            kick.notified().await;
+            assert_eq!(execute(&state, AdminCommand::Kick(8)).await.status, 404);
+
+            state.connections.record_rtt(3, Duration::from_millis(25));
+            let metrics = execute(&state, AdminCommand::Metrics).await;
+            assert_eq!(metrics.content_type, OPENMETRICS_CONTENT_TYPE);
+            assert!(metrics.body.contains("gengine_connected_clients 1\n"));
+            assert!(
+                metrics
+                    .body
+                    .contains("gengine_client_rtt_seconds{client_id=\"3\"} 0.025\n")
+            );
+        });
+    }
+}
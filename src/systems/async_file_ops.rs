@@ -3,8 +3,12 @@ use std::path::PathBuf;
 
 use crate::{
     asset_paths,
-    resources::{CompleteGameState, SaveFileMetadata},
-    systems::{error_handling::SaveSystemError, server_file_ops},
+    resources::{CompleteGameState, SaveFileMetadata, SaveThumbnail},
+    systems::{
+        error_handling::{SaveBackupPolicy, SaveSystemError},
+        save_signing::SaveSigningKey,
+        save_thumbnail, server_file_ops,
+    },
 };
 
 /// 异步文件操作管理器
@@ -14,6 +18,10 @@ pub struct AsyncFileManager {
     pub compression_level: u32,
     pub max_concurrent_operations: usize,
     pub operation_timeout_seconds: u64,
+    /// 关闭后存档不再签名（仍写校验和）
+    pub disable_signing: bool,
+    /// 启动时由 `save_signing::load_install_signing_key` 填入
+    pub signing_key: Option<SaveSigningKey>,
 }
 
 impl AsyncFileManager {
@@ -23,6 +31,8 @@ impl AsyncFileManager {
             compression_level: 3, // Zstd level 3: good speed/ratio balance
             max_concurrent_operations: 4,
             operation_timeout_seconds: 30,
+            disable_signing: false,
+            signing_key: None,
         }
     }
 }
@@ -64,23 +74,49 @@ pub async fn save_game_state_async(
     metadata: SaveFileMetadata,
     compression_enabled: bool,
     compression_level: u32,
-) -> Result<(), SaveSystemError> {
+    backup_policy: Option<SaveBackupPolicy>,
+    signing_key: Option<SaveSigningKey>,
+) -> Result<String, SaveSystemError> {
     server_file_ops::save_game_state_internal(
         save_path,
         game_state,
         metadata,
         compression_enabled,
         compression_level,
+        backup_policy,
+        signing_key,
     )
     .await
 }
 
+/// 异步写入存档缩略图（失败只记录警告，不影响已写入的存档）
+pub async fn save_thumbnail_async(save_path: PathBuf, thumbnail: Option<SaveThumbnail>) {
+    if let Err(error) = save_thumbnail::write_save_thumbnail(&save_path, thumbnail.as_ref()) {
+        warn!(
+            "Failed to write thumbnail for {}: {}",
+            save_path.display(),
+            error
+        );
+    }
+}
+
 /// 异步加载游戏状态
 pub async fn load_game_state_async(
     save_path: PathBuf,
     compression_enabled: bool,
+    signing_key: Option<SaveSigningKey>,
+) -> Result<(CompleteGameState, SaveFileMetadata), SaveSystemError> {
+    server_file_ops::load_game_state_internal(save_path, compression_enabled, signing_key).await
+}
+
+/// 异步从滚动备份恢复存档
+pub async fn restore_game_state_from_backup_async(
+    save_path: PathBuf,
+    backup_policy: SaveBackupPolicy,
+    signing_key: Option<SaveSigningKey>,
 ) -> Result<(CompleteGameState, SaveFileMetadata), SaveSystemError> {
-    server_file_ops::load_game_state_internal(save_path, compression_enabled).await
+    server_file_ops::restore_game_state_from_backup_internal(save_path, backup_policy, signing_key)
+        .await
 }
 
 /// 异步扫描存档文件
@@ -1,12 +1,14 @@
 use crate::{
-    events::{StartLoadGame, StartSaveGame},
+    events::{SaveWritten, StartLoadGame, StartSaveGame},
     resources::{CompleteGameState, PauseManager, SaveFileManager, SaveFileMetadata},
     states::GameState,
     systems::{
         async_file_ops::{
-            AsyncFileManager, OperationProgress, load_game_state_async, save_game_state_async,
+            AsyncFileManager, OperationProgress, load_game_state_async,
+            restore_game_state_from_backup_async, save_game_state_async, save_thumbnail_async,
         },
-        error_handling::SaveSystemError,
+        error_handling::{ErrorRecoveryManager, RecoveryAction, SaveSystemError},
+        server_file_ops::save_slot_name,
         ui::{LoadedGameState, SaveLoadUiState},
     },
 };
@@ -17,11 +19,23 @@ use futures_lite::future;
 use std::collections::HashSet;
 use std::path::{Path, PathBuf};
 
+/// 手动存档任务，完成时返回存档名与写入的 JSON
 #[derive(Component)]
-pub struct SaveTask(Task<Result<(), SaveSystemError>>);
+pub struct SaveTask(Task<Result<(String, String), SaveSystemError>>);
 
 #[derive(Component)]
-pub struct LoadTask(Task<Result<(CompleteGameState, SaveFileMetadata), SaveSystemError>>);
+pub struct LoadTask {
+    task: Task<Result<(CompleteGameState, SaveFileMetadata), SaveSystemError>>,
+    file_path: String,
+    restoring_backup: bool,
+}
+
+#[derive(SystemParam)]
+pub struct SaveRequestConfig<'w> {
+    file_manager: Res<'w, AsyncFileManager>,
+    save_file_manager: Res<'w, SaveFileManager>,
+    error_recovery: Res<'w, ErrorRecoveryManager>,
+}
 
 #[derive(SystemParam)]
 pub struct PollAsyncTaskState<'w> {
@@ -30,6 +44,14 @@ pub struct PollAsyncTaskState<'w> {
     pause_manager: ResMut<'w, PauseManager>,
     operation_progress: ResMut<'w, OperationProgress>,
     save_load_ui_state: ResMut<'w, SaveLoadUiState>,
+    error_recovery: ResMut<'w, ErrorRecoveryManager>,
+    saves_written: MessageWriter<'w, SaveWritten>,
+}
+
+fn slot_name_for(file_path: &str) -> String {
+    save_slot_name(Path::new(file_path))
+        .map(str::to_string)
+        .unwrap_or_else(|_| file_path.to_string())
 }
 
 fn resolve_save_target(
@@ -82,8 +104,7 @@ fn is_valid_save_path(path: &Path) -> bool {
 pub fn handle_save_requests(
     mut commands: Commands,
     mut ev_save: MessageReader<StartSaveGame>,
-    file_manager: Res<AsyncFileManager>,
-    save_file_manager: Res<SaveFileManager>,
+    config: SaveRequestConfig,
     active_save_tasks: Query<(), With<SaveTask>>,
     mut operation_progress: ResMut<OperationProgress>,
     mut save_load_ui_state: ResMut<SaveLoadUiState>,
@@ -101,12 +122,15 @@ pub fn handle_save_requests(
 
         let state = ev.state.clone();
         let save_name = ev.save_name.clone();
-        let compression_enabled = file_manager.compression_enabled;
-        let compression_level = file_manager.compression_level;
+        let thumbnail = ev.thumbnail.clone();
+        let compression_enabled = config.file_manager.compression_enabled;
+        let compression_level = config.file_manager.compression_level;
+        let backup_policy = config.error_recovery.backup_policy();
+        let signing_key = config.file_manager.signing_key.clone();
 
-        let save_dir = PathBuf::from(&save_file_manager.save_directory);
+        let save_dir = PathBuf::from(&config.save_file_manager.save_directory);
         let (resolved_name, file_path, is_overwrite) =
-            resolve_save_target(&save_dir, &save_name, &save_file_manager.save_files);
+            resolve_save_target(&save_dir, &save_name, &config.save_file_manager.save_files);
 
         let metadata = SaveFileMetadata {
             name: resolved_name.clone(),
@@ -115,18 +139,23 @@ pub fn handle_save_requests(
             play_time: state.play_time,
             save_timestamp: state.save_timestamp,
             file_path: file_path.to_string_lossy().to_string(),
-            selected_character: state.selected_character.clone(),
+            selected_character: state.selected_character,
         };
 
+        let written_name = resolved_name.clone();
         let task = ComputeTaskPool::get().spawn(async move {
-            save_game_state_async(
-                file_path,
+            let save_json = save_game_state_async(
+                file_path.clone(),
                 state,
                 metadata,
                 compression_enabled,
                 compression_level,
+                Some(backup_policy),
+                signing_key,
             )
-            .await
+            .await?;
+            save_thumbnail_async(file_path, thumbnail).await;
+            Ok((written_name, save_json))
         });
 
         commands.spawn(SaveTask(task));
@@ -155,6 +184,7 @@ pub fn handle_load_requests(
     mut commands: Commands,
     mut ev_load: MessageReader<StartLoadGame>,
     file_manager: Res<AsyncFileManager>,
+    error_recovery: Res<ErrorRecoveryManager>,
     active_load_tasks: Query<(), With<LoadTask>>,
     mut operation_progress: ResMut<OperationProgress>,
     mut save_load_ui_state: ResMut<SaveLoadUiState>,
@@ -172,17 +202,35 @@ pub fn handle_load_requests(
 
         let file_path = PathBuf::from(&ev.file_path);
         let compression_enabled = file_manager.compression_enabled;
+        let backup_policy = error_recovery.backup_policy();
+        let signing_key = file_manager.signing_key.clone();
+        let restoring_backup = ev.restore_from_backup;
 
-        let task = ComputeTaskPool::get()
-            .spawn(async move { load_game_state_async(file_path, compression_enabled).await });
+        let task = ComputeTaskPool::get().spawn(async move {
+            if restoring_backup {
+                restore_game_state_from_backup_async(file_path, backup_policy, signing_key).await
+            } else {
+                load_game_state_async(file_path, compression_enabled, signing_key).await
+            }
+        });
 
-        commands.spawn(LoadTask(task));
+        commands.spawn(LoadTask {
+            task,
+            file_path: ev.file_path.clone(),
+            restoring_backup,
+        });
         load_task_spawned = true;
 
         save_load_ui_state.is_busy = true;
+        save_load_ui_state.pending_backup_restore = None;
         save_load_ui_state.error_message.clear();
-        save_load_ui_state.status_message = "Loading save data...".to_string();
-        operation_progress.start_operation("Loading save".to_string());
+        if restoring_backup {
+            save_load_ui_state.status_message = "Restoring last good backup...".to_string();
+            operation_progress.start_operation("Restoring backup".to_string());
+        } else {
+            save_load_ui_state.status_message = "Loading save data...".to_string();
+            operation_progress.start_operation("Loading save".to_string());
+        }
 
         crate::debug_log!("Spawned async load task for '{}'", ev.file_path);
     }
@@ -199,7 +247,11 @@ pub fn poll_async_tasks(
     for (entity, mut task) in &mut save_tasks {
         if let Some(result) = future::block_on(future::poll_once(&mut task.0)) {
             match result {
-                Ok(_) => {
+                Ok((save_name, save_json)) => {
+                    state.saves_written.write(SaveWritten {
+                        save_name,
+                        save_json,
+                    });
                     state.save_load_ui_state.is_busy = false;
                     state.save_load_ui_state.error_message.clear();
                     state.save_load_ui_state.status_message =
@@ -221,7 +273,8 @@ pub fn poll_async_tasks(
 
     // Poll load tasks
     for (entity, mut task) in &mut load_tasks {
-        if let Some(result) = future::block_on(future::poll_once(&mut task.0)) {
+        if let Some(result) = future::block_on(future::poll_once(&mut task.task)) {
+            let slot_name = slot_name_for(&task.file_path);
             match result {
                 Ok((game_state, metadata)) => {
                     crate::debug_log!(
@@ -239,19 +292,40 @@ pub fn poll_async_tasks(
                     state.save_load_ui_state.is_busy = false;
                     state.save_load_ui_state.error_message.clear();
                     state.save_load_ui_state.pending_load_index = None;
-                    state.save_load_ui_state.status_message =
-                        "Load completed, restoring scene...".to_string();
+                    state.save_load_ui_state.status_message = if task.restoring_backup {
+                        "Restored from backup, restoring scene...".to_string()
+                    } else {
+                        "Load completed, restoring scene...".to_string()
+                    };
                     state.operation_progress.complete_operation();
+                    state.error_recovery.clear_retry_count(&slot_name);
 
                     NextState::set_if_neq(&mut state.next_state, GameState::Playing);
                 }
                 Err(e) => {
+                    crate::debug_log!("Async load task failed: {:?}", e);
                     state.save_load_ui_state.is_busy = false;
                     state.save_load_ui_state.status_message.clear();
-                    state.save_load_ui_state.error_message = e.to_user_message().to_string();
                     state.operation_progress.complete_operation();
-                    crate::debug_log!("Async load task failed: {:?}", e);
-                    // Here we could fire another event to show an error UI
+
+                    // 存档损坏且有可用备份时，下一次点击该存档会改为从备份恢复
+                    let action = state
+                        .error_recovery
+                        .handle_load_error(e.clone(), &slot_name);
+                    if matches!(action, RecoveryAction::UseBackup) && !task.restoring_backup {
+                        state.save_load_ui_state.error_message = format!(
+                            "'{}' is damaged. Click it again to restore the last good backup.",
+                            slot_name
+                        );
+                        state.save_load_ui_state.pending_backup_restore =
+                            Some(task.file_path.clone());
+                    } else {
+                        state.save_load_ui_state.error_message = match action {
+                            RecoveryAction::ShowError(message) => message,
+                            _ => e.to_user_message().to_string(),
+                        };
+                        state.save_load_ui_state.pending_backup_restore = None;
+                    }
                 }
             }
             commands.entity(entity).despawn();
@@ -0,0 +1,515 @@
+//! Boss 战
+//!
+//! - Boss 由 LDtk 的 `SkyBoss` 实体生成，复用普通敌人的受伤、击杀与竞技场清场流程
+//! - 血量比例跌破阶段阈值时切换阶段：打断当前招式、短暂硬直，之后使用新阶段的移动/伤害倍率与招式表
+//! - 招式按阶段招式表轮换：连续冲刺、扇形弹幕、场地光柱
+//!
+//! 数值来自 `config/gameplay_tuning.ron` 的 `boss` 段。
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::{
+    components::*,
+    events::{CameraImpulseEvent, DamageEvent, DamageSource},
+    resources::{BossPattern, BossPhaseTuning, GameplayTuning},
+    systems::{
+        collision::CollisionBox,
+        hitboxes::{collision_rect, rects_overlap},
+    },
+};
+
+const BOSS_RENDER_SIZE: Vec2 = Vec2::new(84.0, 150.0);
+pub const BOSS_COLLISION_SIZE: Vec2 = Vec2::new(60.0, 112.0);
+const BOSS_BASE_COLOR: Color = Color::srgba(0.30, 0.26, 0.46, 0.98);
+const BOSS_PROJECTILE_LIFETIME_SECS: f32 = 3.0;
+const BOSS_HAZARD_HEIGHT: f32 = 220.0;
+
+type BossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Boss,
+        &'static mut EnemyState,
+        &'static mut Transform,
+        &'static mut Velocity,
+        Option<&'static SkyEncounterEnemy>,
+    ),
+    With<Enemy>,
+>;
+
+/// 生成 Boss；`health_multiplier` 与 `patrol_range` 来自关卡摆放。
+pub fn spawn_boss(
+    commands: &mut Commands,
+    position: Vec3,
+    spawn: &SkyBoss,
+    tuning: &GameplayTuning,
+) -> Entity {
+    let boss_tuning = &tuning.boss;
+    let health = ((boss_tuning.health.max(1) as f32 * spawn.health_multiplier.max(0.25)).round()
+        as i32)
+        .max(1);
+    let enemy_state = EnemyState::new(health, spawn.patrol_range)
+        .with_spawn_origin(position.x)
+        .with_movement(
+            boss_tuning.base_speed.max(10.0),
+            boss_tuning.contact_damage.max(1.0),
+            position.x * 0.013,
+        );
+
+    commands
+        .spawn((
+            Sprite {
+                color: BOSS_BASE_COLOR,
+                custom_size: Some(BOSS_RENDER_SIZE),
+                ..default()
+            },
+            Transform::from_translation(position),
+            Enemy,
+            EnemyType::EnemyHeroicSpirit,
+            enemy_state,
+            Boss::new(spawn.arena),
+            Velocity { x: 0.0, y: 0.0 },
+            CollisionBox::new(BOSS_COLLISION_SIZE),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Sprite {
+                    color: Color::srgba(1.0, 0.84, 0.46, 0.98),
+                    custom_size: Some(Vec2::new(30.0, 16.0)),
+                    ..default()
+                },
+                Transform::from_xyz(0.0, 50.0, 0.25),
+            ));
+            parent.spawn((
+                Sprite {
+                    color: Color::srgba(0.92, 0.22, 0.30, 0.92),
+                    custom_size: Some(Vec2::new(12.0, 96.0)),
+                    ..default()
+                },
+                Transform::from_xyz(40.0, -4.0, 0.2).with_rotation(Quat::from_rotation_z(0.32)),
+            ));
+            parent.spawn((
+                Sprite {
+                    color: Color::srgba(0.92, 0.22, 0.30, 0.92),
+                    custom_size: Some(Vec2::new(12.0, 96.0)),
+                    ..default()
+                },
+                Transform::from_xyz(-40.0, -4.0, 0.2).with_rotation(Quat::from_rotation_z(-0.32)),
+            ));
+        })
+        .id()
+}
+
+/// 本帧出招所需的空间信息
+struct PatternContext {
+    origin: Vec3,
+    target: Vec3,
+    bounds: (f32, f32),
+    ground_y: f32,
+    speed: f32,
+    delta: f32,
+    cooldown: f32,
+}
+
+impl PatternContext {
+    fn facing_to_target(&self) -> f32 {
+        if self.target.x >= self.origin.x {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+fn start_pattern(
+    commands: &mut Commands,
+    boss: &mut Boss,
+    pattern: BossPattern,
+    context: &PatternContext,
+) {
+    match pattern {
+        BossPattern::DashChain {
+            dashes,
+            charge_secs,
+            ..
+        } => {
+            boss.action = BossAction::DashCharge;
+            boss.action_timer = charge_secs.max(0.05);
+            boss.repeats_left = dashes.max(1);
+            boss.dash_direction = context.facing_to_target();
+        }
+        BossPattern::ProjectileBarrage {
+            volleys,
+            interval_secs,
+            ..
+        } => {
+            boss.action = BossAction::Barrage;
+            boss.action_timer = interval_secs.max(0.1);
+            boss.repeats_left = volleys.max(1);
+        }
+        BossPattern::ArenaHazard {
+            pillars,
+            spacing,
+            width,
+            telegraph_secs,
+            active_secs,
+            damage,
+        } => {
+            spawn_hazard_row(
+                commands,
+                context,
+                pillars.max(1),
+                spacing,
+                BossHazard {
+                    telegraph_timer: telegraph_secs.max(0.1),
+                    active_timer: active_secs.max(0.05),
+                    damage,
+                    width: width.max(8.0),
+                    has_hit: false,
+                },
+            );
+            boss.action = BossAction::Casting;
+            boss.action_timer = telegraph_secs.max(0.1);
+        }
+    }
+}
+
+fn advance_pattern(
+    commands: &mut Commands,
+    boss: &mut Boss,
+    velocity: &mut Velocity,
+    pattern: BossPattern,
+    context: &PatternContext,
+) {
+    boss.action_timer -= context.delta;
+
+    match (boss.action, pattern) {
+        (BossAction::DashCharge, BossPattern::DashChain { dash_secs, .. }) => {
+            if boss.action_timer <= 0.0 {
+                boss.action = BossAction::Dashing;
+                boss.action_timer = dash_secs.max(0.05);
+            }
+        }
+        (
+            BossAction::Dashing,
+            BossPattern::DashChain {
+                charge_secs,
+                speed_multiplier,
+                ..
+            },
+        ) => {
+            velocity.x = context.speed * speed_multiplier.max(1.0) * boss.dash_direction;
+            if boss.action_timer <= 0.0 {
+                boss.repeats_left = boss.repeats_left.saturating_sub(1);
+                if boss.repeats_left > 0 {
+                    boss.action = BossAction::DashCharge;
+                    boss.action_timer = charge_secs.max(0.05);
+                    boss.dash_direction = context.facing_to_target();
+                } else {
+                    boss.finish_pattern(context.cooldown);
+                }
+            }
+        }
+        (
+            BossAction::Barrage,
+            BossPattern::ProjectileBarrage {
+                projectiles,
+                spread_degrees,
+                interval_secs,
+                projectile_speed,
+                projectile_damage,
+                ..
+            },
+        ) => {
+            if boss.action_timer <= 0.0 {
+                fire_barrage(
+                    commands,
+                    context,
+                    projectiles.max(1),
+                    spread_degrees,
+                    projectile_speed.max(20.0),
+                    projectile_damage,
+                );
+                boss.repeats_left = boss.repeats_left.saturating_sub(1);
+                if boss.repeats_left > 0 {
+                    boss.action_timer = interval_secs.max(0.1);
+                } else {
+                    boss.finish_pattern(context.cooldown);
+                }
+            }
+        }
+        (BossAction::Casting, BossPattern::ArenaHazard { .. }) => {
+            if boss.action_timer <= 0.0 {
+                boss.finish_pattern(context.cooldown);
+            }
+        }
+        // 热重载改动了招式表时放弃当前招式。
+        _ => boss.finish_pattern(context.cooldown),
+    }
+}
+
+/// 扇形弹幕各发的方向：以瞄准方向为中心均匀铺开
+pub fn barrage_directions(aim: Vec2, projectiles: u32, spread_degrees: f32) -> Vec<Vec2> {
+    let aim = aim.normalize_or_zero();
+    if aim == Vec2::ZERO {
+        return Vec::new();
+    }
+    let count = projectiles.max(1);
+    let spread = spread_degrees.to_radians();
+    (0..count)
+        .map(|index| {
+            let t = if count == 1 {
+                0.0
+            } else {
+                index as f32 / (count - 1) as f32 - 0.5
+            };
+            Vec2::from_angle(spread * t).rotate(aim)
+        })
+        .collect()
+}
+
+fn fire_barrage(
+    commands: &mut Commands,
+    context: &PatternContext,
+    projectiles: u32,
+    spread_degrees: f32,
+    speed: f32,
+    damage: f32,
+) {
+    let aim = (context.target - context.origin).truncate();
+    for direction in barrage_directions(aim, projectiles, spread_degrees) {
+        crate::systems::combat::spawn_enemy_projectile(
+            commands,
+            context.origin + (direction * 48.0).extend(0.0),
+            direction,
+            speed,
+            damage,
+            BOSS_PROJECTILE_LIFETIME_SECS,
+        );
+    }
+}
+
+fn spawn_hazard_row(
+    commands: &mut Commands,
+    context: &PatternContext,
+    pillars: u32,
+    spacing: f32,
+    hazard: BossHazard,
+) {
+    let (left, right) = context.bounds;
+    let center = context.target.x.clamp(left, right);
+    let half_span = (pillars - 1) as f32 * 0.5;
+    for index in 0..pillars {
+        let x = (center + (index as f32 - half_span) * spacing).clamp(left, right);
+        commands.spawn((
+            Sprite {
+                color: Color::srgba(1.0, 0.78, 0.36, 0.18),
+                custom_size: Some(Vec2::new(hazard.width, BOSS_HAZARD_HEIGHT)),
+                ..default()
+            },
+            Transform::from_xyz(x, context.ground_y + BOSS_HAZARD_HEIGHT * 0.5, 1.2),
+            hazard.clone(),
+        ));
+    }
+}
+
+/// Boss 行为驱动所需的系统参数。
+#[derive(SystemParam)]
+pub struct BossAiParams<'w, 's> {
+    players: Query<'w, 's, &'static Transform, (With<Player>, Without<Enemy>)>,
+    tuning: Option<Res<'w, GameplayTuning>>,
+    encounters: Option<Res<'w, SkyEncounterState>>,
+    time: Res<'w, Time>,
+    camera_impulse_writer: MessageWriter<'w, CameraImpulseEvent>,
+}
+
+/// Boss AI：阶段切换与招式执行。Boss 有霸体，击退与硬直不会打断招式。
+pub fn boss_ai(mut commands: Commands, mut bosses: BossQuery, mut params: BossAiParams) {
+    let default_tuning = GameplayTuning::default();
+    let boss_tuning = &params.tuning.as_deref().unwrap_or(&default_tuning).boss;
+    let Some(player) = params.players.iter().next() else {
+        return;
+    };
+    let target = player.translation;
+    let delta = params.time.delta_secs();
+    let elapsed = params.time.elapsed_secs();
+
+    for (mut boss, mut state, mut transform, mut velocity, authored) in bosses.iter_mut() {
+        velocity.x = 0.0;
+        velocity.y = 0.0;
+        if !state.is_alive {
+            continue;
+        }
+        state.tick_timers(delta);
+
+        let anchor_y = authored
+            .map(|enemy| enemy.anchor_y)
+            .unwrap_or(transform.translation.y);
+        let engaged = params
+            .encounters
+            .as_deref()
+            .is_none_or(|encounters| encounters.active_arena == Some(boss.arena));
+        if !engaged {
+            transform.translation.y = anchor_y + (elapsed * 2.0 + state.hover_phase).sin() * 3.0;
+            continue;
+        }
+        transform.translation.y = anchor_y;
+
+        let health_fraction = state.health as f32 / state.max_health.max(1) as f32;
+        let phase_index = boss_tuning
+            .phase_for_health(health_fraction)
+            .max(boss.phase);
+        if phase_index != boss.phase {
+            boss.enter_phase(phase_index, boss_tuning.phase_transition_secs);
+            params.camera_impulse_writer.write(CameraImpulseEvent {
+                intensity: 9.0,
+                duration: 0.35,
+            });
+        }
+        let Some(phase) = boss_tuning.phase(boss.phase) else {
+            continue;
+        };
+        state.contact_damage =
+            boss_tuning.contact_damage.max(1.0) * phase.damage_multiplier.max(0.0);
+
+        if boss.transition_timer > 0.0 {
+            boss.transition_timer = (boss.transition_timer - delta).max(0.0);
+            if boss.transition_timer > 0.0 {
+                continue;
+            }
+        }
+
+        let context = PatternContext {
+            origin: transform.translation,
+            target,
+            bounds: state.patrol_world_bounds(),
+            ground_y: anchor_y - BOSS_COLLISION_SIZE.y * 0.5,
+            speed: boss_tuning.base_speed.max(10.0) * phase.speed_multiplier.max(0.1),
+            delta,
+            cooldown: phase.pattern_cooldown_secs.max(0.1),
+        };
+
+        if boss.action == BossAction::Idle {
+            let to_target = target.x - transform.translation.x;
+            if to_target.abs() > 24.0 {
+                state.move_direction = to_target.signum();
+                velocity.x = context.speed * 0.6 * state.move_direction;
+            }
+            boss.pattern_cooldown = (boss.pattern_cooldown - delta).max(0.0);
+            if boss.pattern_cooldown <= 0.0
+                && let Some((index, pattern)) = next_pattern(&boss, phase)
+            {
+                boss.active_pattern = index;
+                boss.next_pattern = index + 1;
+                start_pattern(&mut commands, &mut boss, pattern, &context);
+            }
+        } else {
+            match phase.patterns.get(boss.active_pattern).copied() {
+                Some(pattern) => {
+                    advance_pattern(&mut commands, &mut boss, &mut velocity, pattern, &context)
+                }
+                None => boss.finish_pattern(context.cooldown),
+            }
+        }
+
+        let (left, right) = context.bounds;
+        transform.translation.x = (transform.translation.x + velocity.x * delta).clamp(left, right);
+    }
+}
+
+fn next_pattern(boss: &Boss, phase: &BossPhaseTuning) -> Option<(usize, BossPattern)> {
+    if phase.patterns.is_empty() {
+        return None;
+    }
+    let index = boss.next_pattern % phase.patterns.len();
+    Some((index, phase.patterns[index]))
+}
+
+/// 场地光柱：预警 → 生效（对玩家造成一次伤害）→ 消失
+pub fn update_boss_hazards(
+    mut commands: Commands,
+    mut hazards: Query<(Entity, &mut BossHazard, &Transform, &mut Sprite)>,
+    players: Query<(Entity, &Transform, &CollisionBox), With<Player>>,
+    time: Res<Time>,
+    mut damage_writer: MessageWriter<DamageEvent>,
+) {
+    let delta = time.delta_secs();
+    let player = players.iter().next();
+
+    for (entity, mut hazard, transform, mut sprite) in hazards.iter_mut() {
+        if hazard.telegraph_timer > 0.0 {
+            hazard.telegraph_timer -= delta;
+            let pulse = (time.elapsed_secs() * 18.0).sin() * 0.5 + 0.5;
+            sprite.color = Color::srgba(1.0, 0.78, 0.36, 0.14 + pulse * 0.16);
+            continue;
+        }
+
+        hazard.active_timer -= delta;
+        if hazard.active_timer <= 0.0 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        sprite.color = Color::srgba(1.0, 0.92, 0.62, 0.88);
+
+        let Some((player_entity, player_transform, player_box)) = player else {
+            continue;
+        };
+        let hazard_rect = Rect::from_center_size(
+            transform.translation.truncate(),
+            Vec2::new(hazard.width, BOSS_HAZARD_HEIGHT),
+        );
+        if !hazard.has_hit
+            && rects_overlap(hazard_rect, collision_rect(player_transform, player_box))
+        {
+            hazard.has_hit = true;
+            damage_writer.write(DamageEvent {
+                target: player_entity,
+                amount: hazard.damage,
+                source: DamageSource::EnemyContact,
+            });
+        }
+    }
+}
+
+fn boss_action_color(action: BossAction) -> Color {
+    match action {
+        BossAction::Idle => BOSS_BASE_COLOR,
+        BossAction::DashCharge => Color::srgba(1.0, 0.34, 0.27, 1.0),
+        BossAction::Dashing => Color::srgba(1.0, 0.50, 0.40, 1.0),
+        BossAction::Barrage => Color::srgba(0.92, 0.70, 1.0, 1.0),
+        BossAction::Casting => Color::srgba(1.0, 0.86, 0.48, 1.0),
+    }
+}
+
+/// Boss 读招视觉：出招前闪烁对应颜色，阶段切换时发白放大。
+pub fn update_boss_visuals(
+    mut bosses: Query<(&Boss, &EnemyState, &mut Sprite, &mut Transform), With<Enemy>>,
+    time: Res<Time>,
+) {
+    let pulse = (time.elapsed_secs() * 26.0).sin() * 0.5 + 0.5;
+
+    for (boss, state, mut sprite, mut transform) in bosses.iter_mut() {
+        transform.scale = Vec3::ONE;
+        if !state.is_alive {
+            sprite.color = Color::srgba(0.10, 0.08, 0.14, 0.42);
+            continue;
+        }
+
+        if boss.transition_timer > 0.0 {
+            sprite.color = Color::srgba(1.0, 1.0, 1.0, 0.6 + pulse * 0.4);
+            transform.scale = Vec3::splat(1.08 + 0.06 * pulse);
+            continue;
+        }
+
+        let mut color = boss_action_color(boss.action).to_srgba();
+        if matches!(
+            boss.action,
+            BossAction::DashCharge | BossAction::Barrage | BossAction::Casting
+        ) {
+            color.alpha = 0.7 + pulse * 0.3;
+            transform.scale = Vec3::splat(1.0 + 0.06 * pulse);
+        }
+        sprite.color = color.into();
+    }
+}
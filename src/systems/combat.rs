@@ -3,9 +3,15 @@
 use crate::{
     asset_paths,
     components::*,
-    events::{CameraImpulseEvent, DamageEvent, DamageSource},
-    resources::{GameConfig, GameplayTuning},
+    events::{CameraImpulseEvent, DamageEvent, DamageSource, EnemyDefeated, StatusEffectEvent},
+    resources::{
+        EnemyPhysicsTuning, GameConfig, GameplayTuning, MoveListTuning, StatusEffectTuning,
+        move_list::{MovePreset, ProjectileDefinition},
+    },
     states::GameState,
+    systems::enemy_behavior::{ContactEffect, behavior_profile},
+    systems::hitboxes::{FrameDrivenAttack, active_attack_boxes, player_hurtboxes, rects_overlap},
+    systems::sprite_animation::SpriteAnimation,
 };
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
@@ -14,20 +20,6 @@ const PLAYER_CONTACT_DAMAGE_COOLDOWN: f32 = 1.0;
 const PROJECTILE_MUZZLE_X_OFFSET: f32 = 54.0;
 const PROJECTILE_MUZZLE_Y_OFFSET: f32 = 18.0;
 const REFERENCE_ATTACK_FRAME_SECS: f32 = asset_paths::HF_SHIROU_OVEREDGE_ATTACK_FRAME_DURATION_SECS;
-const GROUND_LIGHT_RUNTIME_ROWS: &[u8] = &[1, 2, 3, 4, 5];
-const AIR_COMBO_RUNTIME_ROWS: &[u8] = &[1, 2, 3, 4, 5];
-const HEAVY_RUNTIME_ROWS: &[u8] = &[1, 2, 3, 4, 5];
-const ULTIMATE_RUNTIME_ROWS: &[u8] = &[1, 2, 3];
-const MOBILITY_RUNTIME_ROWS: &[u8] = &[1, 2];
-const NINJUTSU_RUNTIME_ROWS: &[u8] = &[1, 2, 3];
-const WEAPON_PROJ_RUNTIME_ROWS: &[u8] = &[1, 2, 3, 4];
-const OVEREDGE_GROUND_LIGHT_ROWS: &[u8] = &[3, 4, 5];
-const OVEREDGE_AIR_COMBO_ROWS: &[u8] = &[2, 4, 5];
-const OVEREDGE_HEAVY_ROWS: &[u8] = &[3, 5, 4];
-const OVEREDGE_ULTIMATE_ROWS: &[u8] = &[2, 3];
-const OVEREDGE_MOBILITY_ROWS: &[u8] = &[3, 4, 2];
-const OVEREDGE_NINJUTSU_ROWS: &[u8] = &[2, 3, 4];
-const OVEREDGE_WEAPON_PROJ_ROWS: &[u8] = &[2, 3, 4];
 const REFERENCE_ACTION_VFX_FRAME_SECS: f32 = 0.045;
 
 const ENEMY_PROJECTILE_RENDER_SIZE: Vec2 = Vec2::new(16.0, 16.0);
@@ -71,6 +63,7 @@ struct KnifeAttackPreset {
     slash_color: Color,
     knockback_x: f32,
     knockback_y: f32,
+    launch_y: f32,
     hit_stop_secs: f32,
 }
 
@@ -80,6 +73,8 @@ pub struct KnifeComboRuntime {
     combo_step: u8,
     combo_family: Option<AttackComboFamily>,
     combo_reset_timer: f32,
+    /// 连段窗口内连续出招的次数（不受招式族与段数上限影响），用于计分倍率
+    combo_chain: u32,
     queued_attack: Option<AttackAnimationStyle>,
     ground_light_visual_step: u8,
     heavy_visual_step: u8,
@@ -117,8 +112,11 @@ pub struct KnifeSlash {
     pub damage: f32,
     pub lifetime: Timer,
     pub combo_step: u8,
+    pub combo_chain: u32,
     pub knockback_x: f32,
     pub knockback_y: f32,
+    /// 挑空速度，见 [`MovePreset::launch_y`]
+    pub launch_y: f32,
     pub hit_stop_secs: f32,
 }
 
@@ -147,10 +145,13 @@ pub struct PendingKnifeAttack {
     owner: Entity,
     timer: Timer,
     combo_step: u8,
+    combo_chain: u32,
     facing: f32,
     is_crouching: bool,
     overedge_enabled: bool,
     attack_style: AttackAnimationStyle,
+    /// 命中由玩家的 `FrameDrivenAttack` 按帧判定，刀光只作表现
+    frame_driven: bool,
 }
 
 type PlayerKnifeAttackItem<'a> = (
@@ -184,6 +185,7 @@ pub struct KnifeAttackResources<'w, 's> {
     keyboard: Res<'w, ButtonInput<KeyCode>>,
     game_input: Option<Res<'w, crate::systems::input::GameInput>>,
     tuning: Option<Res<'w, GameplayTuning>>,
+    frame_data: Option<Res<'w, FrameDataMap>>,
     runtime: Local<'s, KnifeComboRuntime>,
     time: Res<'w, Time>,
 }
@@ -197,6 +199,8 @@ struct KnifeAttackRequest<'a> {
     player_state: &'a PlayerState,
     facing_sign: f32,
     knife_tuning: &'a crate::resources::KnifeCombatTuning,
+    moves: &'a MoveListTuning,
+    frame_data: Option<&'a FrameDataMap>,
     overedge_enabled: bool,
     requested_style: AttackAnimationStyle,
 }
@@ -303,71 +307,43 @@ fn projectile_config(is_overedge: bool) -> ProjectileConfig {
     }
 }
 
-fn projectile_config_for_attack_style(style: AttackAnimationStyle) -> ProjectileConfig {
-    let mut config = projectile_config(false);
-
-    match style {
-        AttackAnimationStyle::NinjutsuRefRow(1) => {
-            config.projectile_type = ProjectileType::Fireball;
-            config.damage = 7;
-            config.speed = 380.0;
-            config.lifetime = 1.35;
-            config.collision_size = Vec2::new(58.0, 42.0);
-            config.core_size = Vec2::new(46.0, 28.0);
-            config.core_color = Color::srgba(1.0, 0.32, 0.08, 0.96);
-            config.aura_size = Vec2::new(74.0, 44.0);
-            config.aura_color = Color::srgba(1.0, 0.10, 0.04, 0.36);
-            config.accent_size = Vec2::new(54.0, 5.0);
-            config.accent_color = Color::srgba(1.0, 0.84, 0.38, 0.82);
-            config.pulse_amount = 0.10;
-            config.spin_speed = 2.6;
-        }
-        AttackAnimationStyle::NinjutsuRefRow(2) => {
-            config.damage = 5;
-            config.speed = 500.0;
-            config.lifetime = 1.05;
-            config.collision_size = Vec2::new(76.0, 48.0);
-            config.core_size = Vec2::new(72.0, 26.0);
-            config.core_color = Color::srgba(0.62, 0.88, 1.0, 0.92);
-            config.aura_size = Vec2::new(116.0, 54.0);
-            config.aura_color = Color::srgba(0.24, 0.58, 1.0, 0.30);
-            config.accent_size = Vec2::new(70.0, 4.0);
-            config.accent_color = Color::srgba(0.92, 0.98, 1.0, 0.86);
-            config.pulse_speed = 20.0;
-        }
-        AttackAnimationStyle::NinjutsuRefRow(3) => {
-            config.damage = 8;
-            config.speed = 260.0;
-            config.lifetime = 0.8;
-            config.collision_size = Vec2::new(42.0, 108.0);
-            config.core_size = Vec2::new(18.0, 96.0);
-            config.core_color = Color::srgba(0.78, 0.42, 1.0, 0.92);
-            config.aura_size = Vec2::new(44.0, 128.0);
-            config.aura_color = Color::srgba(0.56, 0.16, 1.0, 0.28);
-            config.accent_size = Vec2::new(7.0, 96.0);
-            config.accent_color = Color::srgba(0.96, 0.88, 1.0, 0.82);
-            config.initial_rotation = 0.0;
-            config.accent_rotation = 0.0;
-            config.pulse_amount = 0.14;
-        }
-        AttackAnimationStyle::NinjutsuRefRow(4) => {
-            config.projectile_type = ProjectileType::Overedge;
-            config.damage = 9;
-            config.speed = 440.0;
-            config.lifetime = 1.0;
-            config.collision_size = Vec2::new(96.0, 52.0);
-            config.core_size = Vec2::new(90.0, 22.0);
-            config.core_color = Color::srgba(0.98, 0.16, 0.18, 0.96);
-            config.aura_size = Vec2::new(128.0, 44.0);
-            config.aura_color = Color::srgba(0.72, 0.0, 0.0, 0.34);
-            config.accent_size = Vec2::new(86.0, 5.0);
-            config.accent_color = Color::srgba(1.0, 0.88, 0.82, 0.86);
-            config.pulse_amount = 0.12;
+impl From<&ProjectileDefinition> for ProjectileConfig {
+    fn from(definition: &ProjectileDefinition) -> Self {
+        let size = |(width, height): (f32, f32)| Vec2::new(width, height);
+        let color = |(r, g, b, a): (f32, f32, f32, f32)| Color::srgba(r, g, b, a);
+        let offset = |(x, y, z): (f32, f32, f32)| Vec3::new(x, y, z);
+        Self {
+            projectile_type: definition.kind,
+            damage: definition.damage,
+            speed: definition.speed,
+            lifetime: definition.lifetime,
+            collision_size: size(definition.collision_size),
+            core_size: size(definition.core_size),
+            core_color: color(definition.core_color),
+            aura_size: size(definition.aura_size),
+            aura_color: color(definition.aura_color),
+            accent_size: size(definition.accent_size),
+            accent_color: color(definition.accent_color),
+            aura_offset: offset(definition.aura_offset),
+            accent_offset: offset(definition.accent_offset),
+            initial_rotation: definition.initial_rotation,
+            accent_rotation: definition.accent_rotation,
+            cooldown: definition.cooldown,
+            pulse_speed: definition.pulse_speed,
+            pulse_amount: definition.pulse_amount,
+            spin_speed: definition.spin_speed,
         }
-        _ => {}
     }
+}
 
-    config
+fn projectile_config_for_attack_style(
+    moves: &MoveListTuning,
+    style: AttackAnimationStyle,
+) -> ProjectileConfig {
+    moves
+        .projectile(style)
+        .map(ProjectileConfig::from)
+        .unwrap_or_else(|| projectile_config(false))
 }
 
 fn reset_reference_visual_steps(runtime: &mut KnifeComboRuntime) {
@@ -405,76 +381,37 @@ fn direct_mobility_row(input_row: u8) -> u8 {
         .min(asset_paths::REFERENCE_BOARD_MOBILITY_ROWS as u8)
 }
 
-fn resolve_reference_visual_style(
+fn reference_visual_step(
     runtime: &mut KnifeComboRuntime,
-    style: AttackAnimationStyle,
-) -> AttackAnimationStyle {
-    match style {
-        AttackAnimationStyle::GroundLight => {
-            AttackAnimationStyle::GroundLightRow(next_reference_visual_row(
-                &mut runtime.ground_light_visual_step,
-                GROUND_LIGHT_RUNTIME_ROWS,
-            ))
-        }
-        AttackAnimationStyle::AirCombo => AttackAnimationStyle::AirComboRow(
-            next_reference_visual_row(&mut runtime.air_combo_visual_step, AIR_COMBO_RUNTIME_ROWS),
-        ),
-        AttackAnimationStyle::HeavyRef => AttackAnimationStyle::HeavyRefRow(
-            next_reference_visual_row(&mut runtime.heavy_visual_step, HEAVY_RUNTIME_ROWS),
-        ),
-        AttackAnimationStyle::UltimateRef => AttackAnimationStyle::UltimateRefRow(
-            next_reference_visual_row(&mut runtime.ultimate_visual_step, ULTIMATE_RUNTIME_ROWS),
-        ),
-        AttackAnimationStyle::MobilityRef => AttackAnimationStyle::MobilityRefRow(
-            next_reference_visual_row(&mut runtime.mobility_visual_step, MOBILITY_RUNTIME_ROWS),
-        ),
-        AttackAnimationStyle::NinjutsuRef => AttackAnimationStyle::NinjutsuRefRow(
-            next_reference_visual_row(&mut runtime.ninjutsu_visual_step, NINJUTSU_RUNTIME_ROWS),
-        ),
-        AttackAnimationStyle::WeaponProjRef => {
-            AttackAnimationStyle::WeaponProjRefRow(next_reference_visual_row(
-                &mut runtime.weapon_proj_visual_step,
-                WEAPON_PROJ_RUNTIME_ROWS,
-            ))
-        }
-        _ => style,
+    family: AttackAnimationStyle,
+) -> Option<&mut u8> {
+    match family {
+        AttackAnimationStyle::GroundLight => Some(&mut runtime.ground_light_visual_step),
+        AttackAnimationStyle::AirCombo => Some(&mut runtime.air_combo_visual_step),
+        AttackAnimationStyle::HeavyRef => Some(&mut runtime.heavy_visual_step),
+        AttackAnimationStyle::UltimateRef => Some(&mut runtime.ultimate_visual_step),
+        AttackAnimationStyle::MobilityRef => Some(&mut runtime.mobility_visual_step),
+        AttackAnimationStyle::NinjutsuRef => Some(&mut runtime.ninjutsu_visual_step),
+        AttackAnimationStyle::WeaponProjRef => Some(&mut runtime.weapon_proj_visual_step),
+        _ => None,
     }
 }
 
-fn resolve_overedge_reference_visual_style(
+/// 招式族按招式表中的行顺序轮换为具体的行；已经是具体行的样式保持不变
+fn resolve_reference_visual_style(
     runtime: &mut KnifeComboRuntime,
+    moves: &MoveListTuning,
     style: AttackAnimationStyle,
+    overedge_enabled: bool,
 ) -> AttackAnimationStyle {
-    match style {
-        AttackAnimationStyle::GroundLight => {
-            AttackAnimationStyle::GroundLightRow(next_reference_visual_row(
-                &mut runtime.ground_light_visual_step,
-                OVEREDGE_GROUND_LIGHT_ROWS,
-            ))
-        }
-        AttackAnimationStyle::AirCombo => AttackAnimationStyle::AirComboRow(
-            next_reference_visual_row(&mut runtime.air_combo_visual_step, OVEREDGE_AIR_COMBO_ROWS),
-        ),
-        AttackAnimationStyle::HeavyRef => AttackAnimationStyle::HeavyRefRow(
-            next_reference_visual_row(&mut runtime.heavy_visual_step, OVEREDGE_HEAVY_ROWS),
-        ),
-        AttackAnimationStyle::UltimateRef => AttackAnimationStyle::UltimateRefRow(
-            next_reference_visual_row(&mut runtime.ultimate_visual_step, OVEREDGE_ULTIMATE_ROWS),
-        ),
-        AttackAnimationStyle::MobilityRef => AttackAnimationStyle::MobilityRefRow(
-            next_reference_visual_row(&mut runtime.mobility_visual_step, OVEREDGE_MOBILITY_ROWS),
-        ),
-        AttackAnimationStyle::NinjutsuRef => AttackAnimationStyle::NinjutsuRefRow(
-            next_reference_visual_row(&mut runtime.ninjutsu_visual_step, OVEREDGE_NINJUTSU_ROWS),
-        ),
-        AttackAnimationStyle::WeaponProjRef => {
-            AttackAnimationStyle::WeaponProjRefRow(next_reference_visual_row(
-                &mut runtime.weapon_proj_visual_step,
-                OVEREDGE_WEAPON_PROJ_ROWS,
-            ))
-        }
-        _ => style,
+    if style.reference_family() != Some(style) {
+        return style;
     }
+    let Some(current) = reference_visual_step(runtime, style) else {
+        return style;
+    };
+    let row = next_reference_visual_row(current, moves.family_rows(style, overedge_enabled));
+    style.with_reference_row(row).unwrap_or(style)
 }
 
 fn normalize_attack_style_for_overedge(
@@ -496,473 +433,38 @@ fn normalize_attack_style_for_overedge(
     }
 }
 
-fn knife_attack_preset(step: u8, overedge: bool) -> KnifeAttackPreset {
-    let base = match step {
-        1 => KnifeAttackPreset {
-            damage: 6.0,
-            cooldown: 0.28,
-            windup_secs: 0.11,
-            animation_duration_secs: 0.30,
-            lifetime: 0.09,
-            hitbox_size: Vec2::new(72.0, 34.0),
-            x_offset: 58.0,
-            y_offset: 12.0,
-            crouch_y_offset: -8.0,
-            slash_render_size: Vec2::new(66.0, 16.0),
-            slash_color: Color::srgba(0.96, 0.96, 1.0, 0.20),
-            knockback_x: 90.0,
-            knockback_y: 10.0,
-            hit_stop_secs: 0.018,
-        },
-        2 => KnifeAttackPreset {
-            damage: 8.0,
-            cooldown: 0.26,
-            windup_secs: 0.10,
-            animation_duration_secs: 0.29,
-            lifetime: 0.10,
-            hitbox_size: Vec2::new(82.0, 38.0),
-            x_offset: 66.0,
-            y_offset: 14.0,
-            crouch_y_offset: -6.0,
-            slash_render_size: Vec2::new(74.0, 18.0),
-            slash_color: Color::srgba(0.98, 0.88, 0.84, 0.23),
-            knockback_x: 130.0,
-            knockback_y: 20.0,
-            hit_stop_secs: 0.024,
-        },
-        _ => KnifeAttackPreset {
-            damage: 12.0,
-            cooldown: 0.30,
-            windup_secs: 0.08,
-            animation_duration_secs: 0.28,
-            lifetime: 0.12,
-            hitbox_size: Vec2::new(96.0, 44.0),
-            x_offset: 74.0,
-            y_offset: 16.0,
-            crouch_y_offset: -4.0,
-            slash_render_size: Vec2::new(88.0, 22.0),
-            slash_color: Color::srgba(1.0, 0.78, 0.65, 0.27),
-            knockback_x: 220.0,
-            knockback_y: 35.0,
-            hit_stop_secs: 0.035,
-        },
-    };
-
-    if overedge {
-        KnifeAttackPreset {
-            damage: base.damage * 1.25,
-            cooldown: (base.cooldown - 0.03).max(0.14),
-            windup_secs: (base.windup_secs - 0.01).max(0.04),
-            animation_duration_secs: (base.animation_duration_secs - 0.02).max(0.18),
-            lifetime: base.lifetime,
-            hitbox_size: base.hitbox_size * Vec2::new(1.12, 1.06),
-            x_offset: base.x_offset + 6.0,
-            y_offset: base.y_offset + 2.0,
-            crouch_y_offset: base.crouch_y_offset,
-            slash_render_size: base.slash_render_size * Vec2::new(1.1, 1.1),
-            slash_color: Color::srgba(1.0, 0.58, 0.48, 0.32),
-            knockback_x: base.knockback_x * 1.25,
-            knockback_y: base.knockback_y * 1.2,
-            hit_stop_secs: base.hit_stop_secs + 0.008,
+impl From<MovePreset> for KnifeAttackPreset {
+    fn from(preset: MovePreset) -> Self {
+        let (hitbox_width, hitbox_height) = preset.hitbox_size;
+        let (slash_width, slash_height) = preset.slash_render_size;
+        let (r, g, b, a) = preset.slash_color;
+        Self {
+            damage: preset.damage,
+            cooldown: preset.cooldown,
+            windup_secs: preset.windup_secs,
+            animation_duration_secs: preset.animation_duration_secs,
+            lifetime: preset.lifetime,
+            hitbox_size: Vec2::new(hitbox_width, hitbox_height),
+            x_offset: preset.x_offset,
+            y_offset: preset.y_offset,
+            crouch_y_offset: preset.crouch_y_offset,
+            slash_render_size: Vec2::new(slash_width, slash_height),
+            slash_color: Color::srgba(r, g, b, a),
+            knockback_x: preset.knockback_x,
+            knockback_y: preset.knockback_y,
+            launch_y: preset.launch_y,
+            hit_stop_secs: preset.hit_stop_secs,
         }
-    } else {
-        base
     }
 }
 
 fn knife_attack_preset_for_style(
+    moves: &MoveListTuning,
     step: u8,
     overedge: bool,
     style: AttackAnimationStyle,
 ) -> KnifeAttackPreset {
-    let base = knife_attack_preset(step, overedge);
-
-    let family_preset = match style {
-        AttackAnimationStyle::AirCombo | AttackAnimationStyle::AirComboRow(_) => {
-            KnifeAttackPreset {
-                cooldown: base.cooldown.max(0.24),
-                windup_secs: 0.07,
-                animation_duration_secs: base.animation_duration_secs.max(0.48),
-                lifetime: 0.11,
-                hitbox_size: base.hitbox_size * Vec2::new(1.08, 1.18),
-                y_offset: 28.0,
-                crouch_y_offset: 12.0,
-                knockback_y: base.knockback_y + 26.0,
-                slash_color: Color::srgba(0.84, 0.96, 1.0, 0.26),
-                ..base
-            }
-        }
-        AttackAnimationStyle::HeavyRef | AttackAnimationStyle::HeavyRefRow(_) => {
-            KnifeAttackPreset {
-                damage: base.damage * 1.45,
-                cooldown: base.cooldown.max(0.42),
-                windup_secs: 0.16,
-                animation_duration_secs: base.animation_duration_secs.max(0.58),
-                lifetime: 0.14,
-                hitbox_size: base.hitbox_size * Vec2::new(1.35, 1.25),
-                x_offset: base.x_offset + 18.0,
-                slash_render_size: base.slash_render_size * Vec2::new(1.4, 1.3),
-                slash_color: Color::srgba(1.0, 0.30, 0.24, 0.34),
-                knockback_x: base.knockback_x * 1.45,
-                knockback_y: base.knockback_y + 18.0,
-                hit_stop_secs: base.hit_stop_secs + 0.025,
-                ..base
-            }
-        }
-        AttackAnimationStyle::UltimateRef | AttackAnimationStyle::UltimateRefRow(_) => {
-            KnifeAttackPreset {
-                damage: base.damage * 2.6,
-                cooldown: base.cooldown.max(1.05),
-                windup_secs: 0.22,
-                animation_duration_secs: base.animation_duration_secs.max(0.72),
-                lifetime: 0.18,
-                hitbox_size: Vec2::new(190.0, 116.0),
-                x_offset: 96.0,
-                y_offset: 24.0,
-                crouch_y_offset: 10.0,
-                slash_render_size: Vec2::new(180.0, 42.0),
-                slash_color: Color::srgba(1.0, 0.18, 0.14, 0.40),
-                knockback_x: base.knockback_x * 2.2,
-                knockback_y: 90.0,
-                hit_stop_secs: base.hit_stop_secs + 0.06,
-            }
-        }
-        AttackAnimationStyle::MobilityRef | AttackAnimationStyle::MobilityRefRow(_) => {
-            KnifeAttackPreset {
-                damage: base.damage * 0.95,
-                cooldown: base.cooldown.min(0.24),
-                windup_secs: 0.045,
-                animation_duration_secs: base.animation_duration_secs.max(0.36),
-                lifetime: 0.10,
-                hitbox_size: base.hitbox_size * Vec2::new(1.18, 0.95),
-                x_offset: base.x_offset + 24.0,
-                y_offset: 4.0,
-                crouch_y_offset: -6.0,
-                slash_render_size: base.slash_render_size * Vec2::new(1.35, 0.9),
-                slash_color: Color::srgba(1.0, 0.22, 0.16, 0.28),
-                knockback_x: base.knockback_x * 1.25,
-                knockback_y: base.knockback_y * 0.7,
-                hit_stop_secs: base.hit_stop_secs + 0.006,
-            }
-        }
-        AttackAnimationStyle::NinjutsuRef | AttackAnimationStyle::NinjutsuRefRow(_) => {
-            KnifeAttackPreset {
-                damage: base.damage,
-                cooldown: base.cooldown.max(0.54),
-                windup_secs: 0.18,
-                animation_duration_secs: base.animation_duration_secs.max(0.56),
-                lifetime: 0.0,
-                hitbox_size: Vec2::ZERO,
-                slash_render_size: Vec2::ZERO,
-                slash_color: Color::srgba(0.0, 0.0, 0.0, 0.0),
-                hit_stop_secs: 0.0,
-                ..base
-            }
-        }
-        AttackAnimationStyle::WeaponProjRef | AttackAnimationStyle::WeaponProjRefRow(_) => {
-            KnifeAttackPreset {
-                damage: base.damage * 1.25,
-                cooldown: base.cooldown.max(0.46),
-                windup_secs: 0.13,
-                animation_duration_secs: base.animation_duration_secs.max(0.42),
-                lifetime: 0.13,
-                hitbox_size: base.hitbox_size * Vec2::new(1.25, 1.08),
-                x_offset: base.x_offset + 12.0,
-                slash_render_size: base.slash_render_size * Vec2::new(1.28, 1.05),
-                slash_color: Color::srgba(1.0, 0.24, 0.20, 0.32),
-                knockback_x: base.knockback_x * 1.28,
-                knockback_y: base.knockback_y + 8.0,
-                hit_stop_secs: base.hit_stop_secs + 0.014,
-                ..base
-            }
-        }
-        _ => base,
-    };
-
-    tune_reference_row_attack_preset(family_preset, style)
-}
-
-fn tune_reference_row_attack_preset(
-    preset: KnifeAttackPreset,
-    style: AttackAnimationStyle,
-) -> KnifeAttackPreset {
-    match style {
-        AttackAnimationStyle::GroundLightRow(1) => KnifeAttackPreset {
-            damage: preset.damage * 0.92,
-            cooldown: preset.cooldown.min(0.22),
-            windup_secs: 0.055,
-            animation_duration_secs: preset.animation_duration_secs.min(0.26),
-            lifetime: 0.075,
-            hitbox_size: Vec2::new(66.0, 32.0),
-            x_offset: 54.0,
-            y_offset: 12.0,
-            slash_render_size: Vec2::new(58.0, 14.0),
-            knockback_x: 82.0,
-            hit_stop_secs: preset.hit_stop_secs.min(0.018),
-            ..preset
-        },
-        AttackAnimationStyle::GroundLightRow(2) => KnifeAttackPreset {
-            damage: preset.damage * 1.04,
-            hitbox_size: Vec2::new(78.0, 56.0),
-            y_offset: 24.0,
-            slash_render_size: Vec2::new(68.0, 30.0),
-            knockback_x: preset.knockback_x * 1.05,
-            knockback_y: 46.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.006,
-            ..preset
-        },
-        AttackAnimationStyle::GroundLightRow(3) => KnifeAttackPreset {
-            damage: preset.damage * 1.18,
-            cooldown: preset.cooldown.max(0.30),
-            windup_secs: 0.075,
-            lifetime: 0.11,
-            hitbox_size: Vec2::new(124.0, 34.0),
-            x_offset: 82.0,
-            slash_render_size: Vec2::new(112.0, 15.0),
-            knockback_x: preset.knockback_x * 1.42,
-            knockback_y: preset.knockback_y + 8.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.012,
-            ..preset
-        },
-        AttackAnimationStyle::GroundLightRow(4) => KnifeAttackPreset {
-            damage: preset.damage * 1.08,
-            cooldown: preset.cooldown.max(0.31),
-            lifetime: 0.13,
-            hitbox_size: Vec2::new(138.0, 30.0),
-            x_offset: 42.0,
-            y_offset: 5.0,
-            crouch_y_offset: -5.0,
-            slash_render_size: Vec2::new(132.0, 18.0),
-            knockback_x: preset.knockback_x * 1.18,
-            knockback_y: 8.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.01,
-            ..preset
-        },
-        AttackAnimationStyle::GroundLightRow(5) => KnifeAttackPreset {
-            damage: preset.damage * 1.30,
-            cooldown: preset.cooldown.max(0.36),
-            windup_secs: 0.09,
-            lifetime: 0.13,
-            hitbox_size: Vec2::new(98.0, 86.0),
-            x_offset: 68.0,
-            y_offset: 38.0,
-            slash_render_size: Vec2::new(96.0, 38.0),
-            knockback_x: preset.knockback_x * 1.20,
-            knockback_y: 94.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.02,
-            ..preset
-        },
-        AttackAnimationStyle::HeavyRefRow(1) => KnifeAttackPreset {
-            damage: preset.damage * 0.95,
-            hitbox_size: Vec2::new(116.0, 58.0),
-            x_offset: 78.0,
-            slash_render_size: Vec2::new(112.0, 28.0),
-            ..preset
-        },
-        AttackAnimationStyle::HeavyRefRow(2) => KnifeAttackPreset {
-            damage: preset.damage * 1.05,
-            hitbox_size: Vec2::new(104.0, 128.0),
-            x_offset: 68.0,
-            y_offset: 38.0,
-            slash_render_size: Vec2::new(96.0, 72.0),
-            knockback_y: 92.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.012,
-            ..preset
-        },
-        AttackAnimationStyle::HeavyRefRow(3) => KnifeAttackPreset {
-            damage: preset.damage * 1.18,
-            cooldown: preset.cooldown.max(0.48),
-            hitbox_size: Vec2::new(174.0, 44.0),
-            x_offset: 112.0,
-            y_offset: 18.0,
-            slash_render_size: Vec2::new(164.0, 18.0),
-            knockback_x: preset.knockback_x * 1.34,
-            knockback_y: preset.knockback_y + 8.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.018,
-            ..preset
-        },
-        AttackAnimationStyle::HeavyRefRow(4) => KnifeAttackPreset {
-            damage: preset.damage * 1.12,
-            cooldown: preset.cooldown.max(0.50),
-            lifetime: 0.16,
-            hitbox_size: Vec2::new(172.0, 48.0),
-            x_offset: 42.0,
-            y_offset: 8.0,
-            slash_render_size: Vec2::new(168.0, 24.0),
-            knockback_x: preset.knockback_x * 1.18,
-            knockback_y: 18.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.016,
-            ..preset
-        },
-        AttackAnimationStyle::HeavyRefRow(5) => KnifeAttackPreset {
-            damage: preset.damage * 1.42,
-            cooldown: preset.cooldown.max(0.64),
-            windup_secs: 0.19,
-            lifetime: 0.18,
-            hitbox_size: Vec2::new(206.0, 126.0),
-            x_offset: 86.0,
-            y_offset: 34.0,
-            slash_render_size: Vec2::new(198.0, 54.0),
-            knockback_x: preset.knockback_x * 1.50,
-            knockback_y: 76.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.04,
-            ..preset
-        },
-        AttackAnimationStyle::AirComboRow(1) => KnifeAttackPreset {
-            hitbox_size: Vec2::new(82.0, 58.0),
-            y_offset: 32.0,
-            knockback_y: 58.0,
-            ..preset
-        },
-        AttackAnimationStyle::AirComboRow(2) => KnifeAttackPreset {
-            damage: preset.damage * 1.08,
-            hitbox_size: Vec2::new(126.0, 36.0),
-            x_offset: 86.0,
-            y_offset: 24.0,
-            slash_render_size: Vec2::new(118.0, 15.0),
-            knockback_x: preset.knockback_x * 1.28,
-            ..preset
-        },
-        AttackAnimationStyle::AirComboRow(3) => KnifeAttackPreset {
-            damage: preset.damage * 1.16,
-            cooldown: preset.cooldown.max(0.30),
-            hitbox_size: Vec2::new(78.0, 118.0),
-            x_offset: 48.0,
-            y_offset: -18.0,
-            slash_render_size: Vec2::new(72.0, 68.0),
-            knockback_y: -16.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.014,
-            ..preset
-        },
-        AttackAnimationStyle::AirComboRow(4) => KnifeAttackPreset {
-            damage: preset.damage * 1.20,
-            cooldown: preset.cooldown.max(0.34),
-            lifetime: 0.14,
-            hitbox_size: Vec2::new(154.0, 74.0),
-            x_offset: 34.0,
-            y_offset: 8.0,
-            slash_render_size: Vec2::new(150.0, 34.0),
-            knockback_x: preset.knockback_x * 1.15,
-            knockback_y: 44.0,
-            ..preset
-        },
-        AttackAnimationStyle::AirComboRow(5) => KnifeAttackPreset {
-            damage: preset.damage * 1.36,
-            cooldown: preset.cooldown.max(0.40),
-            windup_secs: 0.10,
-            lifetime: 0.16,
-            hitbox_size: Vec2::new(118.0, 136.0),
-            x_offset: 58.0,
-            y_offset: -26.0,
-            slash_render_size: Vec2::new(106.0, 76.0),
-            knockback_x: preset.knockback_x * 1.22,
-            knockback_y: -34.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.026,
-            ..preset
-        },
-        AttackAnimationStyle::MobilityRefRow(2) => KnifeAttackPreset {
-            damage: preset.damage * 0.96,
-            hitbox_size: Vec2::new(128.0, 28.0),
-            x_offset: 88.0,
-            y_offset: 0.0,
-            crouch_y_offset: -5.0,
-            slash_render_size: Vec2::new(128.0, 15.0),
-            knockback_y: 6.0,
-            ..preset
-        },
-        AttackAnimationStyle::MobilityRefRow(3) => KnifeAttackPreset {
-            damage: preset.damage * 0.82,
-            cooldown: preset.cooldown.max(0.30),
-            hitbox_size: Vec2::new(88.0, 46.0),
-            x_offset: 34.0,
-            y_offset: 14.0,
-            slash_render_size: Vec2::new(80.0, 16.0),
-            knockback_x: -preset.knockback_x.abs() * 0.60,
-            knockback_y: 30.0,
-            ..preset
-        },
-        AttackAnimationStyle::MobilityRefRow(4) => KnifeAttackPreset {
-            damage: preset.damage * 1.08,
-            cooldown: preset.cooldown.max(0.34),
-            hitbox_size: Vec2::new(96.0, 88.0),
-            x_offset: 52.0,
-            y_offset: 42.0,
-            slash_render_size: Vec2::new(88.0, 46.0),
-            knockback_x: preset.knockback_x * 0.92,
-            knockback_y: 112.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.012,
-            ..preset
-        },
-        AttackAnimationStyle::UltimateRefRow(1) => KnifeAttackPreset {
-            damage: preset.damage * 0.95,
-            hitbox_size: Vec2::new(202.0, 136.0),
-            x_offset: 52.0,
-            y_offset: 34.0,
-            knockback_y: 76.0,
-            ..preset
-        },
-        AttackAnimationStyle::UltimateRefRow(2) => KnifeAttackPreset {
-            damage: preset.damage * 1.18,
-            hitbox_size: Vec2::new(260.0, 82.0),
-            x_offset: 142.0,
-            y_offset: 24.0,
-            slash_render_size: Vec2::new(248.0, 36.0),
-            knockback_x: preset.knockback_x * 1.35,
-            knockback_y: 54.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.026,
-            ..preset
-        },
-        AttackAnimationStyle::UltimateRefRow(3) => KnifeAttackPreset {
-            damage: preset.damage * 1.30,
-            cooldown: preset.cooldown.max(1.20),
-            windup_secs: 0.26,
-            hitbox_size: Vec2::new(248.0, 168.0),
-            x_offset: 76.0,
-            y_offset: 54.0,
-            slash_render_size: Vec2::new(232.0, 62.0),
-            knockback_x: preset.knockback_x * 1.20,
-            knockback_y: 124.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.04,
-            ..preset
-        },
-        AttackAnimationStyle::WeaponProjRefRow(1) => KnifeAttackPreset {
-            damage: preset.damage * 0.94,
-            hitbox_size: Vec2::new(92.0, 46.0),
-            x_offset: 68.0,
-            ..preset
-        },
-        AttackAnimationStyle::WeaponProjRefRow(2) => KnifeAttackPreset {
-            damage: preset.damage * 1.08,
-            hitbox_size: Vec2::new(116.0, 58.0),
-            x_offset: 64.0,
-            slash_render_size: Vec2::new(112.0, 24.0),
-            knockback_y: preset.knockback_y + 18.0,
-            ..preset
-        },
-        AttackAnimationStyle::WeaponProjRefRow(3) => KnifeAttackPreset {
-            damage: preset.damage * 1.22,
-            cooldown: preset.cooldown.max(0.50),
-            hitbox_size: Vec2::new(158.0, 50.0),
-            x_offset: 106.0,
-            slash_render_size: Vec2::new(150.0, 22.0),
-            knockback_x: preset.knockback_x * 1.34,
-            ..preset
-        },
-        AttackAnimationStyle::WeaponProjRefRow(4) => KnifeAttackPreset {
-            damage: preset.damage * 1.16,
-            cooldown: preset.cooldown.max(0.54),
-            windup_secs: 0.15,
-            hitbox_size: Vec2::new(136.0, 78.0),
-            x_offset: 86.0,
-            y_offset: 30.0,
-            slash_render_size: Vec2::new(132.0, 32.0),
-            knockback_x: preset.knockback_x * 1.16,
-            knockback_y: preset.knockback_y + 44.0,
-            hit_stop_secs: preset.hit_stop_secs + 0.018,
-            ..preset
-        },
-        _ => preset,
-    }
+    moves.preset_for(step, overedge, style).into()
 }
 
 fn overedge_animation_duration(style: AttackAnimationStyle) -> Option<f32> {
@@ -1036,49 +538,9 @@ fn overedge_animation_duration(style: AttackAnimationStyle) -> Option<f32> {
     Some((frame_count as f32 + 1.0) * REFERENCE_ATTACK_FRAME_SECS)
 }
 
-fn reference_action_lock_floor(style: AttackAnimationStyle) -> Option<f32> {
-    match style {
-        AttackAnimationStyle::GroundLightRow(1) => Some(0.18),
-        AttackAnimationStyle::GroundLightRow(2) => Some(0.22),
-        AttackAnimationStyle::GroundLightRow(3) => Some(0.28),
-        AttackAnimationStyle::GroundLightRow(4) => Some(0.30),
-        AttackAnimationStyle::GroundLightRow(5) => Some(0.34),
-        AttackAnimationStyle::GroundLightRow(_) => Some(0.24),
-        AttackAnimationStyle::AirComboRow(1) => Some(0.22),
-        AttackAnimationStyle::AirComboRow(2) => Some(0.24),
-        AttackAnimationStyle::AirComboRow(3) => Some(0.30),
-        AttackAnimationStyle::AirComboRow(4) => Some(0.32),
-        AttackAnimationStyle::AirComboRow(5) => Some(0.38),
-        AttackAnimationStyle::AirComboRow(_) => Some(0.26),
-        AttackAnimationStyle::HeavyRefRow(1) => Some(0.36),
-        AttackAnimationStyle::HeavyRefRow(2) => Some(0.42),
-        AttackAnimationStyle::HeavyRefRow(3) => Some(0.46),
-        AttackAnimationStyle::HeavyRefRow(4) => Some(0.48),
-        AttackAnimationStyle::HeavyRefRow(5) => Some(0.58),
-        AttackAnimationStyle::HeavyRefRow(_) => Some(0.40),
-        AttackAnimationStyle::UltimateRefRow(1) => Some(0.78),
-        AttackAnimationStyle::UltimateRefRow(2) => Some(0.86),
-        AttackAnimationStyle::UltimateRefRow(3) => Some(0.96),
-        AttackAnimationStyle::UltimateRefRow(_) => Some(0.80),
-        AttackAnimationStyle::MobilityRefRow(1) | AttackAnimationStyle::MobilityRefRow(2) => {
-            Some(0.18)
-        }
-        AttackAnimationStyle::MobilityRefRow(3) => Some(0.26),
-        AttackAnimationStyle::MobilityRefRow(4) => Some(0.32),
-        AttackAnimationStyle::NinjutsuRefRow(4) => Some(0.50),
-        AttackAnimationStyle::NinjutsuRefRow(_) => Some(0.44),
-        AttackAnimationStyle::WeaponProjRefRow(1) => Some(0.32),
-        AttackAnimationStyle::WeaponProjRefRow(2) => Some(0.36),
-        AttackAnimationStyle::WeaponProjRefRow(3) => Some(0.42),
-        AttackAnimationStyle::WeaponProjRefRow(4) => Some(0.46),
-        AttackAnimationStyle::WeaponProjRefRow(_) => Some(0.34),
-        _ => None,
-    }
-}
-
-fn attack_cooldown_floor(style: AttackAnimationStyle) -> f32 {
-    if let Some(lock_floor) = reference_action_lock_floor(style) {
-        return lock_floor;
+fn attack_cooldown_floor(moves: &MoveListTuning, style: AttackAnimationStyle) -> f32 {
+    if let Some(floor) = moves.cooldown_floor(style) {
+        return floor;
     }
 
     match style {
@@ -1307,17 +769,8 @@ fn slash_feedback_for_style(
     }
 }
 
-fn spawn_knife_slash(
-    commands: &mut Commands,
-    player_transform: &Transform,
-    player_state: &PlayerState,
-    combo_step: u8,
-    facing: f32,
-    overedge_enabled: bool,
-    attack_style: AttackAnimationStyle,
-) {
-    let preset = knife_attack_preset_for_style(combo_step, overedge_enabled, attack_style);
-    let base_alpha = match attack_style {
+fn slash_base_alpha(combo_step: u8, attack_style: AttackAnimationStyle) -> f32 {
+    match attack_style {
         AttackAnimationStyle::UltimateRef | AttackAnimationStyle::UltimateRefRow(_) => 0.40,
         AttackAnimationStyle::HeavyRef
         | AttackAnimationStyle::HeavyRefRow(_)
@@ -1326,8 +779,28 @@ fn spawn_knife_slash(
         AttackAnimationStyle::MobilityRef | AttackAnimationStyle::MobilityRefRow(_) => 0.28,
         AttackAnimationStyle::AirCombo | AttackAnimationStyle::AirComboRow(_) => 0.26,
         _ => 0.22 + combo_step as f32 * 0.03,
-    };
-    let y_offset = if player_state.is_crouching {
+    }
+}
+
+fn spawn_knife_slash(
+    commands: &mut Commands,
+    moves: &MoveListTuning,
+    player_transform: &Transform,
+    pending: &PendingKnifeAttack,
+) {
+    let PendingKnifeAttack {
+        combo_step,
+        combo_chain,
+        facing,
+        is_crouching,
+        overedge_enabled,
+        attack_style,
+        frame_driven,
+        ..
+    } = *pending;
+    let preset = knife_attack_preset_for_style(moves, combo_step, overedge_enabled, attack_style);
+    let base_alpha = slash_base_alpha(combo_step, attack_style);
+    let y_offset = if is_crouching {
         preset.crouch_y_offset
     } else {
         preset.y_offset
@@ -1341,30 +814,32 @@ fn spawn_knife_slash(
         2.4,
     );
 
-    commands.spawn((
+    let mut slash = commands.spawn((
         Sprite {
             color: preset.slash_color,
             custom_size: Some(preset.slash_render_size),
             ..default()
         },
         Transform::from_translation(slash_position).with_rotation(Quat::from_rotation_z(
-            if player_state.is_crouching {
-                0.06
-            } else {
-                -0.12
-            } * facing,
+            if is_crouching { 0.06 } else { -0.12 } * facing,
         )),
         KnifeSlash {
             damage: preset.damage,
             lifetime: Timer::from_seconds(preset.lifetime, TimerMode::Once),
             combo_step,
+            combo_chain,
             knockback_x: preset.knockback_x * facing,
             knockback_y: preset.knockback_y,
+            launch_y: preset.launch_y,
             hit_stop_secs: preset.hit_stop_secs,
         },
-        crate::systems::collision::CollisionBox::new(preset.hitbox_size),
         slash_feedback_for_style(combo_step, attack_style, base_alpha, facing),
     ));
+    if !frame_driven {
+        slash.insert(crate::systems::collision::CollisionBox::new(
+            preset.hitbox_size,
+        ));
+    }
 }
 
 fn should_spawn_reference_action_vfx(style: AttackAnimationStyle) -> bool {
@@ -1816,21 +1291,38 @@ fn perform_knife_attack(
     } else {
         base_style
     };
-    let attack_style = if request.overedge_enabled {
-        resolve_overedge_reference_visual_style(runtime, attack_style)
+    let attack_style = resolve_reference_visual_style(
+        runtime,
+        request.moves,
+        attack_style,
+        request.overedge_enabled,
+    );
+    runtime.combo_chain = if runtime.combo_reset_timer > 0.0 {
+        runtime.combo_chain.saturating_add(1)
     } else {
-        resolve_reference_visual_style(runtime, attack_style)
+        1
     };
+    let combo_chain = runtime.combo_chain;
     runtime.combo_step = combo_step;
     runtime.combo_family = Some(combo_family);
     runtime.combo_reset_timer = request.knife_tuning.combo_reset_window_secs.max(0.1);
     runtime.queued_attack = None;
 
-    let preset = knife_attack_preset_for_style(combo_step, request.overedge_enabled, attack_style);
-    runtime.cooldown = preset.cooldown.max(attack_cooldown_floor(attack_style));
+    let preset = knife_attack_preset_for_style(
+        request.moves,
+        combo_step,
+        request.overedge_enabled,
+        attack_style,
+    );
+    runtime.cooldown = preset
+        .cooldown
+        .max(attack_cooldown_floor(request.moves, attack_style));
     let player_visual_style = stable_player_visual_style(attack_style);
-    let animation_duration =
-        overedge_animation_duration(player_visual_style).unwrap_or(preset.animation_duration_secs);
+    let animation_duration = request
+        .moves
+        .animation_duration(player_visual_style)
+        .or_else(|| overedge_animation_duration(player_visual_style))
+        .unwrap_or(preset.animation_duration_secs);
     attack_animation.trigger_with_style(animation_duration, player_visual_style);
 
     let facing = if request.facing_sign < 0.0 {
@@ -1864,14 +1356,42 @@ fn perform_knife_attack(
         attack_style,
     );
 
+    let frame_driven = request
+        .frame_data
+        .is_some_and(|frame_data| frame_data.has_hitboxes(player_visual_style));
+    if frame_driven {
+        commands
+            .entity(request.player_entity)
+            .insert(FrameDrivenAttack {
+                trigger_serial: attack_animation.trigger_serial,
+                combo_step,
+                combo_chain,
+                facing,
+                damage: preset.damage,
+                knockback_x: preset.knockback_x,
+                knockback_y: preset.knockback_y,
+                launch_y: preset.launch_y,
+                hit_stop_secs: preset.hit_stop_secs,
+                feedback: slash_feedback_for_style(
+                    combo_step,
+                    attack_style,
+                    slash_base_alpha(combo_step, attack_style),
+                    facing,
+                ),
+                hit_targets: Vec::new(),
+            });
+    }
+
     commands.spawn((PendingKnifeAttack {
         owner: request.player_entity,
         timer: Timer::from_seconds(preset.windup_secs, TimerMode::Once),
         combo_step,
+        combo_chain,
         facing,
         is_crouching: request.player_state.is_crouching,
         overedge_enabled: request.overedge_enabled,
         attack_style,
+        frame_driven,
     },));
 }
 
@@ -1938,17 +1458,22 @@ pub fn player_knife_attack(
         keyboard,
         game_input,
         tuning,
+        frame_data,
         mut runtime,
         time,
     } = resources;
     let default_tuning = GameplayTuning::default();
-    let knife_tuning = &tuning.as_deref().unwrap_or(&default_tuning).knife;
+    let tuning = tuning.as_deref().unwrap_or(&default_tuning);
+    let knife_tuning = &tuning.knife;
+    let moves = tuning.moves.as_ref();
+    let frame_data = frame_data.as_deref();
 
     runtime.cooldown = (runtime.cooldown - time.delta_secs()).max(0.0);
     runtime.combo_reset_timer = (runtime.combo_reset_timer - time.delta_secs()).max(0.0);
 
     if runtime.combo_reset_timer <= 0.0 {
         runtime.combo_step = 0;
+        runtime.combo_chain = 0;
         runtime.combo_family = None;
         runtime.queued_attack = None;
         reset_reference_visual_steps(&mut runtime);
@@ -2045,6 +1570,8 @@ pub fn player_knife_attack(
                     player_state,
                     facing_sign,
                     knife_tuning,
+                    moves,
+                    frame_data,
                     overedge_enabled,
                     requested_style: attack_style,
                 },
@@ -2082,6 +1609,8 @@ pub fn player_knife_attack(
                 player_state,
                 facing_sign,
                 knife_tuning,
+                moves,
+                frame_data,
                 overedge_enabled,
                 requested_style: attack_style,
             },
@@ -2092,9 +1621,13 @@ pub fn player_knife_attack(
 pub fn resolve_pending_knife_attacks(
     mut commands: Commands,
     time: Res<Time>,
+    tuning: Option<Res<GameplayTuning>>,
     mut pending_query: Query<(Entity, &mut PendingKnifeAttack)>,
     player_query: Query<&Transform, With<Player>>,
 ) {
+    let default_tuning = GameplayTuning::default();
+    let moves = tuning.as_deref().unwrap_or(&default_tuning).moves.as_ref();
+
     for (pending_entity, mut pending) in pending_query.iter_mut() {
         pending.timer.tick(time.delta());
         if !pending.timer.just_finished() && !pending.timer.is_finished() {
@@ -2106,10 +1639,6 @@ pub fn resolve_pending_knife_attacks(
             continue;
         };
 
-        let player_state = PlayerState {
-            is_grounded: true,
-            is_crouching: pending.is_crouching,
-        };
         if matches!(
             pending.attack_style,
             AttackAnimationStyle::NinjutsuRef | AttackAnimationStyle::NinjutsuRefRow(_)
@@ -2122,19 +1651,11 @@ pub fn resolve_pending_knife_attacks(
             spawn_projectile_with_style(
                 &mut commands,
                 spawn_position,
-                projectile_config_for_attack_style(pending.attack_style),
+                projectile_config_for_attack_style(moves, pending.attack_style),
                 pending.facing,
             );
         } else {
-            spawn_knife_slash(
-                &mut commands,
-                player_transform,
-                &player_state,
-                pending.combo_step,
-                pending.facing,
-                pending.overedge_enabled,
-                pending.attack_style,
-            );
+            spawn_knife_slash(&mut commands, moves, player_transform, &pending);
         }
         commands.entity(pending_entity).despawn();
     }
@@ -2300,11 +1821,97 @@ pub fn maintain_hit_stop_timescale(
     }
 }
 
+/// 刀攻击命中后的伤害、镜头与 HitStop 反馈
+#[derive(SystemParam)]
+pub struct KnifeHitFeedback<'w> {
+    damage_writer: MessageWriter<'w, DamageEvent>,
+    camera_impulse_writer: MessageWriter<'w, CameraImpulseEvent>,
+    hit_stop: Option<ResMut<'w, HitStopState>>,
+}
+
+impl KnifeHitFeedback<'_> {
+    fn apply(
+        &mut self,
+        target: Entity,
+        damage: f32,
+        combo_step: u8,
+        combo_chain: u32,
+        hit_stop_secs: f32,
+        feedback: Option<&KnifeSlashFeedback>,
+    ) {
+        self.damage_writer.write(DamageEvent {
+            target,
+            amount: damage,
+            source: DamageSource::Knife { combo_chain },
+        });
+
+        let shake_intensity = feedback
+            .map(|feedback| feedback.camera_intensity)
+            .unwrap_or(2.2 + combo_step as f32 * 0.8);
+        let shake_duration = feedback
+            .map(|feedback| feedback.camera_duration)
+            .unwrap_or(0.06);
+        self.camera_impulse_writer.write(CameraImpulseEvent {
+            intensity: shake_intensity,
+            duration: shake_duration,
+        });
+
+        if let Some(hit_stop) = self.hit_stop.as_deref_mut() {
+            let freeze_speed = feedback
+                .map(|feedback| feedback.hit_stop_freeze_speed)
+                .unwrap_or(0.12);
+            hit_stop.trigger(hit_stop_secs, freeze_speed);
+        }
+    }
+}
+
+type KnifeEnemyItem<'a> = (
+    Entity,
+    &'a Transform,
+    &'a mut EnemyState,
+    &'a mut Velocity,
+    &'a crate::systems::collision::CollisionBox,
+    Option<&'a mut EnemyBody>,
+);
+
+type FrameAttackerItem<'a> = (
+    Entity,
+    &'a Transform,
+    &'a SpriteAnimation,
+    &'a AttackAnimationState,
+    &'a mut FrameDrivenAttack,
+);
+
+/// 施加刀攻击的击退与硬直：带 [`EnemyBody`] 的敌人按物理调参击飞/挑空，
+/// 其余敌人（Boss）直接使用招式的击退速度
+fn knock_back_enemy(
+    state: &mut EnemyState,
+    velocity: &mut Velocity,
+    body: Option<Mut<EnemyBody>>,
+    knockback: Vec2,
+    launch_y: f32,
+    hit_stop_secs: f32,
+    physics: &EnemyPhysicsTuning,
+) {
+    match body {
+        Some(mut body) => body.take_hit(velocity, knockback, launch_y, physics),
+        None => {
+            velocity.x = knockback.x;
+            velocity.y = knockback.y;
+        }
+    }
+    state.apply_hit_stun(0.12 + hit_stop_secs * 3.0);
+}
+
 /// 刀攻击命中敌人后统一发伤害事件，并施加击退/硬直。
+///
+/// 单盒刀光命中一个敌人后消失；按帧判定的攻击（[`FrameDrivenAttack`]）跟随玩家当前
+/// 动画帧的攻击框，一次攻击对每个敌人只命中一次。
 pub fn knife_enemy_collision(
     mut commands: Commands,
-    mut damage_writer: MessageWriter<DamageEvent>,
-    mut camera_impulse_writer: MessageWriter<CameraImpulseEvent>,
+    mut hits: KnifeHitFeedback,
+    frame_data: Option<Res<FrameDataMap>>,
+    tuning: Option<Res<GameplayTuning>>,
     knife_query: Query<(
         Entity,
         &Transform,
@@ -2312,85 +1919,173 @@ pub fn knife_enemy_collision(
         Option<&KnifeSlashFeedback>,
         &crate::systems::collision::CollisionBox,
     )>,
-    mut enemy_query: Query<
-        (
-            Entity,
-            &Transform,
-            &mut EnemyState,
-            &mut Velocity,
-            &crate::systems::collision::CollisionBox,
-        ),
-        With<Enemy>,
-    >,
-    mut hit_stop: Option<ResMut<HitStopState>>,
+    mut attacker_query: Query<FrameAttackerItem, (With<Player>, Without<Enemy>)>,
+    mut enemy_query: Query<KnifeEnemyItem, With<Enemy>>,
 ) {
+    let default_tuning = GameplayTuning::default();
+    let physics = &tuning.as_deref().unwrap_or(&default_tuning).enemies.physics;
+
     for (slash_entity, slash_transform, slash, feedback, slash_box) in knife_query.iter() {
+        let slash_rect = crate::systems::hitboxes::collision_rect(slash_transform, slash_box);
         let mut hit_target = None;
 
-        for (enemy_entity, enemy_transform, mut enemy_state, mut enemy_velocity, enemy_box) in
-            enemy_query.iter_mut()
+        for (
+            enemy_entity,
+            enemy_transform,
+            mut enemy_state,
+            mut enemy_velocity,
+            enemy_box,
+            enemy_body,
+        ) in enemy_query.iter_mut()
         {
             if !enemy_state.is_alive {
                 continue;
             }
 
-            let dx = (slash_transform.translation.x - enemy_transform.translation.x).abs();
-            let dy = (slash_transform.translation.y - enemy_transform.translation.y).abs();
-            let collision_x = dx < (slash_box.size.x + enemy_box.size.x) / 2.0;
-            let collision_y = dy < (slash_box.size.y + enemy_box.size.y) / 2.0;
-
-            if collision_x && collision_y {
-                enemy_velocity.x = slash.knockback_x;
-                enemy_velocity.y = slash.knockback_y;
-                enemy_state.apply_hit_stun(0.12 + slash.hit_stop_secs * 3.0);
+            let enemy_rect = crate::systems::hitboxes::collision_rect(enemy_transform, enemy_box);
+            if rects_overlap(slash_rect, enemy_rect) {
+                knock_back_enemy(
+                    &mut enemy_state,
+                    &mut enemy_velocity,
+                    enemy_body,
+                    Vec2::new(slash.knockback_x, slash.knockback_y),
+                    slash.launch_y,
+                    slash.hit_stop_secs,
+                    physics,
+                );
                 hit_target = Some(enemy_entity);
                 break;
             }
         }
 
         if let Some(enemy_entity) = hit_target {
-            damage_writer.write(DamageEvent {
-                target: enemy_entity,
-                amount: slash.damage,
-                source: DamageSource::Knife,
-            });
+            hits.apply(
+                enemy_entity,
+                slash.damage,
+                slash.combo_step,
+                slash.combo_chain,
+                slash.hit_stop_secs,
+                feedback,
+            );
+            commands.entity(slash_entity).despawn();
+        }
+    }
 
-            let shake_intensity = feedback
-                .map(|feedback| feedback.camera_intensity)
-                .unwrap_or(2.2 + slash.combo_step as f32 * 0.8);
-            let shake_duration = feedback
-                .map(|feedback| feedback.camera_duration)
-                .unwrap_or(0.06);
-            camera_impulse_writer.write(CameraImpulseEvent {
-                intensity: shake_intensity,
-                duration: shake_duration,
-            });
+    let Some(frame_data) = frame_data.as_deref() else {
+        return;
+    };
+    for (attacker_entity, attacker_transform, animation, attack_state, mut attack) in
+        attacker_query.iter_mut()
+    {
+        if attack_state.trigger_serial != attack.trigger_serial || !attack_state.is_active() {
+            commands
+                .entity(attacker_entity)
+                .remove::<FrameDrivenAttack>();
+            continue;
+        }
 
-            if let Some(hit_stop) = hit_stop.as_deref_mut() {
-                let freeze_speed = feedback
-                    .map(|feedback| feedback.hit_stop_freeze_speed)
-                    .unwrap_or(0.12);
-                hit_stop.trigger(slash.hit_stop_secs, freeze_speed);
+        let boxes = active_attack_boxes(
+            frame_data,
+            attacker_transform,
+            animation,
+            attack_state,
+            &attack,
+        );
+        for (
+            enemy_entity,
+            enemy_transform,
+            mut enemy_state,
+            mut enemy_velocity,
+            enemy_box,
+            enemy_body,
+        ) in enemy_query.iter_mut()
+        {
+            if !enemy_state.is_alive || attack.hit_targets.contains(&enemy_entity) {
+                continue;
             }
 
-            commands.entity(slash_entity).despawn();
+            let enemy_rect = crate::systems::hitboxes::collision_rect(enemy_transform, enemy_box);
+            let Some((_, damage_scale, knockback_scale)) = boxes
+                .iter()
+                .find(|(rect, _, _)| rects_overlap(*rect, enemy_rect))
+            else {
+                continue;
+            };
+
+            knock_back_enemy(
+                &mut enemy_state,
+                &mut enemy_velocity,
+                enemy_body,
+                Vec2::new(
+                    attack.knockback_x * knockback_scale.0 * attack.facing,
+                    attack.knockback_y * knockback_scale.1,
+                ),
+                attack.launch_y * knockback_scale.1,
+                attack.hit_stop_secs,
+                physics,
+            );
+            attack.hit_targets.push(enemy_entity);
+            hits.apply(
+                enemy_entity,
+                attack.damage * damage_scale,
+                attack.combo_step,
+                attack.combo_chain,
+                attack.hit_stop_secs,
+                Some(&attack.feedback),
+            );
         }
     }
 }
 
-/// 投射物命中敌人后只发伤害事件，统一交给伤害结算系统处理。
+/// 命中时发出的伤害与状态异常事件
+#[derive(SystemParam)]
+pub struct HitWriters<'w> {
+    damage_writer: MessageWriter<'w, DamageEvent>,
+    status_writer: MessageWriter<'w, StatusEffectEvent>,
+}
+
+impl HitWriters<'_> {
+    fn status(&mut self, target: Entity, kind: StatusKind, stacks: u32) {
+        if stacks > 0 {
+            self.status_writer.write(StatusEffectEvent {
+                target,
+                kind,
+                stacks,
+            });
+        }
+    }
+}
+
+type PlayerProjectileHitQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        &'static ProjectileData,
+        &'static crate::systems::collision::CollisionBox,
+        Option<&'static ProjectileType>,
+    ),
+    With<Projectile>,
+>;
+
+/// 玩家投射物附带的状态异常：火球灼烧，Overedge 眩晕
+fn projectile_status(
+    projectile_type: ProjectileType,
+    tuning: &StatusEffectTuning,
+) -> Option<(StatusKind, u32)> {
+    match projectile_type {
+        ProjectileType::Fireball => Some((StatusKind::Burn, tuning.fireball_burn_stacks)),
+        ProjectileType::Overedge => Some((StatusKind::Stun, tuning.overedge_stun_stacks)),
+        ProjectileType::MagicWave => None,
+    }
+}
+
+/// 投射物命中敌人后只发伤害与状态异常事件，统一交给结算系统处理。
 pub fn projectile_enemy_collision(
     mut commands: Commands,
-    mut damage_writer: MessageWriter<DamageEvent>,
-    projectile_query: Query<
-        (
-            Entity,
-            &Transform,
-            &ProjectileData,
-            &crate::systems::collision::CollisionBox,
-        ),
-        With<Projectile>,
-    >,
+    mut writers: HitWriters,
+    projectile_query: PlayerProjectileHitQuery,
     enemy_query: Query<
         (
             Entity,
@@ -2400,9 +2095,18 @@ pub fn projectile_enemy_collision(
         ),
         With<Enemy>,
     >,
+    tuning: Option<Res<GameplayTuning>>,
 ) {
-    for (projectile_entity, projectile_transform, projectile_data, projectile_box) in
-        projectile_query.iter()
+    let default_tuning = GameplayTuning::default();
+    let status_tuning = &tuning.as_deref().unwrap_or(&default_tuning).status_effects;
+
+    for (
+        projectile_entity,
+        projectile_transform,
+        projectile_data,
+        projectile_box,
+        projectile_type,
+    ) in projectile_query.iter()
     {
         let mut hit_target = None;
 
@@ -2424,50 +2128,91 @@ pub fn projectile_enemy_collision(
         }
 
         if let Some(enemy_entity) = hit_target {
-            damage_writer.write(DamageEvent {
+            writers.damage_writer.write(DamageEvent {
                 target: enemy_entity,
                 amount: projectile_data.damage as f32,
                 source: DamageSource::Projectile,
             });
+            if let Some((kind, stacks)) = projectile_type
+                .and_then(|&projectile_type| projectile_status(projectile_type, status_tuning))
+            {
+                writers.status(enemy_entity, kind, stacks);
+            }
             commands.entity(projectile_entity).despawn();
         }
     }
 }
 
+type PlayerHurtboxItem<'a> = (
+    Entity,
+    &'a Transform,
+    &'a crate::systems::collision::CollisionBox,
+    Option<&'a SpriteAnimation>,
+    Option<&'a AttackAnimationState>,
+    Option<&'a FacingDirection>,
+);
+
+/// 玩家实体与其当前帧的受击框
+fn first_player_hurtboxes(
+    frame_data: Option<&FrameDataMap>,
+    player_query: &Query<PlayerHurtboxItem, With<Player>>,
+) -> Option<(Entity, Vec<Rect>)> {
+    let (player_entity, transform, collision_box, animation, attack_state, facing) =
+        player_query.iter().next()?;
+    Some((
+        player_entity,
+        player_hurtboxes(
+            frame_data,
+            transform,
+            collision_box,
+            animation,
+            attack_state,
+            facing,
+        ),
+    ))
+}
+
 /// 敌方投射物命中玩家。
 pub fn enemy_projectile_player_collision(
     mut commands: Commands,
-    mut damage_writer: MessageWriter<DamageEvent>,
+    mut writers: HitWriters,
+    frame_data: Option<Res<FrameDataMap>>,
     projectile_query: Query<(
         Entity,
         &Transform,
         &EnemyProjectile,
         &crate::systems::collision::CollisionBox,
     )>,
-    player_query: Query<
-        (Entity, &Transform, &crate::systems::collision::CollisionBox),
-        With<Player>,
-    >,
+    player_query: Query<PlayerHurtboxItem, With<Player>>,
+    tuning: Option<Res<GameplayTuning>>,
 ) {
-    let Some((player_entity, player_transform, player_box)) = player_query.iter().next() else {
+    let Some((player_entity, hurtboxes)) =
+        first_player_hurtboxes(frame_data.as_deref(), &player_query)
+    else {
         return;
     };
+    let default_tuning = GameplayTuning::default();
+    let status_tuning = &tuning.as_deref().unwrap_or(&default_tuning).status_effects;
 
     for (projectile_entity, projectile_transform, projectile_data, projectile_box) in
         projectile_query.iter()
     {
-        let dx = (projectile_transform.translation.x - player_transform.translation.x).abs();
-        let dy = (projectile_transform.translation.y - player_transform.translation.y).abs();
-
-        let collision_x = dx < (projectile_box.size.x + player_box.size.x) / 2.0;
-        let collision_y = dy < (projectile_box.size.y + player_box.size.y) / 2.0;
-
-        if collision_x && collision_y {
-            damage_writer.write(DamageEvent {
+        let projectile_rect =
+            crate::systems::hitboxes::collision_rect(projectile_transform, projectile_box);
+        if hurtboxes
+            .iter()
+            .any(|hurtbox| rects_overlap(*hurtbox, projectile_rect))
+        {
+            writers.damage_writer.write(DamageEvent {
                 target: player_entity,
                 amount: projectile_data.damage,
                 source: DamageSource::EnemyProjectile,
             });
+            writers.status(
+                player_entity,
+                StatusKind::Poison,
+                status_tuning.enemy_projectile_poison_stacks,
+            );
             commands.entity(projectile_entity).despawn();
         }
     }
@@ -2475,60 +2220,117 @@ pub fn enemy_projectile_player_collision(
 
 /// 玩家与敌人接触伤害（带冷却），伤害通过事件统一结算。
 pub fn player_enemy_collision(
-    player_query: Query<
-        (Entity, &Transform, &crate::systems::collision::CollisionBox),
-        With<Player>,
-    >,
+    frame_data: Option<Res<FrameDataMap>>,
+    player_query: Query<PlayerHurtboxItem, With<Player>>,
     enemy_query: Query<
         (
             &Transform,
             &EnemyState,
             &crate::systems::collision::CollisionBox,
+            Option<&EnemyType>,
         ),
         With<Enemy>,
     >,
+    tuning: Option<Res<GameplayTuning>>,
     mut last_damage_time: Local<f32>,
     time: Res<Time>,
-    mut damage_writer: MessageWriter<DamageEvent>,
+    mut writers: HitWriters,
 ) {
-    *last_damage_time += time.delta_secs();
+    let delta = time.delta_secs();
+    *last_damage_time += delta;
+    let default_tuning = GameplayTuning::default();
+    let tuning = tuning.as_deref().unwrap_or(&default_tuning);
+    let enemy_tuning = &tuning.enemies;
 
-    if let Some((player_entity, player_transform, player_box)) = player_query.iter().next() {
-        for (enemy_transform, enemy_state, enemy_box) in enemy_query.iter() {
+    if let Some((player_entity, hurtboxes)) =
+        first_player_hurtboxes(frame_data.as_deref(), &player_query)
+    {
+        let mut struck = false;
+        for (enemy_transform, enemy_state, enemy_box, enemy_type) in enemy_query.iter() {
             if !enemy_state.is_alive {
                 continue;
             }
 
-            let dx = (player_transform.translation.x - enemy_transform.translation.x).abs();
-            let dy = (player_transform.translation.y - enemy_transform.translation.y).abs();
-
-            let collision_x = dx < (player_box.size.x + enemy_box.size.x) / 2.0;
-            let collision_y = dy < (player_box.size.y + enemy_box.size.y) / 2.0;
+            let enemy_rect = crate::systems::hitboxes::collision_rect(enemy_transform, enemy_box);
+            let touching = hurtboxes
+                .iter()
+                .any(|hurtbox| rects_overlap(*hurtbox, enemy_rect));
+            if !touching {
+                continue;
+            }
 
-            if collision_x && collision_y && *last_damage_time >= PLAYER_CONTACT_DAMAGE_COOLDOWN {
-                damage_writer.write(DamageEvent {
-                    target: player_entity,
-                    amount: enemy_state.contact_damage,
-                    source: DamageSource::EnemyContact,
-                });
-                *last_damage_time = 0.0;
-                break;
+            let contact = enemy_type
+                .map(|&enemy_type| behavior_profile(enemy_type, enemy_tuning).contact)
+                .unwrap_or_default();
+            match contact {
+                // 吸取不受接触冷却限制，按帧时长持续结算
+                ContactEffect::Drain => {
+                    writers.damage_writer.write(DamageEvent {
+                        target: player_entity,
+                        amount: enemy_state.contact_damage * delta,
+                        source: DamageSource::MudDrain,
+                    });
+                    writers.status(
+                        player_entity,
+                        StatusKind::Slow,
+                        tuning.status_effects.black_mud_slow_stacks,
+                    );
+                }
+                ContactEffect::Strike => {
+                    if struck || *last_damage_time < PLAYER_CONTACT_DAMAGE_COOLDOWN {
+                        continue;
+                    }
+                    writers.damage_writer.write(DamageEvent {
+                        target: player_entity,
+                        amount: enemy_state.contact_damage,
+                        source: DamageSource::EnemyContact,
+                    });
+                    *last_damage_time = 0.0;
+                    struck = true;
+                }
             }
         }
     }
 }
 
+type DamagedPlayerItem<'a> = (
+    &'a mut Health,
+    Option<&'a mut DamageInvulnerability>,
+    Option<&'a PlayerDefense>,
+);
+
+/// 伤害结算的附带输出：镜头冲击、击杀通知与竞技场受伤记录
+#[derive(SystemParam)]
+pub struct DamageOutcomes<'w> {
+    camera_impulse_writer: MessageWriter<'w, CameraImpulseEvent>,
+    defeated_writer: MessageWriter<'w, EnemyDefeated>,
+    encounters: Option<ResMut<'w, SkyEncounterState>>,
+}
+
 /// 统一伤害结算管线，处理玩家和敌人的受击逻辑。
+///
+/// 玩家格挡时敌方伤害只按 `defense.guard_chip_ratio` 削血；竞技场进行中受到敌方伤害会
+/// 记入 [`SkyEncounterState::damaged_arenas`]。[`DamageSource::MudDrain`] 与
+/// [`DamageSource::StatusTick`] 是持续的小额伤害，不触发无敌帧、格挡与镜头冲击。敌人被击杀时发出 [`EnemyDefeated`]，
+/// 刀击杀带上当次连段长度供计分使用。
 pub fn apply_damage_events(
     mut damage_events: MessageReader<DamageEvent>,
-    mut player_query: Query<&mut Health, With<Player>>,
-    mut player_guard_query: Query<&mut DamageInvulnerability, With<Player>>,
-    mut enemy_query: Query<&mut EnemyState, With<Enemy>>,
+    mut player_query: Query<DamagedPlayerItem, With<Player>>,
+    mut enemy_query: Query<(&mut EnemyState, Option<&EnemyType>), With<Enemy>>,
+    tuning: Option<Res<GameplayTuning>>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut camera_impulse_writer: MessageWriter<CameraImpulseEvent>,
+    outcomes: DamageOutcomes,
 ) {
+    let DamageOutcomes {
+        mut camera_impulse_writer,
+        mut defeated_writer,
+        mut encounters,
+    } = outcomes;
+    let default_tuning = GameplayTuning::default();
+    let defense_tuning = &tuning.as_deref().unwrap_or(&default_tuning).defense;
+
     for event in damage_events.read() {
-        if let Ok(mut health) = player_query.get_mut(event.target) {
+        if let Ok((mut health, invulnerability, defense)) = player_query.get_mut(event.target) {
             if health.is_dead() {
                 continue;
             }
@@ -2537,19 +2339,39 @@ pub fn apply_damage_events(
                 event.source,
                 DamageSource::EnemyContact | DamageSource::EnemyProjectile
             );
+            // 黑泥吸取与状态跳伤绕过无敌帧与格挡，但仍算作竞技场受伤
+            let is_hit = is_hostile_damage
+                || matches!(
+                    event.source,
+                    DamageSource::MudDrain | DamageSource::StatusTick
+                );
 
-            if is_hostile_damage && let Ok(mut guard) = player_guard_query.get_mut(event.target) {
+            if is_hostile_damage && let Some(mut guard) = invulnerability {
                 if guard.is_active() {
                     continue;
                 }
                 guard.trigger(0.45);
             }
 
-            health.take_damage(event.amount);
+            let guarded = is_hostile_damage && defense.is_some_and(PlayerDefense::is_guarding);
+            let amount = if guarded {
+                event.amount * defense_tuning.guard_chip_ratio.clamp(0.0, 1.0)
+            } else {
+                event.amount
+            };
+            health.take_damage(amount);
+
+            if is_hit
+                && amount > 0.0
+                && let Some(encounters) = encounters.as_deref_mut()
+                && let Some(arena) = encounters.active_arena
+            {
+                encounters.damaged_arenas.insert(arena);
+            }
 
             if is_hostile_damage {
                 camera_impulse_writer.write(CameraImpulseEvent {
-                    intensity: 3.0,
+                    intensity: if guarded { 1.5 } else { 3.0 },
                     duration: 0.08,
                 });
             }
@@ -2565,12 +2387,25 @@ pub fn apply_damage_events(
             continue;
         }
 
-        if let Ok(mut enemy_state) = enemy_query.get_mut(event.target) {
+        if let Ok((mut enemy_state, enemy_type)) = enemy_query.get_mut(event.target) {
             if !enemy_state.is_alive {
                 continue;
             }
 
             enemy_state.take_damage(event.amount.ceil() as i32);
+            if !enemy_state.is_alive
+                && let Some(&enemy_type) = enemy_type
+            {
+                let combo_chain = match event.source {
+                    DamageSource::Knife { combo_chain } => combo_chain,
+                    _ => 0,
+                };
+                defeated_writer.write(EnemyDefeated {
+                    enemy: event.target,
+                    enemy_type,
+                    combo_chain,
+                });
+            }
         }
     }
 }
@@ -4,11 +4,11 @@ use bevy::prelude::*;
 
 use crate::{
     components::{
-        DamageInvulnerability, FacingDirection, Health, LedgeTraversal, Player, PlayerState,
-        ShroudState, Velocity,
+        DamageInvulnerability, FacingDirection, Health, LedgeTraversal, Player, PlayerDefense,
+        PlayerState, ShroudState, Velocity,
     },
     events::{DamageEvent, DamageSource},
-    resources::{GameAssets, GameConfig, GameStats},
+    resources::{GameAssets, GameConfig, GameStats, GameplayTuning},
     states::GameState,
 };
 
@@ -23,6 +23,7 @@ type RevivePlayerItem<'a> = (
     Option<&'a mut FacingDirection>,
     Option<&'a mut DamageInvulnerability>,
     Option<&'a mut LedgeTraversal>,
+    Option<&'a mut PlayerDefense>,
 );
 
 #[derive(Component)]
@@ -55,7 +56,12 @@ pub fn setup_game_over_ui(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     game_assets: Option<Res<GameAssets>>,
+    game_stats: Res<GameStats>,
+    tuning: Option<Res<GameplayTuning>>,
 ) {
+    let default_tuning = GameplayTuning::default();
+    let scoring = &tuning.as_deref().unwrap_or(&default_tuning).scoring;
+
     let font = game_assets
         .as_ref()
         .map(|assets| assets.font.clone())
@@ -86,6 +92,19 @@ pub fn setup_game_over_ui(
                 },
                 TextColor(Color::srgb(0.95, 0.2, 0.2)),
             ));
+            parent.spawn((
+                Text::new(crate::systems::scoring::run_result_text(
+                    &game_stats,
+                    scoring,
+                )),
+                TextFont {
+                    font: font.clone().into(),
+                    font_size: FontSize::Px(26.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.82, 0.42)),
+                TextLayout::justify(Justify::Center),
+            ));
             parent.spawn((
                 Text::new("Press R to Revive"),
                 TextFont {
@@ -135,6 +154,7 @@ pub fn revive_player(
         mut facing,
         mut invulnerability,
         mut traversal,
+        mut defense,
     )) = player_query.iter_mut().next()
     {
         let is_sky_level = sky_level.as_deref().is_some_and(|level| level.active);
@@ -164,10 +184,14 @@ pub fn revive_player(
         if let Some(traversal) = traversal.as_deref_mut() {
             traversal.reset();
         }
+        if let Some(defense) = defense.as_deref_mut() {
+            defense.reset();
+        }
 
         game_stats.distance_traveled = 0.0;
         game_stats.jump_count = 0;
         game_stats.play_time = 0.0;
+        game_stats.combat = Default::default();
 
         NextState::set_if_neq(&mut next_state, GameState::Playing);
     }
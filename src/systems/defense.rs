@@ -0,0 +1,311 @@
+//! 玩家防御动作
+//!
+//! - 格挡（按住 C）：站地且未出招时生效，敌方伤害按 `guard_chip_ratio` 削血，结算见 `apply_damage_events`
+//! - 弹反（F）：短窗口内碰到的敌方飞弹被反弹成玩家投射物，近身敌人进入硬直
+//! - 闪避（E）：沿输入方向冲刺，前段带无敌帧
+//!
+//! 数值来自 `config/gameplay_tuning.ron` 的 `defense` 段。
+
+use bevy::prelude::*;
+
+use crate::{
+    components::*,
+    events::CameraImpulseEvent,
+    resources::GameplayTuning,
+    systems::{
+        collision::CollisionBox,
+        combat::EnemyProjectile,
+        hitboxes::{collision_rect, player_hurtboxes, rects_overlap},
+        input::GameInput,
+        sprite_animation::SpriteAnimation,
+    },
+};
+
+const REFLECTED_PROJECTILE_COLOR: Color = Color::srgba(0.55, 0.9, 1.0, 0.95);
+
+type DefenseInputItem<'a> = (
+    Entity,
+    &'a mut PlayerDefense,
+    &'a mut DamageInvulnerability,
+    &'a PlayerState,
+    &'a AttackAnimationState,
+    &'a FacingDirection,
+    Option<&'a LedgeTraversal>,
+);
+
+type ParryPlayerItem<'a> = (
+    &'a Transform,
+    &'a CollisionBox,
+    &'a PlayerDefense,
+    &'a mut DamageInvulnerability,
+    Option<&'a SpriteAnimation>,
+    Option<&'a AttackAnimationState>,
+    Option<&'a FacingDirection>,
+);
+
+type ParryProjectileItem<'a> = (
+    Entity,
+    &'a Transform,
+    &'a mut Velocity,
+    &'a EnemyProjectile,
+    &'a CollisionBox,
+    Option<&'a mut Sprite>,
+);
+
+/// 推进防御计时，并根据输入开始闪避/弹反、更新格挡状态。
+///
+/// 出招和抓边期间不能开始防御动作；闪避优先于同帧的弹反。
+pub fn update_player_defense(
+    mut commands: Commands,
+    game_input: Res<GameInput>,
+    tuning: Option<Res<GameplayTuning>>,
+    time: Res<Time>,
+    mut player_query: Query<DefenseInputItem, With<Player>>,
+) {
+    let default_tuning = GameplayTuning::default();
+    let tuning = &tuning.as_deref().unwrap_or(&default_tuning).defense;
+
+    for (entity, mut defense, mut invulnerability, player_state, attack_state, facing, traversal) in
+        player_query.iter_mut()
+    {
+        defense.tick(time.delta_secs());
+
+        let busy = attack_state.is_active() || traversal.is_some_and(LedgeTraversal::is_active);
+        if busy {
+            defense.guarding = false;
+            continue;
+        }
+
+        if game_input.dodge_pressed_this_frame
+            && defense.dodge_cooldown <= 0.0
+            && !defense.is_dodging()
+        {
+            let direction = FacingDirection::from_horizontal_input(
+                game_input.move_left,
+                game_input.move_right,
+                *facing,
+            )
+            .sign();
+            defense.dodge_remaining = tuning.dodge_duration_secs;
+            defense.dodge_cooldown = tuning.dodge_cooldown_secs;
+            defense.parry_remaining = 0.0;
+            invulnerability.trigger(tuning.dodge_iframe_secs);
+            commands.entity(entity).insert(AttackMomentum::new(
+                direction,
+                tuning.dodge_speed,
+                tuning.dodge_duration_secs,
+                0.0,
+                0.0,
+                0.0,
+            ));
+        } else if game_input.parry_pressed_this_frame
+            && defense.parry_cooldown <= 0.0
+            && !defense.is_dodging()
+        {
+            defense.parry_remaining = tuning.parry_window_secs;
+            defense.parry_cooldown = tuning.parry_cooldown_secs;
+        }
+
+        defense.guarding = game_input.guard && player_state.is_grounded;
+    }
+}
+
+/// 弹反窗口内：反弹接触到受击框的敌方飞弹，并让重叠的敌人进入硬直。
+///
+/// 需要在敌方飞弹/接触伤害判定之前运行；成功时给玩家短暂无敌，
+/// 同一帧的接触伤害会被 `apply_damage_events` 忽略。
+pub fn resolve_parries(
+    mut commands: Commands,
+    tuning: Option<Res<GameplayTuning>>,
+    frame_data: Option<Res<FrameDataMap>>,
+    mut player_query: Query<ParryPlayerItem, With<Player>>,
+    mut projectile_query: Query<ParryProjectileItem>,
+    mut enemy_query: Query<(&Transform, &mut EnemyState, &CollisionBox), With<Enemy>>,
+    mut camera_impulse_writer: MessageWriter<CameraImpulseEvent>,
+) {
+    let default_tuning = GameplayTuning::default();
+    let tuning = &tuning.as_deref().unwrap_or(&default_tuning).defense;
+
+    let Some((
+        transform,
+        collision_box,
+        defense,
+        mut invulnerability,
+        animation,
+        attack_state,
+        facing,
+    )) = player_query.iter_mut().next()
+    else {
+        return;
+    };
+    if !defense.is_parrying() {
+        return;
+    }
+
+    let hurtboxes = player_hurtboxes(
+        frame_data.as_deref(),
+        transform,
+        collision_box,
+        animation,
+        attack_state,
+        facing,
+    );
+    let touches = |rect: Rect| {
+        hurtboxes
+            .iter()
+            .any(|hurtbox| rects_overlap(*hurtbox, rect))
+    };
+    let mut parried = false;
+
+    for (entity, projectile_transform, mut velocity, projectile, projectile_box, sprite) in
+        projectile_query.iter_mut()
+    {
+        if !touches(collision_rect(projectile_transform, projectile_box)) {
+            continue;
+        }
+
+        let speed = Vec2::new(velocity.x, velocity.y).length() * tuning.parry_reflect_speed_scale;
+        velocity.x = -velocity.x * tuning.parry_reflect_speed_scale;
+        velocity.y = -velocity.y * tuning.parry_reflect_speed_scale;
+        if let Some(mut sprite) = sprite {
+            sprite.color = REFLECTED_PROJECTILE_COLOR;
+        }
+        commands.entity(entity).remove::<EnemyProjectile>().insert((
+            Projectile,
+            ProjectileData {
+                elapsed: projectile.elapsed,
+                ..ProjectileData::new(projectile.damage.ceil() as i32, speed, projectile.lifetime)
+            },
+        ));
+        parried = true;
+    }
+
+    for (enemy_transform, mut enemy_state, enemy_box) in enemy_query.iter_mut() {
+        if enemy_state.is_alive && touches(collision_rect(enemy_transform, enemy_box)) {
+            enemy_state.apply_hit_stun(tuning.parry_stun_secs);
+            parried = true;
+        }
+    }
+
+    if parried {
+        invulnerability.trigger(tuning.parry_invulnerability_secs);
+        camera_impulse_writer.write(CameraImpulseEvent {
+            intensity: 2.4,
+            duration: 0.06,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn defense_test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<GameInput>()
+            .add_message::<CameraImpulseEvent>()
+            .add_systems(Update, (update_player_defense, resolve_parries).chain());
+        app
+    }
+
+    fn spawn_defending_player(app: &mut App) -> Entity {
+        app.world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                CollisionBox::new(Vec2::new(40.0, 60.0)),
+                PlayerDefense::default(),
+                DamageInvulnerability::default(),
+                PlayerState::default(),
+                AttackAnimationState::default(),
+                FacingDirection::Right,
+            ))
+            .id()
+    }
+
+    #[test]
+    fn parry_reflects_enemy_projectiles_and_staggers_enemies() {
+        let mut app = defense_test_app();
+        let player = spawn_defending_player(&mut app);
+        let projectile = app
+            .world_mut()
+            .spawn((
+                EnemyProjectile::new(8.0, 2.0),
+                Transform::from_xyz(10.0, 0.0, 0.0),
+                Velocity { x: -200.0, y: 0.0 },
+                CollisionBox::new(Vec2::splat(16.0)),
+            ))
+            .id();
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                EnemyState::default(),
+                Transform::from_xyz(-20.0, 0.0, 0.0),
+                CollisionBox::new(Vec2::splat(30.0)),
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<GameInput>()
+            .parry_pressed_this_frame = true;
+        app.update();
+
+        let projectile = app.world().entity(projectile);
+        assert!(projectile.get::<EnemyProjectile>().is_none());
+        assert!(projectile.contains::<Projectile>());
+        assert_eq!(
+            projectile.get::<ProjectileData>().map(|data| data.damage),
+            Some(8)
+        );
+        assert!(projectile.get::<Velocity>().unwrap().x > 200.0);
+
+        let enemy_state = app.world().entity(enemy).get::<EnemyState>().unwrap();
+        assert!(enemy_state.hit_stun_timer > 0.0);
+        assert!(
+            app.world()
+                .entity(player)
+                .get::<DamageInvulnerability>()
+                .unwrap()
+                .is_active()
+        );
+    }
+
+    #[test]
+    fn dodge_grants_iframes_and_respects_cooldown() {
+        let mut app = defense_test_app();
+        let player = spawn_defending_player(&mut app);
+        let tuning = GameplayTuning::default().defense;
+
+        {
+            let mut input = app.world_mut().resource_mut::<GameInput>();
+            input.dodge_pressed_this_frame = true;
+            input.move_left = true;
+        }
+        app.update();
+
+        let entity = app.world().entity(player);
+        let defense = *entity.get::<PlayerDefense>().unwrap();
+        assert!(defense.is_dodging());
+        assert!(entity.get::<DamageInvulnerability>().unwrap().is_active());
+        let momentum = entity.get::<AttackMomentum>().unwrap();
+        assert_eq!(momentum.direction, -1.0);
+        assert_eq!(momentum.min_horizontal_speed, tuning.dodge_speed);
+
+        app.world_mut()
+            .entity_mut(player)
+            .get_mut::<PlayerDefense>()
+            .unwrap()
+            .dodge_remaining = 0.0;
+        app.update();
+        assert!(
+            !app.world()
+                .entity(player)
+                .get::<PlayerDefense>()
+                .unwrap()
+                .is_dodging(),
+            "dodge should not restart while on cooldown"
+        );
+    }
+}
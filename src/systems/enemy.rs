@@ -3,6 +3,7 @@
 use crate::asset_paths;
 use crate::components::*;
 use crate::resources::{EnemyArchetypeTuning, EnemyDirectorTuning, GameConfig, GameplayTuning};
+use crate::systems::enemy_behavior::{behavior_profile, node_duration};
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
@@ -15,30 +16,22 @@ const FAMILIAR_RENDER_SIZE: Vec2 = Vec2::new(72.0, 40.0);
 const FAMILIAR_COLLISION_SIZE: Vec2 = Vec2::new(48.0, 28.0);
 const HEROIC_SPIRIT_RENDER_SIZE: Vec2 = Vec2::new(52.0, 110.0);
 const HEROIC_SPIRIT_COLLISION_SIZE: Vec2 = Vec2::new(40.0, 78.0);
-const ENEMY_RETIRE_BEHIND_DISTANCE: f32 = 96.0;
+const SHADOW_RENDER_SIZE: Vec2 = Vec2::new(46.0, 92.0);
+const SHADOW_COLLISION_SIZE: Vec2 = Vec2::new(34.0, 70.0);
+const BLACK_MUD_RENDER_SIZE: Vec2 = Vec2::new(128.0, 30.0);
+const BLACK_MUD_COLLISION_SIZE: Vec2 = Vec2::new(116.0, 18.0);
 
-type EnemyPatrolQuery<'w, 's> = Query<
+type EnemyTelegraphQuery<'w, 's> = Query<
     'w,
     's,
     (
         &'static EnemyType,
+        &'static EnemyState,
+        &'static EnemyBrain,
+        &'static mut Sprite,
         &'static mut Transform,
-        &'static mut EnemyState,
-        &'static mut Velocity,
-        Option<&'static crate::components::SkyEncounterEnemy>,
     ),
-    With<Enemy>,
->;
-type EnemyRangedQuery<'w, 's> = Query<
-    'w,
-    's,
-    (
-        &'static EnemyType,
-        &'static Transform,
-        &'static mut EnemyState,
-        Option<&'static crate::components::SkyEncounterEnemy>,
-    ),
-    With<Enemy>,
+    (With<Enemy>, Without<Boss>),
 >;
 
 #[derive(Clone, Copy)]
@@ -56,25 +49,39 @@ fn enemy_base_color(enemy_type: EnemyType) -> Color {
         EnemyType::Slime => Color::srgba(0.45, 0.95, 0.58, 0.96),
         EnemyType::Familiar => Color::srgba(0.62, 0.64, 0.97, 0.92),
         EnemyType::EnemyHeroicSpirit => Color::srgba(0.38, 0.42, 0.52, 0.96),
+        EnemyType::Shadow => Color::srgba(0.10, 0.08, 0.14, 0.78),
+        EnemyType::BlackMud => Color::srgba(0.12, 0.06, 0.10, 0.94),
     }
 }
 
 fn enemy_type_for_roll(roll: f32, tuning: &EnemyDirectorTuning) -> EnemyType {
-    let slime = tuning.spawn_weights.slime.max(0.0);
-    let familiar = tuning.spawn_weights.familiar.max(0.0);
-    let heroic = tuning.spawn_weights.heroic_spirit.max(0.0);
-    let sum = (slime + familiar + heroic).max(f32::EPSILON);
-
-    let slime_threshold = slime / sum;
-    let familiar_threshold = slime_threshold + familiar / sum;
-
-    if roll < slime_threshold {
-        EnemyType::Slime
-    } else if roll < familiar_threshold {
-        EnemyType::Familiar
-    } else {
-        EnemyType::EnemyHeroicSpirit
+    let weights = &tuning.spawn_weights;
+    let table = [
+        (EnemyType::Slime, weights.slime),
+        (EnemyType::Familiar, weights.familiar),
+        (EnemyType::EnemyHeroicSpirit, weights.heroic_spirit),
+        (EnemyType::Shadow, weights.shadow),
+        (EnemyType::BlackMud, weights.black_mud),
+    ];
+    let sum = table
+        .iter()
+        .map(|(_, weight)| weight.max(0.0))
+        .sum::<f32>()
+        .max(f32::EPSILON);
+
+    let mut threshold = 0.0;
+    for (enemy_type, weight) in table {
+        threshold += weight.max(0.0) / sum;
+        if roll < threshold {
+            return enemy_type;
+        }
     }
+    table
+        .iter()
+        .rev()
+        .find(|(_, weight)| *weight > 0.0)
+        .map(|(enemy_type, _)| *enemy_type)
+        .unwrap_or(EnemyType::Slime)
 }
 
 fn enemy_archetype(enemy_type: EnemyType, tuning: &EnemyDirectorTuning) -> EnemyArchetype {
@@ -82,6 +89,8 @@ fn enemy_archetype(enemy_type: EnemyType, tuning: &EnemyDirectorTuning) -> Enemy
         EnemyType::Slime => &tuning.slime,
         EnemyType::Familiar => &tuning.familiar,
         EnemyType::EnemyHeroicSpirit => &tuning.heroic_spirit,
+        EnemyType::Shadow => &tuning.shadow,
+        EnemyType::BlackMud => &tuning.black_mud,
     };
 
     EnemyArchetype {
@@ -94,19 +103,6 @@ fn enemy_archetype(enemy_type: EnemyType, tuning: &EnemyDirectorTuning) -> Enemy
     }
 }
 
-fn enemy_has_passed_player(enemy_x: f32, player_x: f32) -> bool {
-    enemy_x < player_x - ENEMY_RETIRE_BEHIND_DISTANCE
-}
-
-fn retire_passed_enemy_state(state: &mut EnemyState) {
-    state.move_direction = -1.0;
-    state.pending_ranged_shot = false;
-    state.ranged_windup_timer = 0.0;
-    state.ranged_shot_direction = Vec2::ZERO;
-    state.dash_charge_timer = 0.0;
-    state.dash_active_timer = 0.0;
-}
-
 fn lerp_color(base: Color, target: Color, t: f32, alpha: f32) -> Color {
     let base = base.to_srgba();
     let target = target.to_srgba();
@@ -141,6 +137,8 @@ fn spawn_slime(
             Enemy,
             EnemyType::Slime,
             enemy_state,
+            EnemyBrain::default(),
+            EnemyBody::default(),
             Velocity { x: 0.0, y: 0.0 },
             crate::systems::collision::CollisionBox::new(SLIME_COLLISION_SIZE),
         ))
@@ -202,6 +200,8 @@ fn spawn_familiar(
             Enemy,
             EnemyType::Familiar,
             enemy_state,
+            EnemyBrain::default(),
+            EnemyBody::default(),
             Velocity { x: 0.0, y: 0.0 },
             crate::systems::collision::CollisionBox::new(FAMILIAR_COLLISION_SIZE),
         ))
@@ -259,6 +259,8 @@ fn spawn_enemy_heroic_spirit(
             Enemy,
             EnemyType::EnemyHeroicSpirit,
             enemy_state,
+            EnemyBrain::default(),
+            EnemyBody::default(),
             Velocity { x: 0.0, y: 0.0 },
             crate::systems::collision::CollisionBox::new(HEROIC_SPIRIT_COLLISION_SIZE),
         ))
@@ -283,6 +285,94 @@ fn spawn_enemy_heroic_spirit(
         .id()
 }
 
+fn spawn_shadow(
+    commands: &mut Commands,
+    spawn_x: f32,
+    spawn_y: f32,
+    enemy_state: EnemyState,
+) -> Entity {
+    commands
+        .spawn((
+            Sprite {
+                color: enemy_base_color(EnemyType::Shadow),
+                custom_size: Some(SHADOW_RENDER_SIZE),
+                ..default()
+            },
+            Transform::from_xyz(spawn_x, spawn_y, 1.3),
+            Enemy,
+            EnemyType::Shadow,
+            enemy_state,
+            EnemyBrain::default(),
+            EnemyBody::default(),
+            Velocity { x: 0.0, y: 0.0 },
+            crate::systems::collision::CollisionBox::new(SHADOW_COLLISION_SIZE),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Sprite {
+                    color: Color::srgba(0.95, 0.22, 0.30, 0.95),
+                    custom_size: Some(Vec2::new(5.0, 3.0)),
+                    ..default()
+                },
+                Transform::from_xyz(-6.0, 30.0, 0.2),
+            ));
+            parent.spawn((
+                Sprite {
+                    color: Color::srgba(0.95, 0.22, 0.30, 0.95),
+                    custom_size: Some(Vec2::new(5.0, 3.0)),
+                    ..default()
+                },
+                Transform::from_xyz(6.0, 30.0, 0.2),
+            ));
+            parent.spawn((
+                Sprite {
+                    color: Color::srgba(0.06, 0.04, 0.10, 0.6),
+                    custom_size: Some(Vec2::new(14.0, 60.0)),
+                    ..default()
+                },
+                Transform::from_xyz(-24.0, -6.0, -0.1).with_rotation(Quat::from_rotation_z(-0.35)),
+            ));
+        })
+        .id()
+}
+
+fn spawn_black_mud(
+    commands: &mut Commands,
+    spawn_x: f32,
+    spawn_y: f32,
+    enemy_state: EnemyState,
+) -> Entity {
+    commands
+        .spawn((
+            Sprite {
+                color: enemy_base_color(EnemyType::BlackMud),
+                custom_size: Some(BLACK_MUD_RENDER_SIZE),
+                ..default()
+            },
+            Transform::from_xyz(spawn_x, spawn_y, 0.9),
+            Enemy,
+            EnemyType::BlackMud,
+            enemy_state,
+            EnemyBrain::default(),
+            EnemyBody::default(),
+            Velocity { x: 0.0, y: 0.0 },
+            crate::systems::collision::CollisionBox::new(BLACK_MUD_COLLISION_SIZE),
+        ))
+        .with_children(|parent| {
+            for (x, y, size) in [(-34.0, 9.0, 12.0), (6.0, 12.0, 9.0), (38.0, 8.0, 14.0)] {
+                parent.spawn((
+                    Sprite {
+                        color: Color::srgba(0.42, 0.08, 0.18, 0.85),
+                        custom_size: Some(Vec2::splat(size)),
+                        ..default()
+                    },
+                    Transform::from_xyz(x, y, 0.2),
+                ));
+            }
+        })
+        .id()
+}
+
 /// Spawns an enemy from an authored LDtk placement instead of the endless runner director.
 pub fn spawn_authored_enemy(
     commands: &mut Commands,
@@ -297,6 +387,8 @@ pub fn spawn_authored_enemy(
         crate::components::SkyEnemyKind::Slime => EnemyType::Slime,
         crate::components::SkyEnemyKind::Familiar => EnemyType::Familiar,
         crate::components::SkyEnemyKind::HeroicSpirit => EnemyType::EnemyHeroicSpirit,
+        crate::components::SkyEnemyKind::Shadow => EnemyType::Shadow,
+        crate::components::SkyEnemyKind::BlackMud => EnemyType::BlackMud,
     };
     let archetype = enemy_archetype(enemy_type, &tuning.enemies);
     let health = ((archetype.health as f32 * health_multiplier.max(0.25)).round() as i32).max(1);
@@ -318,6 +410,8 @@ pub fn spawn_authored_enemy(
         EnemyType::EnemyHeroicSpirit => {
             spawn_enemy_heroic_spirit(commands, position.x, position.y, enemy_state)
         }
+        EnemyType::Shadow => spawn_shadow(commands, position.x, position.y, enemy_state),
+        EnemyType::BlackMud => spawn_black_mud(commands, position.x, position.y, enemy_state),
     }
 }
 
@@ -408,6 +502,14 @@ pub fn spawn_enemies(mut commands: Commands, mut params: EnemySpawnParams) {
             spawn_enemy_heroic_spirit(&mut commands, spawn_x, spawn_y, enemy_state);
             crate::debug_log!("🔴 生成敌方英灵 at x={:.1}", spawn_x);
         }
+        EnemyType::Shadow => {
+            spawn_shadow(&mut commands, spawn_x, spawn_y, enemy_state);
+            crate::debug_log!("⚫ 生成影 at x={:.1}", spawn_x);
+        }
+        EnemyType::BlackMud => {
+            spawn_black_mud(&mut commands, spawn_x, spawn_y, enemy_state);
+            crate::debug_log!("🟤 生成黑泥 at x={:.1}", spawn_x);
+        }
     }
 
     let min_spawn_interval = enemy_tuning.spawn_interval_min_secs.max(0.15);
@@ -417,310 +519,9 @@ pub fn spawn_enemies(mut commands: Commands, mut params: EnemySpawnParams) {
     *params.spawn_cooldown = rng.random_range(min_spawn_interval..max_spawn_interval);
 }
 
-/// 敌人 AI - 巡逻与战斗行为。
-pub fn enemy_patrol_ai(
-    mut enemy_query: EnemyPatrolQuery,
-    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    tuning: Option<Res<GameplayTuning>>,
-    time: Res<Time>,
-) {
-    let default_tuning = GameplayTuning::default();
-    let enemy_tuning = &tuning.as_deref().unwrap_or(&default_tuning).enemies;
-    let slime_behavior = &enemy_tuning.slime_behavior;
-    let heroic_behavior = &enemy_tuning.heroic_spirit_behavior;
-
-    let player_x = player_query
-        .iter()
-        .next()
-        .map(|transform| transform.translation.x)
-        .unwrap_or_default();
-    let elapsed = time.elapsed_secs();
-    let delta = time.delta_secs();
-
-    for (enemy_type, mut transform, mut state, mut velocity, authored) in enemy_query.iter_mut() {
-        if !state.is_alive {
-            velocity.x = 0.0;
-            velocity.y = 0.0;
-            continue;
-        }
-
-        let was_charging = state.dash_charge_timer > 0.0;
-        state.tick_timers(delta);
-        let authored_bounds = authored.map(|_| state.patrol_world_bounds());
-
-        if was_charging && state.dash_charge_timer <= f32::EPSILON {
-            state.dash_active_timer = heroic_behavior.dash_active_secs.max(0.08);
-            velocity.x = state.base_speed
-                * heroic_behavior.dash_speed_multiplier.max(1.2)
-                * state.dash_direction;
-            state.attack_cooldown = heroic_behavior.dash_cooldown_secs.max(0.6);
-        }
-
-        if state.hit_stun_timer > 0.0 {
-            transform.translation.x += velocity.x * delta;
-            transform.translation.y += velocity.y * delta;
-            if let Some((left, right)) = authored_bounds {
-                transform.translation.x = transform.translation.x.clamp(left, right);
-            }
-            velocity.x *= 0.84;
-            velocity.y *= 0.80;
-            continue;
-        }
-
-        match enemy_type {
-            EnemyType::Slime => {
-                if authored.is_none() && enemy_has_passed_player(transform.translation.x, player_x)
-                {
-                    retire_passed_enemy_state(&mut state);
-                    velocity.x = -state.base_speed.max(20.0);
-                    velocity.y = 0.0;
-                    transform.translation.x += velocity.x * delta;
-                    transform.translation.y =
-                        GameConfig::GROUND_LEVEL + enemy_tuning.slime.spawn_y_offset;
-                    continue;
-                }
-
-                let (patrol_left, patrol_right) = state.patrol_world_bounds();
-
-                if state.move_direction > 0.0 && transform.translation.x > patrol_right {
-                    state.move_direction = -1.0;
-                } else if state.move_direction < 0.0 && transform.translation.x < patrol_left {
-                    state.move_direction = 1.0;
-                }
-
-                let player_delta = player_x - transform.translation.x;
-                if player_delta.abs() < slime_behavior.engage_distance.max(1.0)
-                    && state.attack_cooldown <= 0.0
-                {
-                    state.move_direction = player_delta.signum();
-                    velocity.x = state.base_speed
-                        * slime_behavior.burst_speed_multiplier
-                        * state.move_direction;
-                    state.attack_cooldown = slime_behavior.burst_cooldown_secs.max(0.1);
-                } else {
-                    velocity.x = state.base_speed * state.move_direction;
-                }
-
-                transform.translation.x += velocity.x * delta;
-                if let Some((left, right)) = authored_bounds {
-                    transform.translation.x = transform.translation.x.clamp(left, right);
-                }
-                transform.translation.y = authored
-                    .map(|enemy| enemy.anchor_y)
-                    .unwrap_or(GameConfig::GROUND_LEVEL + enemy_tuning.slime.spawn_y_offset);
-            }
-            EnemyType::Familiar => {
-                if authored.is_none() && enemy_has_passed_player(transform.translation.x, player_x)
-                {
-                    retire_passed_enemy_state(&mut state);
-                    velocity.x = -state.base_speed.max(20.0);
-                    let target_y = authored
-                        .map(|enemy| enemy.anchor_y)
-                        .unwrap_or(GameConfig::GROUND_LEVEL + enemy_tuning.familiar.spawn_y_offset)
-                        + (elapsed * 2.7 + state.hover_phase).sin() * 22.0;
-                    velocity.y = ((target_y - transform.translation.y) * 3.2).clamp(-110.0, 110.0);
-                    transform.translation.x += velocity.x * delta;
-                    transform.translation.y += velocity.y * delta;
-                    continue;
-                }
-
-                let (patrol_left, patrol_right) = state.patrol_world_bounds();
-                let desired_x = (player_x + 140.0).clamp(patrol_left, patrol_right);
-                let x_delta = desired_x - transform.translation.x;
-
-                if x_delta.abs() > 8.0 {
-                    state.move_direction = x_delta.signum();
-                }
-
-                let speed_factor = if x_delta.abs() > 120.0 { 1.2 } else { 0.8 };
-                velocity.x = state.base_speed * speed_factor * state.move_direction;
-                if state.pending_ranged_shot {
-                    velocity.x *= 0.22;
-                }
-
-                let target_y = authored
-                    .map(|enemy| enemy.anchor_y)
-                    .unwrap_or(GameConfig::GROUND_LEVEL + enemy_tuning.familiar.spawn_y_offset)
-                    + (elapsed * 2.7 + state.hover_phase).sin() * 30.0;
-                velocity.y = ((target_y - transform.translation.y) * 4.2).clamp(-140.0, 140.0);
-                if state.pending_ranged_shot {
-                    velocity.y *= 0.55;
-                }
-
-                transform.translation.x += velocity.x * delta;
-                transform.translation.y += velocity.y * delta;
-                if let Some((left, right)) = authored_bounds {
-                    transform.translation.x = transform.translation.x.clamp(left, right);
-                }
-            }
-            EnemyType::EnemyHeroicSpirit => {
-                let target_x = authored_bounds
-                    .map(|(left, right)| player_x.clamp(left, right))
-                    .unwrap_or(player_x);
-                let player_delta = target_x - transform.translation.x;
-                let base_y = authored.map(|enemy| enemy.anchor_y).unwrap_or(
-                    GameConfig::GROUND_LEVEL + enemy_tuning.heroic_spirit.spawn_y_offset,
-                ) + (elapsed * 8.0 + state.hover_phase).sin() * 3.0;
-
-                if authored.is_none() && enemy_has_passed_player(transform.translation.x, player_x)
-                {
-                    retire_passed_enemy_state(&mut state);
-                    velocity.x = -state.base_speed.max(20.0);
-                    velocity.y = 0.0;
-                    transform.translation.x += velocity.x * delta;
-                    transform.translation.y = base_y;
-                    continue;
-                }
-
-                if state.dash_charge_timer > 0.0 {
-                    velocity.x = 0.0;
-                    transform.translation.y = base_y + (elapsed * 45.0).sin() * 2.5;
-                    continue;
-                }
-
-                if state.dash_active_timer > 0.0 {
-                    transform.translation.x += velocity.x * delta;
-                    if let Some((left, right)) = authored_bounds {
-                        transform.translation.x = transform.translation.x.clamp(left, right);
-                    }
-                    transform.translation.y = base_y;
-                    continue;
-                }
-
-                if player_delta.abs() > 4.0 {
-                    state.move_direction = player_delta.signum();
-                }
-
-                let chase_speed = if player_delta.abs() > 240.0 {
-                    state.base_speed * 1.2
-                } else {
-                    state.base_speed * 0.92
-                };
-                velocity.x = chase_speed * state.move_direction;
-
-                transform.translation.x += velocity.x * delta;
-                if let Some((left, right)) = authored_bounds {
-                    transform.translation.x = transform.translation.x.clamp(left, right);
-                }
-                transform.translation.y = base_y;
-
-                if state.attack_cooldown <= 0.0
-                    && player_delta.abs() > heroic_behavior.dash_trigger_distance.max(1.0)
-                {
-                    state.dash_direction = player_delta.signum();
-                    state.dash_charge_timer = heroic_behavior.dash_charge_secs.max(0.05);
-                    state.attack_cooldown = heroic_behavior.dash_cooldown_secs.max(0.6);
-                }
-            }
-        }
-    }
-}
-
-/// 使魔的远程飞弹攻击。
-pub fn enemy_ranged_attack(
-    mut commands: Commands,
-    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
-    tuning: Option<Res<GameplayTuning>>,
-    mut enemy_query: EnemyRangedQuery,
-) {
-    let default_tuning = GameplayTuning::default();
-    let familiar_tuning = &tuning
-        .as_deref()
-        .unwrap_or(&default_tuning)
-        .enemies
-        .familiar_behavior;
-
-    let Some(player_transform) = player_query.iter().next() else {
-        return;
-    };
-
-    for (enemy_type, enemy_transform, mut enemy_state, authored) in enemy_query.iter_mut() {
-        if *enemy_type != EnemyType::Familiar || !enemy_state.is_alive {
-            continue;
-        }
-
-        if authored.is_none()
-            && enemy_has_passed_player(
-                enemy_transform.translation.x,
-                player_transform.translation.x,
-            )
-        {
-            enemy_state.pending_ranged_shot = false;
-            enemy_state.ranged_windup_timer = 0.0;
-            enemy_state.ranged_shot_direction = Vec2::ZERO;
-            continue;
-        }
-
-        if enemy_state.hit_stun_timer > 0.0 {
-            enemy_state.pending_ranged_shot = false;
-            enemy_state.ranged_windup_timer = 0.0;
-            continue;
-        }
-
-        let to_player = player_transform.translation - enemy_transform.translation;
-        let distance = to_player.length();
-        let direction_to_player = to_player.truncate().normalize_or_zero();
-
-        if enemy_state.pending_ranged_shot {
-            if enemy_state.ranged_windup_timer > 0.0 {
-                continue;
-            }
-
-            let fire_direction = if enemy_state.ranged_shot_direction == Vec2::ZERO {
-                direction_to_player
-            } else {
-                enemy_state.ranged_shot_direction.normalize_or_zero()
-            };
-
-            if fire_direction == Vec2::ZERO {
-                enemy_state.pending_ranged_shot = false;
-                enemy_state.ranged_shot_direction = Vec2::ZERO;
-                continue;
-            }
-
-            let spawn_position = enemy_transform.translation
-                + Vec3::new(fire_direction.x * 24.0, fire_direction.y * 18.0, 0.0);
-            crate::systems::combat::spawn_enemy_projectile(
-                &mut commands,
-                spawn_position,
-                fire_direction,
-                familiar_tuning.projectile_speed.max(20.0),
-                enemy_state.contact_damage * 0.85,
-                familiar_tuning.projectile_lifetime_secs.max(0.2),
-            );
-
-            enemy_state.pending_ranged_shot = false;
-            enemy_state.ranged_shot_direction = Vec2::ZERO;
-            enemy_state.ranged_cooldown = familiar_tuning.attack_cooldown_secs.max(0.2);
-            enemy_state.attack_cooldown = enemy_state.attack_cooldown.max(0.24);
-            continue;
-        }
-
-        if enemy_state.ranged_cooldown > 0.0 {
-            continue;
-        }
-
-        let min_distance = familiar_tuning.attack_min_distance.max(0.0);
-        let max_distance = familiar_tuning.attack_max_distance.max(min_distance + 1.0);
-        if !(min_distance..=max_distance).contains(&distance) {
-            continue;
-        }
-
-        let direction = direction_to_player;
-        if direction == Vec2::ZERO {
-            continue;
-        }
-
-        enemy_state.pending_ranged_shot = true;
-        enemy_state.ranged_shot_direction = direction;
-        enemy_state.ranged_windup_timer = familiar_tuning.cast_windup_secs.max(0.01);
-        enemy_state.attack_cooldown = enemy_state.attack_cooldown.max(0.32);
-    }
-}
-
-/// 敌人攻击预警视觉，提升读招公平性。
+/// 敌人攻击预警视觉，提升读招公平性。颜色与缩放来自各原型的行为配置。
 pub fn update_enemy_telegraph_visuals(
-    mut enemy_query: Query<(&EnemyType, &EnemyState, &mut Sprite, &mut Transform), With<Enemy>>,
+    mut enemy_query: EnemyTelegraphQuery,
     tuning: Option<Res<GameplayTuning>>,
     time: Res<Time>,
 ) {
@@ -728,7 +529,7 @@ pub fn update_enemy_telegraph_visuals(
     let enemy_tuning = &tuning.as_deref().unwrap_or(&default_tuning).enemies;
     let elapsed = time.elapsed_secs();
 
-    for (enemy_type, enemy_state, mut sprite, mut transform) in enemy_query.iter_mut() {
+    for (enemy_type, enemy_state, brain, mut sprite, mut transform) in enemy_query.iter_mut() {
         let base_color = enemy_base_color(*enemy_type);
         transform.scale = Vec3::ONE;
 
@@ -737,75 +538,41 @@ pub fn update_enemy_telegraph_visuals(
             continue;
         }
 
-        match enemy_type {
-            EnemyType::Slime => {
-                let cooldown = enemy_tuning.slime_behavior.burst_cooldown_secs.max(0.1);
-                let warning_window = (cooldown * 0.22).clamp(0.08, 0.35);
-                if enemy_state.attack_cooldown > 0.0
-                    && enemy_state.attack_cooldown <= warning_window
-                {
-                    let progress = 1.0 - enemy_state.attack_cooldown / warning_window;
-                    let pulse = (elapsed * 20.0).sin() * 0.5 + 0.5;
-                    let blend = (progress * 0.68 + pulse * 0.32).clamp(0.0, 1.0);
-                    sprite.color =
-                        lerp_color(base_color, Color::srgba(0.90, 1.0, 0.92, 1.0), blend, 0.98);
-                    transform.scale = Vec3::splat(1.0 + 0.06 * blend);
-                } else {
-                    sprite.color = base_color;
-                }
+        let profile = behavior_profile(*enemy_type, enemy_tuning);
+        let visuals = &profile.visuals;
+        let duration = node_duration(brain.node, &profile).max(0.01);
+        match brain.node {
+            BehaviorNode::Telegraph => {
+                let progress = 1.0 - (brain.node_timer / duration).clamp(0.0, 1.0);
+                let pulse = (elapsed * 28.0).sin() * 0.5 + 0.5;
+                let blend = (progress * 0.68 + pulse * 0.32).clamp(0.0, 1.0);
+                sprite.color = lerp_color(base_color, visuals.telegraph_color, blend, 0.99);
+                transform.scale = Vec3::splat(1.0 + visuals.telegraph_scale * blend);
+            }
+            BehaviorNode::Dash => {
+                let blend = (brain.node_timer / duration).clamp(0.0, 1.0).powf(0.45);
+                sprite.color = lerp_color(base_color, visuals.dash_color, blend, 0.98);
+                transform.scale = Vec3::splat(1.0 + visuals.dash_scale * blend);
             }
-            EnemyType::Familiar => {
-                if enemy_state.pending_ranged_shot {
-                    let windup = enemy_tuning.familiar_behavior.cast_windup_secs.max(0.01);
-                    let progress = 1.0 - (enemy_state.ranged_windup_timer / windup).clamp(0.0, 1.0);
-                    let pulse = (elapsed * 24.0).sin() * 0.5 + 0.5;
-                    let blend = (progress * 0.65 + pulse * 0.35).clamp(0.0, 1.0);
-                    sprite.color =
-                        lerp_color(base_color, Color::srgba(0.92, 0.84, 1.0, 1.0), blend, 0.98);
-                    transform.scale = Vec3::splat(1.0 + 0.08 * blend);
-                } else {
-                    let warning_window = enemy_tuning
-                        .familiar_behavior
-                        .telegraph_window_secs
-                        .max(0.05);
-                    if enemy_state.ranged_cooldown > 0.0
-                        && enemy_state.ranged_cooldown <= warning_window
-                    {
-                        let progress = 1.0 - enemy_state.ranged_cooldown / warning_window;
-                        let pulse = (elapsed * 16.0).sin() * 0.5 + 0.5;
-                        let blend = (progress * 0.52 + pulse * 0.48).clamp(0.0, 1.0);
-                        sprite.color =
-                            lerp_color(base_color, Color::srgba(0.86, 0.88, 1.0, 1.0), blend, 0.95);
-                    } else {
-                        sprite.color = base_color;
-                    }
-                }
+            BehaviorNode::Grab => {
+                let pulse = (elapsed * 12.0).sin() * 0.5 + 0.5;
+                sprite.color = lerp_color(base_color, visuals.dash_color, 0.7 + pulse * 0.3, 0.98);
+                transform.scale = Vec3::splat(1.0 + visuals.dash_scale * (0.5 + pulse * 0.5));
             }
-            EnemyType::EnemyHeroicSpirit => {
-                if enemy_state.dash_charge_timer > 0.0 {
-                    let charge = enemy_tuning
-                        .heroic_spirit_behavior
-                        .dash_charge_secs
-                        .max(0.05);
-                    let progress = 1.0 - (enemy_state.dash_charge_timer / charge).clamp(0.0, 1.0);
-                    let pulse = (elapsed * 34.0).sin() * 0.5 + 0.5;
-                    let blend = (progress * 0.7 + pulse * 0.3).clamp(0.0, 1.0);
-                    sprite.color =
-                        lerp_color(base_color, Color::srgba(1.0, 0.34, 0.27, 1.0), blend, 0.99);
-                    transform.scale = Vec3::splat(1.0 + 0.11 * blend);
-                } else if enemy_state.dash_active_timer > 0.0 {
-                    let active = enemy_tuning
-                        .heroic_spirit_behavior
-                        .dash_active_secs
-                        .max(0.05);
-                    let progress = (enemy_state.dash_active_timer / active).clamp(0.0, 1.0);
-                    let blend = progress.powf(0.45);
-                    sprite.color =
-                        lerp_color(base_color, Color::srgba(1.0, 0.50, 0.4, 1.0), blend, 0.98);
-                    transform.scale = Vec3::splat(1.0 + 0.05 * blend);
-                } else {
-                    sprite.color = base_color;
-                }
+            _ if brain.attack_cooldown > 0.0
+                && brain.attack_cooldown <= visuals.ready_warning_secs =>
+            {
+                let progress = 1.0 - brain.attack_cooldown / visuals.ready_warning_secs;
+                let pulse = (elapsed * 18.0).sin() * 0.5 + 0.5;
+                let blend = (progress * 0.6 + pulse * 0.4).clamp(0.0, 1.0);
+                sprite.color = lerp_color(base_color, visuals.ready_color, blend, 0.96);
+                transform.scale = Vec3::splat(1.0 + visuals.ready_scale * blend);
+            }
+            _ => {
+                sprite.color = match visuals.idle_alpha {
+                    Some(alpha) => base_color.with_alpha(alpha),
+                    None => base_color,
+                };
             }
         }
     }
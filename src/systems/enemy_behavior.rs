@@ -0,0 +1,934 @@
+//! 数据驱动的敌人行为
+//!
+//! - 每个原型的行为由 [`EnemyBehaviorProfile`] 描述：空闲时用哪个移动节点、怎样移动、出什么招、如何预警
+//! - [`EnemyBrain`] 保存当前节点与计时器；[`enemy_behavior_ai`] 对所有原型执行同一套节点跳转：
+//!   空闲（Patrol/Chase）→ Telegraph → Dash/Shoot → 空闲；受击进入 Stunned，越过玩家的非编排敌人进入 Retreat
+//! - 擒拿类出招在冲刺中碰到玩家时进入 Grab，期间玩家带 [`Grabbed`] 标记、无法移动
+//! - 贴地原型带 [`EnemyBody`]：受击后在 Stunned 节点按重力下落，落在 `SkyMergedCollider` 顶面或锚点高度，
+//!   撞上地形侧面或编排区间边界时反弹；挑空、浮空追击与反弹数值见 [`EnemyPhysicsTuning`]
+//! - 新增原型只需在 [`behavior_profile`] 中给出配置，行为系统与预警视觉不需要改动
+//!
+//! 数值来自 `config/gameplay_tuning.ron` 的 `enemies` 段，每帧回查以支持热重载。
+
+use bevy::prelude::*;
+
+use crate::{
+    components::*,
+    resources::{EnemyDirectorTuning, EnemyPhysicsTuning, GameConfig, GameplayTuning},
+    systems::{
+        collision::CollisionBox,
+        hitboxes::{collision_rect, rects_overlap},
+    },
+};
+
+/// 已越过玩家多远的非编排敌人开始离场
+const ENEMY_RETIRE_BEHIND_DISTANCE: f32 = 96.0;
+/// 飞弹出手后的收招时间
+const SHOOT_RECOVERY_SECS: f32 = 0.24;
+/// 顶面高出敌人脚底超过该值的地形才算墙，低于它的视为脚下的地面
+const ENEMY_WALL_STEP_HEIGHT: f32 = 12.0;
+/// 判定落地/站立时允许的竖直误差
+const ENEMY_FLOOR_TOLERANCE: f32 = 2.0;
+
+type EnemyBehaviorQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static EnemyType,
+        &'static mut EnemyBrain,
+        &'static mut EnemyState,
+        &'static mut Transform,
+        &'static mut Velocity,
+        Option<&'static SkyEncounterEnemy>,
+        Option<&'static CollisionBox>,
+        Option<&'static StatusEffects>,
+        Option<&'static mut EnemyBody>,
+    ),
+    (With<Enemy>, Without<Boss>),
+>;
+
+type EnemyTerrainQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Transform, &'static CollisionBox),
+    (With<SkyMergedCollider>, Without<Enemy>),
+>;
+
+type BehaviorTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static Transform,
+        Option<&'static CollisionBox>,
+        Option<&'static DamageInvulnerability>,
+        Option<&'static Grabbed>,
+    ),
+    (With<Player>, Without<Enemy>),
+>;
+
+/// 一个敌人原型的行为配置
+#[derive(Debug, Clone, Copy)]
+pub struct EnemyBehaviorProfile {
+    /// 空闲时使用的移动节点（[`BehaviorNode::Patrol`] 或 [`BehaviorNode::Chase`]）
+    pub idle: BehaviorNode,
+    pub locomotion: Locomotion,
+    pub chase: ChaseRule,
+    pub attack: Option<AttackProfile>,
+    /// 非编排敌人的锚点高度（相对地面）
+    pub anchor_offset: f32,
+    pub contact: ContactEffect,
+    pub visuals: TelegraphVisuals,
+}
+
+/// 接触玩家时的伤害方式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContactEffect {
+    /// 按接触冷却造成一次 `contact_damage`
+    #[default]
+    Strike,
+    /// 每秒持续吸取 `contact_damage`，以 [`crate::events::DamageSource::MudDrain`] 结算
+    Drain,
+}
+
+/// 垂直方向的移动方式
+#[derive(Debug, Clone, Copy)]
+pub enum Locomotion {
+    /// 贴地移动，y 固定在锚点，可带轻微起伏
+    Ground {
+        bob_amplitude: f32,
+        bob_frequency: f32,
+    },
+    /// 浮空，y 以弹簧方式跟随上下摆动的锚点
+    Hover {
+        amplitude: f32,
+        frequency: f32,
+        stiffness: f32,
+        max_speed: f32,
+    },
+    /// 无视地形，y 以固定速度靠向玩家高度，限制在锚点上下 `range` 内
+    Phase { vertical_speed: f32, range: f32 },
+}
+
+/// Chase 节点的追踪规则
+#[derive(Debug, Clone, Copy)]
+pub struct ChaseRule {
+    /// 目标点相对玩家的水平偏移
+    pub offset_x: f32,
+    /// 目标点是否始终限制在巡逻区间内（编排敌人总是限制）
+    pub leash_to_patrol: bool,
+    /// 近/远距离下相对基础速度的倍率
+    pub near_speed: f32,
+    pub far_speed: f32,
+    pub far_distance: f32,
+    /// 距目标点小于该值时保持当前朝向
+    pub arrive_distance: f32,
+}
+
+impl Default for ChaseRule {
+    fn default() -> Self {
+        Self {
+            offset_x: 0.0,
+            leash_to_patrol: false,
+            near_speed: 1.0,
+            far_speed: 1.0,
+            far_distance: 0.0,
+            arrive_distance: 4.0,
+        }
+    }
+}
+
+/// 出招配置：距离条件、预警、冷却与出招动作
+#[derive(Debug, Clone, Copy)]
+pub struct AttackProfile {
+    pub action: AttackAction,
+    /// 与玩家距离在该区间内才会出招
+    pub min_distance: f32,
+    pub max_distance: f32,
+    pub telegraph_secs: f32,
+    pub cooldown_secs: f32,
+    /// 预警与收招期间保留的水平移动比例
+    pub telegraph_move_scale: f32,
+    /// 预警期间的原地抖动幅度
+    pub telegraph_shake: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum AttackAction {
+    /// 沿锁定方向冲刺
+    Dash {
+        speed_multiplier: f32,
+        active_secs: f32,
+    },
+    /// 沿锁定方向发射一枚飞弹，伤害为接触伤害乘以 `damage_scale`
+    Shoot {
+        projectile_speed: f32,
+        lifetime_secs: f32,
+        damage_scale: f32,
+    },
+    /// 沿锁定方向扑出，途中碰到玩家则擒住 `hold_secs`
+    Grab {
+        lunge_speed_multiplier: f32,
+        lunge_secs: f32,
+        hold_secs: f32,
+    },
+}
+
+/// 预警视觉：预警、冲刺与冷却即将结束时的颜色和缩放
+#[derive(Debug, Clone, Copy)]
+pub struct TelegraphVisuals {
+    pub telegraph_color: Color,
+    pub telegraph_scale: f32,
+    pub dash_color: Color,
+    pub dash_scale: f32,
+    /// 冷却结束前的提示窗口，0 表示不提示
+    pub ready_warning_secs: f32,
+    pub ready_color: Color,
+    pub ready_scale: f32,
+    /// 空闲时的身体透明度，`None` 表示沿用原型底色
+    pub idle_alpha: Option<f32>,
+}
+
+impl Default for TelegraphVisuals {
+    fn default() -> Self {
+        Self {
+            telegraph_color: Color::WHITE,
+            telegraph_scale: 0.0,
+            dash_color: Color::WHITE,
+            dash_scale: 0.0,
+            ready_warning_secs: 0.0,
+            ready_color: Color::WHITE,
+            ready_scale: 0.0,
+            idle_alpha: None,
+        }
+    }
+}
+
+/// 各原型的行为配置，由导演调参换算而来
+pub fn behavior_profile(
+    enemy_type: EnemyType,
+    tuning: &EnemyDirectorTuning,
+) -> EnemyBehaviorProfile {
+    match enemy_type {
+        EnemyType::Slime => {
+            let behavior = &tuning.slime_behavior;
+            let cooldown = behavior.burst_cooldown_secs.max(0.1);
+            EnemyBehaviorProfile {
+                idle: BehaviorNode::Patrol,
+                locomotion: Locomotion::Ground {
+                    bob_amplitude: 0.0,
+                    bob_frequency: 0.0,
+                },
+                chase: ChaseRule::default(),
+                attack: Some(AttackProfile {
+                    action: AttackAction::Dash {
+                        speed_multiplier: behavior.burst_speed_multiplier.max(1.0),
+                        active_secs: behavior.burst_secs.max(0.05),
+                    },
+                    min_distance: 0.0,
+                    max_distance: behavior.engage_distance.max(1.0),
+                    telegraph_secs: 0.0,
+                    cooldown_secs: cooldown,
+                    telegraph_move_scale: 1.0,
+                    telegraph_shake: 0.0,
+                }),
+                anchor_offset: tuning.slime.spawn_y_offset,
+                contact: ContactEffect::Strike,
+                visuals: TelegraphVisuals {
+                    dash_color: Color::srgba(0.90, 1.0, 0.92, 1.0),
+                    dash_scale: 0.04,
+                    ready_warning_secs: (cooldown * 0.22).clamp(0.08, 0.35),
+                    ready_color: Color::srgba(0.90, 1.0, 0.92, 1.0),
+                    ready_scale: 0.06,
+                    ..default()
+                },
+            }
+        }
+        EnemyType::Familiar => {
+            let behavior = &tuning.familiar_behavior;
+            let min_distance = behavior.attack_min_distance.max(0.0);
+            EnemyBehaviorProfile {
+                idle: BehaviorNode::Chase,
+                locomotion: Locomotion::Hover {
+                    amplitude: 30.0,
+                    frequency: 2.7,
+                    stiffness: 4.2,
+                    max_speed: 140.0,
+                },
+                chase: ChaseRule {
+                    offset_x: 140.0,
+                    leash_to_patrol: true,
+                    near_speed: 0.8,
+                    far_speed: 1.2,
+                    far_distance: 120.0,
+                    arrive_distance: 8.0,
+                },
+                attack: Some(AttackProfile {
+                    action: AttackAction::Shoot {
+                        projectile_speed: behavior.projectile_speed.max(20.0),
+                        lifetime_secs: behavior.projectile_lifetime_secs.max(0.2),
+                        damage_scale: 0.85,
+                    },
+                    min_distance,
+                    max_distance: behavior.attack_max_distance.max(min_distance + 1.0),
+                    telegraph_secs: behavior.cast_windup_secs.max(0.01),
+                    cooldown_secs: behavior.attack_cooldown_secs.max(0.2),
+                    telegraph_move_scale: 0.22,
+                    telegraph_shake: 0.0,
+                }),
+                anchor_offset: tuning.familiar.spawn_y_offset,
+                contact: ContactEffect::Strike,
+                visuals: TelegraphVisuals {
+                    telegraph_color: Color::srgba(0.92, 0.84, 1.0, 1.0),
+                    telegraph_scale: 0.08,
+                    ready_warning_secs: behavior.telegraph_window_secs.max(0.05),
+                    ready_color: Color::srgba(0.86, 0.88, 1.0, 1.0),
+                    ..default()
+                },
+            }
+        }
+        EnemyType::EnemyHeroicSpirit => {
+            let behavior = &tuning.heroic_spirit_behavior;
+            EnemyBehaviorProfile {
+                idle: BehaviorNode::Chase,
+                locomotion: Locomotion::Ground {
+                    bob_amplitude: 3.0,
+                    bob_frequency: 8.0,
+                },
+                chase: ChaseRule {
+                    near_speed: 0.92,
+                    far_speed: 1.2,
+                    far_distance: 240.0,
+                    ..default()
+                },
+                attack: Some(AttackProfile {
+                    action: AttackAction::Dash {
+                        speed_multiplier: behavior.dash_speed_multiplier.max(1.2),
+                        active_secs: behavior.dash_active_secs.max(0.08),
+                    },
+                    min_distance: behavior.dash_trigger_distance.max(1.0),
+                    max_distance: f32::INFINITY,
+                    telegraph_secs: behavior.dash_charge_secs.max(0.05),
+                    cooldown_secs: behavior.dash_cooldown_secs.max(0.6),
+                    telegraph_move_scale: 0.0,
+                    telegraph_shake: 2.5,
+                }),
+                anchor_offset: tuning.heroic_spirit.spawn_y_offset,
+                contact: ContactEffect::Strike,
+                visuals: TelegraphVisuals {
+                    telegraph_color: Color::srgba(1.0, 0.34, 0.27, 1.0),
+                    telegraph_scale: 0.11,
+                    dash_color: Color::srgba(1.0, 0.50, 0.4, 1.0),
+                    dash_scale: 0.05,
+                    ..default()
+                },
+            }
+        }
+        EnemyType::Shadow => {
+            let behavior = &tuning.shadow_behavior;
+            EnemyBehaviorProfile {
+                idle: BehaviorNode::Chase,
+                locomotion: Locomotion::Phase {
+                    vertical_speed: 90.0,
+                    range: behavior.phase_range.max(0.0),
+                },
+                chase: ChaseRule {
+                    near_speed: 0.7,
+                    far_speed: 1.1,
+                    far_distance: 220.0,
+                    arrive_distance: 12.0,
+                    ..default()
+                },
+                attack: Some(AttackProfile {
+                    action: AttackAction::Grab {
+                        lunge_speed_multiplier: behavior.lunge_speed_multiplier.max(1.0),
+                        lunge_secs: behavior.lunge_secs.max(0.05),
+                        hold_secs: behavior.grab_hold_secs.max(0.1),
+                    },
+                    min_distance: 0.0,
+                    max_distance: behavior.grab_trigger_distance.max(1.0),
+                    telegraph_secs: behavior.lunge_windup_secs.max(0.05),
+                    cooldown_secs: behavior.grab_cooldown_secs.max(0.5),
+                    telegraph_move_scale: 0.0,
+                    telegraph_shake: 0.0,
+                }),
+                anchor_offset: tuning.shadow.spawn_y_offset,
+                contact: ContactEffect::Strike,
+                visuals: TelegraphVisuals {
+                    telegraph_color: Color::srgba(0.55, 0.08, 0.16, 1.0),
+                    telegraph_scale: 0.12,
+                    dash_color: Color::srgba(0.32, 0.02, 0.10, 1.0),
+                    dash_scale: 0.18,
+                    idle_alpha: Some(behavior.phase_alpha.clamp(0.05, 1.0)),
+                    ..default()
+                },
+            }
+        }
+        EnemyType::BlackMud => {
+            let behavior = &tuning.black_mud_behavior;
+            EnemyBehaviorProfile {
+                idle: BehaviorNode::Patrol,
+                locomotion: Locomotion::Ground {
+                    bob_amplitude: 1.5,
+                    bob_frequency: 2.0,
+                },
+                chase: ChaseRule::default(),
+                attack: Some(AttackProfile {
+                    action: AttackAction::Dash {
+                        speed_multiplier: behavior.surge_speed_multiplier.max(1.0),
+                        active_secs: behavior.surge_secs.max(0.05),
+                    },
+                    min_distance: 0.0,
+                    max_distance: behavior.surge_trigger_distance.max(1.0),
+                    telegraph_secs: behavior.surge_windup_secs.max(0.05),
+                    cooldown_secs: behavior.surge_cooldown_secs.max(0.5),
+                    telegraph_move_scale: 0.0,
+                    telegraph_shake: 1.5,
+                }),
+                anchor_offset: tuning.black_mud.spawn_y_offset,
+                contact: ContactEffect::Drain,
+                visuals: TelegraphVisuals {
+                    telegraph_color: Color::srgba(0.46, 0.06, 0.20, 1.0),
+                    telegraph_scale: 0.06,
+                    dash_color: Color::srgba(0.30, 0.04, 0.14, 1.0),
+                    dash_scale: 0.10,
+                    ..default()
+                },
+            }
+        }
+    }
+}
+
+fn enemy_has_passed_player(enemy_x: f32, player_x: f32) -> bool {
+    enemy_x < player_x - ENEMY_RETIRE_BEHIND_DISTANCE
+}
+
+/// 预警/收招节点的总时长，用于视觉进度
+pub fn node_duration(node: BehaviorNode, profile: &EnemyBehaviorProfile) -> f32 {
+    match (node, profile.attack) {
+        (BehaviorNode::Telegraph, Some(attack)) => attack.telegraph_secs,
+        (
+            BehaviorNode::Dash,
+            Some(AttackProfile {
+                action: AttackAction::Dash { active_secs, .. },
+                ..
+            }),
+        ) => active_secs,
+        (
+            BehaviorNode::Dash,
+            Some(AttackProfile {
+                action: AttackAction::Grab { lunge_secs, .. },
+                ..
+            }),
+        ) => lunge_secs,
+        (
+            BehaviorNode::Grab,
+            Some(AttackProfile {
+                action: AttackAction::Grab { hold_secs, .. },
+                ..
+            }),
+        ) => hold_secs,
+        (BehaviorNode::Shoot, Some(_)) => SHOOT_RECOVERY_SECS,
+        _ => 0.0,
+    }
+}
+
+/// 空闲节点下的水平速度
+fn idle_velocity_x(
+    node: BehaviorNode,
+    profile: &EnemyBehaviorProfile,
+    state: &mut EnemyState,
+    x: f32,
+    player_x: f32,
+    authored: bool,
+) -> f32 {
+    let (patrol_left, patrol_right) = state.patrol_world_bounds();
+    match node {
+        BehaviorNode::Chase => {
+            let rule = &profile.chase;
+            let mut target_x = player_x + rule.offset_x;
+            if rule.leash_to_patrol || authored {
+                target_x = target_x.clamp(patrol_left, patrol_right);
+            }
+            let x_delta = target_x - x;
+            if x_delta.abs() > rule.arrive_distance {
+                state.move_direction = x_delta.signum();
+            }
+            let speed = if x_delta.abs() > rule.far_distance {
+                rule.far_speed
+            } else {
+                rule.near_speed
+            };
+            state.base_speed * speed * state.move_direction
+        }
+        _ => {
+            if state.move_direction > 0.0 && x > patrol_right {
+                state.move_direction = -1.0;
+            } else if state.move_direction < 0.0 && x < patrol_left {
+                state.move_direction = 1.0;
+            }
+            state.base_speed * state.move_direction
+        }
+    }
+}
+
+/// 脚底 `bottom` 下方（含误差）与 `rect` 水平重叠的最高地形顶面
+fn terrain_floor_below(rect: Rect, bottom: f32, terrain: &[Rect]) -> Option<f32> {
+    terrain
+        .iter()
+        .filter(|block| block.min.x < rect.max.x && block.max.x > rect.min.x)
+        .filter(|block| block.max.y <= bottom + ENEMY_FLOOR_TOLERANCE)
+        .map(|block| block.max.y)
+        .max_by(f32::total_cmp)
+}
+
+/// 敌人物理一帧所需的环境
+struct BodyStepContext<'a> {
+    anchor_y: f32,
+    /// 编排敌人的活动区间，同样视为墙
+    bounds: Option<(f32, f32)>,
+    terrain: &'a [Rect],
+    physics: &'a EnemyPhysicsTuning,
+    delta: f32,
+}
+
+/// 被击飞的贴地敌人一帧的物理：重力/摩擦、撞墙反弹与落地
+fn step_enemy_body(
+    transform: &mut Transform,
+    velocity: &mut Velocity,
+    body: &mut EnemyBody,
+    state: &mut EnemyState,
+    half_size: Vec2,
+    context: &BodyStepContext,
+) {
+    let BodyStepContext {
+        anchor_y,
+        bounds,
+        terrain,
+        physics,
+        delta,
+    } = *context;
+    let rect_at = |position: Vec2| Rect::from_center_half_size(position, half_size);
+    if body.airborne {
+        velocity.y = (velocity.y - physics.gravity * delta).max(-physics.max_fall_speed);
+        velocity.x *= (-physics.air_drag * delta).exp();
+    } else {
+        velocity.y = 0.0;
+        velocity.x *= (-physics.ground_friction * delta).exp();
+    }
+
+    // 水平：地形侧面与编排区间边界都算墙
+    let previous = transform.translation.truncate();
+    transform.translation.x += velocity.x * delta;
+    let mut hit_wall = false;
+    let bottom = previous.y - half_size.y;
+    for block in terrain {
+        let rect = rect_at(transform.translation.truncate());
+        if !rects_overlap(rect, *block) || block.max.y <= bottom + ENEMY_WALL_STEP_HEIGHT {
+            continue;
+        }
+        transform.translation.x = if previous.x < block.center().x {
+            block.min.x - half_size.x
+        } else {
+            block.max.x + half_size.x
+        };
+        hit_wall = true;
+    }
+    if let Some((left, right)) = bounds
+        && !(left..=right).contains(&transform.translation.x)
+    {
+        transform.translation.x = transform.translation.x.clamp(left, right);
+        hit_wall = true;
+    }
+    if hit_wall {
+        if velocity.x.abs() >= physics.wall_bounce_min_speed
+            && body.wall_bounces < physics.max_wall_bounces
+        {
+            velocity.x = -velocity.x * physics.wall_restitution;
+            body.wall_bounces += 1;
+        } else {
+            velocity.x = 0.0;
+        }
+    }
+
+    if !body.airborne {
+        // 从平台边缘滑出后开始下落
+        if body.rest_y.is_some()
+            && terrain_floor_below(rect_at(transform.translation.truncate()), bottom, terrain)
+                .is_none()
+        {
+            body.airborne = true;
+        }
+        return;
+    }
+
+    // 竖直：上升时撞顶，下降时落在地形顶面或锚点高度
+    transform.translation.y += velocity.y * delta;
+    let rect = rect_at(transform.translation.truncate());
+    if velocity.y > 0.0 {
+        let top = previous.y + half_size.y;
+        if let Some(ceiling) = terrain
+            .iter()
+            .filter(|block| {
+                rects_overlap(rect, **block) && block.min.y >= top - ENEMY_FLOOR_TOLERANCE
+            })
+            .map(|block| block.min.y)
+            .min_by(f32::total_cmp)
+        {
+            transform.translation.y = ceiling - half_size.y;
+            velocity.y = 0.0;
+        }
+        return;
+    }
+
+    let terrain_rest = terrain_floor_below(rect, bottom, terrain)
+        .map(|floor| floor + half_size.y)
+        .filter(|rest| *rest > anchor_y);
+    let rest_y = terrain_rest.unwrap_or(anchor_y);
+    if transform.translation.y <= rest_y {
+        transform.translation.y = rest_y;
+        velocity.y = 0.0;
+        body.land(terrain_rest);
+        state.apply_hit_stun(physics.landing_stun_secs);
+    }
+}
+
+/// 预警结束，执行出招动作
+fn release_attack(
+    commands: &mut Commands,
+    brain: &mut EnemyBrain,
+    state: &EnemyState,
+    transform: &Transform,
+    velocity: &mut Velocity,
+    action: AttackAction,
+) {
+    match action {
+        AttackAction::Dash {
+            speed_multiplier,
+            active_secs,
+        } => {
+            brain.enter(BehaviorNode::Dash, active_secs);
+            velocity.x = state.base_speed * speed_multiplier * brain.aim.x.signum();
+        }
+        AttackAction::Shoot {
+            projectile_speed,
+            lifetime_secs,
+            damage_scale,
+        } => {
+            brain.enter(BehaviorNode::Shoot, SHOOT_RECOVERY_SECS);
+            let direction = brain.aim.normalize_or_zero();
+            if direction == Vec2::ZERO {
+                return;
+            }
+            let spawn_position =
+                transform.translation + Vec3::new(direction.x * 24.0, direction.y * 18.0, 0.0);
+            crate::systems::combat::spawn_enemy_projectile(
+                commands,
+                spawn_position,
+                direction,
+                projectile_speed,
+                state.contact_damage * damage_scale,
+                lifetime_secs,
+            );
+        }
+        AttackAction::Grab {
+            lunge_speed_multiplier,
+            lunge_secs,
+            ..
+        } => {
+            brain.enter(BehaviorNode::Dash, lunge_secs);
+            let lunge = brain.aim.normalize_or_zero() * state.base_speed * lunge_speed_multiplier;
+            velocity.x = lunge.x;
+            velocity.y = lunge.y;
+        }
+    }
+}
+
+/// 敌人行为状态机：节点跳转与各节点的移动。
+pub fn enemy_behavior_ai(
+    mut commands: Commands,
+    mut enemy_query: EnemyBehaviorQuery,
+    player_query: BehaviorTargetQuery,
+    terrain_query: EnemyTerrainQuery,
+    tuning: Option<Res<GameplayTuning>>,
+    time: Res<Time>,
+) {
+    let default_tuning = GameplayTuning::default();
+    let tuning = tuning.as_deref().unwrap_or(&default_tuning);
+    let enemy_tuning = &tuning.enemies;
+    let status_tuning = &tuning.status_effects;
+    let terrain: Vec<Rect> = terrain_query
+        .iter()
+        .map(|(transform, collision_box)| collision_rect(transform, collision_box))
+        .collect();
+
+    let target = player_query.iter().next();
+    let player = target.map(|(_, transform, ..)| transform.translation);
+    let player_x = player.map(|position| position.x).unwrap_or_default();
+    let player_rect = target.and_then(|(_, transform, collision_box, ..)| {
+        collision_box.map(|collision_box| collision_rect(transform, collision_box))
+    });
+    let player_entity = target.map(|(entity, ..)| entity);
+    let mut player_grabbable = target.is_some_and(|(_, _, _, invulnerability, grabbed)| {
+        grabbed.is_none() && !invulnerability.is_some_and(DamageInvulnerability::is_active)
+    });
+    let elapsed = time.elapsed_secs();
+    let delta = time.delta_secs();
+
+    // 擒拿者死亡、被打断或松手后释放玩家
+    if let Some((player_entity, _, _, _, Some(grabbed))) = target {
+        let holding = enemy_query
+            .get(grabbed.by)
+            .is_ok_and(|(_, _, brain, state, ..)| {
+                state.is_alive && brain.node == BehaviorNode::Grab && brain.node_timer > 0.0
+            });
+        if !holding {
+            commands.entity(player_entity).remove::<Grabbed>();
+        }
+    }
+
+    for (
+        entity,
+        enemy_type,
+        mut brain,
+        mut state,
+        mut transform,
+        mut velocity,
+        authored,
+        collision_box,
+        statuses,
+        body,
+    ) in enemy_query.iter_mut()
+    {
+        if !state.is_alive {
+            velocity.x = 0.0;
+            velocity.y = 0.0;
+            continue;
+        }
+
+        state.tick_timers(delta);
+        brain.tick(delta);
+        let profile = behavior_profile(*enemy_type, enemy_tuning);
+        let authored_bounds = authored.map(|_| state.patrol_world_bounds());
+        let anchor_y = authored
+            .map(|enemy| enemy.anchor_y)
+            .unwrap_or(GameConfig::GROUND_LEVEL + profile.anchor_offset);
+        // 只有贴地原型受重力影响
+        let mut body = body.filter(|_| matches!(profile.locomotion, Locomotion::Ground { .. }));
+        let airborne = body.as_ref().is_some_and(|body| body.airborne);
+
+        // 节点跳转：浮空中保持硬直直到落地
+        if state.hit_stun_timer > 0.0 || airborne {
+            if brain.node != BehaviorNode::Stunned {
+                brain.enter(BehaviorNode::Stunned, 0.0);
+                brain.aim = Vec2::ZERO;
+            }
+        } else if brain.node == BehaviorNode::Stunned {
+            brain.enter(profile.idle, 0.0);
+        }
+        if authored.is_none()
+            && !matches!(brain.node, BehaviorNode::Stunned | BehaviorNode::Retreat)
+            && enemy_has_passed_player(transform.translation.x, player_x)
+        {
+            brain.enter(BehaviorNode::Retreat, 0.0);
+            brain.aim = Vec2::ZERO;
+        }
+
+        if matches!(brain.node, BehaviorNode::Patrol | BehaviorNode::Chase) {
+            brain.node = profile.idle;
+            if let (Some(attack), Some(player)) = (profile.attack, player)
+                && brain.attack_cooldown <= 0.0
+            {
+                let to_player = (player - transform.translation).truncate();
+                let distance = to_player.length();
+                if (attack.min_distance..=attack.max_distance).contains(&distance)
+                    && to_player != Vec2::ZERO
+                {
+                    // 冷却从预警开始计算，被打断的出招同样进入冷却
+                    brain.aim = to_player.normalize();
+                    brain.attack_cooldown = attack.cooldown_secs;
+                    brain.enter(BehaviorNode::Telegraph, attack.telegraph_secs);
+                }
+            }
+        }
+        if brain.node == BehaviorNode::Telegraph && brain.node_timer <= 0.0 {
+            match profile.attack {
+                Some(attack) => release_attack(
+                    &mut commands,
+                    &mut brain,
+                    &state,
+                    &transform,
+                    &mut velocity,
+                    attack.action,
+                ),
+                None => brain.enter(profile.idle, 0.0),
+            }
+        } else if matches!(
+            brain.node,
+            BehaviorNode::Dash | BehaviorNode::Shoot | BehaviorNode::Grab
+        ) && brain.node_timer <= 0.0
+        {
+            brain.enter(profile.idle, 0.0);
+        }
+
+        // 擒拿：扑击途中碰到玩家即抓住
+        if brain.node == BehaviorNode::Dash
+            && player_grabbable
+            && let Some(AttackProfile {
+                action: AttackAction::Grab { hold_secs, .. },
+                ..
+            }) = profile.attack
+            && let (Some(player_entity), Some(player_rect), Some(collision_box)) =
+                (player_entity, player_rect, collision_box)
+            && rects_overlap(collision_rect(&transform, collision_box), player_rect)
+        {
+            brain.enter(BehaviorNode::Grab, hold_secs);
+            commands
+                .entity(player_entity)
+                .insert(Grabbed { by: entity });
+            player_grabbable = false;
+        }
+
+        // 节点移动
+        let x = transform.translation.x;
+        match brain.node {
+            BehaviorNode::Stunned => {
+                if let (Some(body), Some(collision_box)) = (body.as_deref_mut(), collision_box) {
+                    step_enemy_body(
+                        &mut transform,
+                        &mut velocity,
+                        body,
+                        &mut state,
+                        collision_box.size * 0.5,
+                        &BodyStepContext {
+                            anchor_y,
+                            bounds: authored_bounds,
+                            terrain: &terrain,
+                            physics: &enemy_tuning.physics,
+                            delta,
+                        },
+                    );
+                    continue;
+                }
+                transform.translation.x += velocity.x * delta;
+                transform.translation.y += velocity.y * delta;
+                if let Some((left, right)) = authored_bounds {
+                    transform.translation.x = transform.translation.x.clamp(left, right);
+                }
+                velocity.x *= 0.84;
+                velocity.y *= 0.80;
+                continue;
+            }
+            BehaviorNode::Retreat => {
+                state.move_direction = -1.0;
+                velocity.x = -state.base_speed.max(20.0);
+            }
+            BehaviorNode::Patrol | BehaviorNode::Chase => {
+                velocity.x = idle_velocity_x(
+                    brain.node,
+                    &profile,
+                    &mut state,
+                    x,
+                    player_x,
+                    authored.is_some(),
+                );
+            }
+            BehaviorNode::Telegraph | BehaviorNode::Shoot => {
+                let move_scale = profile
+                    .attack
+                    .map(|attack| attack.telegraph_move_scale)
+                    .unwrap_or(1.0);
+                velocity.x = idle_velocity_x(
+                    profile.idle,
+                    &profile,
+                    &mut state,
+                    x,
+                    player_x,
+                    authored.is_some(),
+                ) * move_scale;
+            }
+            BehaviorNode::Dash => {}
+            BehaviorNode::Grab => {
+                velocity.x = 0.0;
+                velocity.y = 0.0;
+                if let Some(player) = player {
+                    transform.translation.x = player.x;
+                    transform.translation.y = player.y;
+                }
+                continue;
+            }
+        }
+
+        let speed_multiplier = statuses
+            .map(|statuses| statuses.speed_multiplier(status_tuning))
+            .unwrap_or(1.0);
+        transform.translation.x += velocity.x * speed_multiplier * delta;
+        if let Some((left, right)) = authored_bounds {
+            transform.translation.x = transform.translation.x.clamp(left, right);
+        }
+
+        match profile.locomotion {
+            Locomotion::Ground {
+                bob_amplitude,
+                bob_frequency,
+            } => {
+                let shake = match (brain.node, profile.attack) {
+                    (BehaviorNode::Telegraph, Some(attack)) => {
+                        (elapsed * 45.0).sin() * attack.telegraph_shake
+                    }
+                    _ => 0.0,
+                };
+                velocity.y = 0.0;
+                let rest_y = match (body.as_deref_mut(), collision_box) {
+                    (Some(body), Some(collision_box)) => {
+                        let half_size = collision_box.size * 0.5;
+                        let rect = Rect::from_center_half_size(
+                            transform.translation.truncate(),
+                            half_size,
+                        );
+                        let bottom = transform.translation.y - half_size.y;
+                        // 走出平台边缘后下落
+                        if body.rest_y.is_some()
+                            && terrain_floor_below(rect, bottom, &terrain).is_none()
+                        {
+                            body.airborne = true;
+                        }
+                        body.rest_y.unwrap_or(anchor_y)
+                    }
+                    _ => anchor_y,
+                };
+                transform.translation.y = rest_y
+                    + (elapsed * bob_frequency + state.hover_phase).sin() * bob_amplitude
+                    + shake;
+            }
+            Locomotion::Hover {
+                amplitude,
+                frequency,
+                stiffness,
+                max_speed,
+            } => {
+                let target_y =
+                    anchor_y + (elapsed * frequency + state.hover_phase).sin() * amplitude;
+                velocity.y =
+                    ((target_y - transform.translation.y) * stiffness).clamp(-max_speed, max_speed);
+                transform.translation.y += velocity.y * delta;
+            }
+            Locomotion::Phase {
+                vertical_speed,
+                range,
+            } => {
+                if brain.node != BehaviorNode::Dash {
+                    let target_y = player
+                        .map(|player| player.y)
+                        .unwrap_or(anchor_y)
+                        .clamp(anchor_y - range, anchor_y + range);
+                    let y_delta = target_y - transform.translation.y;
+                    velocity.y =
+                        y_delta.signum() * vertical_speed.min(y_delta.abs() / delta.max(1e-4));
+                }
+                transform.translation.y += velocity.y * delta;
+            }
+        }
+    }
+}
@@ -29,6 +29,7 @@ pub enum SaveSystemError {
     // 验证错误
     InvalidFileName(String),
     ChecksumMismatch,
+    SignatureMismatch,
     VersionMismatch(String),
     NameAlreadyExists(String),
 
@@ -64,6 +65,7 @@ impl SaveSystemError {
 
             SaveSystemError::InvalidFileName(_) => SaveLoadText::INVALID_NAME_ERROR,
             SaveSystemError::ChecksumMismatch => "Save file integrity check failed",
+            SaveSystemError::SignatureMismatch => "Save file was modified outside the game",
             SaveSystemError::VersionMismatch(_) => "Incompatible save file version",
             SaveSystemError::NameAlreadyExists(_) => SaveLoadText::NAME_EXISTS_ERROR,
 
@@ -115,9 +117,22 @@ impl SaveSystemError {
 
             SaveSystemError::DiskSpaceInsufficient => "Insufficient disk space".to_string(),
             SaveSystemError::ChecksumMismatch => "Checksum mismatch".to_string(),
+            SaveSystemError::SignatureMismatch => "Signature mismatch".to_string(),
         }
     }
 
+    /// 判断错误是否意味着存档内容已损坏（可尝试从备份恢复）
+    pub fn is_corruption(&self) -> bool {
+        matches!(
+            self,
+            SaveSystemError::FileCorrupted(_)
+                | SaveSystemError::ChecksumMismatch
+                | SaveSystemError::SignatureMismatch
+                | SaveSystemError::DeserializationFailed(_)
+                | SaveSystemError::DecompressionFailed(_)
+        )
+    }
+
     /// 判断错误是否可以重试
     pub fn is_retryable(&self) -> bool {
         !matches!(
@@ -126,6 +141,7 @@ impl SaveSystemError {
                 | SaveSystemError::PermissionDenied(_)
                 | SaveSystemError::FileCorrupted(_)
                 | SaveSystemError::ChecksumMismatch
+                | SaveSystemError::SignatureMismatch
                 | SaveSystemError::VersionMismatch(_)
                 | SaveSystemError::InvalidFileName(_)
                 | SaveSystemError::NameAlreadyExists(_)
@@ -141,12 +157,76 @@ impl std::fmt::Display for SaveSystemError {
 
 impl std::error::Error for SaveSystemError {}
 
+/// 滚动备份策略
+///
+/// 每个存档槽位在备份目录下保留 `<槽位名>.<代数>.bak`，第 1 代最新；
+/// 写入新备份时旧的依次后移，超过 `max_backups` 的最旧一代被删除。
+#[derive(Debug, Clone)]
+pub struct SaveBackupPolicy {
+    pub directory: PathBuf,
+    pub max_backups: usize,
+}
+
+impl SaveBackupPolicy {
+    /// 指定槽位第 `generation` 代备份的路径（从 1 开始）
+    pub fn backup_path(&self, save_name: &str, generation: usize) -> PathBuf {
+        self.directory
+            .join(format!("{}.{}.bak", save_name, generation))
+    }
+
+    /// 损坏的原存档在恢复前被转存到这里，便于排查
+    pub fn corrupted_copy_path(&self, save_name: &str) -> PathBuf {
+        self.directory.join(format!("{}.corrupt", save_name))
+    }
+
+    /// 已存在的备份，按新到旧排列
+    pub fn existing_backups(&self, save_name: &str) -> Vec<PathBuf> {
+        (1..=self.max_backups)
+            .map(|generation| self.backup_path(save_name, generation))
+            .filter(|path| path.exists())
+            .collect()
+    }
+
+    /// 把一份完好的存档数据写为第 1 代备份，并轮转旧备份
+    pub fn rotate(&self, save_name: &str, data: &[u8]) -> Result<(), SaveSystemError> {
+        use std::fs;
+
+        if self.max_backups == 0 {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.directory)
+            .map_err(|e| SaveSystemError::DirectoryCreationFailed(e.to_string()))?;
+
+        let oldest = self.backup_path(save_name, self.max_backups);
+        if oldest.exists() {
+            fs::remove_file(&oldest).map_err(|e| convert_io_error(e, &oldest.to_string_lossy()))?;
+        }
+
+        for generation in (1..self.max_backups).rev() {
+            let from = self.backup_path(save_name, generation);
+            if from.exists() {
+                let to = self.backup_path(save_name, generation + 1);
+                fs::rename(&from, &to).map_err(|e| convert_io_error(e, &from.to_string_lossy()))?;
+            }
+        }
+
+        let newest = self.backup_path(save_name, 1);
+        crate::systems::shared_utils::atomic_write_file(&newest, data)
+            .map_err(|e| SaveSystemError::FileWriteFailed(e.to_string()))?;
+
+        crate::debug_log!("💾 Backup rotated: {}", newest.display());
+        Ok(())
+    }
+}
+
 /// 错误恢复管理器
 #[derive(Resource)]
 pub struct ErrorRecoveryManager {
     pub retry_attempts: HashMap<String, u32>,
     pub max_retries: u32,
     pub backup_directory: PathBuf,
+    pub max_backups_per_slot: usize,
     pub error_history: Vec<ErrorRecord>,
 }
 
@@ -156,10 +236,19 @@ impl ErrorRecoveryManager {
             retry_attempts: HashMap::new(),
             max_retries: 3,
             backup_directory: PathBuf::from("saves/backup"),
+            max_backups_per_slot: 3,
             error_history: Vec::new(),
         }
     }
 
+    /// 当前的滚动备份策略（异步存读档任务持有其副本）
+    pub fn backup_policy(&self) -> SaveBackupPolicy {
+        SaveBackupPolicy {
+            directory: self.backup_directory.clone(),
+            max_backups: self.max_backups_per_slot,
+        }
+    }
+
     /// 处理保存错误
     pub fn handle_save_error(&mut self, error: SaveSystemError, operation: &str) -> RecoveryAction {
         self.log_error(&error, operation);
@@ -204,21 +293,24 @@ impl ErrorRecoveryManager {
         self.log_error(&error, operation);
 
         match error {
+            // 任何形式的损坏都先尝试从滚动备份恢复
+            _ if error.is_corruption() && self.backup_exists(operation) => {
+                RecoveryAction::UseBackup
+            }
             SaveSystemError::FileNotFound(_) => RecoveryAction::ShowError(
                 "Save file not found. It may have been deleted.".to_string(),
             ),
-            SaveSystemError::FileCorrupted(_) => {
-                if self.backup_exists(operation) {
-                    RecoveryAction::UseBackup
-                } else {
-                    RecoveryAction::ShowError(
-                        "Save file is corrupted and no backup is available".to_string(),
-                    )
-                }
+            SaveSystemError::FileCorrupted(_) | SaveSystemError::DecompressionFailed(_) => {
+                RecoveryAction::ShowError(
+                    "Save file is corrupted and no backup is available".to_string(),
+                )
             }
             SaveSystemError::ChecksumMismatch => RecoveryAction::ShowError(
                 "Save file integrity check failed. File may be corrupted.".to_string(),
             ),
+            SaveSystemError::SignatureMismatch => RecoveryAction::ShowError(
+                "Save file was modified outside the game and cannot be loaded.".to_string(),
+            ),
             SaveSystemError::VersionMismatch(_) => {
                 RecoveryAction::ShowError("Save file is from an incompatible version".to_string())
             }
@@ -243,42 +335,31 @@ impl ErrorRecoveryManager {
         }
     }
 
-    /// 创建备份
+    /// 创建备份（轮转保留最近 `max_backups_per_slot` 份）
     pub fn create_backup(&self, save_name: &str, data: &str) -> Result<(), SaveSystemError> {
-        use std::fs;
-
-        // 确保备份目录存在
-        if !self.backup_directory.exists() {
-            fs::create_dir_all(&self.backup_directory)
-                .map_err(|e| SaveSystemError::DirectoryCreationFailed(e.to_string()))?;
-        }
-
-        // 创建备份文件
-        let backup_file = self
-            .backup_directory
-            .join(format!("{}_backup.json", save_name));
-        fs::write(&backup_file, data)
-            .map_err(|e| SaveSystemError::SerializationFailed(e.to_string()))?;
-
-        crate::debug_log!("💾 Backup created: {}", backup_file.display());
-        Ok(())
+        self.backup_policy().rotate(save_name, data.as_bytes())
     }
 
-    /// 从备份恢复
+    /// 从最新的一份备份恢复，返回解码后的存档 JSON
     pub fn restore_from_backup(&self, save_name: &str) -> Result<String, SaveSystemError> {
         use std::fs;
 
-        let backup_file = self
-            .backup_directory
-            .join(format!("{}_backup.json", save_name));
-
-        if !backup_file.exists() {
-            return Err(SaveSystemError::FileNotFound(
-                backup_file.to_string_lossy().to_string(),
-            ));
-        }
+        let Some(backup_file) = self
+            .backup_policy()
+            .existing_backups(save_name)
+            .into_iter()
+            .next()
+        else {
+            return Err(SaveSystemError::FileNotFound(format!(
+                "No backup for '{}' in {}",
+                save_name,
+                self.backup_directory.display()
+            )));
+        };
 
-        let data = fs::read_to_string(&backup_file)
+        let file_data = fs::read(&backup_file)
+            .map_err(|e| convert_io_error(e, &backup_file.to_string_lossy()))?;
+        let data = crate::systems::shared_utils::decode_file_payload(&file_data)
             .map_err(|e| SaveSystemError::DeserializationFailed(e.to_string()))?;
 
         crate::debug_log!("📂 Restored from backup: {}", backup_file.display());
@@ -286,11 +367,8 @@ impl ErrorRecoveryManager {
     }
 
     /// 检查备份是否存在
-    fn backup_exists(&self, save_name: &str) -> bool {
-        let backup_file = self
-            .backup_directory
-            .join(format!("{}_backup.json", save_name));
-        backup_file.exists()
+    pub fn backup_exists(&self, save_name: &str) -> bool {
+        !self.backup_policy().existing_backups(save_name).is_empty()
     }
 
     /// 记录错误
@@ -54,7 +54,7 @@ pub fn setup_game(mut commands: Commands, mut params: SetupGameParams) {
     if params.loaded_game_state.should_restore
         && let Some(state) = &params.loaded_game_state.state
     {
-        params.character_selection.selected_character = state.selected_character.clone();
+        params.character_selection.selected_character = state.selected_character;
         if let Some(level) = params.sky_level.as_deref_mut().filter(|level| level.active) {
             // The LDtk start initializer runs asynchronously after the save
             // handoff. Marking this position initialized prevents it from
@@ -143,10 +143,12 @@ pub fn setup_game(mut commands: Commands, mut params: SetupGameParams) {
         AttackAnimationState::default(),
         FacingDirection::default(),
         DamageInvulnerability::default(),
+        PlayerDefense::default(),
         crate::systems::collision::CollisionBox::new(GameConfig::PLAYER_SIZE),
         Health::default(),
         ShroudState::default(),
         LedgeTraversal::default(),
+        StatusEffects::default(),
     );
 
     if params.character_selection.selected_character == CharacterType::Shirou
@@ -196,11 +198,12 @@ pub fn setup_game(mut commands: Commands, mut params: SetupGameParams) {
 /// 鎭㈠锷犺浇镄勬父鎴忕姸镐佷腑镄勫疄浣扑綅缃?
 pub fn restore_loaded_game_entities(
     mut loaded_game_state: ResMut<crate::systems::ui::LoadedGameState>,
-    mut player_query: Query<(&mut Transform, &mut Velocity, &mut PlayerState), With<Player>>,
+    mut player_query: crate::systems::pause_save::RestorePlayerQuery,
     mut camera_query: Query<&mut Transform, (With<Camera>, Without<Player>)>,
     mut game_stats: ResMut<GameStats>,
     mut character_selection: ResMut<CharacterSelection>,
     mut audio_state_manager: ResMut<AudioStateManager>,
+    tuning: Option<Res<GameplayTuning>>,
 ) {
     use crate::systems::text_constants::SaveLoadText;
 
@@ -214,13 +217,19 @@ pub fn restore_loaded_game_entities(
     crate::debug_log!("Loading Game...");
     let mut player_restored = false;
 
-    if let Ok((mut player_transform, mut player_velocity, mut player_state)) =
+    if let Ok((mut player_transform, mut player_velocity, mut player_state, status_effects)) =
         player_query.single_mut()
     {
         player_transform.translation = state.player_position;
         *player_velocity = state.player_velocity.clone();
         player_state.is_grounded = state.player_grounded;
         player_state.is_crouching = state.player_crouching;
+        if let Some(mut status_effects) = status_effects {
+            // 存档内容不可信，层数与时长按当前调参收敛
+            let default_tuning = GameplayTuning::default();
+            let status_tuning = &tuning.as_deref().unwrap_or(&default_tuning).status_effects;
+            *status_effects = state.player_status_effects.restored(status_tuning);
+        }
         player_restored = true;
     } else {
         warn!("Player entity not ready yet, retrying save restore next frame");
@@ -233,8 +242,10 @@ pub fn restore_loaded_game_entities(
     game_stats.distance_traveled = state.distance_traveled;
     game_stats.jump_count = state.jump_count;
     game_stats.play_time = state.play_time;
+    game_stats.combat = state.combat;
+    game_stats.untrusted_save = state.untrusted_save;
 
-    character_selection.selected_character = state.selected_character.clone();
+    character_selection.selected_character = state.selected_character;
 
     audio_state_manager.music_playing = state.music_playing;
     audio_state_manager.music_volume = state.audio_volume;
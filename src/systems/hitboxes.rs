@@ -0,0 +1,286 @@
+//! 逐帧判定框
+//!
+//! - [`load_frame_data`]：加载编译时内嵌的 `hf_shirou_frames.ron`（热重载见 `hot_reload`）
+//! - [`FrameDrivenAttack`]：按帧判定的攻击挂在玩家身上，`knife_enemy_collision` 按当前动画帧取攻击框
+//! - [`player_hurtboxes`]：玩家当前帧的受击框，没有配置时使用 `CollisionBox`
+//! - F4 调试层：绘制攻击框、受击框、敌人碰撞盒以及敌人当前的行为节点
+
+use bevy::prelude::*;
+
+use crate::components::{
+    AnimationType, AttackAnimationState, Enemy, EnemyBrain, FacingDirection, FrameDataMap, Player,
+};
+use crate::systems::collision::CollisionBox;
+use crate::systems::combat::{KnifeSlash, KnifeSlashFeedback};
+use crate::systems::sprite_animation::SpriteAnimation;
+
+const HF_SHIROU_FRAME_DATA: &str = include_str!("../../assets/animations/hf_shirou_frames.ron");
+
+/// 判定框调试层开关键
+pub const HITBOX_OVERLAY_KEY: KeyCode = KeyCode::F4;
+const OVERLAY_Z: f32 = 9.0;
+
+/// 加载编译时内嵌的逐帧判定数据
+pub fn load_frame_data() -> FrameDataMap {
+    let frame_data: FrameDataMap = ron::from_str(HF_SHIROU_FRAME_DATA)
+        .unwrap_or_else(|error| panic!("HF 士郎逐帧判定无法解析: {error}"));
+    frame_data
+        .validate()
+        .unwrap_or_else(|error| panic!("HF 士郎逐帧判定无效: {error}"));
+    frame_data
+}
+
+/// 正在进行的按帧判定攻击。攻击开始时插入，只对同一 `trigger_serial` 的动画生效；
+/// 每个敌人在一次攻击中只会被命中一次。
+#[derive(Component, Debug, Clone)]
+pub struct FrameDrivenAttack {
+    pub trigger_serial: u32,
+    pub combo_step: u8,
+    pub combo_chain: u32,
+    pub facing: f32,
+    pub damage: f32,
+    pub knockback_x: f32,
+    pub knockback_y: f32,
+    pub launch_y: f32,
+    pub hit_stop_secs: f32,
+    pub feedback: KnifeSlashFeedback,
+    pub hit_targets: Vec<Entity>,
+}
+
+/// 两个判定矩形是否重叠（贴边不算）
+pub fn rects_overlap(a: Rect, b: Rect) -> bool {
+    !a.intersect(b).is_empty()
+}
+
+/// 按 `CollisionBox` 得到的矩形（与其余碰撞判定一致，不计偏移）
+pub fn collision_rect(transform: &Transform, collision_box: &CollisionBox) -> Rect {
+    Rect::from_center_size(transform.translation.truncate(), collision_box.size)
+}
+
+/// 玩家当前帧的受击框
+pub fn player_hurtboxes(
+    frame_data: Option<&FrameDataMap>,
+    transform: &Transform,
+    collision_box: &CollisionBox,
+    animation: Option<&SpriteAnimation>,
+    attack_state: Option<&AttackAnimationState>,
+    facing: Option<&FacingDirection>,
+) -> Vec<Rect> {
+    let center = transform.translation.truncate();
+    let facing = facing.copied().unwrap_or_default().sign();
+    frame_data
+        .zip(animation)
+        .and_then(|(frame_data, animation)| {
+            frame_data.hurtboxes_at(
+                &animation.current_animation,
+                attack_state
+                    .filter(|state| state.is_active())
+                    .map(|state| state.style),
+                animation.current_frame,
+            )
+        })
+        .map(|boxes| {
+            boxes
+                .iter()
+                .map(|frame_box| frame_box.world_rect(center, facing))
+                .collect()
+        })
+        .unwrap_or_else(|| vec![collision_rect(transform, collision_box)])
+}
+
+/// 当前动画帧生效的攻击框（世界坐标），附带该框的伤害/击退倍率
+pub fn active_attack_boxes(
+    frame_data: &FrameDataMap,
+    transform: &Transform,
+    animation: &SpriteAnimation,
+    attack_state: &AttackAnimationState,
+    attack: &FrameDrivenAttack,
+) -> Vec<(Rect, f32, (f32, f32))> {
+    if attack_state.trigger_serial != attack.trigger_serial
+        || !attack_state.is_active()
+        || animation.current_animation != AnimationType::Attacking
+    {
+        return Vec::new();
+    }
+    let center = transform.translation.truncate();
+    frame_data
+        .hitboxes_at(attack_state.style, animation.current_frame)
+        .map(|frame_box| {
+            (
+                frame_box.world_rect(center, attack.facing),
+                frame_box.damage_scale,
+                frame_box.knockback_scale,
+            )
+        })
+        .collect()
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct HitboxDebugOverlay {
+    pub enabled: bool,
+}
+
+/// 调试层生成的矩形，每帧重建
+#[derive(Component)]
+pub struct HitboxDebugShape;
+
+/// F4 打开/关闭判定框调试层
+pub fn toggle_hitbox_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<HitboxDebugOverlay>,
+) {
+    if keyboard.just_pressed(HITBOX_OVERLAY_KEY) {
+        overlay.enabled = !overlay.enabled;
+    }
+}
+
+type OverlayPlayerItem<'a> = (
+    &'a Transform,
+    &'a CollisionBox,
+    Option<&'a SpriteAnimation>,
+    Option<&'a AttackAnimationState>,
+    Option<&'a FacingDirection>,
+    Option<&'a FrameDrivenAttack>,
+);
+
+/// 绘制攻击框（红）、玩家受击框（绿）、敌人碰撞盒（蓝）和单盒刀光判定（橙），
+/// 并在敌人头顶标出当前行为节点
+pub fn draw_hitbox_overlay(
+    mut commands: Commands,
+    overlay: Res<HitboxDebugOverlay>,
+    frame_data: Option<Res<FrameDataMap>>,
+    shapes: Query<Entity, With<HitboxDebugShape>>,
+    players: Query<OverlayPlayerItem, With<Player>>,
+    enemies: Query<(&Transform, &CollisionBox, Option<&EnemyBrain>), With<Enemy>>,
+    slashes: Query<(&Transform, &CollisionBox), With<KnifeSlash>>,
+) {
+    for entity in &shapes {
+        commands.entity(entity).despawn();
+    }
+    if !overlay.enabled {
+        return;
+    }
+
+    let mut rects = Vec::new();
+    for (transform, collision_box, animation, attack_state, facing, attack) in &players {
+        for rect in player_hurtboxes(
+            frame_data.as_deref(),
+            transform,
+            collision_box,
+            animation,
+            attack_state,
+            facing,
+        ) {
+            rects.push((rect, Color::srgba(0.2, 1.0, 0.4, 0.35)));
+        }
+        if let (Some(frame_data), Some(animation), Some(attack_state), Some(attack)) =
+            (frame_data.as_deref(), animation, attack_state, attack)
+        {
+            for (rect, _, _) in
+                active_attack_boxes(frame_data, transform, animation, attack_state, attack)
+            {
+                rects.push((rect, Color::srgba(1.0, 0.2, 0.2, 0.4)));
+            }
+        }
+    }
+    for (transform, collision_box, brain) in &enemies {
+        let rect = collision_rect(transform, collision_box);
+        rects.push((rect, Color::srgba(0.3, 0.5, 1.0, 0.3)));
+        if let Some(brain) = brain {
+            commands.spawn((
+                Text2d::new(brain.node.label()),
+                TextFont {
+                    font_size: FontSize::Px(11.0),
+                    ..default()
+                },
+                TextColor(Color::srgba(1.0, 0.95, 0.55, 0.95)),
+                Transform::from_xyz(rect.center().x, rect.max.y + 10.0, OVERLAY_Z),
+                HitboxDebugShape,
+            ));
+        }
+    }
+    for (transform, collision_box) in &slashes {
+        rects.push((
+            collision_rect(transform, collision_box),
+            Color::srgba(1.0, 0.6, 0.1, 0.4),
+        ));
+    }
+
+    for (rect, color) in rects {
+        commands.spawn((
+            Sprite {
+                color,
+                custom_size: Some(rect.size()),
+                ..default()
+            },
+            Transform::from_translation(rect.center().extend(OVERLAY_Z)),
+            HitboxDebugShape,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::AttackAnimationStyle;
+
+    #[test]
+    fn embedded_frame_data_is_valid_and_follows_frames() {
+        let frame_data = load_frame_data();
+        let style = AttackAnimationStyle::GroundLightRow(1);
+        assert!(frame_data.has_hitboxes(style));
+        assert_eq!(frame_data.hitboxes_at(style, 0).count(), 0);
+        assert_eq!(frame_data.hitboxes_at(style, 2).count(), 1);
+        assert_eq!(frame_data.hitboxes_at(style, 7).count(), 0);
+
+        let hitbox = frame_data.hitboxes_at(style, 2).next().copied().unwrap();
+        let right = hitbox.world_rect(Vec2::ZERO, 1.0);
+        let left = hitbox.world_rect(Vec2::ZERO, -1.0);
+        assert!(right.center().x > 0.0);
+        assert_eq!(left.center().x, -right.center().x);
+    }
+
+    #[test]
+    fn hurtboxes_fall_back_to_collision_box() {
+        let frame_data = load_frame_data();
+        let transform = Transform::from_xyz(10.0, 20.0, 0.0);
+        let collision_box = CollisionBox::new(Vec2::new(40.0, 60.0));
+        let mut animation = SpriteAnimation::default();
+
+        let idle = player_hurtboxes(
+            Some(&frame_data),
+            &transform,
+            &collision_box,
+            Some(&animation),
+            None,
+            None,
+        );
+        assert_eq!(
+            idle,
+            vec![Rect::from_center_size(
+                Vec2::new(10.0, 20.0),
+                Vec2::new(40.0, 60.0)
+            )]
+        );
+
+        animation.current_animation = AnimationType::Crouching;
+        let crouch = player_hurtboxes(
+            Some(&frame_data),
+            &transform,
+            &collision_box,
+            Some(&animation),
+            None,
+            None,
+        );
+        assert_eq!(crouch.len(), 1);
+        assert!(crouch[0].max.y < idle[0].max.y);
+
+        let mut bad = frame_data.clone();
+        bad.attacks
+            .get_mut(&AttackAnimationStyle::Normal)
+            .unwrap()
+            .hitboxes[0]
+            .frames = (3, 1);
+        assert!(bad.validate().is_err());
+    }
+}
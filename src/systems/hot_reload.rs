@@ -0,0 +1,404 @@
+//! 配置热重载
+//!
+//! 启动时 `GameplayTuning::load_from_disk` 与 `load_animation_data` 先给出一份可用的配置；
+//! 随后同样的 RON 文件再作为 Bevy 资源加载一次，之后每次文件变化（需要开启
+//! `hot-reload` 特性，即 Bevy 的 `file_watcher`）都会重新解析、校验并替换：
+//!
+//! - `config/gameplay_tuning.ron` → [`GameplayTuning`]（敌人导演、刀连招窗口、镜头反馈、自动存档）
+//! - `config/move_list.ron` → [`GameplayTuning::moves`]
+//! - `animations/hf_shirou.ron` → [`AnimationDataMap`] 与所有 [`SpriteAnimation`] 的动画片段
+//! - `animations/hf_shirou_frames.ron` → [`FrameDataMap`]（逐帧攻击框/受击框）
+//!
+//! 解析或校验失败时保留当前配置，并通过 [`ShowToast`] 在屏幕上提示错误。
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoadFailedEvent, AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::components::AnimationType;
+use crate::components::FrameDataMap;
+use crate::components::animation_data::{AnimationDataMap, CharacterAnimationData};
+use crate::events::{ShowToast, ToastKind};
+use crate::resources::{GameplayTuning, MoveListTuning};
+use crate::systems::sprite_animation::{SpriteAnimation, validate_profile};
+
+/// 热重载的动画配置对应的角色
+const HOT_RELOAD_CHARACTER: &str = "hf_shirou";
+
+/// 可热重载的 RON 配置
+pub trait RonConfig: Asset + DeserializeOwned {
+    /// 相对 assets 目录的路径
+    const ASSET_PATH: &'static str;
+
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Clone, serde::Deserialize)]
+#[serde(transparent)]
+pub struct GameplayTuningAsset(pub GameplayTuning);
+
+impl RonConfig for GameplayTuningAsset {
+    const ASSET_PATH: &'static str = "config/gameplay_tuning.ron";
+}
+
+#[derive(Asset, TypePath, Debug, Clone, serde::Deserialize)]
+#[serde(transparent)]
+pub struct MoveListAsset(pub MoveListTuning);
+
+impl RonConfig for MoveListAsset {
+    const ASSET_PATH: &'static str = "config/move_list.ron";
+
+    fn validate(&self) -> Result<(), String> {
+        self.0.validate().map_err(|errors| errors.join("; "))
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Clone, serde::Deserialize)]
+#[serde(transparent)]
+pub struct AnimationProfileAsset(pub CharacterAnimationData);
+
+impl RonConfig for AnimationProfileAsset {
+    const ASSET_PATH: &'static str = "animations/hf_shirou.ron";
+
+    fn validate(&self) -> Result<(), String> {
+        validate_profile(HOT_RELOAD_CHARACTER, &self.0)
+    }
+}
+
+#[derive(Asset, TypePath, Debug, Clone, serde::Deserialize)]
+#[serde(transparent)]
+pub struct FrameDataAsset(pub FrameDataMap);
+
+impl RonConfig for FrameDataAsset {
+    const ASSET_PATH: &'static str = "animations/hf_shirou_frames.ron";
+
+    fn validate(&self) -> Result<(), String> {
+        self.0.validate()
+    }
+}
+
+#[derive(Debug)]
+pub enum RonConfigError {
+    Io(std::io::Error),
+    Parse(ron::error::SpannedError),
+    Invalid(String),
+}
+
+impl fmt::Display for RonConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "read failed: {error}"),
+            Self::Parse(error) => write!(f, "parse error at {error}"),
+            Self::Invalid(message) => write!(f, "invalid: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for RonConfigError {}
+
+/// 解析并校验一份配置
+pub fn parse_config<A: RonConfig>(bytes: &[u8]) -> Result<A, RonConfigError> {
+    let config: A = ron::de::from_bytes(bytes).map_err(RonConfigError::Parse)?;
+    config.validate().map_err(RonConfigError::Invalid)?;
+    Ok(config)
+}
+
+/// 通用 RON 配置加载器；每种配置类型注册一个
+#[derive(TypePath)]
+pub struct RonConfigLoader<A>(PhantomData<fn() -> A>);
+
+impl<A> Default for RonConfigLoader<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<A: RonConfig> AssetLoader for RonConfigLoader<A> {
+    type Asset = A;
+    type Settings = ();
+    type Error = RonConfigError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(RonConfigError::Io)?;
+        parse_config(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["ron"]
+    }
+}
+
+/// 热重载配置的资源句柄（保持句柄存活，资源才会被监听）
+#[derive(Resource, Debug, Clone)]
+pub struct ConfigAssetHandles {
+    pub tuning: Handle<GameplayTuningAsset>,
+    pub moves: Handle<MoveListAsset>,
+    pub hf_shirou: Handle<AnimationProfileAsset>,
+    pub hf_shirou_frames: Handle<FrameDataAsset>,
+}
+
+pub fn load_config_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ConfigAssetHandles {
+        tuning: asset_server.load(GameplayTuningAsset::ASSET_PATH),
+        moves: asset_server.load(MoveListAsset::ASSET_PATH),
+        hf_shirou: asset_server.load(AnimationProfileAsset::ASSET_PATH),
+        hf_shirou_frames: asset_server.load(FrameDataAsset::ASSET_PATH),
+    });
+}
+
+/// 本帧是否有新加载或修改的配置；返回配置与“是否为修改”
+fn updated_config<'a, A: Asset>(
+    events: &mut MessageReader<AssetEvent<A>>,
+    assets: &'a Assets<A>,
+    handle: &Handle<A>,
+) -> Option<(&'a A, bool)> {
+    let mut modified = None;
+    for event in events.read() {
+        match event {
+            AssetEvent::Added { id } if *id == handle.id() => {
+                modified = Some(modified.unwrap_or(false));
+            }
+            AssetEvent::Modified { id } if *id == handle.id() => modified = Some(true),
+            _ => {}
+        }
+    }
+    let modified = modified?;
+    assets.get(handle).map(|config| (config, modified))
+}
+
+fn reloaded_toast(path: &str) -> ShowToast {
+    info!("Reloaded {}", path);
+    ShowToast {
+        text: format!("Reloaded {path}"),
+        kind: ToastKind::Info,
+    }
+}
+
+/// 用重新加载的玩法参数替换 [`GameplayTuning`]（招式表保持不变）
+pub fn apply_gameplay_tuning_reloads(
+    mut events: MessageReader<AssetEvent<GameplayTuningAsset>>,
+    assets: Res<Assets<GameplayTuningAsset>>,
+    handles: Option<Res<ConfigAssetHandles>>,
+    tuning: Option<ResMut<GameplayTuning>>,
+    mut toasts: MessageWriter<ShowToast>,
+) {
+    let Some(handles) = handles else {
+        return;
+    };
+    let Some((config, modified)) = updated_config(&mut events, &assets, &handles.tuning) else {
+        return;
+    };
+    let Some(mut tuning) = tuning else {
+        return;
+    };
+
+    let moves = tuning.moves.clone();
+    *tuning = GameplayTuning {
+        moves,
+        ..config.0.clone()
+    };
+    if modified {
+        toasts.write(reloaded_toast(GameplayTuningAsset::ASSET_PATH));
+    }
+}
+
+/// 用重新加载的招式表替换 [`GameplayTuning::moves`]
+pub fn apply_move_list_reloads(
+    mut events: MessageReader<AssetEvent<MoveListAsset>>,
+    assets: Res<Assets<MoveListAsset>>,
+    handles: Option<Res<ConfigAssetHandles>>,
+    tuning: Option<ResMut<GameplayTuning>>,
+    mut toasts: MessageWriter<ShowToast>,
+) {
+    let Some(handles) = handles else {
+        return;
+    };
+    let Some((config, modified)) = updated_config(&mut events, &assets, &handles.moves) else {
+        return;
+    };
+    let Some(mut tuning) = tuning else {
+        return;
+    };
+
+    tuning.moves = Arc::new(config.0.clone());
+    if modified {
+        toasts.write(reloaded_toast(MoveListAsset::ASSET_PATH));
+    }
+}
+
+/// 用重新加载的动画配置替换 HF 士郎的动画片段，已生成的角色立即切换
+pub fn apply_animation_profile_reloads(
+    mut events: MessageReader<AssetEvent<AnimationProfileAsset>>,
+    assets: Res<Assets<AnimationProfileAsset>>,
+    handles: Option<Res<ConfigAssetHandles>>,
+    animation_data: Option<ResMut<AnimationDataMap>>,
+    mut animations: Query<&mut SpriteAnimation>,
+    mut toasts: MessageWriter<ShowToast>,
+) {
+    let Some(handles) = handles else {
+        return;
+    };
+    let Some((config, modified)) = updated_config(&mut events, &assets, &handles.hf_shirou) else {
+        return;
+    };
+
+    if let Some(mut animation_data) = animation_data {
+        animation_data
+            .0
+            .insert(HOT_RELOAD_CHARACTER.to_string(), config.0.clone());
+    }
+    // 目前只有 HF 士郎带 SpriteAnimation
+    for mut animation in &mut animations {
+        animation.animations.clone_from(&config.0.animations);
+        if !animation
+            .animations
+            .contains_key(&animation.current_animation)
+        {
+            animation.current_animation = AnimationType::Idle;
+        }
+        animation.apply_immediate_frame = true;
+    }
+    if modified {
+        toasts.write(reloaded_toast(AnimationProfileAsset::ASSET_PATH));
+    }
+}
+
+/// 用重新加载的逐帧判定替换 [`FrameDataMap`]
+pub fn apply_frame_data_reloads(
+    mut events: MessageReader<AssetEvent<FrameDataAsset>>,
+    assets: Res<Assets<FrameDataAsset>>,
+    handles: Option<Res<ConfigAssetHandles>>,
+    mut commands: Commands,
+    mut toasts: MessageWriter<ShowToast>,
+) {
+    let Some(handles) = handles else {
+        return;
+    };
+    let Some((config, modified)) = updated_config(&mut events, &assets, &handles.hf_shirou_frames)
+    else {
+        return;
+    };
+
+    commands.insert_resource(config.0.clone());
+    if modified {
+        toasts.write(reloaded_toast(FrameDataAsset::ASSET_PATH));
+    }
+}
+
+fn load_failed_toast<A: Asset>(failure: &AssetLoadFailedEvent<A>) -> ShowToast {
+    warn!(
+        "Failed to load config '{}', keeping current values: {}",
+        failure.path, failure.error
+    );
+    ShowToast {
+        text: format!(
+            "{} failed to load, keeping current values:\n{}",
+            failure.path, failure.error
+        ),
+        kind: ToastKind::Error,
+    }
+}
+
+/// 配置加载失败时提示（当前配置保持不变）
+pub fn report_config_load_failures(
+    mut tuning_failures: MessageReader<AssetLoadFailedEvent<GameplayTuningAsset>>,
+    mut move_failures: MessageReader<AssetLoadFailedEvent<MoveListAsset>>,
+    mut profile_failures: MessageReader<AssetLoadFailedEvent<AnimationProfileAsset>>,
+    mut frame_data_failures: MessageReader<AssetLoadFailedEvent<FrameDataAsset>>,
+    mut toasts: MessageWriter<ShowToast>,
+) {
+    toasts.write_batch(tuning_failures.read().map(load_failed_toast));
+    toasts.write_batch(move_failures.read().map(load_failed_toast));
+    toasts.write_batch(profile_failures.read().map(load_failed_toast));
+    toasts.write_batch(frame_data_failures.read().map(load_failed_toast));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_parsing_reports_syntax_and_validation_errors() {
+        let tuning: GameplayTuningAsset =
+            parse_config(b"(enemies: (max_active_enemies: 3))").expect("partial tuning parses");
+        assert_eq!(tuning.0.enemies.max_active_enemies, 3);
+
+        let syntax = parse_config::<GameplayTuningAsset>(b"(enemies: (").unwrap_err();
+        assert!(matches!(syntax, RonConfigError::Parse(_)), "{syntax}");
+
+        let mut moves = MoveListTuning::builtin().as_ref().clone();
+        moves.combo_steps.clear();
+        let moves = ron::to_string(&moves).expect("move list serializes");
+        let invalid = parse_config::<MoveListAsset>(moves.as_bytes()).unwrap_err();
+        assert!(matches!(invalid, RonConfigError::Invalid(_)), "{invalid}");
+
+        let builtin = include_bytes!("../../assets/animations/hf_shirou.ron");
+        assert!(parse_config::<AnimationProfileAsset>(builtin).is_ok());
+    }
+
+    #[test]
+    fn modified_tuning_replaces_resource_but_keeps_moves() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_asset::<GameplayTuningAsset>()
+            .add_message::<ShowToast>()
+            .init_resource::<GameplayTuning>()
+            .add_systems(Update, apply_gameplay_tuning_reloads);
+
+        let custom_moves = Arc::new(MoveListTuning::builtin().as_ref().clone());
+        app.world_mut().resource_mut::<GameplayTuning>().moves = custom_moves.clone();
+
+        let mut loaded = GameplayTuning::default();
+        loaded.enemies.max_active_enemies = 2;
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<GameplayTuningAsset>>()
+            .add(GameplayTuningAsset(loaded));
+        app.insert_resource(ConfigAssetHandles {
+            tuning: handle.clone(),
+            moves: Handle::default(),
+            hf_shirou: Handle::default(),
+            hf_shirou_frames: Handle::default(),
+        });
+        app.update();
+        app.update();
+
+        let tuning = app.world().resource::<GameplayTuning>();
+        assert_eq!(tuning.enemies.max_active_enemies, 2);
+        assert!(Arc::ptr_eq(&tuning.moves, &custom_moves));
+
+        app.world_mut()
+            .resource_mut::<Assets<GameplayTuningAsset>>()
+            .get_mut(&handle)
+            .expect("tuning asset exists")
+            .0
+            .enemies
+            .max_active_enemies = 5;
+        app.update();
+        app.update();
+
+        assert_eq!(
+            app.world()
+                .resource::<GameplayTuning>()
+                .enemies
+                .max_active_enemies,
+            5
+        );
+        assert!(!app.world().resource::<Messages<ShowToast>>().is_empty());
+    }
+}
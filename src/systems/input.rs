@@ -52,6 +52,11 @@ pub struct GameInput {
     pub action2_pressed_this_frame: bool,
     pub jump_pressed_this_frame: bool,
     pub jump_buffer_seconds: f32,
+
+    // 防御输入
+    pub guard: bool, // 按住格挡
+    pub parry_pressed_this_frame: bool,
+    pub dodge_pressed_this_frame: bool,
 }
 
 /// 更新游戏输入系统
@@ -95,6 +100,11 @@ pub fn update_game_input(
         keyboard_input.pressed(KeyCode::KeyJ) || keyboard_input.pressed(KeyCode::KeyZ);
     let new_action2 = keyboard_input.pressed(KeyCode::KeyX);
 
+    // 更新防御输入
+    let new_guard = keyboard_input.pressed(KeyCode::KeyC);
+    game_input.parry_pressed_this_frame = keyboard_input.just_pressed(KeyCode::KeyF);
+    game_input.dodge_pressed_this_frame = keyboard_input.just_pressed(KeyCode::KeyE);
+
     // 更新菜单输入
     let new_confirm =
         keyboard_input.pressed(KeyCode::Enter) || keyboard_input.pressed(KeyCode::Space);
@@ -123,6 +133,7 @@ pub fn update_game_input(
     game_input.crouch = new_crouch;
     game_input.action1 = new_action1;
     game_input.action2 = new_action2;
+    game_input.guard = new_guard;
     game_input.confirm = new_confirm;
     game_input.cancel = new_cancel;
     game_input.pause = new_pause;
@@ -209,5 +220,8 @@ impl GameInput {
         self.action2_pressed_this_frame = false;
         self.jump_pressed_this_frame = false;
         self.jump_buffer_seconds = 0.0;
+        self.guard = false;
+        self.parry_pressed_this_frame = false;
+        self.dodge_pressed_this_frame = false;
     }
 }
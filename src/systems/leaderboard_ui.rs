@@ -0,0 +1,592 @@
+//! 排行榜界面（主菜单浮层）
+//!
+//! 打开或切换榜单时通过 [`LeaderboardState::request`] 向服务器请求前 N 名，
+//! 服务器返回的 `GamePacket::Leaderboard` 由网络系统写回 `LeaderboardState`，
+//! 这里每帧把最新结果渲染成文本行。未连接服务器时只显示离线提示。
+
+use crate::protocol::{LeaderboardEntry, LeaderboardKind, LeaderboardQuery};
+use crate::resources::GameAssets;
+use crate::states::CharacterType;
+use crate::systems::network::{LeaderboardState, NetworkResource};
+use crate::systems::text_constants::LeaderboardText;
+use bevy::prelude::*;
+
+/// 每页显示的名次数
+pub const LEADERBOARD_PAGE_SIZE: u16 = 10;
+
+/// 排行榜浮层根节点，记录当前浏览的榜单
+#[derive(Component)]
+pub struct LeaderboardOverlayRoot {
+    pub query: LeaderboardQuery,
+}
+
+#[derive(Component)]
+pub struct MenuLeaderboardButton;
+
+#[derive(Component)]
+pub struct LeaderboardBackButton;
+
+/// 榜单切换按钮：排行类型或角色筛选
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeaderboardFilterButton {
+    Kind(LeaderboardKind),
+    Character(Option<CharacterType>),
+}
+
+impl LeaderboardFilterButton {
+    fn label(self) -> &'static str {
+        match self {
+            LeaderboardFilterButton::Kind(LeaderboardKind::BestScore) => {
+                LeaderboardText::BEST_SCORE
+            }
+            LeaderboardFilterButton::Kind(LeaderboardKind::FastestClear) => {
+                LeaderboardText::FASTEST_CLEAR
+            }
+            LeaderboardFilterButton::Character(None) => LeaderboardText::ALL_CHARACTERS,
+            LeaderboardFilterButton::Character(Some(character)) => character.as_str(),
+        }
+    }
+
+    fn is_selected(self, query: &LeaderboardQuery) -> bool {
+        match self {
+            LeaderboardFilterButton::Kind(kind) => query.kind == kind,
+            LeaderboardFilterButton::Character(character) => {
+                query.character.as_deref() == character.map(|character| character.as_str())
+            }
+        }
+    }
+
+    fn apply(self, query: &mut LeaderboardQuery) {
+        match self {
+            LeaderboardFilterButton::Kind(kind) => query.kind = kind,
+            LeaderboardFilterButton::Character(character) => {
+                query.character = character.map(|character| character.as_str().to_string());
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct LeaderboardStatusText;
+
+#[derive(Component)]
+pub struct LeaderboardRowsText;
+
+#[derive(Component)]
+pub struct LeaderboardOwnRankText;
+
+type MenuLeaderboardInteractionQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<MenuLeaderboardButton>),
+>;
+
+type LeaderboardFilterInteractionQuery<'w, 's> =
+    Query<'w, 's, (&'static Interaction, &'static LeaderboardFilterButton), Changed<Interaction>>;
+
+type LeaderboardBackInteractionQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<LeaderboardBackButton>),
+>;
+
+type LeaderboardTextQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Text,
+        Has<LeaderboardStatusText>,
+        Has<LeaderboardRowsText>,
+    ),
+    Or<(
+        With<LeaderboardStatusText>,
+        With<LeaderboardRowsText>,
+        With<LeaderboardOwnRankText>,
+    )>,
+>;
+
+mod palette {
+    use bevy::prelude::Color;
+
+    pub const OVERLAY: Color = Color::srgba(0.02, 0.02, 0.04, 0.72);
+    pub const PANEL: Color = Color::srgba(0.07, 0.075, 0.09, 0.97);
+    pub const PANEL_BORDER: Color = Color::srgba(0.95, 0.74, 0.42, 0.36);
+    pub const TITLE: Color = Color::srgba(0.95, 0.92, 0.86, 1.0);
+    pub const LABEL: Color = Color::srgba(0.78, 0.78, 0.84, 0.96);
+    pub const MUTED_LABEL: Color = Color::srgba(0.58, 0.60, 0.67, 0.92);
+    pub const OWN_RANK: Color = Color::srgba(0.95, 0.63, 0.24, 0.96);
+    pub const BTN_IDLE: Color = Color::srgba(0.13, 0.14, 0.18, 0.94);
+    pub const BTN_SELECTED: Color = Color::srgba(0.35, 0.24, 0.16, 0.98);
+    pub const BTN_BORDER_IDLE: Color = Color::srgba(0.72, 0.58, 0.36, 0.32);
+    pub const BACK_IDLE: Color = Color::srgba(0.11, 0.12, 0.15, 0.92);
+    pub const BACK_HOVER: Color = Color::srgba(0.22, 0.18, 0.13, 0.96);
+}
+
+/// 榜单数值的显示格式：分数取整，通关用时显示为 `分:秒.百分秒`
+pub fn format_leaderboard_value(kind: LeaderboardKind, value: f64) -> String {
+    match kind {
+        LeaderboardKind::BestScore => format!("{:.0}", value),
+        LeaderboardKind::FastestClear => {
+            let centis = (value.max(0.0) * 100.0).round() as u64;
+            format!(
+                "{}:{:02}.{:02}",
+                centis / 6000,
+                (centis / 100) % 60,
+                centis % 100
+            )
+        }
+    }
+}
+
+fn format_entry(kind: LeaderboardKind, entry: &LeaderboardEntry) -> String {
+    format!(
+        "#{:<3} {:<20} {:<8} {}",
+        entry.rank,
+        entry.username,
+        entry.character,
+        format_leaderboard_value(kind, entry.value)
+    )
+}
+
+/// 浮层显示内容：状态提示、名次行、自己的名次
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderboardView {
+    pub status: String,
+    pub rows: String,
+    pub own_rank: String,
+}
+
+/// 根据当前请求状态生成浮层文本
+pub fn leaderboard_view(
+    state: &LeaderboardState,
+    query: &LeaderboardQuery,
+    online: bool,
+) -> LeaderboardView {
+    let page = state.page.as_ref().filter(|page| page.query == *query);
+    let status = if state.pending.as_ref() == Some(query) {
+        LeaderboardText::LOADING.to_string()
+    } else if let Some(reason) = &state.unavailable_reason {
+        reason.clone()
+    } else if page.is_none() && !online {
+        LeaderboardText::OFFLINE.to_string()
+    } else if page.is_some_and(|page| page.entries.is_empty()) {
+        LeaderboardText::EMPTY.to_string()
+    } else {
+        String::new()
+    };
+
+    let rows = page
+        .map(|page| {
+            page.entries
+                .iter()
+                .map(|entry| format_entry(query.kind, entry))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default();
+    let own_rank = page
+        .and_then(|page| page.own_entry.as_ref())
+        .map(|entry| {
+            format!(
+                "{} #{}  {}",
+                LeaderboardText::OWN_RANK_PREFIX,
+                entry.rank,
+                format_leaderboard_value(query.kind, entry.value)
+            )
+        })
+        .unwrap_or_else(|| LeaderboardText::UNRANKED.to_string());
+
+    LeaderboardView {
+        status,
+        rows,
+        own_rank,
+    }
+}
+
+fn request_page(
+    leaderboard: Option<&mut LeaderboardState>,
+    net: Option<&NetworkResource>,
+    query: &LeaderboardQuery,
+) {
+    if let (Some(leaderboard), Some(net)) = (leaderboard, net) {
+        leaderboard.request(net, query.clone(), LEADERBOARD_PAGE_SIZE);
+    }
+}
+
+pub fn open_leaderboard_overlay(
+    commands: &mut Commands,
+    font: Handle<Font>,
+    query: LeaderboardQuery,
+    existing: &Query<(), With<LeaderboardOverlayRoot>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    let filters = [
+        vec![
+            LeaderboardFilterButton::Kind(LeaderboardKind::BestScore),
+            LeaderboardFilterButton::Kind(LeaderboardKind::FastestClear),
+        ],
+        vec![
+            LeaderboardFilterButton::Character(None),
+            LeaderboardFilterButton::Character(Some(CharacterType::Shirou)),
+            LeaderboardFilterButton::Character(Some(CharacterType::Sakura)),
+        ],
+    ];
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                position_type: PositionType::Absolute,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            BackgroundColor(palette::OVERLAY),
+            ZIndex(20),
+            LeaderboardOverlayRoot {
+                query: query.clone(),
+            },
+        ))
+        .with_children(|root| {
+            root.spawn((
+                Node {
+                    width: Val::Percent(90.0),
+                    min_width: Val::Px(360.0),
+                    max_width: Val::Px(560.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Stretch,
+                    padding: UiRect::axes(Val::Px(30.0), Val::Px(26.0)),
+                    row_gap: Val::Px(14.0),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BackgroundColor(palette::PANEL),
+                BorderColor::all(palette::PANEL_BORDER),
+            ))
+            .with_children(|panel| {
+                panel.spawn((
+                    Text::new(LeaderboardText::TITLE),
+                    TextFont {
+                        font: font.clone().into(),
+                        font_size: FontSize::Px(30.0),
+                        ..default()
+                    },
+                    TextColor(palette::TITLE),
+                ));
+
+                for row in filters {
+                    panel
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(8.0),
+                            ..default()
+                        })
+                        .with_children(|row_node| {
+                            for filter in row {
+                                let background = if filter.is_selected(&query) {
+                                    palette::BTN_SELECTED
+                                } else {
+                                    palette::BTN_IDLE
+                                };
+                                row_node
+                                    .spawn((
+                                        Button,
+                                        Node {
+                                            padding: UiRect::axes(Val::Px(14.0), Val::Px(8.0)),
+                                            border: UiRect::all(Val::Px(1.0)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..default()
+                                        },
+                                        BackgroundColor(background),
+                                        BorderColor::all(palette::BTN_BORDER_IDLE),
+                                        filter,
+                                    ))
+                                    .with_children(|btn| {
+                                        btn.spawn((
+                                            Text::new(filter.label()),
+                                            TextFont {
+                                                font: font.clone().into(),
+                                                font_size: FontSize::Px(15.0),
+                                                ..default()
+                                            },
+                                            TextColor(palette::TITLE),
+                                        ));
+                                    });
+                            }
+                        });
+                }
+
+                panel.spawn((
+                    Text::new(LeaderboardText::LOADING),
+                    TextFont {
+                        font: font.clone().into(),
+                        font_size: FontSize::Px(14.0),
+                        ..default()
+                    },
+                    TextColor(palette::MUTED_LABEL),
+                    LeaderboardStatusText,
+                ));
+
+                panel.spawn((
+                    Text::new(""),
+                    TextFont {
+                        font: font.clone().into(),
+                        font_size: FontSize::Px(16.0),
+                        ..default()
+                    },
+                    TextColor(palette::LABEL),
+                    Node {
+                        min_height: Val::Px(220.0),
+                        ..default()
+                    },
+                    LeaderboardRowsText,
+                ));
+
+                panel.spawn((
+                    Text::new(LeaderboardText::UNRANKED),
+                    TextFont {
+                        font: font.clone().into(),
+                        font_size: FontSize::Px(16.0),
+                        ..default()
+                    },
+                    TextColor(palette::OWN_RANK),
+                    LeaderboardOwnRankText,
+                ));
+
+                panel
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(44.0),
+                            margin: UiRect::top(Val::Px(8.0)),
+                            border: UiRect::all(Val::Px(1.0)),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(palette::BACK_IDLE),
+                        BorderColor::all(palette::BTN_BORDER_IDLE),
+                        LeaderboardBackButton,
+                    ))
+                    .with_children(|btn| {
+                        btn.spawn((
+                            Text::new(LeaderboardText::BACK),
+                            TextFont {
+                                font: font.into(),
+                                font_size: FontSize::Px(18.0),
+                                ..default()
+                            },
+                            TextColor(palette::TITLE),
+                        ));
+                    });
+            });
+        });
+}
+
+/// 主菜单排行榜按钮：打开浮层并请求默认榜单（全角色最高分）
+pub fn handle_menu_leaderboard_button(
+    mut commands: Commands,
+    mut interaction_query: MenuLeaderboardInteractionQuery,
+    game_assets: Option<Res<GameAssets>>,
+    net: Option<Res<NetworkResource>>,
+    mut leaderboard: Option<ResMut<LeaderboardState>>,
+    existing: Query<(), With<LeaderboardOverlayRoot>>,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                if existing.is_empty() {
+                    let query = LeaderboardQuery {
+                        kind: LeaderboardKind::BestScore,
+                        character: None,
+                    };
+                    request_page(leaderboard.as_deref_mut(), net.as_deref(), &query);
+                    let font = game_assets
+                        .as_ref()
+                        .map(|assets| assets.font.clone())
+                        .unwrap_or_default();
+                    open_leaderboard_overlay(&mut commands, font, query, &existing);
+                }
+                *color = BackgroundColor(Color::srgba(0.22, 0.18, 0.14, 0.95));
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(Color::srgba(0.20, 0.17, 0.14, 0.92));
+            }
+            Interaction::None => {
+                *color = BackgroundColor(Color::srgba(0.14, 0.12, 0.10, 0.85));
+            }
+        }
+    }
+}
+
+/// 切换排行类型/角色筛选并重新请求
+pub fn handle_leaderboard_filter_buttons(
+    interaction_query: LeaderboardFilterInteractionQuery,
+    mut root_query: Query<&mut LeaderboardOverlayRoot>,
+    net: Option<Res<NetworkResource>>,
+    mut leaderboard: Option<ResMut<LeaderboardState>>,
+) {
+    let Ok(mut root) = root_query.single_mut() else {
+        return;
+    };
+
+    for (interaction, filter) in &interaction_query {
+        if *interaction != Interaction::Pressed || filter.is_selected(&root.query) {
+            continue;
+        }
+        filter.apply(&mut root.query);
+        request_page(leaderboard.as_deref_mut(), net.as_deref(), &root.query);
+    }
+}
+
+/// 把请求状态与结果同步到浮层文本和筛选按钮高亮
+pub fn update_leaderboard_display(
+    root_query: Query<&LeaderboardOverlayRoot>,
+    leaderboard: Option<Res<LeaderboardState>>,
+    net: Option<Res<NetworkResource>>,
+    mut text_query: LeaderboardTextQuery,
+    mut filter_query: Query<(&LeaderboardFilterButton, &mut BackgroundColor)>,
+) {
+    let Ok(root) = root_query.single() else {
+        return;
+    };
+    let online = net
+        .as_deref()
+        .is_some_and(|net| net.status == crate::systems::network::NetworkStatus::Connected);
+    let view = match leaderboard.as_deref() {
+        Some(state) => leaderboard_view(state, &root.query, online),
+        None => leaderboard_view(&LeaderboardState::default(), &root.query, false),
+    };
+
+    for (mut text, is_status, is_rows) in &mut text_query {
+        let content = if is_status {
+            &view.status
+        } else if is_rows {
+            &view.rows
+        } else {
+            &view.own_rank
+        };
+        if text.0 != *content {
+            text.0.clone_from(content);
+        }
+    }
+
+    for (filter, mut color) in &mut filter_query {
+        let target = if filter.is_selected(&root.query) {
+            palette::BTN_SELECTED
+        } else {
+            palette::BTN_IDLE
+        };
+        if color.0 != target {
+            *color = BackgroundColor(target);
+        }
+    }
+}
+
+fn despawn_leaderboard_overlays(
+    commands: &mut Commands,
+    overlays: impl IntoIterator<Item = Entity>,
+) {
+    for entity in overlays {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn cleanup_leaderboard_overlay(
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<LeaderboardOverlayRoot>>,
+) {
+    despawn_leaderboard_overlays(&mut commands, overlay_query.iter());
+}
+
+pub fn close_leaderboard_overlay_on_escape(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<LeaderboardOverlayRoot>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        despawn_leaderboard_overlays(&mut commands, overlay_query.iter());
+    }
+}
+
+pub fn handle_leaderboard_back_button(
+    mut interaction_query: LeaderboardBackInteractionQuery,
+    mut commands: Commands,
+    overlay_query: Query<Entity, With<LeaderboardOverlayRoot>>,
+) {
+    for (interaction, mut color) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                despawn_leaderboard_overlays(&mut commands, overlay_query.iter());
+            }
+            Interaction::Hovered => {
+                *color = BackgroundColor(palette::BACK_HOVER);
+            }
+            Interaction::None => {
+                *color = BackgroundColor(palette::BACK_IDLE);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::LeaderboardPage;
+
+    #[test]
+    fn clear_times_render_as_minutes_and_scores_as_integers() {
+        assert_eq!(
+            format_leaderboard_value(LeaderboardKind::FastestClear, 83.456),
+            "1:23.46"
+        );
+        assert_eq!(
+            format_leaderboard_value(LeaderboardKind::BestScore, 1250.0),
+            "1250"
+        );
+    }
+
+    #[test]
+    fn view_shows_rows_for_the_selected_board_and_own_rank() {
+        let query = LeaderboardQuery {
+            kind: LeaderboardKind::BestScore,
+            character: Some("Sakura".to_string()),
+        };
+        let entry = LeaderboardEntry {
+            rank: 3,
+            username: "rin".to_string(),
+            character: "Sakura".to_string(),
+            value: 900.0,
+        };
+        let state = LeaderboardState {
+            pending: None,
+            page: Some(LeaderboardPage {
+                query: query.clone(),
+                entries: vec![entry.clone()],
+                own_entry: Some(entry),
+            }),
+            unavailable_reason: None,
+        };
+
+        let view = leaderboard_view(&state, &query, true);
+        assert!(view.status.is_empty());
+        assert!(view.rows.contains("rin"));
+        assert_eq!(view.own_rank, "Your rank: #3  900");
+
+        let other = LeaderboardQuery {
+            character: None,
+            ..query
+        };
+        let offline = leaderboard_view(&state, &other, false);
+        assert_eq!(offline.status, LeaderboardText::OFFLINE);
+        assert!(offline.rows.is_empty());
+    }
+}
@@ -345,6 +345,49 @@ pub fn setup_menu(
                                 ));
                             }
                         });
+
+                    parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(200.0),
+                                height: Val::Px(50.0),
+                                border: UiRect::all(Val::Px(2.0)),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                ..default()
+                            },
+                            BorderColor::all(Color::srgba(0.75, 0.62, 0.38, 0.9)),
+                            BackgroundColor(Color::srgba(0.14, 0.12, 0.10, 0.85)),
+                            crate::systems::leaderboard_ui::MenuLeaderboardButton,
+                        ))
+                        .with_children(|parent| {
+                            if let Some(assets) = &game_assets {
+                                parent.spawn((
+                                    Text::new(
+                                        crate::systems::text_constants::MainMenuText::LEADERBOARD,
+                                    ),
+                                    TextFont {
+                                        font: assets.font.clone().into(),
+                                        font_size: FontSize::Px(18.0),
+                                        ..default()
+                                    },
+                                    TextColor(Color::srgba(0.92, 0.88, 0.82, 1.0)),
+                                ));
+                            } else {
+                                parent.spawn((
+                                    Text::new(
+                                        crate::systems::text_constants::MainMenuText::LEADERBOARD,
+                                    ),
+                                    TextFont {
+                                        font_size: FontSize::Px(18.0),
+                                        ..default()
+                                    },
+                                    TextColor(Color::srgba(0.92, 0.88, 0.82, 1.0)),
+                                ));
+                            }
+                        });
                 });
 
             // 角色选择按钮
@@ -468,6 +511,8 @@ pub fn handle_start_button(
                 game_stats.distance_traveled = 0.0;
                 game_stats.jump_count = 0;
                 game_stats.play_time = 0.0;
+                game_stats.combat = Default::default();
+                game_stats.untrusted_save = false;
 
                 // 清理暂停管理器状态
                 pause_manager.clear_pause_state();
@@ -570,7 +615,7 @@ pub fn handle_character_select(
     for (interaction, mut color, button) in &mut interaction_query {
         match *interaction {
             Interaction::Pressed => {
-                character_selection.selected_character = button.character_type.clone();
+                character_selection.selected_character = button.character_type;
                 crate::debug_log!("选择角色: {:?}", button.character_type);
 
                 // 更新按钮颜色表示选中状态
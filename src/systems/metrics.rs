@@ -0,0 +1,414 @@
+//! 运行指标注册表
+//!
+//! 服务端与客户端共用的指标存储：计数器、仪表与直方图按名称和标签分组，
+//! 可渲染为 OpenMetrics 文本（服务端运维接口 `/metrics`），
+//! 也可压缩成几行文字供客户端调试面板显示。
+//!
+//! 已有的统计资源（快照带宽、Redis 写入队列、重连耗时、输入同步计数）保持不变，
+//! 由导出系统每帧把它们的当前值写入注册表；帧/tick 耗时与逐系统耗时在这里直接测量。
+
+use bevy::platform::time::Instant;
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// 帧/tick/系统耗时直方图的桶（秒）
+pub const DURATION_BUCKETS_SECS: &[f64] = &[
+    0.0001, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.0167, 0.025, 0.05, 0.1, 0.25,
+];
+/// 断线重连耗时直方图的桶（秒）
+pub const RECONNECT_BUCKETS_SECS: &[f64] = &[0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 30.0, 60.0];
+
+/// OpenMetrics 文本的 Content-Type
+pub const OPENMETRICS_CONTENT_TYPE: &str =
+    "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MetricKind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl MetricKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Counter => "counter",
+            Self::Gauge => "gauge",
+            Self::Histogram => "histogram",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Histogram {
+    bounds: &'static [f64],
+    /// 每个桶的累计计数（`le` 语义），最后一个是 `+Inf`
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: vec![0; bounds.len() + 1],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        for (bucket, bound) in self
+            .buckets
+            .iter_mut()
+            .zip(self.bounds.iter().copied().chain([f64::INFINITY]))
+        {
+            if value <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+
+    fn mean(&self) -> f64 {
+        if self.count == 0 {
+            0.0
+        } else {
+            self.sum / self.count as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Series {
+    Value(f64),
+    Histogram(Histogram),
+}
+
+#[derive(Debug, Clone)]
+struct MetricFamily {
+    kind: MetricKind,
+    help: &'static str,
+    /// 键为渲染好的标签串（`a="1",b="2"`），无标签时为空串
+    series: BTreeMap<String, Series>,
+}
+
+fn label_key(labels: &[(&str, &str)]) -> String {
+    labels
+        .iter()
+        .map(|(name, value)| {
+            let escaped = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{name}=\"{escaped}\"")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn sample_name(name: &str, suffix: &str, labels: &str) -> String {
+    if labels.is_empty() {
+        format!("{name}{suffix}")
+    } else {
+        format!("{name}{suffix}{{{labels}}}")
+    }
+}
+
+/// 共享的指标注册表；克隆得到同一份数据（服务端运维接口在 Tokio 任务中读取）
+#[derive(Resource, Clone, Default)]
+pub struct MetricsRegistry(Arc<Mutex<BTreeMap<&'static str, MetricFamily>>>);
+
+impl MetricsRegistry {
+    fn family<T>(
+        &self,
+        name: &'static str,
+        kind: MetricKind,
+        help: &'static str,
+        f: impl FnOnce(&mut MetricFamily) -> T,
+    ) -> T {
+        let mut families = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let family = families.entry(name).or_insert_with(|| MetricFamily {
+            kind,
+            help,
+            series: BTreeMap::new(),
+        });
+        f(family)
+    }
+
+    /// 写入计数器的当前累计值（来源统计本身单调递增）
+    pub fn set_counter(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&str, &str)],
+        total: f64,
+    ) {
+        self.family(name, MetricKind::Counter, help, |family| {
+            family
+                .series
+                .insert(label_key(labels), Series::Value(total));
+        });
+    }
+
+    pub fn set_gauge(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        self.family(name, MetricKind::Gauge, help, |family| {
+            family
+                .series
+                .insert(label_key(labels), Series::Value(value));
+        });
+    }
+
+    /// 用一组新样本替换整个仪表族（例如按连接的 RTT，断开的连接随之消失）
+    pub fn replace_gauges(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        samples: &[(&[(&str, &str)], f64)],
+    ) {
+        self.family(name, MetricKind::Gauge, help, |family| {
+            family.series = samples
+                .iter()
+                .map(|(labels, value)| (label_key(labels), Series::Value(*value)))
+                .collect();
+        });
+    }
+
+    pub fn observe(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&str, &str)],
+        buckets: &'static [f64],
+        value: f64,
+    ) {
+        self.family(name, MetricKind::Histogram, help, |family| {
+            let series = family
+                .series
+                .entry(label_key(labels))
+                .or_insert_with(|| Series::Histogram(Histogram::new(buckets)));
+            if let Series::Histogram(histogram) = series {
+                histogram.observe(value);
+            }
+        });
+    }
+
+    /// 读取无标签（或指定标签）的计数器/仪表值
+    pub fn value(&self, name: &str, labels: &[(&str, &str)]) -> Option<f64> {
+        let families = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        match families.get(name)?.series.get(&label_key(labels))? {
+            Series::Value(value) => Some(*value),
+            Series::Histogram(_) => None,
+        }
+    }
+
+    /// OpenMetrics 文本，以 `# EOF` 结尾
+    pub fn render(&self) -> String {
+        let families = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut out = String::new();
+        for (name, family) in families.iter() {
+            let _ = writeln!(out, "# TYPE {name} {}", family.kind.as_str());
+            let _ = writeln!(out, "# HELP {name} {}", family.help);
+            for (labels, series) in &family.series {
+                match series {
+                    Series::Value(value) => {
+                        let suffix = if family.kind == MetricKind::Counter {
+                            "_total"
+                        } else {
+                            ""
+                        };
+                        let _ = writeln!(out, "{} {value}", sample_name(name, suffix, labels));
+                    }
+                    Series::Histogram(histogram) => {
+                        let bounds = histogram.bounds.iter().map(f64::to_string);
+                        for (bound, count) in
+                            bounds.chain(["+Inf".to_string()]).zip(&histogram.buckets)
+                        {
+                            let le = if labels.is_empty() {
+                                format!("le=\"{bound}\"")
+                            } else {
+                                format!("{labels},le=\"{bound}\"")
+                            };
+                            let _ = writeln!(out, "{name}_bucket{{{le}}} {count}");
+                        }
+                        let _ = writeln!(
+                            out,
+                            "{} {}",
+                            sample_name(name, "_sum", labels),
+                            histogram.sum
+                        );
+                        let _ = writeln!(
+                            out,
+                            "{} {}",
+                            sample_name(name, "_count", labels),
+                            histogram.count
+                        );
+                    }
+                }
+            }
+        }
+        out.push_str("# EOF\n");
+        out
+    }
+
+    /// 调试面板用的紧凑摘要：每个样本一行，直方图显示次数与均值（毫秒）
+    pub fn summary_lines(&self) -> Vec<String> {
+        let families = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut lines = Vec::new();
+        for (name, family) in families.iter() {
+            let short = name.strip_prefix("gengine_").unwrap_or(name);
+            for (labels, series) in &family.series {
+                let label = if labels.is_empty() {
+                    short.to_string()
+                } else {
+                    format!("{short}{{{labels}}}")
+                };
+                match series {
+                    Series::Value(value) => lines.push(format!("{label} = {value}")),
+                    Series::Histogram(histogram) => lines.push(format!(
+                        "{label}: n={} avg={:.2}ms",
+                        histogram.count,
+                        histogram.mean() * 1000.0
+                    )),
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// 固定帧（tick）与其中各系统的计时
+#[derive(Resource, Default)]
+pub struct TickTimer {
+    tick_started: Option<Instant>,
+    stage_started: Option<Instant>,
+}
+
+/// `FixedFirst`：开始计时
+pub fn begin_tick_timing(mut timer: ResMut<TickTimer>) {
+    let now = Instant::now();
+    timer.tick_started = Some(now);
+    timer.stage_started = Some(now);
+}
+
+/// 放在链式系统之间：记录上一个系统的耗时到 `gengine_system_duration_seconds{system}`
+pub fn time_stage(system: &'static str) -> impl FnMut(ResMut<TickTimer>, Res<MetricsRegistry>) {
+    move |mut timer: ResMut<TickTimer>, registry: Res<MetricsRegistry>| {
+        let now = Instant::now();
+        if let Some(started) = timer.stage_started.replace(now) {
+            registry.observe(
+                "gengine_system_duration_seconds",
+                "Wall time of individual fixed-update systems",
+                &[("system", system)],
+                DURATION_BUCKETS_SECS,
+                now.duration_since(started).as_secs_f64(),
+            );
+        }
+    }
+}
+
+/// `FixedLast`：记录整个 tick 的耗时
+pub fn end_tick_timing(mut timer: ResMut<TickTimer>, registry: Res<MetricsRegistry>) {
+    if let Some(started) = timer.tick_started.take() {
+        registry.observe(
+            "gengine_tick_duration_seconds",
+            "Wall time of one fixed-update tick",
+            &[],
+            DURATION_BUCKETS_SECS,
+            started.elapsed().as_secs_f64(),
+        );
+    }
+    timer.stage_started = None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_follows_openmetrics_layout() {
+        let registry = MetricsRegistry::default();
+        registry.set_counter(
+            "gengine_snapshots",
+            "Snapshots broadcast",
+            &[("kind", "full")],
+            3.0,
+        );
+        registry.set_gauge("gengine_entities", "Networked entities", &[], 2.0);
+        for value in [0.002, 0.02, 2.0] {
+            registry.observe(
+                "gengine_tick_duration_seconds",
+                "Tick time",
+                &[],
+                &[0.01, 0.1],
+                value,
+            );
+        }
+
+        let text = registry.render();
+        assert!(text.contains("# TYPE gengine_snapshots counter\n"));
+        assert!(text.contains("gengine_snapshots_total{kind=\"full\"} 3\n"));
+        assert!(text.contains("gengine_entities 2\n"));
+        assert!(text.contains("gengine_tick_duration_seconds_bucket{le=\"0.01\"} 1\n"));
+        assert!(text.contains("gengine_tick_duration_seconds_bucket{le=\"0.1\"} 2\n"));
+        assert!(text.contains("gengine_tick_duration_seconds_bucket{le=\"+Inf\"} 3\n"));
+        assert!(text.contains("gengine_tick_duration_seconds_count 3\n"));
+        assert!(text.ends_with("# EOF\n"));
+    }
+
+    #[test]
+    fn replaced_gauges_drop_stale_series() {
+        let registry = MetricsRegistry::default();
+        registry.replace_gauges(
+            "gengine_client_rtt_seconds",
+            "RTT",
+            &[(&[("client_id", "1")], 0.03)],
+        );
+        registry.replace_gauges(
+            "gengine_client_rtt_seconds",
+            "RTT",
+            &[(&[("client_id", "2")], 0.05)],
+        );
+
+        assert_eq!(
+            registry.value("gengine_client_rtt_seconds", &[("client_id", "2")]),
+            Some(0.05)
+        );
+        assert_eq!(
+            registry.value("gengine_client_rtt_seconds", &[("client_id", "1")]),
+            None
+        );
+        assert_eq!(
+            registry.summary_lines(),
+            vec!["client_rtt_seconds{client_id=\"2\"} = 0.05".to_string()]
+        );
+    }
+
+    #[test]
+    fn fixed_tick_timing_records_tick_and_stage_histograms() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<MetricsRegistry>()
+            .init_resource::<TickTimer>()
+            .add_systems(
+                Update,
+                (begin_tick_timing, time_stage("noop"), end_tick_timing).chain(),
+            );
+        app.update();
+        app.update();
+
+        let text = app.world().resource::<MetricsRegistry>().render();
+        assert!(text.contains("gengine_tick_duration_seconds_count 2\n"));
+        assert!(text.contains("gengine_system_duration_seconds_count{system=\"noop\"} 2\n"));
+    }
+}
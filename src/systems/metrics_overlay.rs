@@ -0,0 +1,268 @@
+//! 客户端网络指标与调试面板
+//!
+//! [`export_client_metrics`] 每帧把帧耗时、连接状态、重连耗时与输入同步计数写入
+//! [`MetricsRegistry`]；按 F3 切换左上角的调试面板，面板每半秒刷新一次注册表摘要。
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::systems::input::NetworkInputSyncState;
+use crate::systems::metrics::{DURATION_BUCKETS_SECS, MetricsRegistry, RECONNECT_BUCKETS_SECS};
+use crate::systems::network::{
+    NetworkLifecycleState, NetworkReconnectState, NetworkResource, NetworkSnapshotState,
+    NetworkStatus,
+};
+
+/// 调试面板开关键
+pub const METRICS_OVERLAY_KEY: KeyCode = KeyCode::F3;
+const OVERLAY_REFRESH_SECS: f32 = 0.5;
+
+#[derive(Component)]
+pub struct MetricsOverlayRoot;
+
+#[derive(Component)]
+pub struct MetricsOverlayText;
+
+/// 客户端网络相关的统计资源
+#[derive(SystemParam)]
+pub struct ClientNetStats<'w> {
+    net: Res<'w, NetworkResource>,
+    lifecycle: Res<'w, NetworkLifecycleState>,
+    reconnect: Res<'w, NetworkReconnectState>,
+    input_sync: Res<'w, NetworkInputSyncState>,
+    snapshots: Res<'w, NetworkSnapshotState>,
+}
+
+/// 把客户端已有的网络统计写入指标注册表
+pub fn export_client_metrics(
+    registry: Res<MetricsRegistry>,
+    time: Res<Time>,
+    stats: ClientNetStats,
+    mut reported_reconnects: Local<u64>,
+) {
+    let frame_secs = time.delta_secs_f64();
+    if frame_secs > 0.0 {
+        registry.observe(
+            "gengine_client_frame_seconds",
+            "Client frame time",
+            &[],
+            DURATION_BUCKETS_SECS,
+            frame_secs,
+        );
+    }
+
+    let connected = stats.net.status == NetworkStatus::Connected;
+    registry.set_gauge(
+        "gengine_client_connected",
+        "1 while the client is connected to the server",
+        &[],
+        if connected { 1.0 } else { 0.0 },
+    );
+    registry.set_counter(
+        "gengine_client_reconnect_attempts",
+        "Reconnect attempts since start",
+        &[],
+        f64::from(stats.reconnect.attempt_count),
+    );
+
+    // 只观测新增的重连耗时：按单调的重连计数判断新增几条，取耗时列表末尾的对应条目
+    let durations = &stats.lifecycle.reconnect_durations_secs;
+    let completed = stats.lifecycle.completed_reconnects;
+    let new_count = completed.saturating_sub(*reported_reconnects);
+    let new_count = usize::try_from(new_count).map_or(durations.len(), |n| n.min(durations.len()));
+    for duration in &durations[durations.len() - new_count..] {
+        registry.observe(
+            "gengine_client_reconnect_duration_seconds",
+            "Time from disconnect to reconnected",
+            &[],
+            RECONNECT_BUCKETS_SECS,
+            f64::from(*duration),
+        );
+    }
+    *reported_reconnects = completed;
+
+    registry.set_counter(
+        "gengine_client_input_sent",
+        "Input messages sent to the server",
+        &[("stream", "state")],
+        stats.input_sync.sent_state_count as f64,
+    );
+    registry.set_counter(
+        "gengine_client_input_sent",
+        "Input messages sent to the server",
+        &[("stream", "event")],
+        stats.input_sync.sent_event_count as f64,
+    );
+    registry.set_gauge(
+        "gengine_client_server_tick",
+        "Latest server tick seen in a snapshot",
+        &[],
+        stats.snapshots.last_server_tick as f64,
+    );
+}
+
+/// F3 打开/关闭调试面板
+pub fn toggle_metrics_overlay(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    overlays: Query<Entity, With<MetricsOverlayRoot>>,
+) {
+    if !keyboard.just_pressed(METRICS_OVERLAY_KEY) {
+        return;
+    }
+    if overlays.is_empty() {
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(8.0),
+                    top: Val::Px(8.0),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                ZIndex(30),
+                MetricsOverlayRoot,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(""),
+                    TextFont {
+                        font_size: FontSize::Px(12.0),
+                        ..default()
+                    },
+                    TextColor(Color::srgba(0.75, 0.95, 0.75, 1.0)),
+                    MetricsOverlayText,
+                ));
+            });
+    } else {
+        for entity in &overlays {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// 面板打开时定期刷新注册表摘要
+pub fn update_metrics_overlay(
+    time: Res<Time>,
+    registry: Res<MetricsRegistry>,
+    mut texts: Query<&mut Text, With<MetricsOverlayText>>,
+    mut since_refresh: Local<Option<f32>>,
+) {
+    if texts.is_empty() {
+        *since_refresh = None;
+        return;
+    }
+    let elapsed = since_refresh.map_or(OVERLAY_REFRESH_SECS, |secs| secs + time.delta_secs());
+    if elapsed < OVERLAY_REFRESH_SECS {
+        *since_refresh = Some(elapsed);
+        return;
+    }
+    *since_refresh = Some(0.0);
+
+    let summary = registry.summary_lines().join("\n");
+    for mut text in &mut texts {
+        text.0.clone_from(&summary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn client_metrics_report_new_reconnects_once_and_overlay_toggles() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<MetricsRegistry>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<NetworkResource>()
+            .init_resource::<NetworkLifecycleState>()
+            .init_resource::<NetworkReconnectState>()
+            .init_resource::<NetworkInputSyncState>()
+            .init_resource::<NetworkSnapshotState>()
+            .add_systems(
+                Update,
+                (
+                    export_client_metrics,
+                    toggle_metrics_overlay,
+                    update_metrics_overlay,
+                )
+                    .chain(),
+            );
+
+        app.world_mut()
+            .resource_mut::<NetworkLifecycleState>()
+            .record_reconnect(1.5);
+        app.world_mut()
+            .resource_mut::<NetworkInputSyncState>()
+            .sent_event_count = 4;
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(METRICS_OVERLAY_KEY);
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        app.update();
+
+        let registry = app.world().resource::<MetricsRegistry>().clone();
+        let text = registry.render();
+        assert!(text.contains("gengine_client_reconnect_duration_seconds_count 1\n"));
+        assert!(text.contains("gengine_client_input_sent_total{stream=\"event\"} 4\n"));
+        assert_eq!(registry.value("gengine_client_connected", &[]), Some(0.0));
+
+        let mut overlay_texts = app
+            .world_mut()
+            .query_filtered::<&Text, With<MetricsOverlayText>>();
+        let overlay = overlay_texts
+            .single(app.world())
+            .expect("F3 should open the overlay");
+        assert!(
+            overlay
+                .0
+                .contains("client_input_sent{stream=\"event\"} = 4")
+        );
+
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.clear();
+        input.release(METRICS_OVERLAY_KEY);
+        input.clear();
+        input.press(METRICS_OVERLAY_KEY);
+        app.update();
+        let mut roots = app
+            .world_mut()
+            .query_filtered::<Entity, With<MetricsOverlayRoot>>();
+        assert_eq!(roots.iter(app.world()).count(), 0);
+    }
+
+    #[test]
+    fn reconnect_durations_keep_reporting_after_history_is_capped() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<MetricsRegistry>()
+            .init_resource::<NetworkResource>()
+            .init_resource::<NetworkLifecycleState>()
+            .init_resource::<NetworkReconnectState>()
+            .init_resource::<NetworkInputSyncState>()
+            .init_resource::<NetworkSnapshotState>()
+            .add_systems(Update, export_client_metrics);
+
+        // 耗时列表只保留 64 条，之后每次重连仍要被观测到
+        for _ in 0..70 {
+            app.world_mut()
+                .resource_mut::<NetworkLifecycleState>()
+                .record_reconnect(0.5);
+            app.update();
+        }
+        for _ in 0..3 {
+            app.world_mut()
+                .resource_mut::<NetworkLifecycleState>()
+                .record_reconnect(0.5);
+        }
+        app.update();
+
+        let text = app.world().resource::<MetricsRegistry>().render();
+        assert!(text.contains("gengine_client_reconnect_duration_seconds_count 73\n"));
+    }
+}
@@ -7,10 +7,12 @@ pub mod interfaces;
 
 // 核心游戏系统
 pub mod game;
+pub mod hot_reload;
 pub mod setup;
 pub mod sky_level;
 
 // 玩家相关系统
+pub mod defense;
 pub mod input;
 pub mod player;
 pub mod shirou;
@@ -24,10 +26,13 @@ pub mod sprite_animation;
 
 // 物理和碰撞系统
 pub mod collision;
+pub mod hitboxes;
 
 // UI系统
+pub mod leaderboard_ui;
 pub mod menu;
 pub mod settings_ui;
+pub mod toast;
 pub mod ui;
 
 // 音频系统
@@ -38,6 +43,8 @@ pub mod async_file_ops;
 pub mod async_tasks;
 pub mod pause_save;
 pub mod save;
+pub mod save_signing;
+pub mod save_thumbnail;
 pub mod server_file_ops;
 pub mod shared_utils;
 
@@ -45,9 +52,13 @@ pub mod shared_utils;
 pub mod visual_effects;
 
 // 战斗和敌人系统
+pub mod boss;
 pub mod combat;
 pub mod death;
 pub mod enemy;
+pub mod enemy_behavior;
+pub mod scoring;
+pub mod status_effects;
 
 // 文本常量系统
 pub mod text_constants;
@@ -62,9 +73,18 @@ pub mod error_handling;
 pub mod system_sets;
 
 // 网络系统
+pub mod accounts;
+pub mod admin;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod admin_http;
 pub mod ai;
+pub mod metrics;
+pub mod metrics_overlay;
 pub mod network;
 #[cfg(feature = "server")]
 pub mod save_worker;
+pub mod session_recording;
+#[cfg(feature = "server")]
+pub mod session_writer;
 #[cfg(feature = "server")]
 pub mod sync_redis;
@@ -1,7 +1,18 @@
-use crate::protocol::{GamePacket, PlayerAction};
+use crate::events::{CheckpointActivated, SaveWritten, ScoreAwarded, ScoreReason};
+use crate::protocol::{
+    GamePacket, LeaderboardPage, LeaderboardQuery, LobbyEvent, MilestoneKind, PlayerAction,
+};
+use crate::resources::GameStats;
+use crate::states::CharacterSelection;
+use crate::systems::accounts::{
+    AccountIdentity, SESSION_TOKEN_PATH, load_session_token, server_url_with_token,
+    store_session_token,
+};
+use crate::systems::async_file_ops::AsyncFileManager;
 use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tokio::sync::mpsc;
 
@@ -33,6 +44,30 @@ pub struct NetworkConfig {
     pub reconnect_enabled: bool,
     pub reconnect_interval_secs: f32,
     pub heartbeat_interval_secs: f32,
+    /// Session token issued by the server, sent in the handshake on (re)connect
+    pub session_token: Option<String>,
+    /// Where the session token is kept between launches; `None` keeps it in memory only
+    pub token_path: Option<PathBuf>,
+}
+
+impl NetworkConfig {
+    pub fn connect_url(&self) -> String {
+        server_url_with_token(&self.server_url, self.session_token.as_deref())
+    }
+
+    /// Replaces the session token and mirrors it to `token_path`
+    fn set_session_token(&mut self, token: Option<String>) {
+        if let Some(path) = &self.token_path
+            && let Err(error) = store_session_token(path, token.as_deref())
+        {
+            warn!(
+                "Failed to store session token at {}: {}",
+                path.display(),
+                error
+            );
+        }
+        self.session_token = token;
+    }
 }
 
 impl Default for NetworkConfig {
@@ -42,6 +77,8 @@ impl Default for NetworkConfig {
             reconnect_enabled: true,
             reconnect_interval_secs: 2.0,
             heartbeat_interval_secs: 5.0,
+            session_token: None,
+            token_path: Some(PathBuf::from(SESSION_TOKEN_PATH)),
         }
     }
 }
@@ -57,7 +94,10 @@ pub struct NetworkLifecycleState {
     pub last_status: NetworkStatus,
     pub transition_history: Vec<NetworkStatus>,
     pub last_disconnect_time_secs: Option<f32>,
+    /// 最近 64 次重连的耗时
     pub reconnect_durations_secs: Vec<f32>,
+    /// 启动以来完成的重连次数（单调递增，不受耗时列表截断影响）
+    pub completed_reconnects: u64,
 }
 
 impl Default for NetworkLifecycleState {
@@ -67,10 +107,23 @@ impl Default for NetworkLifecycleState {
             transition_history: vec![NetworkStatus::Disconnected],
             last_disconnect_time_secs: None,
             reconnect_durations_secs: Vec::new(),
+            completed_reconnects: 0,
         }
     }
 }
 
+impl NetworkLifecycleState {
+    /// 记录一次完成的重连耗时，只保留最近 64 条
+    pub fn record_reconnect(&mut self, duration_secs: f32) {
+        self.reconnect_durations_secs.push(duration_secs);
+        if self.reconnect_durations_secs.len() > 64 {
+            let drop_count = self.reconnect_durations_secs.len() - 64;
+            self.reconnect_durations_secs.drain(0..drop_count);
+        }
+        self.completed_reconnects = self.completed_reconnects.saturating_add(1);
+    }
+}
+
 impl Default for NetworkResource {
     fn default() -> Self {
         Self {
@@ -106,13 +159,7 @@ fn apply_status_transition(
         }
         NetworkStatus::Connected => {
             if let Some(disconnect_at) = lifecycle.last_disconnect_time_secs.take() {
-                lifecycle
-                    .reconnect_durations_secs
-                    .push((now_secs - disconnect_at).max(0.0));
-                if lifecycle.reconnect_durations_secs.len() > 64 {
-                    let drop_count = lifecycle.reconnect_durations_secs.len() - 64;
-                    lifecycle.reconnect_durations_secs.drain(0..drop_count);
-                }
+                lifecycle.record_reconnect((now_secs - disconnect_at).max(0.0));
             }
         }
         NetworkStatus::Connecting => {}
@@ -289,11 +336,15 @@ fn start_network_connection(net: &mut NetworkResource, server_url: &str) {
 
 pub fn setup_network(
     mut net: ResMut<NetworkResource>,
-    config: Res<NetworkConfig>,
+    mut config: ResMut<NetworkConfig>,
     mut lifecycle: ResMut<NetworkLifecycleState>,
     time: Res<Time>,
 ) {
-    start_network_connection(&mut net, &config.server_url);
+    // 沿用上次启动保存的令牌，避免每次启动都新建游客账号
+    if config.session_token.is_none() {
+        config.session_token = config.token_path.as_deref().and_then(load_session_token);
+    }
+    start_network_connection(&mut net, &config.connect_url());
     if net.status == NetworkStatus::Connecting {
         apply_status_transition(
             &mut net,
@@ -381,7 +432,7 @@ pub fn auto_reconnect_network(
 
             reconnect_state.attempt_count = reconnect_state.attempt_count.wrapping_add(1);
             reconnect_state.cooldown_remaining_secs = config.reconnect_interval_secs.max(0.2);
-            start_network_connection(&mut net, &config.server_url);
+            start_network_connection(&mut net, &config.connect_url());
             if net.status == NetworkStatus::Connecting {
                 apply_status_transition(
                     &mut net,
@@ -436,6 +487,62 @@ pub struct NetworkEntityMap(pub HashMap<u64, Entity>);
 #[derive(Resource, Default)]
 pub struct MyNetworkId(pub Option<u64>);
 
+/// Account the server bound this client to (guest until the player logs in)
+#[derive(Resource, Default)]
+pub struct LocalAccount(pub Option<AccountIdentity>);
+
+/// Leaderboard request/response state shown by the menu overlay
+#[derive(Resource, Debug, Default)]
+pub struct LeaderboardState {
+    pub pending: Option<LeaderboardQuery>,
+    pub page: Option<LeaderboardPage>,
+    pub unavailable_reason: Option<String>,
+}
+
+impl LeaderboardState {
+    /// Asks the server for a page; returns `false` when offline
+    pub fn request(&mut self, net: &NetworkResource, query: LeaderboardQuery, limit: u16) -> bool {
+        let sent = net.status == NetworkStatus::Connected
+            && net.action_tx.as_ref().is_some_and(|tx| {
+                tx.send(PlayerAction::RequestLeaderboard {
+                    query: query.clone(),
+                    limit,
+                })
+                .is_ok()
+            });
+        self.pending = sent.then_some(query);
+        sent
+    }
+}
+
+/// Recent cluster chat lines and lobby notices, oldest first
+#[derive(Resource, Debug, Default)]
+pub struct ChatLog {
+    pub lines: VecDeque<String>,
+}
+
+impl ChatLog {
+    pub const CAPACITY: usize = 50;
+
+    pub fn push(&mut self, line: String) {
+        if self.lines.len() == Self::CAPACITY {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line);
+    }
+
+    /// Sends a chat line to the cluster; returns `false` when offline
+    pub fn send(net: &NetworkResource, text: &str) -> bool {
+        net.status == NetworkStatus::Connected
+            && net.action_tx.as_ref().is_some_and(|tx| {
+                tx.send(PlayerAction::Chat {
+                    text: text.to_string(),
+                })
+                .is_ok()
+            })
+    }
+}
+
 #[derive(Resource, Debug, Clone)]
 pub struct ClientPredictionConfig {
     pub correction_deadzone: f32,
@@ -534,7 +641,12 @@ pub struct NetworkEventParams<'w, 's> {
     net: ResMut<'w, NetworkResource>,
     entity_map: ResMut<'w, NetworkEntityMap>,
     my_id: ResMut<'w, MyNetworkId>,
+    config: ResMut<'w, NetworkConfig>,
+    account: ResMut<'w, LocalAccount>,
+    leaderboard: ResMut<'w, LeaderboardState>,
+    chat: ResMut<'w, ChatLog>,
     prediction_config: Res<'w, ClientPredictionConfig>,
+    file_manager: Option<Res<'w, AsyncFileManager>>,
     snapshot_state: ResMut<'w, NetworkSnapshotState>,
     remote_query:
         Query<'w, 's, RemotePlayerQueryItem<'static>, (With<RemotePlayer>, Without<LocalPlayer>)>,
@@ -573,6 +685,13 @@ pub fn handle_network_events(mut commands: Commands, mut params: NetworkEventPar
                     let _ = tx.send(PlayerAction::ResumeSession { previous_id });
                 }
 
+                // 没有会话令牌时领取游客账号，之后重连凭令牌恢复同一账号
+                if params.config.session_token.is_none()
+                    && let Some(tx) = &params.net.action_tx
+                {
+                    let _ = tx.send(PlayerAction::GuestLogin);
+                }
+
                 if let Ok((entity, _, mut net_id, _)) = params.local_player_query.single_mut() {
                     net_id.0 = id;
                     bind_local_player_id(&mut commands, &mut params.entity_map, entity, id);
@@ -824,6 +943,62 @@ pub fn handle_network_events(mut commands: Commands, mut params: NetworkEventPar
             GamePacket::Pong(id) => {
                 info!("Pong from server: {}", id);
             }
+            GamePacket::AuthAccepted {
+                account,
+                session_token,
+            } => {
+                info!(
+                    "Signed in as {} (guest: {})",
+                    account.username, account.is_guest
+                );
+                params.config.set_session_token(Some(session_token));
+                params.account.0 = Some(account);
+
+                // 向账号登记本机存档密钥，服务器据此校验之后上传的存档
+                if let Some(key) = params
+                    .file_manager
+                    .as_deref()
+                    .and_then(|file_manager| file_manager.signing_key.as_ref())
+                    && let Some(tx) = &params.net.action_tx
+                {
+                    let _ = tx.send(PlayerAction::RegisterSaveKey { key: key.to_hex() });
+                }
+            }
+            GamePacket::Leaderboard(page) => {
+                params.leaderboard.pending = None;
+                params.leaderboard.unavailable_reason = None;
+                params.leaderboard.page = Some(page);
+            }
+            GamePacket::LeaderboardUnavailable { reason } => {
+                params.leaderboard.pending = None;
+                params.leaderboard.unavailable_reason = Some(reason);
+            }
+            GamePacket::Chat { from, text } => {
+                params.chat.push(format!("{}: {}", from, text));
+            }
+            GamePacket::Lobby(LobbyEvent::PlayerOnline { username, .. }) => {
+                params.chat.push(format!("{} is online", username));
+            }
+            GamePacket::Lobby(LobbyEvent::PlayerOffline { username, .. }) => {
+                params.chat.push(format!("{} went offline", username));
+            }
+            GamePacket::SaveAccepted { name } => {
+                params.chat.push(format!("Save '{}' uploaded", name));
+            }
+            GamePacket::SaveRejected { reason } => {
+                warn!("Server refused save: {}", reason);
+                params.chat.push(format!("Save not uploaded: {}", reason));
+            }
+            GamePacket::AuthRejected { reason } => {
+                warn!("Authentication rejected: {}", reason);
+                // 握手令牌失效：丢弃（包括已保存的）令牌并改用游客账号
+                if params.account.0.is_none() && params.config.session_token.is_some() {
+                    params.config.set_session_token(None);
+                    if let Some(tx) = &params.net.action_tx {
+                        let _ = tx.send(PlayerAction::GuestLogin);
+                    }
+                }
+            }
             _ => {}
         }
     }
@@ -868,6 +1043,115 @@ pub fn interpolate_positions(
     }
 }
 
+fn report_milestone(net: &NetworkResource, kind: MilestoneKind) {
+    if net.status != NetworkStatus::Connected {
+        return;
+    }
+    if let Some(tx) = &net.action_tx {
+        let _ = tx.send(PlayerAction::Milestone { kind });
+    }
+}
+
+/// 把本地激活的检查点上报给服务器，写入在线会话记录
+pub fn report_checkpoint_milestones(
+    net: Res<NetworkResource>,
+    mut checkpoints: MessageReader<CheckpointActivated>,
+) {
+    for checkpoint in checkpoints.read() {
+        report_milestone(
+            &net,
+            MilestoneKind::Checkpoint {
+                id: checkpoint.checkpoint_id,
+            },
+        );
+    }
+}
+
+/// 把击杀与无伤清场上报给服务器；分数由服务器按自己的计分表计算。
+/// 从不可信存档继续的一局不上报。
+pub fn report_score_milestones(
+    net: Res<NetworkResource>,
+    game_stats: Res<GameStats>,
+    mut awards: MessageReader<ScoreAwarded>,
+) {
+    for award in awards.read() {
+        if game_stats.untrusted_save {
+            continue;
+        }
+        let kind = match award.reason {
+            ScoreReason::Kill {
+                enemy_type,
+                combo_chain,
+            } => MilestoneKind::Kill {
+                enemy: enemy_type,
+                combo_chain,
+            },
+            ScoreReason::NoDamageClear { arena } => MilestoneKind::NoDamageClear { arena },
+        };
+        report_milestone(&net, kind);
+    }
+}
+
+/// 已登录时把刚写入的签名存档上传给服务器；未签名的存档服务器不会接受，不必上传
+pub fn upload_saved_games(
+    net: Res<NetworkResource>,
+    account: Res<LocalAccount>,
+    file_manager: Option<Res<AsyncFileManager>>,
+    mut saves: MessageReader<SaveWritten>,
+) {
+    let signed = file_manager.is_some_and(|file_manager| file_manager.signing_key.is_some());
+    for save in saves.read() {
+        if !signed || account.0.is_none() || net.status != NetworkStatus::Connected {
+            continue;
+        }
+        if let Some(tx) = &net.action_tx {
+            let _ = tx.send(PlayerAction::UploadSave {
+                name: save.save_name.clone(),
+                save_json: save.save_json.clone(),
+            });
+        }
+    }
+}
+
+pub fn report_death_milestone(net: Res<NetworkResource>) {
+    report_milestone(&net, MilestoneKind::Death);
+}
+
+pub fn report_victory_milestone(net: Res<NetworkResource>, game_stats: Res<GameStats>) {
+    if game_stats.untrusted_save {
+        info!("Run continued from an unverified save, clear time stays local");
+        return;
+    }
+    report_milestone(
+        &net,
+        MilestoneKind::Victory {
+            clear_time_ms: (game_stats.play_time * 1000.0) as u32,
+        },
+    );
+}
+
+/// 新的一局（新游戏或读档）开始时通知服务器，服务器为这一局单独开一条会话记录
+pub fn report_run_started(net: Res<NetworkResource>, selection: Res<CharacterSelection>) {
+    report_milestone(
+        &net,
+        MilestoneKind::RunStarted {
+            character: selection.selected_character,
+        },
+    );
+}
+
+/// 开局时上报所选角色，排行榜按角色区分成绩
+pub fn report_selected_character(net: Res<NetworkResource>, selection: Res<CharacterSelection>) {
+    if net.status != NetworkStatus::Connected {
+        return;
+    }
+    if let Some(tx) = &net.action_tx {
+        let _ = tx.send(PlayerAction::SelectCharacter {
+            character: selection.selected_character,
+        });
+    }
+}
+
 pub fn send_ping_system(input: Res<ButtonInput<KeyCode>>, net: Res<NetworkResource>) {
     if net.status != NetworkStatus::Connected {
         return;
@@ -904,6 +1188,13 @@ mod tests {
             .init_resource::<NetworkResource>()
             .init_resource::<NetworkEntityMap>()
             .init_resource::<MyNetworkId>()
+            .insert_resource(NetworkConfig {
+                token_path: None,
+                ..default()
+            })
+            .init_resource::<LocalAccount>()
+            .init_resource::<LeaderboardState>()
+            .init_resource::<ChatLog>()
             .init_resource::<ClientPredictionConfig>()
             .init_resource::<NetworkSnapshotState>()
             .add_systems(Update, handle_network_events);
@@ -962,6 +1253,101 @@ mod tests {
         assert_eq!(entity_map.0.get(&9), Some(&local_entity));
     }
 
+    #[test]
+    fn welcome_without_token_requests_guest_account_and_stores_issued_token() {
+        let mut app = setup_network_event_app();
+        let (action_tx, mut action_rx) = tokio::sync::mpsc::unbounded_channel::<PlayerAction>();
+        {
+            let mut net = app.world_mut().resource_mut::<NetworkResource>();
+            net.status = NetworkStatus::Connected;
+            net.action_tx = Some(action_tx);
+        }
+
+        let packet_rx = app.world().resource::<NetworkResource>().packet_rx.clone();
+        if let Ok(mut queue) = packet_rx.lock() {
+            queue.push_back(GamePacket::Welcome {
+                id: 3,
+                message: "welcome".to_string(),
+            });
+        }
+        app.update();
+        assert_eq!(action_rx.try_recv().ok(), Some(PlayerAction::GuestLogin));
+
+        let account = AccountIdentity {
+            player_id: uuid::Uuid::from_u128(3),
+            username: "guest_0a0b0c0d0e0f".to_string(),
+            is_guest: true,
+        };
+        if let Ok(mut queue) = packet_rx.lock() {
+            queue.push_back(GamePacket::AuthAccepted {
+                account: account.clone(),
+                session_token: "token-3".to_string(),
+            });
+        }
+        app.update();
+
+        let config = app.world().resource::<NetworkConfig>();
+        assert_eq!(config.session_token.as_deref(), Some("token-3"));
+        assert_eq!(config.connect_url(), "ws://127.0.0.1:8080?token=token-3");
+        assert_eq!(app.world().resource::<LocalAccount>().0, Some(account));
+    }
+
+    #[test]
+    fn issued_token_is_persisted_and_dropped_when_rejected() {
+        let mut app = setup_network_event_app();
+        let token_path =
+            std::env::temp_dir().join(format!("emiyashiro-net-token-{}", uuid::Uuid::new_v4()));
+        app.world_mut().resource_mut::<NetworkConfig>().token_path = Some(token_path.clone());
+        let packet_rx = app.world().resource::<NetworkResource>().packet_rx.clone();
+
+        if let Ok(mut queue) = packet_rx.lock() {
+            queue.push_back(GamePacket::AuthAccepted {
+                account: AccountIdentity {
+                    player_id: uuid::Uuid::from_u128(4),
+                    username: "guest_0102030405ff".to_string(),
+                    is_guest: true,
+                },
+                session_token: "token-4".to_string(),
+            });
+        }
+        app.update();
+        assert_eq!(load_session_token(&token_path).as_deref(), Some("token-4"));
+
+        // 下次启动时握手令牌失效
+        app.world_mut().resource_mut::<LocalAccount>().0 = None;
+        if let Ok(mut queue) = packet_rx.lock() {
+            queue.push_back(GamePacket::AuthRejected {
+                reason: "Session expired, please log in again".to_string(),
+            });
+        }
+        app.update();
+        assert_eq!(app.world().resource::<NetworkConfig>().session_token, None);
+        assert_eq!(load_session_token(&token_path), None);
+    }
+
+    #[test]
+    fn cluster_chat_and_lobby_packets_land_in_chat_log() {
+        let mut app = setup_network_event_app();
+        let packet_rx = app.world().resource::<NetworkResource>().packet_rx.clone();
+        if let Ok(mut queue) = packet_rx.lock() {
+            queue.push_back(GamePacket::Lobby(LobbyEvent::PlayerOnline {
+                username: "rin".to_string(),
+                server_id: "alpha".to_string(),
+            }));
+            queue.push_back(GamePacket::Chat {
+                from: "rin".to_string(),
+                text: "trace on".to_string(),
+            });
+        }
+        app.update();
+
+        let chat = app.world().resource::<ChatLog>();
+        assert_eq!(
+            chat.lines.iter().map(String::as_str).collect::<Vec<_>>(),
+            ["rin is online", "rin: trace on"]
+        );
+    }
+
     #[test]
     fn welcome_replaces_stale_remote_shadow_for_local_id() {
         let mut app = setup_network_event_app();
@@ -8,10 +8,35 @@ use bevy::prelude::*;
 use std::fs;
 use std::path::Path;
 
+/// 快照读取的玩家状态
+pub type SnapshotPlayerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static Velocity,
+        &'static PlayerState,
+        Option<&'static StatusEffects>,
+    ),
+    With<Player>,
+>;
+
+/// 恢复存档时写回的玩家状态
+pub type RestorePlayerQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static mut Transform,
+        &'static mut Velocity,
+        &'static mut PlayerState,
+        Option<&'static mut StatusEffects>,
+    ),
+    With<Player>,
+>;
+
 #[derive(SystemParam)]
 pub struct PauseSnapshotParams<'w, 's> {
-    player_query:
-        Query<'w, 's, (&'static Transform, &'static Velocity, &'static PlayerState), With<Player>>,
+    player_query: SnapshotPlayerQuery<'w, 's>,
     camera_query: Query<'w, 's, &'static Transform, (With<Camera>, Without<Player>)>,
     game_stats: Res<'w, GameStats>,
     character_selection: Res<'w, CharacterSelection>,
@@ -20,38 +45,46 @@ pub struct PauseSnapshotParams<'w, 's> {
 
 #[derive(SystemParam)]
 pub struct RestorePausedParams<'w, 's> {
-    player_query: Query<
-        'w,
-        's,
-        (
-            &'static mut Transform,
-            &'static mut Velocity,
-            &'static mut PlayerState,
-        ),
-        With<Player>,
-    >,
+    player_query: RestorePlayerQuery<'w, 's>,
     camera_query: Query<'w, 's, &'static mut Transform, (With<Camera>, Without<Player>)>,
     game_stats: ResMut<'w, GameStats>,
     character_selection: ResMut<'w, CharacterSelection>,
     audio_state_manager: ResMut<'w, AudioStateManager>,
+    tuning: Option<Res<'w, GameplayTuning>>,
+}
+
+impl PauseSnapshotParams<'_, '_> {
+    /// 以当前帧的世界状态生成完整快照（暂停与自动存档共用）
+    pub fn capture(&self) -> CompleteGameState {
+        capture_game_state(
+            &self.player_query,
+            &self.camera_query,
+            &self.game_stats,
+            &self.character_selection,
+            &self.audio_state_manager,
+        )
+    }
 }
 
 /// 捕获完整游戏状态
 pub fn capture_game_state(
-    player_query: Query<(&Transform, &Velocity, &PlayerState), With<Player>>,
-    camera_query: Query<&Transform, (With<Camera>, Without<Player>)>,
-    game_stats: Res<GameStats>,
-    character_selection: Res<CharacterSelection>,
-    audio_state_manager: Res<AudioStateManager>,
+    player_query: &SnapshotPlayerQuery,
+    camera_query: &Query<&Transform, (With<Camera>, Without<Player>)>,
+    game_stats: &GameStats,
+    character_selection: &CharacterSelection,
+    audio_state_manager: &AudioStateManager,
 ) -> CompleteGameState {
     let mut state = CompleteGameState::default();
 
     // 捕获玩家状态
-    if let Ok((player_transform, player_velocity, player_state)) = player_query.single() {
+    if let Ok((player_transform, player_velocity, player_state, status_effects)) =
+        player_query.single()
+    {
         state.player_position = player_transform.translation;
         state.player_velocity = player_velocity.clone();
         state.player_grounded = player_state.is_grounded;
         state.player_crouching = player_state.is_crouching;
+        state.player_status_effects = status_effects.cloned().unwrap_or_default();
 
         // 根据玩家状态确定动画状态
         state.player_animation_state = if player_state.is_crouching {
@@ -74,13 +107,15 @@ pub fn capture_game_state(
     }
 
     // 捕获游戏统计
-    state.score = (game_stats.distance_traveled * 10.0) as u32 + game_stats.jump_count * 50;
+    state.score = game_stats.score();
     state.distance_traveled = game_stats.distance_traveled;
     state.jump_count = game_stats.jump_count;
     state.play_time = game_stats.play_time;
+    state.combat = game_stats.combat;
+    state.untrusted_save = game_stats.untrusted_save;
 
     // 捕获角色选择和玩家数量
-    state.selected_character = character_selection.selected_character.clone();
+    state.selected_character = character_selection.selected_character;
     state.player_count = match character_selection.selected_character {
         CharacterType::Shirou => PlayerCount::Single,
         CharacterType::Sakura => PlayerCount::Double,
@@ -116,20 +151,23 @@ pub fn capture_game_state(
 pub fn restore_game_state(
     _commands: Commands,
     state: CompleteGameState,
-    mut player_query: Query<(&mut Transform, &mut Velocity, &mut PlayerState), With<Player>>,
+    mut player_query: RestorePlayerQuery,
     mut camera_query: Query<&mut Transform, (With<Camera>, Without<Player>)>,
     mut game_stats: ResMut<GameStats>,
     mut character_selection: ResMut<CharacterSelection>,
     mut audio_state_manager: ResMut<AudioStateManager>,
 ) {
     // 恢复玩家状态
-    if let Ok((mut player_transform, mut player_velocity, mut player_state)) =
+    if let Ok((mut player_transform, mut player_velocity, mut player_state, status_effects)) =
         player_query.single_mut()
     {
         player_transform.translation = state.player_position;
         *player_velocity = state.player_velocity;
         player_state.is_grounded = state.player_grounded;
         player_state.is_crouching = state.player_crouching;
+        if let Some(mut status_effects) = status_effects {
+            *status_effects = state.player_status_effects;
+        }
 
         crate::debug_log!(
             "Restored player state: position({:.1}, {:.1}), animation: {}",
@@ -153,6 +191,8 @@ pub fn restore_game_state(
     game_stats.distance_traveled = state.distance_traveled;
     game_stats.jump_count = state.jump_count;
     game_stats.play_time = state.play_time;
+    game_stats.combat = state.combat;
+    game_stats.untrusted_save = state.untrusted_save;
 
     // 恢复角色选择
     character_selection.selected_character = state.selected_character;
@@ -196,13 +236,7 @@ pub fn handle_pause_input(
 
     match current_state.get() {
         GameState::Playing if esc_just_pressed => {
-            let state = capture_game_state(
-                snapshot.player_query,
-                snapshot.camera_query,
-                snapshot.game_stats,
-                snapshot.character_selection,
-                snapshot.audio_state_manager,
-            );
+            let state = snapshot.capture();
             pause_manager.pause_game(state);
             NextState::set_if_neq(&mut next_state, GameState::Paused);
             crate::debug_log!("Game paused with state snapshot");
@@ -237,7 +271,14 @@ pub fn restore_paused_state(
         return;
     }
 
-    if let Some(state) = pause_manager.resume_game() {
+    if let Some(mut state) = pause_manager.resume_game() {
+        let default_tuning = GameplayTuning::default();
+        let status_tuning = &restore
+            .tuning
+            .as_deref()
+            .unwrap_or(&default_tuning)
+            .status_effects;
+        state.player_status_effects = state.player_status_effects.restored(status_tuning);
         restore_game_state(
             commands,
             state,
@@ -254,6 +295,7 @@ pub fn restore_paused_state(
 pub fn scan_save_files(mut save_file_manager: ResMut<SaveFileManager>) {
     crate::debug_log!("Scanning save files...");
     save_file_manager.save_files.clear();
+    save_file_manager.damaged_save_paths.clear();
 
     let save_dir = Path::new(&save_file_manager.save_directory);
     if !save_dir.exists() {
@@ -271,9 +313,13 @@ pub fn scan_save_files(mut save_file_manager: ResMut<SaveFileManager>) {
             {
                 match process_save_file(&entry, &mut save_file_manager) {
                     Ok(true) => valid_files += 1,
-                    Ok(false) => corrupted_files += 1,
+                    Ok(false) => {
+                        list_damaged_save_file(&entry, &mut save_file_manager);
+                        corrupted_files += 1;
+                    }
                     Err(e) => {
                         crate::debug_log!("Error processing {}: {}", entry.path().display(), e);
+                        list_damaged_save_file(&entry, &mut save_file_manager);
                         corrupted_files += 1;
                     }
                 }
@@ -326,6 +372,32 @@ fn process_save_file(
     Ok(true)
 }
 
+/// 损坏的存档仍以占位信息列出，玩家点击后走读档失败 → 备份恢复流程
+fn list_damaged_save_file(entry: &std::fs::DirEntry, save_file_manager: &mut SaveFileManager) {
+    let path = entry.path();
+    let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return;
+    };
+
+    let save_timestamp = entry
+        .metadata()
+        .and_then(|metadata| metadata.modified())
+        .map(chrono::DateTime::<chrono::Utc>::from)
+        .unwrap_or_else(|_| chrono::Utc::now());
+    let file_path = path.to_string_lossy().to_string();
+
+    save_file_manager.save_files.push(SaveFileMetadata {
+        name: name.to_string(),
+        score: 0,
+        distance: 0.0,
+        play_time: 0.0,
+        save_timestamp,
+        file_path: file_path.clone(),
+        selected_character: CharacterType::default(),
+    });
+    save_file_manager.damaged_save_paths.push(file_path);
+}
+
 /// 删除存档文件
 pub fn delete_save_file(
     save_name: &str,
@@ -348,6 +420,8 @@ pub fn delete_save_file(
         // 删除文件
         fs::remove_file(&file_path)
             .map_err(|e| format!("Failed to delete save file '{}': {}", file_path, e))?;
+        crate::systems::save_thumbnail::write_save_thumbnail(Path::new(&file_path), None)
+            .map_err(|e| format!("Failed to delete save thumbnail '{}': {}", file_path, e))?;
 
         // 从列表中移除
         save_file_manager.save_files.remove(index);
@@ -424,6 +498,15 @@ pub fn rename_save_file(
         crate::systems::shared_utils::atomic_write_file(&new_path, &updated_file_data)?;
         if new_path != old_path {
             fs::remove_file(old_path)?;
+
+            // 缩略图跟随存档改名，缺失时保持没有缩略图。
+            let old_thumbnail = crate::systems::save_thumbnail::thumbnail_path_for(old_path);
+            if old_thumbnail.exists() {
+                fs::rename(
+                    &old_thumbnail,
+                    crate::systems::save_thumbnail::thumbnail_path_for(&new_path),
+                )?;
+            }
         }
 
         save_file_manager.save_files[index] = updated_metadata;
@@ -472,6 +555,7 @@ mod tests {
         let mut manager = SaveFileManager {
             save_directory: temp_dir.to_string_lossy().to_string(),
             save_files: vec![metadata],
+            damaged_save_paths: Vec::new(),
             current_save_name: None,
             selected_save_index: None,
         };
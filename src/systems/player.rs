@@ -21,6 +21,9 @@ type PlayerMovementItem<'a> = (
     &'a PlayerState,
     Option<&'a mut AttackMomentum>,
     Option<&'a LedgeTraversal>,
+    Option<&'a PlayerDefense>,
+    Option<&'a Grabbed>,
+    Option<&'a StatusEffects>,
 );
 
 type PlayerJumpItem<'a> = (
@@ -30,6 +33,8 @@ type PlayerJumpItem<'a> = (
     Option<&'a mut crate::systems::collision::CollisionBox>,
     Option<&'a AttackMomentum>,
     Option<&'a LedgeTraversal>,
+    Option<&'a Grabbed>,
+    Option<&'a StatusEffects>,
 );
 
 type PlayerLedgeTraversalItem<'a> = (
@@ -51,12 +56,14 @@ type PlayerLedgeTraversalItem<'a> = (
 /// * `player_query` - 玩家实体查询
 /// * `time` - 时间资源
 /// * `game_stats` - 游戏统计资源
+/// * `tuning` - 调参（减速状态的速度倍率）
 pub fn player_movement(
     mut commands: Commands,
     game_input: Res<crate::systems::input::GameInput>,
     mut player_query: Query<PlayerMovementItem, With<Player>>,
     time: Res<Time<Fixed>>,
     mut game_stats: ResMut<GameStats>,
+    tuning: Option<Res<GameplayTuning>>,
     sky_level: Option<Res<crate::components::SkyLevelRuntime>>,
 ) {
     if sky_level
@@ -66,8 +73,17 @@ pub fn player_movement(
         return;
     }
 
-    if let Ok((entity, mut transform, mut velocity, player_state, attack_momentum, traversal)) =
-        player_query.single_mut()
+    if let Ok((
+        entity,
+        mut transform,
+        mut velocity,
+        player_state,
+        attack_momentum,
+        traversal,
+        defense,
+        grabbed,
+        statuses,
+    )) = player_query.single_mut()
     {
         let delta_time = time.delta_secs();
         let mut protected_by_attack = false;
@@ -105,15 +121,28 @@ pub fn player_movement(
             return;
         }
 
-        // 获取水平输入方向
-        let input_direction = if !player_state.is_crouching {
+        // 被影擒住或眩晕时无法移动
+        if grabbed.is_some() || statuses.is_some_and(StatusEffects::is_stunned) {
+            velocity.x = 0.0;
+            return;
+        }
+
+        // 获取水平输入方向（格挡时站定）
+        let input_direction = if defense.is_some_and(PlayerDefense::is_guarding) {
+            0.0
+        } else if !player_state.is_crouching {
             game_input.get_horizontal_input()
         } else {
             game_input.get_horizontal_input() * 0.5 // 趴下时移动速度减半
         };
 
         // 计算目标速度
-        let target_speed = input_direction * GameConfig::MOVE_SPEED;
+        let default_tuning = GameplayTuning::default();
+        let status_tuning = &tuning.as_deref().unwrap_or(&default_tuning).status_effects;
+        let speed_multiplier = statuses
+            .map(|statuses| statuses.speed_multiplier(status_tuning))
+            .unwrap_or(1.0);
+        let target_speed = input_direction * GameConfig::MOVE_SPEED * speed_multiplier;
 
         // 应用加速度和减速度（更平滑的移动）
         let acceleration = if input_direction != 0.0 {
@@ -224,9 +253,11 @@ pub fn player_jump(
         mut collision_box,
         attack_momentum,
         traversal,
+        grabbed,
+        statuses,
     )) = player_query.single_mut()
     {
-        if traversal.is_some_and(LedgeTraversal::is_active) {
+        if traversal.is_some_and(LedgeTraversal::is_active) || grabbed.is_some() {
             velocity.x = 0.0;
             velocity.y = 0.0;
             return;
@@ -247,11 +278,13 @@ pub fn player_jump(
             || keyboard_input.just_pressed(KeyCode::ArrowUp)
             || keyboard_input.just_pressed(KeyCode::Numpad8)
             || keyboard_input.just_pressed(KeyCode::Space);
-        let wants_jump = direct_jump_pressed
-            || direct_jump_just_pressed
-            || game_input.jump
-            || game_input.jump_pressed_this_frame
-            || game_input.jump_buffer_seconds > 0.0;
+        // 眩晕时仍受重力，但不响应跳跃
+        let wants_jump = !statuses.is_some_and(StatusEffects::is_stunned)
+            && (direct_jump_pressed
+                || direct_jump_just_pressed
+                || game_input.jump
+                || game_input.jump_pressed_this_frame
+                || game_input.jump_buffer_seconds > 0.0);
 
         // 提升容错：若角色处于蹲伏且玩家请求跳跃，先自动起身再进入跳跃判定。
         if wants_jump && player_state.is_grounded && player_state.is_crouching {
@@ -590,6 +623,7 @@ fn handle_player_death(
     game_stats.jump_count = 0;
     game_stats.distance_traveled = 0.0;
     game_stats.play_time = 0.0;
+    game_stats.combat = Default::default();
 
     crate::debug_log!("📊 本次游戏统计:");
     crate::debug_log!("   距离: {:.1}m", current_distance);
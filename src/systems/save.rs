@@ -1,187 +1,165 @@
+//! 自动存档
+//!
+//! 自动存档直接复用槽位存档系统：按配置的间隔或在激活天空关卡检查点时，
+//! 把完整的 `CompleteGameState` 写入 `saves/autosave_N.json`，在
+//! `AutosaveTuning::slot_count` 个槽位间轮换（优先空槽，否则覆盖最旧的一个）。
+//! 写入走与手动存档相同的异步压缩、校验和与滚动备份流程。
+
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
+use bevy::tasks::{ComputeTaskPool, Task};
+use futures_lite::future;
 use std::fs;
 use std::path::{Path, PathBuf};
 
-use crate::{resources::*, states::*};
+use crate::{
+    events::CheckpointActivated,
+    resources::{GameplayTuning, SaveFileManager, SaveFileMetadata},
+    systems::{
+        async_file_ops::{AsyncFileManager, save_game_state_async, save_thumbnail_async},
+        error_handling::{ErrorRecoveryManager, SaveSystemError},
+        pause_save::PauseSnapshotParams,
+    },
+};
+
+/// 自动存档槽位名前缀：`autosave_1`、`autosave_2`……
+pub const AUTOSAVE_SLOT_PREFIX: &str = "autosave";
+
+/// 自动存档运行时状态
+#[derive(Resource, Debug, Default)]
+pub struct AutosaveState {
+    /// 距上次自动存档经过的游戏时间（秒）
+    pub elapsed_secs: f32,
+    /// 检查点触发时若上一次写入尚未完成，延后到写入结束再存
+    pub checkpoint_pending: bool,
+    /// 最近一次成功写入的自动存档
+    pub last_autosave_path: Option<PathBuf>,
+}
 
-const AUTOSAVE_NAME: &str = "autosave";
+/// 正在进行的自动存档写入
+#[derive(Component)]
+pub struct AutosaveTask(Task<Result<PathBuf, SaveSystemError>>);
 
-type SaveButtonInteractionQuery<'w, 's> = Query<
-    'w,
-    's,
-    (&'static Interaction, &'static mut BackgroundColor),
-    (Changed<Interaction>, With<crate::components::SaveButton>),
->;
+#[derive(SystemParam)]
+pub struct AutosaveIo<'w, 's> {
+    file_manager: Res<'w, AsyncFileManager>,
+    save_file_manager: Res<'w, SaveFileManager>,
+    error_recovery: Res<'w, ErrorRecoveryManager>,
+    active_tasks: Query<'w, 's, (), With<AutosaveTask>>,
+}
 
-/// 保存游戏数据（统一写入 SaveFileData v2）。
-pub fn save_game(
-    game_stats: Res<GameStats>,
-    character_selection: Res<CharacterSelection>,
-    mut save_manager: ResMut<SaveManager>,
-) {
-    let previous_state = save_manager
-        .current_save
-        .as_ref()
-        .map(|save| &save.game_state);
-    let save_timestamp = chrono::Utc::now();
-    let best_distance = game_stats.distance_traveled.max(
-        previous_state
-            .map(|state| state.distance_traveled)
-            .unwrap_or(0.0),
-    );
-    let total_jumps =
-        game_stats.jump_count + previous_state.map(|state| state.jump_count).unwrap_or(0);
-    let total_play_time =
-        game_stats.play_time + previous_state.map(|state| state.play_time).unwrap_or(0.0);
-
-    let mut metadata = save_manager
-        .current_save
-        .as_ref()
-        .map(|save| save.metadata.clone())
-        .unwrap_or_else(|| SaveFileMetadata {
-            name: AUTOSAVE_NAME.to_string(),
-            score: 0,
-            distance: 0.0,
-            play_time: 0.0,
-            save_timestamp,
-            file_path: String::new(),
-            selected_character: character_selection.selected_character.clone(),
-        });
-
-    metadata.file_path = save_manager.save_file_path.clone();
-    metadata.selected_character = character_selection.selected_character.clone();
-    metadata.distance = best_distance;
-    metadata.play_time = total_play_time;
-    metadata.save_timestamp = save_timestamp;
-
-    let mut state = CompleteGameState::default();
-    state.selected_character = metadata.selected_character.clone();
-    state.distance_traveled = best_distance;
-    state.jump_count = total_jumps;
-    state.play_time = total_play_time;
-    state.score = (state.distance_traveled * 10.0) as u32 + state.jump_count * 50;
-    state.save_timestamp = save_timestamp;
-
-    let save_path = PathBuf::from(&save_manager.save_file_path);
-    metadata.score = state.score;
-    metadata.distance = state.distance_traveled;
-    metadata.play_time = state.play_time;
-    metadata.file_path = save_path.to_string_lossy().to_string();
-
-    let save_data = SaveFileData::new(metadata, state);
-    match write_v2_save(&save_path, &save_data) {
-        Ok(()) => {
-            save_manager.current_save = Some(save_data);
-            crate::debug_log!("Game saved (SaveFileData v2)");
-        }
-        Err(error) => {
-            crate::debug_log!("Save failed: {}", error);
-        }
-    }
+/// 第 `slot` 个自动存档槽位的名称（从 1 开始）
+pub fn autosave_slot_name(slot: u8) -> String {
+    format!("{}_{}", AUTOSAVE_SLOT_PREFIX, slot)
 }
 
-/// 加载游戏数据（仅支持 SaveFileData v2）。
-pub fn load_game(
-    mut save_manager: ResMut<SaveManager>,
-    mut character_selection: ResMut<CharacterSelection>,
-) {
-    let save_path = PathBuf::from(&save_manager.save_file_path);
-    let file_data = match fs::read(&save_path) {
-        Ok(data) => data,
-        Err(_) => {
-            crate::debug_log!("No save file found, a new save will be created");
-            return;
-        }
-    };
+/// 选择下一次自动存档写入的槽位：优先尚不存在的槽位，否则覆盖修改时间最早的一个
+pub fn next_autosave_path(save_directory: &Path, slot_count: u8) -> PathBuf {
+    (1..=slot_count.max(1))
+        .map(|slot| save_directory.join(format!("{}.json", autosave_slot_name(slot))))
+        .min_by_key(|path| fs::metadata(path).and_then(|meta| meta.modified()).ok())
+        .expect("at least one autosave slot")
+}
 
-    let json_data = match crate::systems::shared_utils::decode_file_payload(&file_data) {
-        Ok(data) => data,
-        Err(error) => {
-            crate::debug_log!("Failed to decode save file: {}", error);
-            return;
-        }
-    };
+/// 自动存档系统：计时或检查点激活时，把当前完整状态写入下一个自动存档槽位
+pub fn autosave_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    tuning: Res<GameplayTuning>,
+    mut autosave: ResMut<AutosaveState>,
+    mut checkpoints: MessageReader<CheckpointActivated>,
+    snapshot: PauseSnapshotParams,
+    io: AutosaveIo,
+) {
+    let settings = &tuning.autosave;
+    let checkpoint_reached = checkpoints.read().count() > 0;
+    if !settings.enabled {
+        return;
+    }
 
-    let mut v2_save = match serde_json::from_str::<SaveFileData>(&json_data) {
-        Ok(save) => save,
-        Err(error) => {
-            crate::debug_log!(
-                "Unsupported save format at {}: {}",
-                save_path.display(),
-                error
-            );
-            return;
-        }
-    };
+    autosave.elapsed_secs += time.delta_secs();
+    autosave.checkpoint_pending |= settings.save_on_checkpoint && checkpoint_reached;
 
-    if !v2_save.verify_serialized_checksum(&json_data) {
-        crate::debug_log!("Checksum verification failed for {}", save_path.display());
+    let interval_due =
+        settings.interval_secs > 0.0 && autosave.elapsed_secs >= settings.interval_secs;
+    if !(interval_due || autosave.checkpoint_pending) || !io.active_tasks.is_empty() {
         return;
     }
 
-    v2_save.metadata.file_path = save_path.to_string_lossy().to_string();
-    character_selection.selected_character = v2_save.game_state.selected_character.clone();
-    save_manager.current_save = Some(v2_save);
-    crate::debug_log!("Loaded v2 save: {}", save_path.display());
-}
+    let state = snapshot.capture();
+    let save_path = next_autosave_path(
+        Path::new(&io.save_file_manager.save_directory),
+        settings.slot_count,
+    );
+    let metadata = SaveFileMetadata {
+        name: save_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(AUTOSAVE_SLOT_PREFIX)
+            .to_string(),
+        score: state.score,
+        distance: state.distance_traveled,
+        play_time: state.play_time,
+        save_timestamp: state.save_timestamp,
+        file_path: save_path.to_string_lossy().to_string(),
+        selected_character: state.selected_character,
+    };
 
-fn write_v2_save(save_path: &Path, save_data: &SaveFileData) -> Result<(), String> {
-    if let Some(parent) = save_path.parent()
-        && !parent.as_os_str().is_empty()
-    {
-        fs::create_dir_all(parent).map_err(|error| error.to_string())?;
-    }
+    let compression_enabled = io.file_manager.compression_enabled;
+    let compression_level = io.file_manager.compression_level;
+    let backup_policy = io.error_recovery.backup_policy();
+    let signing_key = io.file_manager.signing_key.clone();
 
-    let json_data = serde_json::to_string_pretty(save_data).map_err(|error| error.to_string())?;
-    crate::systems::shared_utils::atomic_write_file(save_path, json_data.as_bytes())
-        .map_err(|error| error.to_string())
+    crate::debug_log!(
+        "Autosaving to {} (checkpoint: {})",
+        save_path.display(),
+        autosave.checkpoint_pending
+    );
+
+    let task = ComputeTaskPool::get().spawn(async move {
+        save_game_state_async(
+            save_path.clone(),
+            state,
+            metadata,
+            compression_enabled,
+            compression_level,
+            Some(backup_policy),
+            signing_key,
+        )
+        .await?;
+        // 自动存档不截图，清掉槽位上一次手动存档可能留下的缩略图
+        save_thumbnail_async(save_path.clone(), None).await;
+        Ok(save_path)
+    });
+    commands.spawn(AutosaveTask(task));
+
+    autosave.elapsed_secs = 0.0;
+    autosave.checkpoint_pending = false;
 }
 
-/// 处理存档按钮点击
-pub fn handle_save_button_click(
-    mut interaction_query: SaveButtonInteractionQuery,
-    game_stats: Res<GameStats>,
-    character_selection: Res<CharacterSelection>,
-    save_manager: ResMut<SaveManager>,
+/// 轮询自动存档写入；失败只记录，不打断游戏
+pub fn poll_autosave_tasks(
+    mut commands: Commands,
+    mut tasks: Query<(Entity, &mut AutosaveTask)>,
+    mut autosave: ResMut<AutosaveState>,
+    mut error_recovery: ResMut<ErrorRecoveryManager>,
 ) {
-    let mut should_save = false;
-
-    for (interaction, mut color) in &mut interaction_query {
-        match *interaction {
-            Interaction::Pressed => {
-                *color = BackgroundColor(Color::srgba(0.05, 0.1, 0.05, 0.8));
-                should_save = true;
-            }
-            Interaction::Hovered => {
-                *color = BackgroundColor(Color::srgba(0.15, 0.3, 0.15, 0.8));
+    for (entity, mut task) in &mut tasks {
+        let Some(result) = future::block_on(future::poll_once(&mut task.0)) else {
+            continue;
+        };
+
+        match result {
+            Ok(save_path) => {
+                crate::debug_log!("Autosave written: {}", save_path.display());
+                error_recovery.clear_retry_count(AUTOSAVE_SLOT_PREFIX);
+                autosave.last_autosave_path = Some(save_path);
             }
-            Interaction::None => {
-                *color = BackgroundColor(Color::srgba(0.1, 0.2, 0.1, 0.8));
+            Err(error) => {
+                warn!("Autosave failed: {}", error.get_details());
+                error_recovery.handle_save_error(error, AUTOSAVE_SLOT_PREFIX);
             }
         }
-    }
-
-    if should_save {
-        save_game(game_stats, character_selection, save_manager);
-    }
-}
-
-/// 自动保存系统
-pub fn auto_save_system(
-    mut timer: Local<Timer>,
-    time: Res<Time>,
-    game_stats: Res<GameStats>,
-    character_selection: Res<CharacterSelection>,
-    save_manager: ResMut<SaveManager>,
-    current_state: Res<State<GameState>>,
-) {
-    if timer.duration().is_zero() {
-        timer.set_duration(std::time::Duration::from_secs(30));
-        timer.set_mode(bevy::time::TimerMode::Repeating);
-    }
-    timer.tick(time.delta());
-
-    if timer.just_finished() && *current_state.get() == GameState::Playing {
-        save_game(game_stats, character_selection, save_manager);
+        commands.entity(entity).despawn();
     }
 }
@@ -0,0 +1,323 @@
+//! 存档签名
+//!
+//! `SaveFileData::checksum` 只能发现损坏，任何人改完分数都能重新算一遍。
+//! 这里给存档加一层可选的 keyed MAC：每个安装首次启动时生成 32 字节随机密钥，
+//! 用 `blake3::keyed_hash` 对写出的存档 JSON 原文签名（校验和与 MAC 的值留空，
+//! 其余字节原样参与）。持有同一密钥的一方（本机或登记过该密钥的服务器）
+//! 拿到存档原文即可判断分数、距离是否由游戏本身写出。
+//!
+//! 没有签名的旧存档照常读取，校验结果为 [`SignatureStatus::Unsigned`]。
+
+use bevy::prelude::*;
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::{
+    resources::{SaveFileData, SaveSignature, serialized_with_blank_field},
+    systems::{async_file_ops::AsyncFileManager, shared_utils::atomic_write_file},
+};
+
+/// 安装密钥文件（十六进制文本，不会被存档扫描当作存档）
+pub const INSTALL_KEY_PATH: &str = "saves/install.key";
+/// 当前签名算法标识
+pub const SIGNATURE_ALGORITHM: &str = "blake3-keyed";
+
+/// 每个安装独有的存档签名密钥
+#[derive(Clone, PartialEq, Eq)]
+pub struct SaveSigningKey {
+    key: [u8; 32],
+}
+
+impl std::fmt::Debug for SaveSigningKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // 不在日志里泄露密钥本身
+        f.debug_struct("SaveSigningKey")
+            .field("key_id", &self.key_id())
+            .finish()
+    }
+}
+
+/// 签名校验结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureStatus {
+    /// 旧存档或关闭签名时写出的存档
+    Unsigned,
+    /// 由持有该密钥的游戏写出，内容未被改动
+    Valid,
+    /// 密钥匹配但 MAC 不符：存档在写出后被改动过
+    Invalid,
+    /// 由其他安装签名，当前密钥无法判断
+    UnknownKey,
+}
+
+impl SaveSigningKey {
+    pub fn from_bytes(key: [u8; 32]) -> Self {
+        Self { key }
+    }
+
+    pub fn generate() -> Self {
+        Self::from_bytes(rand::random())
+    }
+
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        blake3::Hash::from_hex(hex.trim())
+            .ok()
+            .map(|hash| Self::from_bytes(*hash.as_bytes()))
+    }
+
+    pub fn to_hex(&self) -> String {
+        blake3::Hash::from_bytes(self.key).to_hex().to_string()
+    }
+
+    /// 公开的密钥指纹，写进签名里供校验方查找对应密钥
+    pub fn key_id(&self) -> String {
+        blake3::hash(&self.key).to_hex()[..16].to_string()
+    }
+
+    /// 计算负载的 MAC（十六进制）
+    pub fn mac(&self, payload: &[u8]) -> String {
+        blake3::keyed_hash(&self.key, payload).to_hex().to_string()
+    }
+
+    /// 校验 MAC（常数时间比较）
+    pub fn verify_mac(&self, payload: &[u8], mac_hex: &str) -> bool {
+        blake3::Hash::from_hex(mac_hex)
+            .is_ok_and(|expected| blake3::keyed_hash(&self.key, payload) == expected)
+    }
+
+    /// 读取安装密钥，不存在时生成并写入
+    pub fn load_or_create(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Self::from_hex(&content)
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Malformed install key")),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                let key = Self::generate();
+                atomic_write_file(path, key.to_hex().as_bytes())?;
+                crate::debug_log!("Generated save signing key {}", key.key_id());
+                Ok(key)
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+impl SaveFileData {
+    /// 用安装密钥签名，并重新计算覆盖签名的校验和
+    ///
+    /// 签名的是之后 `to_string_pretty` 写出的同一份文本（校验和与 MAC 的值为空），
+    /// 校验时从存档原文中把这两个值留空即可还原出签名时的字节。
+    pub fn sign(&mut self, key: &SaveSigningKey) {
+        self.checksum = String::new();
+        self.signature = Some(SaveSignature {
+            algorithm: SIGNATURE_ALGORITHM.to_string(),
+            key_id: key.key_id(),
+            mac: String::new(),
+        });
+        match serde_json::to_string_pretty(self) {
+            Ok(payload) => {
+                if let Some(signature) = self.signature.as_mut() {
+                    signature.mac = key.mac(payload.as_bytes());
+                }
+            }
+            Err(_) => self.signature = None,
+        }
+        self.refresh_checksum();
+    }
+
+    /// 用给定密钥校验签名；`serialized` 是解析出 `self` 的存档原文
+    pub fn verify_signature(&self, serialized: &str, key: &SaveSigningKey) -> SignatureStatus {
+        let Some(signature) = self.signature.as_ref() else {
+            return SignatureStatus::Unsigned;
+        };
+        if signature.algorithm != SIGNATURE_ALGORITHM || signature.key_id != key.key_id() {
+            return SignatureStatus::UnknownKey;
+        }
+
+        let payload = serialized_with_blank_field(serialized, "checksum", &self.checksum)
+            .and_then(|payload| serialized_with_blank_field(&payload, "mac", &signature.mac));
+        match payload {
+            Some(payload) if key.verify_mac(payload.as_bytes(), &signature.mac) => {
+                SignatureStatus::Valid
+            }
+            _ => SignatureStatus::Invalid,
+        }
+    }
+}
+
+/// 服务器一侧按账号登记的安装密钥
+///
+/// 账号第一次登记的密钥生效（trust on first use），之后只接受同一把密钥，
+/// 其他安装签名的存档对该账号一律视为 [`SignatureStatus::UnknownKey`]。
+/// 启用数据库时以 `player_save_keys` 表为准，这里只缓存已从数据库确认的密钥。
+#[derive(Clone, Default)]
+pub struct SaveKeyRegistry {
+    keys: Arc<Mutex<HashMap<Uuid, SaveSigningKey>>>,
+}
+
+impl SaveKeyRegistry {
+    /// 为账号登记安装密钥；账号已登记另一把密钥时返回 `false`
+    pub fn register(&self, player_id: Uuid, key: SaveSigningKey) -> bool {
+        let Ok(mut keys) = self.keys.lock() else {
+            return false;
+        };
+        *keys.entry(player_id).or_insert_with(|| key.clone()) == key
+    }
+
+    /// 账号已登记的密钥
+    pub fn key_for(&self, player_id: Uuid) -> Option<SaveSigningKey> {
+        self.keys.lock().ok()?.get(&player_id).cloned()
+    }
+}
+
+/// 校验上传的存档原文：必须由账号登记的密钥签名且逐字节未改动，
+/// 并且不是从不可信存档继续的一局
+pub fn verify_upload(key: &SaveSigningKey, save_json: &str) -> Result<(), &'static str> {
+    let save_data = serde_json::from_str::<SaveFileData>(save_json)
+        .map_err(|_| "Save is not a valid save file")?;
+    match save_data.verify_signature(save_json, key) {
+        SignatureStatus::Valid => {}
+        SignatureStatus::Unsigned => return Err("Save is not signed"),
+        SignatureStatus::UnknownKey => return Err("Save was signed by another install"),
+        SignatureStatus::Invalid => return Err("Save was modified after it was signed"),
+    }
+    if save_data.game_state.untrusted_save {
+        return Err("Save continues a run loaded from an unverified save");
+    }
+    Ok(())
+}
+
+/// 启动时加载（或生成）安装密钥；失败时退回不签名写入
+pub fn load_install_signing_key(mut file_manager: ResMut<AsyncFileManager>) {
+    if file_manager.disable_signing {
+        return;
+    }
+
+    match SaveSigningKey::load_or_create(Path::new(INSTALL_KEY_PATH)) {
+        Ok(key) => file_manager.signing_key = Some(key),
+        Err(error) => warn!(
+            "Save signing disabled, failed to load {}: {}",
+            INSTALL_KEY_PATH, error
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resources::{CompleteGameState, SaveFileMetadata};
+
+    fn sample_save() -> SaveFileData {
+        let metadata = SaveFileMetadata {
+            name: "signed".to_string(),
+            score: 1200,
+            distance: 88.0,
+            play_time: 12.0,
+            save_timestamp: chrono::Utc::now(),
+            file_path: "saves/signed.json".to_string(),
+            selected_character: crate::states::CharacterType::Shirou,
+        };
+        SaveFileData::new(metadata, CompleteGameState::default())
+    }
+
+    fn serialize(save: &SaveFileData) -> String {
+        serde_json::to_string_pretty(save).expect("serialize save")
+    }
+
+    fn status_of(json: &str, key: &SaveSigningKey) -> SignatureStatus {
+        serde_json::from_str::<SaveFileData>(json)
+            .expect("parse save")
+            .verify_signature(json, key)
+    }
+
+    #[test]
+    fn signed_save_detects_score_edits_even_with_recomputed_checksum() {
+        let key = SaveSigningKey::from_bytes([7; 32]);
+        let mut save = sample_save();
+        save.sign(&key);
+        let json = serialize(&save);
+        assert!(save.verify_serialized_checksum(&json));
+        assert_eq!(status_of(&json, &key), SignatureStatus::Valid);
+
+        save.game_state.score = 999_999;
+        save.refresh_checksum();
+        let edited = serialize(&save);
+        assert!(save.verify_serialized_checksum(&edited));
+        assert_eq!(status_of(&edited, &key), SignatureStatus::Invalid);
+    }
+
+    #[test]
+    fn signature_covers_the_exact_bytes_written() {
+        let key = SaveSigningKey::from_bytes([5; 32]);
+        let mut save = sample_save();
+        save.sign(&key);
+        let json = serialize(&save);
+
+        // 解析后相同、字节不同的文本（例如重新排版或追加字段）都不再通过校验
+        let compact = serde_json::to_string(&save).expect("serialize compact save");
+        assert_eq!(status_of(&compact, &key), SignatureStatus::Invalid);
+        let padded = json.replacen("\"version\"", "\"extra\": 1,\n  \"version\"", 1);
+        assert_eq!(status_of(&padded, &key), SignatureStatus::Invalid);
+        assert_eq!(status_of(&json, &key), SignatureStatus::Valid);
+    }
+
+    #[test]
+    fn unsigned_and_foreign_saves_are_reported_separately() {
+        let key = SaveSigningKey::from_bytes([1; 32]);
+        let other = SaveSigningKey::from_bytes([2; 32]);
+        let mut save = sample_save();
+        assert_eq!(
+            status_of(&serialize(&save), &key),
+            SignatureStatus::Unsigned
+        );
+
+        save.sign(&other);
+        let json = serialize(&save);
+        assert_eq!(status_of(&json, &key), SignatureStatus::UnknownKey);
+        assert_eq!(status_of(&json, &other), SignatureStatus::Valid);
+    }
+
+    #[test]
+    fn registry_accepts_only_uploads_signed_with_the_first_registered_key() {
+        let registry = SaveKeyRegistry::default();
+        let player_id = Uuid::new_v4();
+        let key = SaveSigningKey::from_bytes([3; 32]);
+        let other = SaveSigningKey::from_bytes([4; 32]);
+
+        let mut save = sample_save();
+        save.sign(&key);
+        let signed = serialize(&save);
+        assert_eq!(registry.key_for(player_id), None);
+
+        assert!(registry.register(player_id, key.clone()));
+        assert!(registry.register(player_id, key.clone()));
+        assert!(!registry.register(player_id, other.clone()));
+        let registered = registry
+            .key_for(player_id)
+            .expect("first key should stay registered");
+        assert_eq!(registered, key);
+        assert_eq!(verify_upload(&registered, &signed), Ok(()));
+
+        let unsigned = serialize(&sample_save());
+        assert!(verify_upload(&registered, &unsigned).is_err());
+
+        save.sign(&other);
+        let foreign = serialize(&save);
+        assert!(verify_upload(&registered, &foreign).is_err());
+
+        // 从不可信存档继续的一局即使重新签名也不被接受
+        save.game_state.untrusted_save = true;
+        save.sign(&key);
+        let laundered = serialize(&save);
+        assert!(verify_upload(&registered, &laundered).is_err());
+    }
+
+    #[test]
+    fn key_roundtrips_through_hex() {
+        let key = SaveSigningKey::generate();
+        assert_eq!(SaveSigningKey::from_hex(&key.to_hex()), Some(key));
+    }
+}
@@ -0,0 +1,280 @@
+//! 存档缩略图
+//!
+//! 暂停瞬间截取主窗口画面并缩小，保存时作为 `<存档名>.thumb` 旁路文件写在
+//! 存档旁边；读档表格再把它解码成 UI 图片，方便按画面找到存档。
+
+use bevy::asset::RenderAssetUsages;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{
+    resources::{PauseManager, SaveThumbnail},
+    states::GameState,
+    systems::shared_utils::{atomic_write_file, compress_data},
+};
+
+/// 缩略图最大宽度（像素）。
+pub const THUMBNAIL_MAX_WIDTH: u32 = 160;
+/// 缩略图最大高度（像素）。
+pub const THUMBNAIL_MAX_HEIGHT: u32 = 90;
+/// 缩略图旁路文件扩展名。
+pub const THUMBNAIL_EXTENSION: &str = "thumb";
+
+const THUMBNAIL_MAGIC: [u8; 4] = *b"ESTH";
+const THUMBNAIL_HEADER_LEN: usize = 12;
+
+/// 存档文件对应的缩略图路径：`saves/slot.json` -> `saves/slot.thumb`。
+pub fn thumbnail_path_for(save_path: &Path) -> PathBuf {
+    save_path.with_extension(THUMBNAIL_EXTENSION)
+}
+
+/// 按比例把截图缩小到 `max_width x max_height` 以内，输出不透明的 sRGB RGBA8 像素。
+///
+/// 截图格式随平台变化（常见为 `Bgra8UnormSrgb`），统一经 `get_color_at` 取色，
+/// 不支持的格式返回 `None`。
+pub fn downscale_screenshot(
+    image: &Image,
+    max_width: u32,
+    max_height: u32,
+) -> Option<SaveThumbnail> {
+    let (source_width, source_height) = (image.width(), image.height());
+    if source_width == 0 || source_height == 0 || max_width == 0 || max_height == 0 {
+        return None;
+    }
+
+    let scale = (max_width as f32 / source_width as f32)
+        .min(max_height as f32 / source_height as f32)
+        .min(1.0);
+    let width = ((source_width as f32 * scale).round() as u32).clamp(1, max_width);
+    let height = ((source_height as f32 * scale).round() as u32).clamp(1, max_height);
+
+    let mut rgba = Vec::with_capacity((width * height * 4) as usize);
+    for y in 0..height {
+        let source_y = (((y as f32 + 0.5) / height as f32) * source_height as f32) as u32;
+        for x in 0..width {
+            let source_x = (((x as f32 + 0.5) / width as f32) * source_width as f32) as u32;
+            let color = image
+                .get_color_at(
+                    source_x.min(source_width - 1),
+                    source_y.min(source_height - 1),
+                )
+                .ok()?;
+            let [r, g, b, _] = color.to_srgba().to_u8_array();
+            rgba.extend_from_slice(&[r, g, b, u8::MAX]);
+        }
+    }
+
+    Some(SaveThumbnail {
+        width,
+        height,
+        rgba,
+    })
+}
+
+/// 编码缩略图：4 字节魔数 + 宽高（u32 LE）+ Zstd 压缩的 RGBA 数据。
+pub fn encode_thumbnail(thumbnail: &SaveThumbnail) -> Result<Vec<u8>, io::Error> {
+    let expected_len = thumbnail.width as usize * thumbnail.height as usize * 4;
+    if thumbnail.rgba.len() != expected_len {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "Thumbnail pixel data does not match its size",
+        ));
+    }
+
+    let compressed = compress_data(&thumbnail.rgba, 3)?;
+    let mut encoded = Vec::with_capacity(THUMBNAIL_HEADER_LEN + compressed.len());
+    encoded.extend_from_slice(&THUMBNAIL_MAGIC);
+    encoded.extend_from_slice(&thumbnail.width.to_le_bytes());
+    encoded.extend_from_slice(&thumbnail.height.to_le_bytes());
+    encoded.extend_from_slice(&compressed);
+    Ok(encoded)
+}
+
+/// 解码 `encode_thumbnail` 写出的数据，尺寸超限或像素数不符时视为损坏。
+pub fn decode_thumbnail(data: &[u8]) -> Result<SaveThumbnail, io::Error> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    if data.len() < THUMBNAIL_HEADER_LEN || data[..4] != THUMBNAIL_MAGIC {
+        return Err(invalid("Unsupported thumbnail format"));
+    }
+
+    let width = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+    let height = u32::from_le_bytes([data[8], data[9], data[10], data[11]]);
+    if width == 0 || height == 0 || width > THUMBNAIL_MAX_WIDTH || height > THUMBNAIL_MAX_HEIGHT {
+        return Err(invalid("Thumbnail size out of range"));
+    }
+
+    let rgba = zstd::stream::decode_all(io::Cursor::new(&data[THUMBNAIL_HEADER_LEN..]))?;
+    if rgba.len() != width as usize * height as usize * 4 {
+        return Err(invalid("Thumbnail pixel data is truncated"));
+    }
+
+    Ok(SaveThumbnail {
+        width,
+        height,
+        rgba,
+    })
+}
+
+/// 写入（或在没有缩略图时清除）存档旁的缩略图，避免覆盖存档后残留旧画面。
+pub fn write_save_thumbnail(
+    save_path: &Path,
+    thumbnail: Option<&SaveThumbnail>,
+) -> Result<(), io::Error> {
+    let thumbnail_path = thumbnail_path_for(save_path);
+    match thumbnail {
+        Some(thumbnail) => atomic_write_file(&thumbnail_path, &encode_thumbnail(thumbnail)?),
+        None => match fs::remove_file(&thumbnail_path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// 读取存档旁的缩略图；缺失或损坏时返回 `None`，不影响存档本身。
+pub fn load_save_thumbnail(save_path: &Path) -> Option<SaveThumbnail> {
+    let thumbnail_path = thumbnail_path_for(save_path);
+    let data = match fs::read(&thumbnail_path) {
+        Ok(data) => data,
+        Err(error) => {
+            if error.kind() != io::ErrorKind::NotFound {
+                warn!(
+                    "Failed to read save thumbnail {}: {}",
+                    thumbnail_path.display(),
+                    error
+                );
+            }
+            return None;
+        }
+    };
+
+    match decode_thumbnail(&data) {
+        Ok(thumbnail) => Some(thumbnail),
+        Err(error) => {
+            warn!(
+                "Ignoring corrupted save thumbnail {}: {}",
+                thumbnail_path.display(),
+                error
+            );
+            None
+        }
+    }
+}
+
+/// 把缩略图转换成可供 `ImageNode` 使用的图片资源。
+pub fn thumbnail_image(thumbnail: &SaveThumbnail) -> Image {
+    Image::new(
+        Extent3d {
+            width: thumbnail.width,
+            height: thumbnail.height,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        thumbnail.rgba.clone(),
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// 在发出暂停请求的同一帧截图。
+///
+/// 暂停菜单要到下一帧 `OnEnter(GameState::Paused)` 才生成，所以这一帧渲染的
+/// 仍是纯游戏画面，与 `PauseManager` 保存的快照一致。
+pub fn capture_pause_thumbnail(
+    mut commands: Commands,
+    next_state: Res<NextState<GameState>>,
+    pause_manager: Res<PauseManager>,
+) {
+    let pause_requested = matches!(
+        next_state.as_ref(),
+        NextState::Pending(GameState::Paused) | NextState::PendingIfNeq(GameState::Paused)
+    );
+    if !pause_requested || !pause_manager.is_paused {
+        return;
+    }
+
+    commands
+        .spawn(Screenshot::primary_window())
+        .observe(store_pause_thumbnail);
+}
+
+fn store_pause_thumbnail(
+    captured: On<ScreenshotCaptured>,
+    mut pause_manager: ResMut<PauseManager>,
+) {
+    // 截图异步返回；若此时已经退出暂停，快照也已失效。
+    if !pause_manager.is_paused {
+        return;
+    }
+
+    pause_manager.preserved_thumbnail =
+        downscale_screenshot(&captured.image, THUMBNAIL_MAX_WIDTH, THUMBNAIL_MAX_HEIGHT);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solid_bgra_screenshot(width: u32, height: u32, bgra: [u8; 4]) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &bgra,
+            TextureFormat::Bgra8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    #[test]
+    fn downscale_keeps_aspect_ratio_and_swizzles_bgra() {
+        let screenshot = solid_bgra_screenshot(1280, 720, [10, 20, 200, 0]);
+
+        let thumbnail =
+            downscale_screenshot(&screenshot, THUMBNAIL_MAX_WIDTH, THUMBNAIL_MAX_HEIGHT)
+                .expect("bgra screenshot should downscale");
+
+        assert_eq!((thumbnail.width, thumbnail.height), (160, 90));
+        assert_eq!(&thumbnail.rgba[..4], &[200, 20, 10, 255]);
+    }
+
+    #[test]
+    fn thumbnail_sidecar_roundtrips_and_is_removed_without_thumbnail() {
+        let save_path = std::env::temp_dir().join(format!(
+            "emiyashiro-thumbnail-test-{}.json",
+            uuid::Uuid::new_v4()
+        ));
+        let thumbnail = SaveThumbnail {
+            width: 2,
+            height: 1,
+            rgba: vec![1, 2, 3, 255, 4, 5, 6, 255],
+        };
+
+        write_save_thumbnail(&save_path, Some(&thumbnail)).expect("write thumbnail");
+        assert_eq!(load_save_thumbnail(&save_path), Some(thumbnail));
+
+        write_save_thumbnail(&save_path, None).expect("clear thumbnail");
+        assert!(!thumbnail_path_for(&save_path).exists());
+        assert_eq!(load_save_thumbnail(&save_path), None);
+    }
+
+    #[test]
+    fn decode_rejects_truncated_pixels() {
+        let mut encoded = encode_thumbnail(&SaveThumbnail {
+            width: 1,
+            height: 1,
+            rgba: vec![0, 0, 0, 255],
+        })
+        .expect("encode thumbnail");
+        encoded[4..8].copy_from_slice(&4u32.to_le_bytes());
+
+        assert!(decode_thumbnail(&encoded).is_err());
+    }
+}
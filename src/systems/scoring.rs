@@ -0,0 +1,63 @@
+//! 战斗计分系统
+//!
+//! 伤害结算发出的 [`EnemyDefeated`] 与竞技场清场的 [`ArenaCleared`] 在这里换算成分数，
+//! 累计到 [`GameStats::combat`]，并发出 [`ScoreAwarded`] 供网络上报使用。
+
+use bevy::prelude::*;
+
+use crate::{
+    events::{ArenaCleared, EnemyDefeated, ScoreAwarded, ScoreReason},
+    resources::{GameStats, GameplayTuning, ScoringTuning},
+    systems::text_constants::GameHUDText,
+};
+
+/// 失败与通关界面显示的本局总分、评级与战斗成绩
+pub fn run_result_text(stats: &GameStats, scoring: &ScoringTuning) -> String {
+    format!(
+        "{}{}  {}{}\n{}{}  {}{}",
+        GameHUDText::SCORE_LABEL,
+        stats.score(),
+        GameHUDText::RANK_LABEL,
+        stats.grade(scoring).label(),
+        GameHUDText::KILLS_LABEL,
+        stats.combat.kills,
+        GameHUDText::BEST_COMBO_LABEL,
+        stats.combat.best_combo
+    )
+}
+
+/// 按击杀与无伤清场累计战斗得分
+pub fn award_combat_score(
+    mut defeated: MessageReader<EnemyDefeated>,
+    mut cleared: MessageReader<ArenaCleared>,
+    mut game_stats: ResMut<GameStats>,
+    tuning: Option<Res<GameplayTuning>>,
+    mut awarded: MessageWriter<ScoreAwarded>,
+) {
+    let default_tuning = GameplayTuning::default();
+    let scoring = &tuning.as_deref().unwrap_or(&default_tuning).scoring;
+
+    for kill in defeated.read() {
+        let points = game_stats
+            .combat
+            .record_kill(kill.enemy_type, kill.combo_chain, scoring);
+        awarded.write(ScoreAwarded {
+            points,
+            reason: ScoreReason::Kill {
+                enemy_type: kill.enemy_type,
+                combo_chain: kill.combo_chain,
+            },
+        });
+    }
+
+    for clear in cleared.read() {
+        if !clear.no_damage {
+            continue;
+        }
+        game_stats.combat.record_no_damage_clear(scoring);
+        awarded.write(ScoreAwarded {
+            points: scoring.no_damage_arena_bonus,
+            reason: ScoreReason::NoDamageClear { arena: clear.arena },
+        });
+    }
+}
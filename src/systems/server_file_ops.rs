@@ -1,22 +1,55 @@
 use bevy::prelude::*;
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::shared_utils::*;
 use crate::{
     resources::{CompleteGameState, SaveFileData, SaveFileMetadata},
-    systems::error_handling::SaveSystemError,
+    systems::{
+        error_handling::{SaveBackupPolicy, SaveSystemError},
+        save_signing::{SaveSigningKey, SignatureStatus},
+    },
 };
 
+/// 存档槽位名（文件名去掉扩展名），用作备份文件前缀
+pub fn save_slot_name(save_path: &Path) -> Result<&str, SaveSystemError> {
+    save_path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .filter(|stem| !stem.is_empty())
+        .ok_or_else(|| SaveSystemError::InvalidFileName(save_path.to_string_lossy().to_string()))
+}
+
+/// 解码并校验一份存档文件的原始字节，同时返回解码后的 JSON 原文（签名按原文校验）
+fn decode_save_file(file_data: &[u8]) -> Result<(SaveFileData, String), SaveSystemError> {
+    // 自动识别压缩格式并解码
+    let json_data = decode_file_payload(file_data)
+        .map_err(|e| SaveSystemError::DeserializationFailed(e.to_string()))?;
+
+    let save_data = serde_json::from_str::<SaveFileData>(&json_data).map_err(|e| {
+        SaveSystemError::DeserializationFailed(format!("Unsupported save file format: {}", e))
+    })?;
+
+    if !save_data.verify_serialized_checksum(&json_data) {
+        return Err(SaveSystemError::ChecksumMismatch);
+    }
+
+    Ok((save_data, json_data))
+}
+
 /// 内部实现：异步保存游戏状态
+///
+/// 返回写入的存档 JSON（压缩前），已签名的存档可以原样上传给服务器校验。
 pub async fn save_game_state_internal(
     save_path: PathBuf,
     game_state: CompleteGameState,
     metadata: SaveFileMetadata,
     compression_enabled: bool,
     compression_level: u32,
-) -> Result<(), SaveSystemError> {
+    backup_policy: Option<SaveBackupPolicy>,
+    signing_key: Option<SaveSigningKey>,
+) -> Result<String, SaveSystemError> {
     if let Some(parent_dir) = save_path.parent()
         && !parent_dir.as_os_str().is_empty()
     {
@@ -24,8 +57,11 @@ pub async fn save_game_state_internal(
             .map_err(|e| SaveSystemError::DirectoryCreationFailed(e.to_string()))?;
     }
 
-    // 创建保存数据结构（v2 + 校验和）
-    let save_data = SaveFileData::new(metadata, game_state);
+    // 创建保存数据结构（v2 + 校验和，有安装密钥时附带签名）
+    let mut save_data = SaveFileData::new(metadata, game_state);
+    if let Some(key) = signing_key.as_ref() {
+        save_data.sign(key);
+    }
 
     // 序列化数据
     let final_json_data = serde_json::to_string_pretty(&save_data)
@@ -36,41 +72,124 @@ pub async fn save_game_state_internal(
         compress_data(final_json_data.as_bytes(), compression_level)
             .map_err(|e| SaveSystemError::CompressionFailed(e.to_string()))?
     } else {
-        final_json_data.into_bytes()
+        final_json_data.clone().into_bytes()
     };
 
     // 原子写入文件
     atomic_write_file(&save_path, &file_data)
         .map_err(|e| SaveSystemError::FileWriteFailed(e.to_string()))?;
 
-    Ok(())
+    // 每次成功写入后轮转一份副本；备份失败不影响已经落盘的存档。
+    if let Some(policy) = backup_policy {
+        let backup_result =
+            save_slot_name(&save_path).and_then(|slot| policy.rotate(slot, &file_data));
+        if let Err(e) = backup_result {
+            warn!("Failed to back up {:?}: {:?}", save_path, e);
+        }
+    }
+
+    Ok(final_json_data)
 }
 
 /// 内部实现：异步加载游戏状态
 pub async fn load_game_state_internal(
     save_path: PathBuf,
     _compression_enabled: bool,
+    signing_key: Option<SaveSigningKey>,
 ) -> Result<(CompleteGameState, SaveFileMetadata), SaveSystemError> {
     // 读取文件
     let file_data =
         fs::read(&save_path).map_err(|e| SaveSystemError::FileNotFound(e.to_string()))?;
 
-    // 自动识别压缩格式并解码
-    let json_data = decode_file_payload(&file_data)
-        .map_err(|e| SaveSystemError::DeserializationFailed(e.to_string()))?;
+    let (save_data, json_data) = decode_save_file(&file_data)?;
 
-    let save_data = serde_json::from_str::<SaveFileData>(&json_data).map_err(|e| {
-        SaveSystemError::DeserializationFailed(format!("Unsupported save file format: {}", e))
-    })?;
-
-    if !save_data.verify_serialized_checksum(&json_data) {
-        return Err(SaveSystemError::ChecksumMismatch);
+    // 未签名的旧存档与其他安装签名的存档照常读取；本机签名被破坏则拒绝
+    let status = signature_status(&save_data, &json_data, signing_key.as_ref());
+    if status == SignatureStatus::Invalid {
+        return Err(SaveSystemError::SignatureMismatch);
     }
 
     let mut metadata = save_data.metadata;
     metadata.file_path = save_path.to_string_lossy().to_string();
 
-    Ok((save_data.game_state, metadata))
+    Ok((trusted_game_state(save_data.game_state, status), metadata))
+}
+
+/// 没有安装密钥时无从校验，按未签名处理
+fn signature_status(
+    save_data: &SaveFileData,
+    json_data: &str,
+    signing_key: Option<&SaveSigningKey>,
+) -> SignatureStatus {
+    signing_key.map_or(SignatureStatus::Unsigned, |key| {
+        save_data.verify_signature(json_data, key)
+    })
+}
+
+/// 只有本机签名校验通过的存档能继续向服务器上报成绩，其余读入后标记为不可信
+fn trusted_game_state(
+    mut game_state: CompleteGameState,
+    status: SignatureStatus,
+) -> CompleteGameState {
+    if status != SignatureStatus::Valid {
+        game_state.untrusted_save = true;
+    }
+    game_state
+}
+
+/// 内部实现：从滚动备份恢复损坏的存档
+///
+/// 按新到旧逐份校验备份，第一份完好的备份原子覆盖存档槽位后作为读档结果返回；
+/// 被替换的损坏文件转存为 `<槽位名>.corrupt`。签名被改动过的备份同样跳过。
+pub async fn restore_game_state_from_backup_internal(
+    save_path: PathBuf,
+    backup_policy: SaveBackupPolicy,
+    signing_key: Option<SaveSigningKey>,
+) -> Result<(CompleteGameState, SaveFileMetadata), SaveSystemError> {
+    let slot_name = save_slot_name(&save_path)?;
+
+    for backup_path in backup_policy.existing_backups(slot_name) {
+        let backup_data = match fs::read(&backup_path) {
+            Ok(data) => data,
+            Err(e) => {
+                warn!("Failed to read backup {:?}: {}", backup_path, e);
+                continue;
+            }
+        };
+
+        let (save_data, json_data) = match decode_save_file(&backup_data) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                warn!("Skipping damaged backup {:?}: {:?}", backup_path, e);
+                continue;
+            }
+        };
+        let status = signature_status(&save_data, &json_data, signing_key.as_ref());
+        if status == SignatureStatus::Invalid {
+            warn!("Skipping tampered backup {:?}", backup_path);
+            continue;
+        }
+
+        if save_path.exists()
+            && let Err(e) = fs::copy(&save_path, backup_policy.corrupted_copy_path(slot_name))
+        {
+            warn!("Failed to keep corrupted copy of {:?}: {}", save_path, e);
+        }
+
+        atomic_write_file(&save_path, &backup_data)
+            .map_err(|e| SaveSystemError::FileWriteFailed(e.to_string()))?;
+
+        let mut metadata = save_data.metadata;
+        metadata.file_path = save_path.to_string_lossy().to_string();
+        crate::debug_log!("Restored {:?} from backup {:?}", save_path, backup_path);
+
+        return Ok((trusted_game_state(save_data.game_state, status), metadata));
+    }
+
+    Err(SaveSystemError::FileNotFound(format!(
+        "No valid backup for '{}'",
+        slot_name
+    )))
 }
 
 /// 内部实现：异步扫描存档文件
@@ -116,16 +235,7 @@ async fn load_save_metadata_internal(
     let file_data =
         fs::read(&save_path).map_err(|e| SaveSystemError::FileNotFound(e.to_string()))?;
 
-    let json_data = decode_file_payload(&file_data)
-        .map_err(|e| SaveSystemError::DeserializationFailed(e.to_string()))?;
-
-    let save_data = serde_json::from_str::<SaveFileData>(&json_data).map_err(|e| {
-        SaveSystemError::DeserializationFailed(format!("Unsupported save metadata format: {}", e))
-    })?;
-
-    if !save_data.verify_serialized_checksum(&json_data) {
-        return Err(SaveSystemError::ChecksumMismatch);
-    }
+    let (save_data, _) = decode_save_file(&file_data)?;
 
     let mut metadata = save_data.metadata;
     metadata.file_path = save_path.to_string_lossy().to_string();
@@ -179,8 +289,11 @@ mod tests {
         let json = serde_json::to_string_pretty(&save_data).expect("serialize test save");
         fs::write(&temp_path, json.as_bytes()).expect("write test save file");
 
-        let result =
-            futures_lite::future::block_on(load_game_state_internal(temp_path.clone(), false));
+        let result = futures_lite::future::block_on(load_game_state_internal(
+            temp_path.clone(),
+            false,
+            None,
+        ));
         assert!(
             matches!(result, Err(SaveSystemError::ChecksumMismatch)),
             "load should fail on checksum mismatch"
@@ -197,8 +310,13 @@ mod tests {
         ));
         fs::write(&temp_path, legacy_character_save_json()).expect("write legacy save");
 
-        let result =
-            futures_lite::future::block_on(load_game_state_internal(temp_path.clone(), false));
+        // 未签名的旧存档在启用签名后仍可读取
+        let signing_key = Some(SaveSigningKey::from_bytes([3; 32]));
+        let result = futures_lite::future::block_on(load_game_state_internal(
+            temp_path.clone(),
+            false,
+            signing_key,
+        ));
         let (state, metadata) = result.expect("legacy save should remain loadable");
         assert_eq!(
             state.selected_character,
@@ -211,4 +329,121 @@ mod tests {
 
         let _ = fs::remove_file(temp_path);
     }
+
+    #[test]
+    fn corrupted_save_is_restored_from_newest_rotated_backup() {
+        let directory =
+            std::env::temp_dir().join(format!("emiyashiro-backup-test-{}", uuid::Uuid::new_v4()));
+        let save_path = directory.join("slot.json");
+        let policy = SaveBackupPolicy {
+            directory: directory.join("backup"),
+            max_backups: 2,
+        };
+
+        for score in [1, 2, 3] {
+            let metadata = SaveFileMetadata {
+                name: "slot".to_string(),
+                score,
+                distance: 0.0,
+                play_time: 0.0,
+                save_timestamp: chrono::Utc::now(),
+                file_path: String::new(),
+                selected_character: crate::states::CharacterType::Shirou,
+            };
+            futures_lite::future::block_on(save_game_state_internal(
+                save_path.clone(),
+                CompleteGameState::default(),
+                metadata,
+                true,
+                3,
+                Some(policy.clone()),
+                None,
+            ))
+            .expect("save with backup");
+        }
+        assert_eq!(policy.existing_backups("slot").len(), 2);
+
+        fs::write(&save_path, b"{ truncated").expect("corrupt save");
+        let load =
+            futures_lite::future::block_on(load_game_state_internal(save_path.clone(), true, None));
+        assert!(load.is_err_and(|error| error.is_corruption()));
+
+        let (_, metadata) = futures_lite::future::block_on(
+            restore_game_state_from_backup_internal(save_path.clone(), policy.clone(), None),
+        )
+        .expect("restore from backup");
+        assert_eq!(metadata.score, 3);
+        assert!(policy.corrupted_copy_path("slot").exists());
+        assert!(
+            futures_lite::future::block_on(load_game_state_internal(save_path, true, None)).is_ok(),
+            "restored slot should load normally"
+        );
+
+        let _ = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn load_rejects_locally_signed_save_edited_outside_the_game() {
+        let save_path = std::env::temp_dir().join(format!(
+            "emiyashiro-signed-save-test-{}.json",
+            uuid::Uuid::new_v4()
+        ));
+        let key = SaveSigningKey::from_bytes([9; 32]);
+        let metadata = SaveFileMetadata {
+            name: "signed".to_string(),
+            score: 10,
+            distance: 1.0,
+            play_time: 1.0,
+            save_timestamp: chrono::Utc::now(),
+            file_path: String::new(),
+            selected_character: crate::states::CharacterType::Shirou,
+        };
+        futures_lite::future::block_on(save_game_state_internal(
+            save_path.clone(),
+            CompleteGameState::default(),
+            metadata,
+            false,
+            3,
+            None,
+            Some(key.clone()),
+        ))
+        .expect("write signed save");
+        let (state, _) = futures_lite::future::block_on(load_game_state_internal(
+            save_path.clone(),
+            false,
+            Some(key.clone()),
+        ))
+        .expect("load signed save");
+        assert!(!state.untrusted_save);
+
+        // 其他安装签名或无法校验的存档照常读取，但不再向服务器上报成绩
+        for other_key in [Some(SaveSigningKey::from_bytes([7; 32])), None] {
+            let (state, _) = futures_lite::future::block_on(load_game_state_internal(
+                save_path.clone(),
+                false,
+                other_key,
+            ))
+            .expect("load foreign save");
+            assert!(state.untrusted_save);
+        }
+
+        let json = fs::read_to_string(&save_path).expect("read signed save");
+        let mut edited: SaveFileData = serde_json::from_str(&json).expect("parse signed save");
+        edited.game_state.score = 999_999;
+        edited.refresh_checksum();
+        fs::write(
+            &save_path,
+            serde_json::to_string_pretty(&edited).expect("serialize edited save"),
+        )
+        .expect("write edited save");
+
+        let result = futures_lite::future::block_on(load_game_state_internal(
+            save_path.clone(),
+            false,
+            Some(key),
+        ));
+        assert!(matches!(result, Err(SaveSystemError::SignatureMismatch)));
+
+        let _ = fs::remove_file(save_path);
+    }
 }
@@ -0,0 +1,442 @@
+//! 在线会话记录
+//!
+//! 服务器运行时为已认证连接上的每一局维护一条 `game_sessions` 记录：认证时开始，
+//! 客户端上报 [`MilestoneKind::RunStarted`] 时结束上一局并重新开始计数，
+//! 跳跃/攻击等输入与客户端上报的死亡、检查点、通关作为 `player_actions` 记录，
+//! 换局、断线（或同一连接切换账号）时写入该局的距离、跳跃次数、游玩时间与分数；
+//! 分数包含击杀与无伤清场得分：客户端只上报敌人类型、连段与竞技场编号，
+//! 分数由服务器按计分表计算，每条事件有上限，击杀按时间窗限速且每局有总数上限，
+//! 每个竞技场只计一次；跳跃只统计服务器模拟中落地后的起跳。
+//! 客户端上报的角色与通关用时用于排行榜；通关用时至少取服务器侧本局的时长。
+//! 检查点与会话结束时生成 [`SaveSnapshot`]，经 RabbitMQ 存档队列写入 `save_games`。
+//!
+//! ECS 侧只负责统计并通过通道发出 [`SessionRecord`]，数据库写入由
+//! `session_writer` 在 Tokio 任务中批量完成，不阻塞固定帧。
+
+use bevy::prelude::*;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::protocol::MilestoneKind;
+use crate::resources::{
+    GameConfig, ScoringTuning,
+    scoring::{CombatRecord, base_run_score},
+};
+
+/// 在线自动存档的槽位名（每个账号一份，检查点与断线时覆盖）
+pub const ONLINE_SAVE_NAME: &str = "online_autosave";
+/// 客户端尚未上报角色时，在线会话记为该类型
+pub const ONLINE_CHARACTER_TYPE: &str = "online";
+/// 天空之城关卡标识（`MilestoneKind::Victory` 对应的关卡）
+pub const SKY_CITY_LEVEL_ID: &str = "sky_city";
+/// 单条击杀/清场事件最多计入的分数
+const MAX_POINTS_PER_SCORE_EVENT: u32 = 5_000;
+/// 击杀计分的限速窗口，窗口内超出上限的击杀只记录不计分
+const KILL_SCORE_WINDOW: Duration = Duration::from_secs(2);
+const MAX_SCORED_KILLS_PER_WINDOW: u32 = 20;
+/// 每一局最多计分的击杀数，远多于一局实际能遇到的敌人
+const MAX_SCORED_KILLS_PER_RUN: u32 = 300;
+/// 每个会话最多计分的无伤清场次数
+const MAX_SCORED_ARENA_CLEARS: usize = 8;
+
+/// 发给数据库写入任务的记录
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionRecord {
+    Start {
+        client_id: u64,
+        player_id: Uuid,
+        character_type: String,
+    },
+    Action {
+        client_id: u64,
+        action: RecordedAction,
+    },
+    /// 会话中途上报（或更换）角色
+    Character {
+        client_id: u64,
+        character_type: String,
+    },
+    LevelCleared {
+        client_id: u64,
+        level_id: &'static str,
+        clear_time: f32,
+    },
+    /// 在线存档（检查点或会话结束前）
+    Save {
+        player_id: Uuid,
+        snapshot: SaveSnapshot,
+    },
+    End {
+        client_id: u64,
+        summary: SessionSummary,
+    },
+}
+
+/// 在线存档内容，序列化后作为 `save_games.game_data`
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SaveSnapshot {
+    pub character_type: String,
+    /// 最近一次到达的检查点
+    pub checkpoint_id: Option<i32>,
+    pub position: Option<(f32, f32)>,
+    pub distance_traveled: f32,
+    pub jump_count: i32,
+    pub play_time: f32,
+    pub score: i32,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// 一条玩家操作
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedAction {
+    pub action_type: &'static str,
+    pub action_data: Option<serde_json::Value>,
+    pub position: Option<(f32, f32)>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// 会话结束时的统计
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SessionSummary {
+    pub distance_traveled: f32,
+    pub jump_count: i32,
+    pub play_time: f32,
+    pub score: i32,
+}
+
+/// 记录的操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionActionKind {
+    Jump,
+    Attack,
+    Milestone(MilestoneKind),
+}
+
+impl SessionActionKind {
+    fn action_type(self) -> &'static str {
+        match self {
+            SessionActionKind::Jump => "jump",
+            SessionActionKind::Attack => "attack",
+            SessionActionKind::Milestone(MilestoneKind::Death) => "death",
+            SessionActionKind::Milestone(MilestoneKind::Checkpoint { .. }) => "checkpoint",
+            SessionActionKind::Milestone(MilestoneKind::Victory { .. }) => "victory",
+            SessionActionKind::Milestone(MilestoneKind::Kill { .. }) => "kill",
+            SessionActionKind::Milestone(MilestoneKind::NoDamageClear { .. }) => "no_damage_clear",
+            SessionActionKind::Milestone(MilestoneKind::RunStarted { .. }) => "run_started",
+        }
+    }
+
+    fn action_data(self, points: Option<u32>) -> Option<serde_json::Value> {
+        match self {
+            SessionActionKind::Milestone(MilestoneKind::Checkpoint { id }) => {
+                Some(serde_json::json!({ "checkpoint_id": id }))
+            }
+            SessionActionKind::Milestone(MilestoneKind::Victory { clear_time_ms }) => {
+                Some(serde_json::json!({ "clear_time_ms": clear_time_ms }))
+            }
+            SessionActionKind::Milestone(MilestoneKind::Kill { enemy, combo_chain }) => Some(
+                serde_json::json!({ "enemy": enemy, "combo_chain": combo_chain, "points": points }),
+            ),
+            SessionActionKind::Milestone(MilestoneKind::NoDamageClear { arena }) => {
+                Some(serde_json::json!({ "arena": arena, "points": points }))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ActiveSession {
+    player_id: Uuid,
+    character_type: String,
+    started_at: Instant,
+    jump_count: u32,
+    /// 服务器按计分表累计的战斗得分
+    combat_points: u32,
+    max_x: f32,
+    last_checkpoint: Option<i32>,
+    kill_window_started: Instant,
+    kills_in_window: u32,
+    /// 本局已计分的击杀数
+    scored_kills: u32,
+    scored_arenas: HashSet<i32>,
+}
+
+impl ActiveSession {
+    /// 按服务器计分表给击杀/清场计分，返回计入的分数；其他里程碑返回 `None`
+    fn score_milestone(&mut self, kind: MilestoneKind, tuning: &ScoringTuning) -> Option<u32> {
+        let points = match kind {
+            MilestoneKind::Kill { enemy, combo_chain } => {
+                if self.kill_window_started.elapsed() >= KILL_SCORE_WINDOW {
+                    self.kill_window_started = Instant::now();
+                    self.kills_in_window = 0;
+                }
+                self.kills_in_window = self.kills_in_window.saturating_add(1);
+                if self.kills_in_window > MAX_SCORED_KILLS_PER_WINDOW
+                    || self.scored_kills >= MAX_SCORED_KILLS_PER_RUN
+                {
+                    0
+                } else {
+                    self.scored_kills += 1;
+                    CombatRecord::default().record_kill(enemy, combo_chain, tuning)
+                }
+            }
+            MilestoneKind::NoDamageClear { arena } => {
+                if arena < 0
+                    || self.scored_arenas.len() >= MAX_SCORED_ARENA_CLEARS
+                    || !self.scored_arenas.insert(arena)
+                {
+                    0
+                } else {
+                    tuning.no_damage_arena_bonus
+                }
+            }
+            _ => return None,
+        }
+        .min(MAX_POINTS_PER_SCORE_EVENT);
+        self.combat_points = self.combat_points.saturating_add(points);
+        Some(points)
+    }
+
+    fn summary(&self) -> SessionSummary {
+        let distance_traveled = (self.max_x - GameConfig::PLAYER_START_POS.x).max(0.0);
+        SessionSummary {
+            distance_traveled,
+            jump_count: self.jump_count as i32,
+            play_time: self.started_at.elapsed().as_secs_f32(),
+            // 与单机存档相同的计分公式
+            score: base_run_score(distance_traveled, self.jump_count)
+                .saturating_add(self.combat_points)
+                .min(i32::MAX as u32) as i32,
+        }
+    }
+
+    fn save_record(&self, position: Option<Vec3>) -> SessionRecord {
+        let summary = self.summary();
+        SessionRecord::Save {
+            player_id: self.player_id,
+            snapshot: SaveSnapshot {
+                character_type: self.character_type.clone(),
+                checkpoint_id: self.last_checkpoint,
+                position: position.map(|pos| (pos.x, pos.y)),
+                distance_traveled: summary.distance_traveled,
+                jump_count: summary.jump_count,
+                play_time: summary.play_time,
+                score: summary.score,
+                saved_at: Utc::now(),
+            },
+        }
+    }
+}
+
+/// 每个连接的会话统计与通往写入任务的通道（没有通道时只统计不落库）
+#[derive(Resource, Default)]
+pub struct SessionRecorder {
+    sink: Option<mpsc::UnboundedSender<SessionRecord>>,
+    active: HashMap<u64, ActiveSession>,
+    selected_characters: HashMap<u64, String>,
+    /// 击杀与清场的计分表（服务器启动时从玩法调参读取）
+    scoring: ScoringTuning,
+}
+
+impl SessionRecorder {
+    pub fn with_sink(sink: mpsc::UnboundedSender<SessionRecord>) -> Self {
+        Self {
+            sink: Some(sink),
+            ..default()
+        }
+    }
+
+    pub fn with_scoring(mut self, scoring: ScoringTuning) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    pub fn is_active(&self, client_id: u64) -> bool {
+        self.active.contains_key(&client_id)
+    }
+
+    fn emit(&self, record: SessionRecord) {
+        if let Some(sink) = &self.sink {
+            let _ = sink.send(record);
+        }
+    }
+
+    /// 客户端开始新的一局：结束该连接当前的会话并为同一账号开始新会话，
+    /// 计数与用时从这一刻重新开始；未认证的连接只记下角色
+    pub fn start_run(&mut self, client_id: u64, character_type: &str, position: Option<Vec3>) {
+        self.selected_characters
+            .insert(client_id, character_type.to_string());
+        if let Some(player_id) = self.active.get(&client_id).map(|session| session.player_id) {
+            self.start(client_id, player_id, position);
+        }
+    }
+
+    /// 连接认证后开始新会话；同一连接已有会话时先结束旧会话
+    pub fn start(&mut self, client_id: u64, player_id: Uuid, position: Option<Vec3>) {
+        self.finish(client_id, position);
+        let character_type = self
+            .selected_characters
+            .get(&client_id)
+            .cloned()
+            .unwrap_or_else(|| ONLINE_CHARACTER_TYPE.to_string());
+        self.active.insert(
+            client_id,
+            ActiveSession {
+                player_id,
+                character_type: character_type.clone(),
+                started_at: Instant::now(),
+                jump_count: 0,
+                combat_points: 0,
+                max_x: position.map_or(GameConfig::PLAYER_START_POS.x, |pos| pos.x),
+                last_checkpoint: None,
+                kill_window_started: Instant::now(),
+                kills_in_window: 0,
+                scored_kills: 0,
+                scored_arenas: HashSet::new(),
+            },
+        );
+        self.emit(SessionRecord::Start {
+            client_id,
+            player_id,
+            character_type,
+        });
+    }
+
+    /// 记录客户端选择的角色；会话进行中且角色变化时同步到会话记录
+    pub fn select_character(&mut self, client_id: u64, character_type: &str) {
+        self.selected_characters
+            .insert(client_id, character_type.to_string());
+        if let Some(session) = self.active.get_mut(&client_id)
+            && session.character_type != character_type
+        {
+            session.character_type = character_type.to_string();
+            self.emit(SessionRecord::Character {
+                client_id,
+                character_type: character_type.to_string(),
+            });
+        }
+    }
+
+    /// 记录一条操作；未认证的连接不记录，到达检查点时额外生成在线存档
+    pub fn record(&mut self, client_id: u64, kind: SessionActionKind, position: Option<Vec3>) {
+        let Some(session) = self.active.get_mut(&client_id) else {
+            return;
+        };
+        let points = match kind {
+            SessionActionKind::Jump => {
+                session.jump_count = session.jump_count.saturating_add(1);
+                None
+            }
+            SessionActionKind::Attack => None,
+            SessionActionKind::Milestone(milestone) => {
+                session.score_milestone(milestone, &self.scoring)
+            }
+        };
+        if let Some(position) = position {
+            session.max_x = session.max_x.max(position.x);
+        }
+        let session_secs = session.started_at.elapsed().as_secs_f32();
+        let save = match kind {
+            SessionActionKind::Milestone(MilestoneKind::Checkpoint { id }) => {
+                session.last_checkpoint = Some(id);
+                Some(session.save_record(position))
+            }
+            _ => None,
+        };
+
+        self.emit(SessionRecord::Action {
+            client_id,
+            action: RecordedAction {
+                action_type: kind.action_type(),
+                action_data: kind.action_data(points),
+                position: position.map(|pos| (pos.x, pos.y)),
+                timestamp: Utc::now(),
+            },
+        });
+
+        if let SessionActionKind::Milestone(MilestoneKind::Victory { clear_time_ms }) = kind {
+            // 通关用时不得短于服务器记录的会话时长，防止上报伪造的极短用时
+            self.emit(SessionRecord::LevelCleared {
+                client_id,
+                level_id: SKY_CITY_LEVEL_ID,
+                clear_time: (clear_time_ms as f32 / 1000.0).max(session_secs),
+            });
+        }
+        if let Some(save) = save {
+            self.emit(save);
+        }
+    }
+
+    /// 结束会话：先发出最终存档，再发出最终统计
+    pub fn finish(&mut self, client_id: u64, position: Option<Vec3>) -> Option<SessionSummary> {
+        let mut session = self.active.remove(&client_id)?;
+        if let Some(position) = position {
+            session.max_x = session.max_x.max(position.x);
+        }
+        self.emit(session.save_record(position));
+        let summary = session.summary();
+        self.emit(SessionRecord::End { client_id, summary });
+        Some(summary)
+    }
+
+    /// 连接断开：结束会话并忘记该连接的角色选择
+    pub fn disconnect(&mut self, client_id: u64, position: Option<Vec3>) -> Option<SessionSummary> {
+        self.selected_characters.remove(&client_id);
+        self.finish(client_id, position)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::EnemyType;
+
+    #[test]
+    fn kill_points_stop_at_the_per_run_budget() {
+        let scoring = ScoringTuning::default();
+        let mut recorder = SessionRecorder::default().with_scoring(scoring.clone());
+        recorder.start(1, Uuid::new_v4(), None);
+
+        let kill = MilestoneKind::Kill {
+            enemy: EnemyType::Slime,
+            combo_chain: 0,
+        };
+        let mut awarded = Vec::new();
+        for _ in 0..MAX_SCORED_KILLS_PER_RUN + 10 {
+            let session = recorder
+                .active
+                .get_mut(&1)
+                .expect("session should be active");
+            // 每次都从新的限速窗口开始，只考察每局总数上限
+            session.kills_in_window = 0;
+            awarded.push(session.score_milestone(kill, &scoring));
+        }
+
+        let scored = awarded
+            .iter()
+            .filter(|points| **points == Some(scoring.slime_points))
+            .count();
+        assert_eq!(scored, MAX_SCORED_KILLS_PER_RUN as usize);
+        assert!(
+            awarded[MAX_SCORED_KILLS_PER_RUN as usize..]
+                .iter()
+                .all(|points| *points == Some(0))
+        );
+
+        // 新的一局重新获得击杀计分额度
+        recorder.start_run(1, "sakura", None);
+        let session = recorder
+            .active
+            .get_mut(&1)
+            .expect("new run should be active");
+        assert_eq!(
+            session.score_milestone(kill, &scoring),
+            Some(scoring.slime_points)
+        );
+    }
+}
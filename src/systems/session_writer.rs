@@ -0,0 +1,203 @@
+//! 在线会话写入任务
+//!
+//! 消费 [`SessionRecord`]：开始时创建 `game_sessions` 行，操作攒批后一次写入
+//! `player_actions`（满批或定时刷新），结束时先刷出剩余操作再写最终统计。
+//! 写入新分数或通关时间后让对应排行榜缓存失效。
+//! 在线存档交给 [`SavePublisher`] 投递到存档队列，不直接写库。
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::database::{Database, leaderboard::LeaderboardService};
+use crate::protocol::LeaderboardKind;
+use crate::systems::save_worker::{SaveGameTask, SavePublisher};
+use crate::systems::session_recording::{ONLINE_SAVE_NAME, RecordedAction, SessionRecord};
+
+const ACTION_BATCH_SIZE: usize = 64;
+const ACTION_FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 写入任务视角下的一个在线会话
+struct OpenSession {
+    session_id: Uuid,
+    character_type: String,
+}
+
+/// 运行会话写入任务，直到记录通道关闭。
+pub async fn run_session_writer(
+    database: Arc<Database>,
+    leaderboard: Arc<LeaderboardService>,
+    saves: Option<SavePublisher>,
+    mut records: mpsc::UnboundedReceiver<SessionRecord>,
+) {
+    let mut sessions: HashMap<u64, OpenSession> = HashMap::new();
+    let mut pending: Vec<(Uuid, RecordedAction)> = Vec::new();
+    let mut flush_timer = tokio::time::interval(ACTION_FLUSH_INTERVAL);
+
+    loop {
+        tokio::select! {
+            record = records.recv() => {
+                let Some(record) = record else {
+                    break;
+                };
+                handle_record(
+                    &database,
+                    &leaderboard,
+                    saves.as_ref(),
+                    record,
+                    &mut sessions,
+                    &mut pending,
+                )
+                .await;
+            }
+            _ = flush_timer.tick() => flush_actions(&database, &mut pending).await,
+        }
+    }
+
+    flush_actions(&database, &mut pending).await;
+}
+
+async fn handle_record(
+    database: &Database,
+    leaderboard: &LeaderboardService,
+    saves: Option<&SavePublisher>,
+    record: SessionRecord,
+    sessions: &mut HashMap<u64, OpenSession>,
+    pending: &mut Vec<(Uuid, RecordedAction)>,
+) {
+    match record {
+        SessionRecord::Start {
+            client_id,
+            player_id,
+            character_type,
+        } => match database
+            .create_game_session(player_id, &character_type)
+            .await
+        {
+            Ok(session) => {
+                sessions.insert(
+                    client_id,
+                    OpenSession {
+                        session_id: session.id,
+                        character_type,
+                    },
+                );
+            }
+            Err(error) => {
+                bevy::log::warn!("Failed to open game session for client {client_id}: {error}");
+            }
+        },
+        SessionRecord::Action { client_id, action } => {
+            if let Some(session) = sessions.get(&client_id) {
+                pending.push((session.session_id, action));
+            }
+            if pending.len() >= ACTION_BATCH_SIZE {
+                flush_actions(database, pending).await;
+            }
+        }
+        SessionRecord::Character {
+            client_id,
+            character_type,
+        } => {
+            let Some(session) = sessions.get_mut(&client_id) else {
+                return;
+            };
+            if let Err(error) = database
+                .set_session_character(session.session_id, &character_type)
+                .await
+            {
+                bevy::log::warn!(
+                    "Failed to update character of game session {}: {error}",
+                    session.session_id
+                );
+            }
+            session.character_type = character_type;
+        }
+        SessionRecord::LevelCleared {
+            client_id,
+            level_id,
+            clear_time,
+        } => {
+            let Some(session) = sessions.get(&client_id) else {
+                return;
+            };
+            match database
+                .record_level_clear(session.session_id, level_id, clear_time)
+                .await
+            {
+                Ok(()) => {
+                    leaderboard
+                        .invalidate(LeaderboardKind::FastestClear, &session.character_type)
+                        .await;
+                }
+                Err(error) => bevy::log::warn!(
+                    "Failed to record clear of game session {}: {error}",
+                    session.session_id
+                ),
+            }
+        }
+        SessionRecord::Save {
+            player_id,
+            snapshot,
+        } => {
+            let Some(saves) = saves else {
+                return;
+            };
+            let saved_at = snapshot.saved_at;
+            let task = match serde_json::to_value(&snapshot) {
+                Ok(game_data) => SaveGameTask {
+                    player_id,
+                    save_name: ONLINE_SAVE_NAME.to_string(),
+                    game_data,
+                    saved_at,
+                },
+                Err(error) => {
+                    bevy::log::warn!(
+                        "Failed to encode online save for player {player_id}: {error}"
+                    );
+                    return;
+                }
+            };
+            if let Err(error) = saves.enqueue(&task) {
+                bevy::log::error!("Failed to queue online save for player {player_id}: {error}");
+            }
+        }
+        SessionRecord::End { client_id, summary } => {
+            let Some(session) = sessions.remove(&client_id) else {
+                return;
+            };
+            flush_actions(database, pending).await;
+            match database
+                .update_game_session(
+                    session.session_id,
+                    summary.distance_traveled,
+                    summary.jump_count,
+                    summary.play_time,
+                    summary.score,
+                )
+                .await
+            {
+                Ok(()) if summary.score > 0 => {
+                    leaderboard
+                        .invalidate(LeaderboardKind::BestScore, &session.character_type)
+                        .await;
+                }
+                Ok(()) => {}
+                Err(error) => bevy::log::warn!(
+                    "Failed to close game session {}: {error}",
+                    session.session_id
+                ),
+            }
+        }
+    }
+}
+
+async fn flush_actions(database: &Database, pending: &mut Vec<(Uuid, RecordedAction)>) {
+    if pending.is_empty() {
+        return;
+    }
+    if let Err(error) = database.log_player_actions(pending).await {
+        bevy::log::warn!("Dropping {} player actions: {error}", pending.len());
+    }
+    pending.clear();
+}
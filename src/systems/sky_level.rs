@@ -7,7 +7,8 @@ use bevy_ecs_ldtk::prelude::*;
 
 use crate::{
     components::*,
-    events::{DamageEvent, DamageSource},
+    events::{ArenaCleared, CheckpointActivated, DamageEvent, DamageSource, StatusEffectEvent},
+    resources::{GameStats, GameplayTuning},
     states::GameState,
     systems::collision::CollisionBox,
 };
@@ -64,7 +65,14 @@ type RuntimeGateQuery<'w, 's> = Query<
     With<SkyGateVisual>,
 >;
 
-fn grid_translation(coords: GridCoords, z: f32) -> Vec3 {
+type PendingArenaSpawnQuery<'w, 's> = Query<
+    'w,
+    's,
+    (Option<&'static SkyEnemySpawn>, Option<&'static SkyBoss>),
+    Or<(With<SkyEnemySpawn>, With<SkyBoss>)>,
+>;
+
+pub(crate) fn grid_translation(coords: GridCoords, z: f32) -> Vec3 {
     bevy_ecs_ldtk::utils::grid_coords_to_translation(coords, IVec2::splat(SKY_LEVEL_GRID)).extend(z)
 }
 
@@ -674,6 +682,7 @@ pub fn activate_map_enemies(
     tuning: Option<Res<crate::resources::GameplayTuning>>,
     players: Query<&Transform, With<Player>>,
     spawns: Query<(Entity, &SkyEnemySpawn, &GridCoords)>,
+    bosses: Query<(Entity, &SkyBoss, &GridCoords)>,
 ) {
     let Some(player) = players.iter().next() else {
         return;
@@ -687,6 +696,8 @@ pub fn activate_map_enemies(
             SkyEnemyKind::Slime => 3.0,
             SkyEnemyKind::Familiar => 96.0,
             SkyEnemyKind::HeroicSpirit => 34.0,
+            SkyEnemyKind::Shadow => 30.0,
+            SkyEnemyKind::BlackMud => -3.0,
         };
         if (position.x - player.translation.x).abs() > MAP_ENEMY_ACTIVATION_DISTANCE {
             continue;
@@ -707,15 +718,31 @@ pub fn activate_map_enemies(
         });
         commands.entity(entity).despawn();
     }
+
+    for (entity, spawn, coords) in bosses.iter() {
+        let mut position = grid_translation(*coords, 1.3);
+        position.y += crate::systems::boss::BOSS_COLLISION_SIZE.y * 0.5 - 8.0;
+        if (position.x - player.translation.x).abs() > MAP_ENEMY_ACTIVATION_DISTANCE {
+            continue;
+        }
+
+        let boss = crate::systems::boss::spawn_boss(&mut commands, position, spawn, tuning);
+        commands.entity(boss).insert(SkyEncounterEnemy {
+            arena: spawn.arena,
+            anchor_y: position.y,
+        });
+        commands.entity(entity).despawn();
+    }
 }
 
 pub fn update_combat_gates(
     mut commands: Commands,
     players: Query<&Transform, With<Player>>,
-    spawns: Query<&SkyEnemySpawn>,
+    spawns: PendingArenaSpawnQuery,
     enemies: Query<(&SkyEncounterEnemy, &EnemyState), With<Enemy>>,
     mut gates: RuntimeGateQuery,
     mut encounters: ResMut<SkyEncounterState>,
+    mut cleared: MessageWriter<ArenaCleared>,
 ) {
     let Some(player) = players.iter().next() else {
         return;
@@ -755,13 +782,22 @@ pub fn update_combat_gates(
     }
 
     if let Some(arena) = encounters.active_arena {
-        let pending = spawns.iter().any(|spawn| spawn.arena == arena);
+        let pending = spawns.iter().any(|(spawn, boss)| {
+            spawn
+                .map(|spawn| spawn.arena)
+                .or(boss.map(|boss| boss.arena))
+                == Some(arena)
+        });
         let alive = enemies
             .iter()
             .any(|(member, state)| member.arena == arena && state.is_alive);
         if !pending && !alive {
             encounters.completed_arenas.insert(arena);
             encounters.active_arena = None;
+            cleared.write(ArenaCleared {
+                arena,
+                no_damage: !encounters.damaged_arenas.contains(&arena),
+            });
         }
     }
 
@@ -791,6 +827,7 @@ pub fn activate_checkpoints(
     players: Query<&Transform, With<Player>>,
     checkpoints: Query<(&SkyCheckpoint, &GridCoords)>,
     mut runtime: ResMut<SkyLevelRuntime>,
+    mut activated: MessageWriter<CheckpointActivated>,
 ) {
     let Some(player) = players.iter().next() else {
         return;
@@ -821,6 +858,9 @@ pub fn activate_checkpoints(
         {
             runtime.checkpoint_id = checkpoint.id;
             runtime.checkpoint_position = position;
+            activated.write(CheckpointActivated {
+                checkpoint_id: checkpoint.id,
+            });
         }
     }
 }
@@ -843,10 +883,13 @@ pub fn apply_wind_lifts(
     }
 }
 
+/// 危险地块：接触造成伤害并施加灼烧
 pub fn damage_sky_hazards(
     hazards: Query<&GridCoords, With<SkyHazardCell>>,
     players: Query<(Entity, &Transform, &CollisionBox), With<Player>>,
+    tuning: Option<Res<crate::resources::GameplayTuning>>,
     mut damage_writer: MessageWriter<DamageEvent>,
+    mut status_writer: MessageWriter<StatusEffectEvent>,
 ) {
     let Some((player_entity, player, collision)) = players.iter().next() else {
         return;
@@ -863,6 +906,19 @@ pub fn damage_sky_hazards(
                 amount: 24.0,
                 source: DamageSource::EnemyContact,
             });
+            let default_tuning = crate::resources::GameplayTuning::default();
+            let stacks = tuning
+                .as_deref()
+                .unwrap_or(&default_tuning)
+                .status_effects
+                .hazard_burn_stacks;
+            if stacks > 0 {
+                status_writer.write(StatusEffectEvent {
+                    target: player_entity,
+                    kind: StatusKind::Burn,
+                    stacks,
+                });
+            }
             break;
         }
     }
@@ -890,7 +946,14 @@ pub fn detect_sky_goal(
     }
 }
 
-pub fn setup_victory_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn setup_victory_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_stats: Res<GameStats>,
+    tuning: Option<Res<GameplayTuning>>,
+) {
+    let default_tuning = GameplayTuning::default();
+    let scoring = &tuning.as_deref().unwrap_or(&default_tuning).scoring;
     let font = asset_server.load("fonts/FiraSans-Bold.ttf");
     commands
         .spawn((
@@ -926,6 +989,19 @@ pub fn setup_victory_ui(mut commands: Commands, asset_server: Res<AssetServer>)
                 },
                 TextColor(Color::srgb(1.0, 0.82, 0.42)),
             ));
+            parent.spawn((
+                Text::new(crate::systems::scoring::run_result_text(
+                    &game_stats,
+                    scoring,
+                )),
+                TextFont {
+                    font: font.clone().into(),
+                    font_size: FontSize::Px(26.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                TextLayout::justify(Justify::Center),
+            ));
             parent.spawn((
                 Text::new("Press Enter or M to return to the menu"),
                 TextFont {
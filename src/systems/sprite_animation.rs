@@ -97,6 +97,7 @@ type PlayerAnimationStateItem<'a> = (
     Option<&'a SpriteAnimationSheets>,
     Option<&'a ShroudState>,
     Option<&'a LedgeTraversal>,
+    Option<&'a PlayerDefense>,
 );
 
 /// 2026推荐：先给出最小可用品质，再给理想帧数。
@@ -108,6 +109,9 @@ pub fn frame_count_guideline(animation_type: &AnimationType) -> (usize, usize) {
         AnimationType::Jumping => (3, 5),
         AnimationType::Crouching => (2, 4),
         AnimationType::Landing => (2, 3),
+        AnimationType::Guarding => (1, 2),
+        AnimationType::Parrying => (2, 3),
+        AnimationType::Dodging => (3, 4),
     }
 }
 
@@ -128,7 +132,10 @@ fn validate_clip_frame_counts(
     }
 }
 
-fn validate_profile(character_name: &str, profile: &CharacterAnimationData) -> Result<(), String> {
+pub(crate) fn validate_profile(
+    character_name: &str,
+    profile: &CharacterAnimationData,
+) -> Result<(), String> {
     const REQUIRED_CLIPS: [AnimationType; 6] = [
         AnimationType::Idle,
         AnimationType::Running,
@@ -150,31 +157,46 @@ fn validate_profile(character_name: &str, profile: &CharacterAnimationData) -> R
     Ok(())
 }
 
+/// 选择目标动画时用到的输入与动作状态
+#[derive(Debug, Clone, Copy, Default)]
+struct AnimationDrivers {
+    has_active_attack: bool,
+    has_move_input: bool,
+    is_traversing: bool,
+    defense: PlayerDefense,
+}
+
 fn resolve_target_animation(
     animation: &mut SpriteAnimation,
     player_state: &PlayerState,
     velocity: &Velocity,
-    has_active_attack: bool,
-    has_move_input: bool,
-    is_traversing: bool,
+    drivers: AnimationDrivers,
     runtime: &AnimationRuntimeConfig,
 ) -> AnimationType {
     let was_grounded = animation.previous_grounded;
     animation.previous_grounded = player_state.is_grounded;
 
     let just_landed = !was_grounded && player_state.is_grounded;
+    let has_clip =
+        |animation_type: &AnimationType| animation.animations.contains_key(animation_type);
 
-    if is_traversing {
+    if drivers.is_traversing {
         AnimationType::Jumping
-    } else if has_active_attack && animation.animations.contains_key(&AnimationType::Attacking) {
+    } else if drivers.defense.is_dodging() && has_clip(&AnimationType::Dodging) {
+        AnimationType::Dodging
+    } else if drivers.defense.is_parrying() && has_clip(&AnimationType::Parrying) {
+        AnimationType::Parrying
+    } else if drivers.has_active_attack && has_clip(&AnimationType::Attacking) {
         AnimationType::Attacking
     } else if !player_state.is_grounded {
         AnimationType::Jumping
-    } else if just_landed && animation.animations.contains_key(&AnimationType::Landing) {
+    } else if drivers.defense.is_guarding() && has_clip(&AnimationType::Guarding) {
+        AnimationType::Guarding
+    } else if just_landed && has_clip(&AnimationType::Landing) {
         AnimationType::Landing
     } else if player_state.is_crouching {
         AnimationType::Crouching
-    } else if has_move_input || velocity.x.abs() > runtime.run_speed_threshold {
+    } else if drivers.has_move_input || velocity.x.abs() > runtime.run_speed_threshold {
         AnimationType::Running
     } else {
         AnimationType::Idle
@@ -662,7 +684,10 @@ fn animation_visual_target(
         }
         AnimationType::Crouching => Vec2::new(1.018, 0.985),
         AnimationType::Landing if frame_position == 0 => Vec2::new(1.045, 0.94),
-        AnimationType::Landing | AnimationType::Attacking => Vec2::ONE,
+        AnimationType::Guarding => Vec2::new(1.02, 0.98),
+        AnimationType::Parrying if frame_position == 0 => Vec2::new(0.985, 1.02),
+        AnimationType::Dodging => Vec2::new(1.05, 0.95),
+        AnimationType::Landing | AnimationType::Attacking | AnimationType::Parrying => Vec2::ONE,
     }
 }
 
@@ -902,6 +927,7 @@ pub fn update_character_animation_state(
         sprite_sheets,
         shroud,
         traversal,
+        defense,
     ) in query.iter_mut()
     {
         let is_traversing = traversal.is_some_and(LedgeTraversal::is_active);
@@ -911,11 +937,19 @@ pub fn update_character_animation_state(
             &mut animation,
             player_state,
             velocity,
-            attack_state.is_active(),
-            has_move_input,
-            is_traversing,
+            AnimationDrivers {
+                has_active_attack: attack_state.is_active(),
+                has_move_input,
+                is_traversing,
+                defense: defense.copied().unwrap_or_default(),
+            },
             runtime,
         );
+        // 闪避和弹反需要立即响应，不等待 Once 片段播完
+        let defense_interrupt = matches!(
+            new_animation,
+            AnimationType::Dodging | AnimationType::Parrying
+        );
 
         let clip_blocks_switch = resolved_animation_clip(
             &animation,
@@ -934,6 +968,7 @@ pub fn update_character_animation_state(
             && new_animation != animation.current_animation
             && !attack_retriggered
             && !is_traversing
+            && !defense_interrupt
             && !landing_cancelled_into_run
         {
             continue;
@@ -0,0 +1,159 @@
+//! 状态异常系统
+//!
+//! 攻击与地形发出 [`StatusEffectEvent`]，在这里按叠加规则写入目标的 [`StatusEffects`]；
+//! 每帧推进持续时间，跳伤以 [`DamageSource::StatusTick`] 交给统一伤害管线结算。
+//! 眩晕对敌人表现为硬直，减速由玩家移动与敌人行为系统读取。
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::{
+    components::*,
+    events::{DamageEvent, DamageSource, StatusEffectEvent},
+    resources::GameplayTuning,
+};
+
+type StatusTargetQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Option<&'static mut StatusEffects>,
+        Option<&'static EnemyState>,
+        Option<&'static Health>,
+        Option<&'static DamageInvulnerability>,
+    ),
+>;
+
+type StatusTintQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Ref<'static, StatusEffects>,
+        &'static mut Sprite,
+        Has<Player>,
+    ),
+    Or<(With<Player>, With<Enemy>)>,
+>;
+
+/// 按叠加规则施加状态异常；死亡目标与处于无敌帧的玩家不受影响
+pub fn apply_status_effect_events(
+    mut commands: Commands,
+    mut events: MessageReader<StatusEffectEvent>,
+    mut targets: StatusTargetQuery,
+    tuning: Option<Res<GameplayTuning>>,
+) {
+    let default_tuning = GameplayTuning::default();
+    let status_tuning = &tuning.as_deref().unwrap_or(&default_tuning).status_effects;
+    let mut inserted = HashMap::<Entity, StatusEffects>::new();
+
+    for event in events.read() {
+        let Ok((effects, enemy_state, health, invulnerability)) = targets.get_mut(event.target)
+        else {
+            continue;
+        };
+        if enemy_state.is_some_and(|state| !state.is_alive)
+            || health.is_some_and(Health::is_dead)
+            || invulnerability.is_some_and(DamageInvulnerability::is_active)
+        {
+            continue;
+        }
+
+        let rule = status_tuning.rule(event.kind);
+        match effects {
+            Some(mut effects) => effects.apply(event.kind, event.stacks, &rule),
+            None => {
+                inserted
+                    .entry(event.target)
+                    .or_default()
+                    .apply(event.kind, event.stacks, &rule)
+            }
+        }
+    }
+
+    for (entity, effects) in inserted {
+        commands.entity(entity).insert(effects);
+    }
+}
+
+/// 推进状态计时，结算跳伤，并把眩晕同步为敌人硬直
+pub fn tick_status_effects(
+    mut query: Query<(Entity, &mut StatusEffects, Option<&mut EnemyState>)>,
+    tuning: Option<Res<GameplayTuning>>,
+    time: Res<Time>,
+    mut damage_writer: MessageWriter<DamageEvent>,
+) {
+    let default_tuning = GameplayTuning::default();
+    let status_tuning = &tuning.as_deref().unwrap_or(&default_tuning).status_effects;
+    let delta = time.delta_secs();
+
+    for (entity, mut effects, enemy_state) in query.iter_mut() {
+        if effects.is_empty() {
+            continue;
+        }
+        if let Some(mut state) = enemy_state {
+            if !state.is_alive {
+                effects.effects.clear();
+                continue;
+            }
+            if let Some(stun) = effects.get(StatusKind::Stun) {
+                state.apply_hit_stun(stun.remaining);
+            }
+        }
+
+        let damage = effects.tick(delta, status_tuning);
+        if damage > 0.0 {
+            damage_writer.write(DamageEvent {
+                target: entity,
+                amount: damage,
+                source: DamageSource::StatusTick,
+            });
+        }
+    }
+}
+
+/// 按剩余时间最长的状态给精灵染色；需排在敌人预警与 Boss 视觉之后
+pub fn update_status_effect_visuals(mut query: StatusTintQuery, time: Res<Time>) {
+    let pulse = (time.elapsed_secs() * 10.0).sin() * 0.5 + 0.5;
+
+    for (effects, mut sprite, is_player) in query.iter_mut() {
+        let Some(dominant) = effects.dominant() else {
+            // 玩家精灵底色为白色，状态结束时复原一次
+            if is_player && effects.is_changed() {
+                sprite.color = Color::WHITE;
+            }
+            continue;
+        };
+
+        let base = if is_player {
+            Color::WHITE
+        } else {
+            sprite.color
+        };
+        let alpha = base.alpha();
+        let tint = dominant.kind.tint();
+        sprite.color = Color::from(base.to_srgba().mix(&tint.to_srgba(), 0.35 + pulse * 0.25))
+            .with_alpha(alpha);
+    }
+}
+
+/// HUD 上的状态文字，例如 `BURN 2.4s  POISON x3 5.1s`
+pub fn status_hud_text(effects: &StatusEffects) -> String {
+    effects
+        .effects
+        .iter()
+        .map(|effect| {
+            if effect.stacks > 1 {
+                format!(
+                    "{} x{} {:.1}s",
+                    effect.kind.label(),
+                    effect.stacks,
+                    effect.remaining
+                )
+            } else {
+                format!("{} {:.1}s", effect.kind.label(), effect.remaining)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("  ")
+}
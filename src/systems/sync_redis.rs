@@ -1,74 +1,138 @@
+use crate::components::ai::BotController;
 use crate::components::network::NetworkId;
-use crate::database::redis::RedisManager;
+use crate::components::physics::Velocity;
+use crate::database::redis::{PLAYER_STATE_TTL_SECS, PlayerStateRecord, RedisManager, RedisWrite};
+use crate::plugins::server::ClientDisconnected;
 use bevy::prelude::*;
+use std::collections::{HashMap, HashSet};
 
-/// 每一帧同步 ECS Transform 到 Redis
-/// 按照设计文档中的 Redis Key Schema: `player:{id}:pos` -> `x,y,vx,vy`
+/// 批量写入间隔（秒）
+const FLUSH_INTERVAL_SECS: f32 = 0.1;
+/// 位置不变时的续期间隔，保证键在 TTL 内被重写
+const REFRESH_INTERVAL_SECS: f32 = PLAYER_STATE_TTL_SECS as f32 / 3.0;
+/// 小于该距离的位置/速度变化不重写
+const CHANGE_EPSILON: f32 = 0.01;
+const METRICS_LOG_INTERVAL_SECS: f32 = 30.0;
+
+#[derive(Default)]
+pub struct RedisSyncState {
+    flush_timer: f32,
+    error_log_cooldown: f32,
+    metrics_log_timer: f32,
+    /// 每个 network id 最近一次写入的位置、速度与写入时间
+    last_written: HashMap<u64, ([f32; 4], f32)>,
+    /// 已断线的 network id，实体仍在 ECS 中但不再写入
+    offline: HashSet<u64>,
+}
+
+fn log_queue_metrics(redis: &RedisManager) {
+    let metrics = redis.metrics_snapshot();
+    info!(
+        "Redis write queue metrics: queued={}, processed={}, dropped={}, failed={}, retries={}, pending={}",
+        metrics.queued_batches,
+        metrics.processed_batches,
+        metrics.dropped_batches,
+        metrics.failed_batches,
+        metrics.retry_attempts,
+        metrics.estimated_pending_batches
+    );
+}
+
+/// 同步已连接玩家的位置到 Redis（键结构见 `database::redis`）
+///
+/// 只在位置变化或接近过期时写入；Bot 不写入；断线或实体销毁时删除对应的键。
 pub fn sync_transform_to_redis(
     redis: Option<Res<RedisManager>>,
-    query: Query<(
-        &Transform,
-        &NetworkId,
-        Option<&crate::components::physics::Velocity>,
-    )>,
+    query: Query<(&Transform, &NetworkId, Option<&Velocity>), Without<BotController>>,
+    mut disconnected: MessageReader<ClientDisconnected>,
     time: Res<Time>,
-    mut flush_timer: Local<f32>,
-    mut error_log_cooldown: Local<f32>,
-    mut metrics_log_timer: Local<f32>,
+    mut state: Local<RedisSyncState>,
 ) {
+    let mut writes: Vec<RedisWrite> = disconnected
+        .read()
+        .map(|event| {
+            state.offline.insert(event.client_id);
+            state.last_written.remove(&event.client_id);
+            RedisWrite::RemovePlayer {
+                network_id: event.client_id,
+            }
+        })
+        .collect();
+
     let Some(redis) = redis else {
         return; // Redis not available, skip
     };
 
-    *flush_timer += time.delta_secs();
-    *error_log_cooldown = (*error_log_cooldown - time.delta_secs()).max(0.0);
-    *metrics_log_timer += time.delta_secs();
-    if *flush_timer < 0.1 {
-        if *metrics_log_timer >= 30.0 {
-            let metrics = redis.metrics_snapshot();
-            info!(
-                "Redis write queue metrics: queued={}, processed={}, dropped={}, failed={}, retries={}, pending={}",
-                metrics.queued_batches,
-                metrics.processed_batches,
-                metrics.dropped_batches,
-                metrics.failed_batches,
-                metrics.retry_attempts,
-                metrics.estimated_pending_batches
-            );
-            *metrics_log_timer = 0.0;
-        }
+    let delta = time.delta_secs();
+    state.flush_timer += delta;
+    state.error_log_cooldown = (state.error_log_cooldown - delta).max(0.0);
+    state.metrics_log_timer += delta;
+    if state.metrics_log_timer >= METRICS_LOG_INTERVAL_SECS {
+        log_queue_metrics(&redis);
+        state.metrics_log_timer = 0.0;
+    }
+    if state.flush_timer < FLUSH_INTERVAL_SECS && writes.is_empty() {
         return;
     }
-    *flush_timer = 0.0;
+    state.flush_timer = 0.0;
 
-    let mut entries = Vec::new();
+    let now = time.elapsed_secs();
+    let updated_at = chrono::Utc::now().timestamp_millis();
+    let mut seen = HashSet::new();
     for (transform, net_id, velocity) in query.iter() {
+        let network_id = net_id.0;
+        seen.insert(network_id);
+        if state.offline.contains(&network_id) {
+            continue;
+        }
+
         let pos = transform.translation;
         let vel = velocity.map(|v| (v.x, v.y)).unwrap_or((0.0, 0.0));
+        let values = [pos.x, pos.y, vel.0, vel.1];
+        let unchanged = state
+            .last_written
+            .get(&network_id)
+            .is_some_and(|(last, written_at)| {
+                now - written_at < REFRESH_INTERVAL_SECS
+                    && last
+                        .iter()
+                        .zip(values)
+                        .all(|(last, value)| (last - value).abs() < CHANGE_EPSILON)
+            });
+        if unchanged {
+            continue;
+        }
 
-        let key = format!("player:{}:pos", net_id.0);
-        let value = format!("{},{},{},{}", pos.x, pos.y, vel.0, vel.1);
-        entries.push((key, value));
+        state.last_written.insert(network_id, (values, now));
+        writes.push(RedisWrite::PlayerState {
+            network_id,
+            state: PlayerStateRecord {
+                x: pos.x,
+                y: pos.y,
+                vx: vel.0,
+                vy: vel.1,
+                updated_at,
+            },
+        });
     }
 
-    if let Err(error) = redis.set_many(&entries)
-        && *error_log_cooldown <= 0.0
-    {
-        warn!("Failed to batch sync player transforms to Redis: {}", error);
-        *error_log_cooldown = 5.0;
+    // 实体已销毁（或 network id 已被重新分配）的键立即删除
+    let despawned: Vec<u64> = state
+        .last_written
+        .keys()
+        .filter(|network_id| !seen.contains(network_id))
+        .copied()
+        .collect();
+    for network_id in despawned {
+        state.last_written.remove(&network_id);
+        writes.push(RedisWrite::RemovePlayer { network_id });
     }
+    state.offline.retain(|network_id| seen.contains(network_id));
 
-    if *metrics_log_timer >= 30.0 {
-        let metrics = redis.metrics_snapshot();
-        info!(
-            "Redis write queue metrics: queued={}, processed={}, dropped={}, failed={}, retries={}, pending={}",
-            metrics.queued_batches,
-            metrics.processed_batches,
-            metrics.dropped_batches,
-            metrics.failed_batches,
-            metrics.retry_attempts,
-            metrics.estimated_pending_batches
-        );
-        *metrics_log_timer = 0.0;
+    if let Err(error) = redis.write_many(writes)
+        && state.error_log_cooldown <= 0.0
+    {
+        warn!("Failed to batch sync player states to Redis: {}", error);
+        state.error_log_cooldown = 5.0;
     }
 }
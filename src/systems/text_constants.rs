@@ -9,6 +9,7 @@ impl MainMenuText {
     pub const START_GAME: &'static str = "Start Game";
     pub const LOAD_GAME: &'static str = "Load Game";
     pub const SETTINGS: &'static str = "Settings";
+    pub const LEADERBOARD: &'static str = "Leaderboard";
     pub const EXIT: &'static str = "Exit";
     pub const CHARACTER_SELECT: &'static str = "Select Character";
     pub const SHIROU1: &'static str = "Shirou (Default)";
@@ -28,6 +29,22 @@ impl SettingsMenuText {
     pub const BACK: &'static str = "Back";
 }
 
+/// 排行榜界面文本常量
+pub struct LeaderboardText;
+
+impl LeaderboardText {
+    pub const TITLE: &'static str = "Leaderboard";
+    pub const BEST_SCORE: &'static str = "Best Score";
+    pub const FASTEST_CLEAR: &'static str = "Fastest Clear";
+    pub const ALL_CHARACTERS: &'static str = "All";
+    pub const LOADING: &'static str = "Loading...";
+    pub const OFFLINE: &'static str = "Connect to a server to view rankings";
+    pub const EMPTY: &'static str = "No results yet";
+    pub const OWN_RANK_PREFIX: &'static str = "Your rank:";
+    pub const UNRANKED: &'static str = "Your rank: -";
+    pub const BACK: &'static str = "Back";
+}
+
 /// 暂停菜单文本常量
 pub struct PauseMenuText;
 
@@ -64,6 +81,7 @@ impl SaveLoadText {
     pub const DELETE_BUTTON: &'static str = "Delete";
 
     // 表格列标题
+    pub const COL_PREVIEW: &'static str = "Preview";
     pub const COL_NAME: &'static str = "Name";
     pub const COL_PLAYERS: &'static str = "Players";
     pub const COL_SCORE: &'static str = "Score";
@@ -81,6 +99,8 @@ impl SaveLoadText {
 
     // 状态消息
     pub const NO_SAVES_FOUND: &'static str = "No save files found";
+    pub const NO_PREVIEW: &'static str = "No preview";
+    pub const DAMAGED_SAVE_TAG: &'static str = "damaged";
     pub const SAVE_SUCCESS: &'static str = "Game saved successfully";
     pub const LOAD_SUCCESS: &'static str = "Game loaded successfully";
     pub const RENAME_SUCCESS: &'static str = "Save renamed successfully";
@@ -119,6 +139,10 @@ impl GameHUDText {
     pub const DISTANCE_LABEL: &'static str = "Distance: ";
     pub const TIME_LABEL: &'static str = "Time: ";
     pub const JUMPS_LABEL: &'static str = "Jumps: ";
+    pub const KILLS_LABEL: &'static str = "Kills: ";
+    pub const BEST_COMBO_LABEL: &'static str = "Best Combo: ";
+    pub const RANK_LABEL: &'static str = "Rank: ";
+    pub const BOSS_PHASE_LABEL: &'static str = "Phase ";
     pub const METERS_UNIT: &'static str = "m";
     pub const SECONDS_UNIT: &'static str = "s";
 }
@@ -0,0 +1,161 @@
+//! 屏幕右下角的短提示（toast）
+//!
+//! 任何系统都可以发送 [`ShowToast`]；[`show_toasts`] 把它们排成一列，
+//! [`expire_toasts`] 在显示一段时间后移除。同时最多保留 [`MAX_VISIBLE_TOASTS`] 条。
+
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use crate::events::{ShowToast, ToastKind};
+use crate::resources::GameAssets;
+
+/// 单条提示的显示时长
+pub const TOAST_LIFETIME_SECS: f32 = 6.0;
+/// 同时显示的最大条数，超出时移除最早的一条
+pub const MAX_VISIBLE_TOASTS: usize = 4;
+
+/// 提示列容器
+#[derive(Component)]
+pub struct ToastStack;
+
+/// 单条提示，`remaining_secs` 归零后移除
+#[derive(Component, Debug)]
+pub struct ToastEntry {
+    pub remaining_secs: f32,
+}
+
+mod palette {
+    use bevy::prelude::Color;
+
+    pub const INFO_BG: Color = Color::srgba(0.07, 0.075, 0.09, 0.92);
+    pub const INFO_BORDER: Color = Color::srgba(0.55, 0.72, 0.95, 0.45);
+    pub const INFO_TEXT: Color = Color::srgba(0.86, 0.90, 0.96, 1.0);
+    pub const ERROR_BG: Color = Color::srgba(0.16, 0.05, 0.05, 0.94);
+    pub const ERROR_BORDER: Color = Color::srgba(0.95, 0.36, 0.30, 0.70);
+    pub const ERROR_TEXT: Color = Color::srgba(1.0, 0.86, 0.82, 1.0);
+}
+
+fn toast_colors(kind: ToastKind) -> (Color, Color, Color) {
+    match kind {
+        ToastKind::Info => (palette::INFO_BG, palette::INFO_BORDER, palette::INFO_TEXT),
+        ToastKind::Error => (
+            palette::ERROR_BG,
+            palette::ERROR_BORDER,
+            palette::ERROR_TEXT,
+        ),
+    }
+}
+
+/// 把新提示加入提示列（需要时先创建容器）
+pub fn show_toasts(
+    mut commands: Commands,
+    mut toasts: MessageReader<ShowToast>,
+    game_assets: Option<Res<GameAssets>>,
+    stacks: Query<Entity, With<ToastStack>>,
+    entries: Query<Entity, With<ToastEntry>>,
+) {
+    if toasts.is_empty() {
+        return;
+    }
+
+    let stack = stacks.iter().next().unwrap_or_else(|| {
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    right: Val::Px(16.0),
+                    bottom: Val::Px(16.0),
+                    max_width: Val::Px(480.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::FlexEnd,
+                    row_gap: Val::Px(6.0),
+                    ..default()
+                },
+                ZIndex(40),
+                ToastStack,
+            ))
+            .id()
+    });
+
+    let mut visible: VecDeque<Entity> = entries.iter().collect();
+    for toast in toasts.read() {
+        while visible.len() >= MAX_VISIBLE_TOASTS {
+            let Some(oldest) = visible.pop_front() else {
+                break;
+            };
+            commands.entity(oldest).despawn();
+        }
+
+        let (background, border, text_color) = toast_colors(toast.kind);
+        let mut font = TextFont {
+            font_size: FontSize::Px(14.0),
+            ..default()
+        };
+        if let Some(assets) = game_assets.as_ref() {
+            font.font = assets.font.clone().into();
+        }
+        let entry = commands
+            .spawn((
+                Node {
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(8.0)),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BackgroundColor(background),
+                BorderColor::all(border),
+                ToastEntry {
+                    remaining_secs: TOAST_LIFETIME_SECS,
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((Text::new(toast.text.clone()), font, TextColor(text_color)));
+            })
+            .id();
+        commands.entity(stack).add_child(entry);
+        visible.push_back(entry);
+    }
+}
+
+/// 倒计时并移除到期的提示
+pub fn expire_toasts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut entries: Query<(Entity, &mut ToastEntry)>,
+) {
+    for (entity, mut entry) in &mut entries {
+        entry.remaining_secs -= time.delta_secs();
+        if entry.remaining_secs <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toasts_are_capped_and_expire() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_message::<ShowToast>()
+            .add_systems(Update, (show_toasts, expire_toasts).chain());
+
+        for index in 0..(MAX_VISIBLE_TOASTS + 2) {
+            app.world_mut().write_message(ShowToast {
+                text: format!("toast {index}"),
+                kind: ToastKind::Error,
+            });
+        }
+        app.update();
+
+        let mut entries = app.world_mut().query::<&mut ToastEntry>();
+        assert_eq!(entries.iter(app.world()).count(), MAX_VISIBLE_TOASTS);
+
+        for mut entry in entries.iter_mut(app.world_mut()) {
+            entry.remaining_secs = 0.0;
+        }
+        app.update();
+        assert_eq!(entries.iter(app.world()).count(), 0);
+    }
+}
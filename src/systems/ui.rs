@@ -19,6 +19,26 @@ pub struct DistanceDisplay;
 #[derive(Component)]
 pub struct HealthDisplay;
 
+/// HUD combat record (kills, best combo, rank) text marker.
+#[derive(Component)]
+pub struct CombatDisplay;
+
+/// HUD status effect (burn, slow, stun, poison) text marker.
+#[derive(Component)]
+pub struct StatusDisplay;
+
+/// Boss health bar root marker; hidden unless a boss fight is in progress.
+#[derive(Component)]
+pub struct BossHealthBar;
+
+/// Boss health bar fill marker.
+#[derive(Component)]
+pub struct BossHealthFill;
+
+/// Boss name and phase text marker.
+#[derive(Component)]
+pub struct BossHealthLabel;
+
 // Enhanced Pause System UI Components
 #[derive(Component)]
 pub struct PauseMenuRoot;
@@ -64,6 +84,8 @@ pub struct SaveLoadUiState {
     pub status_message: String,
     pub error_message: String,
     pub pending_load_index: Option<usize>,
+    /// 上次读取失败、可从备份恢复的存档路径；再次点击该存档即触发恢复
+    pub pending_backup_restore: Option<String>,
 }
 
 // Save Dialog Components
@@ -210,6 +232,7 @@ type ScoreTextQuery<'w, 's> = Query<
         With<ScoreDisplay>,
         Without<DistanceDisplay>,
         Without<HealthDisplay>,
+        Without<CombatDisplay>,
     ),
 >;
 
@@ -221,6 +244,7 @@ type DistanceTextQuery<'w, 's> = Query<
         With<DistanceDisplay>,
         Without<ScoreDisplay>,
         Without<HealthDisplay>,
+        Without<CombatDisplay>,
     ),
 >;
 
@@ -232,6 +256,19 @@ type HealthTextQuery<'w, 's> = Query<
         With<HealthDisplay>,
         Without<ScoreDisplay>,
         Without<DistanceDisplay>,
+        Without<CombatDisplay>,
+    ),
+>;
+
+type CombatTextQuery<'w, 's> = Query<
+    'w,
+    's,
+    &'static mut Text,
+    (
+        With<CombatDisplay>,
+        Without<ScoreDisplay>,
+        Without<DistanceDisplay>,
+        Without<HealthDisplay>,
     ),
 >;
 
@@ -341,6 +378,85 @@ pub fn setup_game_hud(
                 HealthDisplay,
             ));
 
+            parent.spawn((
+                Text::new(combat_hud_text(
+                    &CombatRecord::default(),
+                    RunGrade::default(),
+                )),
+                TextFont {
+                    font_size: FontSize::Px(18.0),
+                    ..default()
+                },
+                TextColor(Color::srgb(1.0, 0.86, 0.55)),
+                Node {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                CombatDisplay,
+            ));
+
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: FontSize::Px(18.0),
+                    ..default()
+                },
+                TextColor(Color::WHITE),
+                Node {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                },
+                StatusDisplay,
+            ));
+
+            parent
+                .spawn((
+                    Node {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(16.0),
+                        left: Val::Percent(25.0),
+                        width: Val::Percent(50.0),
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(6.0),
+                        ..default()
+                    },
+                    Visibility::Hidden,
+                    BossHealthBar,
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: FontSize::Px(20.0),
+                            ..default()
+                        },
+                        TextColor(Color::srgb(1.0, 0.82, 0.72)),
+                        BossHealthLabel,
+                    ));
+                    bar.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(14.0),
+                            border: UiRect::all(Val::Px(2.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.08, 0.05, 0.10, 0.82)),
+                        BorderColor::all(Color::srgba(1.0, 0.82, 0.72, 0.9)),
+                    ))
+                    .with_children(|track| {
+                        track.spawn((
+                            Node {
+                                width: Val::Percent(100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.86, 0.20, 0.28)),
+                            BossHealthFill,
+                        ));
+                    });
+                });
+
             parent.spawn((
                 Text::new(crate::systems::text_constants::PauseMenuText::CONTROLS_HINT),
                 TextFont {
@@ -364,15 +480,22 @@ pub fn update_game_hud(
     mut score_text_query: ScoreTextQuery,
     mut distance_text_query: DistanceTextQuery,
     mut health_text_query: HealthTextQuery,
+    mut combat_text_query: CombatTextQuery,
     player_health_query: Query<&Health, With<Player>>,
     game_stats: Res<GameStats>,
+    tuning: Option<Res<GameplayTuning>>,
 ) {
     use crate::systems::text_constants::GameHUDText;
 
     // 閺囧瓨镆婇崚鍡樻殶閺勫墽锟?
     if let Ok(mut score_text) = score_text_query.single_mut() {
-        let score = (game_stats.distance_traveled * 10.0) as u32 + game_stats.jump_count * 50;
-        **score_text = format!("{}{}", GameHUDText::SCORE_LABEL, score);
+        **score_text = format!("{}{}", GameHUDText::SCORE_LABEL, game_stats.score());
+    }
+
+    if let Ok(mut combat_text) = combat_text_query.single_mut() {
+        let default_tuning = GameplayTuning::default();
+        let scoring = &tuning.as_deref().unwrap_or(&default_tuning).scoring;
+        **combat_text = combat_hud_text(&game_stats.combat, game_stats.grade(scoring));
     }
 
     // 閺囧瓨镆婄捄婵堫潋閺勫墽锟?
@@ -393,6 +516,87 @@ pub fn update_game_hud(
     }
 }
 
+/// Lists the player's active status effects, colored by the longest-lasting one.
+pub fn update_status_hud(
+    player_query: Query<&StatusEffects, With<Player>>,
+    mut status_text_query: Query<(&mut Text, &mut TextColor), With<StatusDisplay>>,
+) {
+    let Ok((mut text, mut color)) = status_text_query.single_mut() else {
+        return;
+    };
+    let effects = player_query.single().ok();
+    let label = effects
+        .map(crate::systems::status_effects::status_hud_text)
+        .unwrap_or_default();
+    if **text != label {
+        **text = label;
+    }
+    if let Some(dominant) = effects.and_then(StatusEffects::dominant) {
+        color.0 = dominant.kind.tint();
+    }
+}
+
+/// Shows the boss health bar while the player is fighting a boss in its arena.
+pub fn update_boss_health_bar(
+    bosses: Query<(&Boss, &EnemyState)>,
+    encounters: Option<Res<SkyEncounterState>>,
+    tuning: Option<Res<GameplayTuning>>,
+    mut bars: Query<&mut Visibility, With<BossHealthBar>>,
+    mut fills: Query<&mut Node, With<BossHealthFill>>,
+    mut labels: Query<&mut Text, With<BossHealthLabel>>,
+) {
+    let engaged = bosses.iter().find(|(boss, state)| {
+        state.is_alive
+            && encounters
+                .as_deref()
+                .is_none_or(|encounters| encounters.active_arena == Some(boss.arena))
+    });
+
+    for mut visibility in bars.iter_mut() {
+        *visibility = if engaged.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+    let Some((boss, state)) = engaged else {
+        return;
+    };
+
+    let fraction = (state.health as f32 / state.max_health.max(1) as f32).clamp(0.0, 1.0);
+    for mut fill in fills.iter_mut() {
+        fill.width = Val::Percent(fraction * 100.0);
+    }
+    if let Ok(mut label) = labels.single_mut() {
+        let default_tuning = GameplayTuning::default();
+        let boss_tuning = &tuning.as_deref().unwrap_or(&default_tuning).boss;
+        **label = boss_hud_text(&boss_tuning.name, boss.phase);
+    }
+}
+
+fn boss_hud_text(name: &str, phase: usize) -> String {
+    format!(
+        "{}  {}{}",
+        name,
+        crate::systems::text_constants::GameHUDText::BOSS_PHASE_LABEL,
+        phase + 1
+    )
+}
+
+fn combat_hud_text(combat: &CombatRecord, grade: RunGrade) -> String {
+    use crate::systems::text_constants::GameHUDText;
+
+    format!(
+        "{}{}  {}{}  {}{}",
+        GameHUDText::KILLS_LABEL,
+        combat.kills,
+        GameHUDText::BEST_COMBO_LABEL,
+        combat.best_combo,
+        GameHUDText::RANK_LABEL,
+        grade.label()
+    )
+}
+
 /// 濞揿懐镇婂〒锻婂灆 HUD
 pub fn cleanup_game_hud(mut commands: Commands, hud_query: Query<Entity, With<GameHUD>>) {
     for entity in hud_query.iter() {
@@ -1096,6 +1300,7 @@ pub fn handle_save_dialog_interactions(
                     ev_save.write(StartSaveGame {
                         save_name,
                         state: state.clone(),
+                        thumbnail: pause_manager.preserved_thumbnail.clone(),
                     });
                     NextState::set_if_neq(&mut next_state, GameState::Paused);
                 }
@@ -1131,6 +1336,7 @@ pub fn setup_load_table(
     game_assets: Option<Res<GameAssets>>,
     save_file_manager: Res<SaveFileManager>,
     mut save_load_ui_state: ResMut<SaveLoadUiState>,
+    mut images: ResMut<Assets<Image>>,
 ) {
     use crate::systems::text_constants::SaveLoadText;
 
@@ -1232,6 +1438,7 @@ pub fn setup_load_table(
             )).with_children(|parent| {
                 use crate::systems::text_constants::SaveLoadText;
                 let headers = [
+                    SaveLoadText::COL_PREVIEW,
                     SaveLoadText::COL_NAME,
                     SaveLoadText::COL_PLAYERS,
                     SaveLoadText::COL_SCORE,
@@ -1240,7 +1447,7 @@ pub fn setup_load_table(
                     SaveLoadText::COL_DATE,
                     SaveLoadText::COL_ACTIONS
                 ];
-                let widths = [18.0, 8.0, 12.0, 12.0, 12.0, 18.0, 20.0];
+                let widths = [12.0, 16.0, 7.0, 10.0, 10.0, 10.0, 15.0, 20.0];
 
                 for (header, width) in headers.iter().zip(widths.iter()) {
                     parent.spawn((
@@ -1315,9 +1522,59 @@ pub fn setup_load_table(
                             }),
                             BorderColor::all(Color::srgba(0.4, 0.4, 0.4, 1.0)),
                         )).with_children(|parent| {
-                            let widths = [18.0, 8.0, 12.0, 12.0, 12.0, 18.0, 20.0];
+                            // 存档缩略图列：点击与信息列一样触发读档
+                            let thumbnail = crate::systems::save_thumbnail::load_save_thumbnail(
+                                std::path::Path::new(&save_file.file_path),
+                            );
+                            parent.spawn((
+                                Button,
+                                Node {
+                                    width: Val::Percent(12.0),
+                                    height: Val::Percent(100.0),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    border: UiRect::right(Val::Px(1.0)),
+                                    ..default()
+                                },
+                                BackgroundColor(Color::NONE),
+                                BorderColor::all(Color::srgba(0.4, 0.4, 0.4, 1.0)),
+                                SaveFileRow { save_index: index },
+                            )).with_children(|parent| {
+                                if let Some(thumbnail) = thumbnail.as_ref() {
+                                    let image = images.add(
+                                        crate::systems::save_thumbnail::thumbnail_image(thumbnail),
+                                    );
+                                    parent.spawn((
+                                        ImageNode::new(image),
+                                        Node {
+                                            width: Val::Px(64.0),
+                                            height: Val::Px(36.0),
+                                            ..default()
+                                        },
+                                    ));
+                                } else {
+                                    parent.spawn((
+                                        Text::new(SaveLoadText::NO_PREVIEW),
+                                        TextFont {
+                                            font: font_handle.clone().into(),
+                                            font_size: FontSize::Px(11.0),
+                                            ..default()
+                                        },
+                                        TextColor(Color::srgba(1.0, 1.0, 1.0, 0.4)),
+                                    ));
+                                }
+                            });
+
+                            let is_damaged = save_file_manager
+                                .damaged_save_paths
+                                .contains(&save_file.file_path);
+                            let widths = [16.0, 7.0, 10.0, 10.0, 10.0, 15.0, 20.0];
                             let values = [
-                                save_file.name.clone(),
+                                if is_damaged {
+                                    format!("{} ({})", save_file.name, SaveLoadText::DAMAGED_SAVE_TAG)
+                                } else {
+                                    save_file.name.clone()
+                                },
                                 save_player_label(&save_file.selected_character).to_string(), // 姒涙颛婚崡镟氭眽濞揿憡鍨欓敍灞炬弓閺夈儱褰叉禒搴＄摠濡楋絾鏆熼幑颜款嚢锟?
                                 save_file.score.to_string(),
                                 format!("{:.1}m", save_file.distance),
@@ -1350,7 +1607,9 @@ pub fn setup_load_table(
                                                 font_size: FontSize::Px(13.0),
                                                 ..default()
                                             },
-                                            TextColor(if i == 1 {
+                                            TextColor(if i == 0 && is_damaged {
+                                                Color::srgba(1.0, 0.5, 0.4, 1.0)
+                                            } else if i == 1 {
                                                 // 阎溾晛颜嶉弫浼村櫤閸掓ぞ濞囬凄銊ょ瑝閸氩矂顤侀懝?
                                                 Color::srgba(0.7, 0.9, 1.0, 1.0)
                                             } else {
@@ -1786,6 +2045,21 @@ pub fn handle_load_table_interactions(
         if index < save_file_manager.save_files.len() {
             let save_file = &save_file_manager.save_files[index];
 
+            if save_load_ui_state.pending_backup_restore.as_deref()
+                == Some(save_file.file_path.as_str())
+            {
+                save_load_ui_state.pending_load_index = None;
+                save_load_ui_state.is_busy = true;
+                save_load_ui_state.error_message.clear();
+                save_load_ui_state.status_message =
+                    format!("Restoring '{}' from backup...", save_file.name);
+                ev_load.write(StartLoadGame {
+                    file_path: save_file.file_path.clone(),
+                    restore_from_backup: true,
+                });
+                return;
+            }
+
             if save_load_ui_state.pending_load_index != Some(index) {
                 save_load_ui_state.pending_load_index = Some(index);
                 save_load_ui_state.error_message.clear();
@@ -1805,6 +2079,7 @@ pub fn handle_load_table_interactions(
             );
             ev_load.write(StartLoadGame {
                 file_path: save_file.file_path.clone(),
+                restore_from_backup: false,
             });
             // The UI will now wait for the async task to finish.
             // We could transition to a "Loading" state here, but for now,
@@ -28,6 +28,9 @@ mod tests {
             AnimationType::Jumping,
             AnimationType::Crouching,
             AnimationType::Landing,
+            AnimationType::Guarding,
+            AnimationType::Parrying,
+            AnimationType::Dodging,
         ] {
             let clip = profile
                 .animations
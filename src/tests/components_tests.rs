@@ -93,4 +93,135 @@ mod tests {
         assert_eq!(trigger.sound_type, SoundType::Jump);
         assert!(trigger.should_play);
     }
+
+    #[test]
+    fn test_status_effect_stacking_rules() {
+        let tuning = crate::resources::StatusEffectTuning::default();
+        let mut effects = StatusEffects::default();
+
+        // Intensity：层数累加到上限
+        for _ in 0..8 {
+            effects.apply(StatusKind::Poison, 1, &tuning.poison);
+        }
+        assert_eq!(
+            effects.get(StatusKind::Poison).unwrap().stacks,
+            tuning.poison.max_stacks
+        );
+
+        // Refresh：层数不变，持续时间刷新
+        effects.apply(StatusKind::Burn, 1, &tuning.burn);
+        effects.tick(1.0, &tuning);
+        effects.apply(StatusKind::Burn, 1, &tuning.burn);
+        let burn = effects.get(StatusKind::Burn).unwrap();
+        assert_eq!(burn.stacks, 1);
+        assert!((burn.remaining - tuning.burn.duration_secs).abs() < 1e-4);
+
+        // Extend：持续时间累加，不超过上限
+        for _ in 0..10 {
+            effects.apply(StatusKind::Stun, 1, &tuning.stun);
+        }
+        assert!(
+            (effects.get(StatusKind::Stun).unwrap().remaining - tuning.stun.max_duration_secs)
+                .abs()
+                < 1e-4
+        );
+        assert!(effects.is_stunned());
+    }
+
+    #[test]
+    fn test_status_effect_ticks_damage_and_expires() {
+        let tuning = crate::resources::StatusEffectTuning::default();
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusKind::Burn, 1, &tuning.burn);
+        effects.apply(StatusKind::Slow, 2, &tuning.slow);
+
+        let slowed = effects.speed_multiplier(&tuning);
+        assert!((slowed - (1.0 - tuning.slow.slow_per_stack * 2.0)).abs() < 1e-4);
+
+        let elapsed = tuning.slow.duration_secs;
+        assert!(elapsed < tuning.burn.duration_secs);
+        let ticks = (elapsed / tuning.burn.tick_interval_secs).floor();
+        let damage = effects.tick(elapsed, &tuning);
+        assert!((damage - ticks * tuning.burn.damage_per_stack).abs() < 1e-4);
+        assert!(!effects.has(StatusKind::Slow), "slow should expire first");
+
+        effects.tick(tuning.burn.duration_secs, &tuning);
+        assert!(effects.is_empty());
+        assert!((effects.speed_multiplier(&tuning) - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_status_effect_tick_is_bounded_for_huge_frames_and_bad_timers() {
+        let tuning = crate::resources::StatusEffectTuning::default();
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusKind::Burn, 1, &tuning.burn);
+        effects.effects[0].remaining = f32::MAX;
+        effects.effects[0].tick_timer = -1.0e30;
+
+        // 巨大的负计时器只结算封顶的跳数，计时器回到正常区间
+        let damage = effects.tick(0.0, &tuning);
+        assert!(damage > 0.0 && damage <= 8.0 * tuning.burn.damage_per_stack);
+        let timer = effects.get(StatusKind::Burn).unwrap().tick_timer;
+        assert!(timer > 0.0 && timer <= tuning.burn.tick_interval_secs);
+
+        effects.effects[0].tick_timer = f32::NEG_INFINITY;
+        assert_eq!(effects.tick(0.0, &tuning), 0.0);
+        assert!(
+            effects
+                .get(StatusKind::Burn)
+                .unwrap()
+                .tick_timer
+                .is_finite()
+        );
+    }
+
+    #[test]
+    fn test_restored_status_effects_are_clamped_to_tuning() {
+        let tuning = crate::resources::StatusEffectTuning::default();
+        let saved = StatusEffects {
+            effects: vec![
+                StatusEffect {
+                    kind: StatusKind::Poison,
+                    stacks: 999,
+                    remaining: 1.0e9,
+                    tick_timer: -50.0,
+                },
+                StatusEffect {
+                    kind: StatusKind::Poison,
+                    stacks: 1,
+                    remaining: 1.0,
+                    tick_timer: 0.1,
+                },
+                StatusEffect {
+                    kind: StatusKind::Stun,
+                    stacks: 0,
+                    remaining: f32::NAN,
+                    tick_timer: 0.0,
+                },
+                StatusEffect {
+                    kind: StatusKind::Burn,
+                    stacks: 0,
+                    remaining: 0.5,
+                    tick_timer: f32::INFINITY,
+                },
+            ],
+        };
+
+        let restored = saved.restored(&tuning);
+        assert_eq!(restored.effects.len(), 2);
+        let poison = restored.get(StatusKind::Poison).unwrap();
+        assert_eq!(poison.stacks, tuning.poison.max_stacks);
+        assert!(
+            poison.remaining
+                <= tuning
+                    .poison
+                    .duration_secs
+                    .max(tuning.poison.max_duration_secs)
+        );
+        assert!(poison.tick_timer >= 0.0);
+        assert!(!restored.has(StatusKind::Stun));
+        let burn = restored.get(StatusKind::Burn).unwrap();
+        assert_eq!(burn.stacks, 1);
+        assert_eq!(burn.tick_timer, tuning.burn.tick_interval_secs);
+    }
 }
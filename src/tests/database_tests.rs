@@ -0,0 +1,355 @@
+//! 数据库操作测试
+//!
+//! 需要一个可丢弃的本地 Postgres（`docker-compose up -d postgres_test`）并设置
+//! `TEST_DATABASE_URL`。每个测试在独立 schema 中执行全部迁移，结束后删除该 schema。
+//! 测试默认标记为 `#[ignore]`，用 `cargo test --features server database_tests -- --ignored`
+//! 运行；启用 `sqlx-checked` 时同一批测试覆盖编译期校验的查询。
+
+#[cfg(test)]
+mod tests {
+    use crate::database::{Database, models::*};
+    use crate::systems::save_worker::{SaveGameTask, persist_save_task};
+    use crate::systems::session_recording::RecordedAction;
+    use futures_util::FutureExt;
+    use sqlx::{AssertSqlSafe, Executor, PgPool, Row, postgres::PgPoolOptions};
+    use std::future::Future;
+    use std::panic::AssertUnwindSafe;
+    use std::sync::{Mutex, PoisonError};
+    use uuid::Uuid;
+
+    /// 迁移会创建扩展，串行执行避免并发建扩展冲突
+    static DATABASE_TESTS: Mutex<()> = Mutex::new(());
+
+    /// 连接 `TEST_DATABASE_URL`，在新 schema 中执行迁移后运行测试，最后删除 schema
+    fn with_test_database<F, Fut>(test: F)
+    where
+        F: FnOnce(Database) -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        let database_url = std::env::var("TEST_DATABASE_URL")
+            .expect("TEST_DATABASE_URL must point at a disposable Postgres");
+        let _guard = DATABASE_TESTS
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("test runtime should build");
+
+        runtime.block_on(async move {
+            let admin = PgPoolOptions::new()
+                .max_connections(1)
+                .connect(&database_url)
+                .await
+                .expect("test database should accept connections");
+            let schema = format!("test_{}", Uuid::new_v4().simple());
+            admin
+                .execute(AssertSqlSafe(format!("CREATE SCHEMA {schema}")))
+                .await
+                .expect("test schema should be created");
+
+            let search_path = format!("SET search_path TO {schema}, public");
+            let pool = PgPoolOptions::new()
+                .max_connections(4)
+                .after_connect(move |connection, _| {
+                    let search_path = search_path.clone();
+                    Box::pin(async move {
+                        connection.execute(AssertSqlSafe(search_path)).await?;
+                        Ok(())
+                    })
+                })
+                .connect(&database_url)
+                .await
+                .expect("test pool should connect");
+            let database = Database::with_pool(pool.clone())
+                .await
+                .expect("migrations should apply to an empty schema");
+
+            let outcome = AssertUnwindSafe(test(database)).catch_unwind().await;
+
+            pool.close().await;
+            admin
+                .execute(AssertSqlSafe(format!("DROP SCHEMA {schema} CASCADE")))
+                .await
+                .expect("test schema should be dropped");
+            if let Err(panic) = outcome {
+                std::panic::resume_unwind(panic);
+            }
+        });
+    }
+
+    fn sample_game_data(distance_traveled: f32) -> GameData {
+        GameData {
+            character_type: "shirou1".to_string(),
+            player_position: (120.0, -40.0, 0.0),
+            player_velocity: (3.5, 0.0),
+            camera_position: (120.0, 0.0, 999.0),
+            game_stats: GameStatsData {
+                distance_traveled,
+                jump_count: 4,
+                play_time: 33.0,
+            },
+            player_state: PlayerStateData {
+                is_grounded: true,
+                is_crouching: false,
+            },
+        }
+    }
+
+    async fn save_count(pool: &PgPool, player_id: Uuid) -> i64 {
+        sqlx::query("SELECT COUNT(*) AS count FROM save_games WHERE player_id = $1")
+            .bind(player_id)
+            .fetch_one(pool)
+            .await
+            .expect("save count query should succeed")
+            .get("count")
+    }
+
+    #[test]
+    #[ignore = "requires TEST_DATABASE_URL"]
+    fn get_or_create_player_is_idempotent() {
+        with_test_database(|database| async move {
+            let created = database
+                .get_or_create_player("rin")
+                .await
+                .expect("player should be created");
+            let fetched = database
+                .get_or_create_player("rin")
+                .await
+                .expect("existing player should be returned");
+            assert_eq!(created.id, fetched.id);
+            assert_eq!(fetched.username, "rin");
+
+            let missing = database.get_player_by_username("sakura").await;
+            assert!(matches!(missing, Err(sqlx::Error::RowNotFound)));
+        });
+    }
+
+    #[test]
+    #[ignore = "requires TEST_DATABASE_URL"]
+    fn session_lifecycle_records_actions_and_final_stats() {
+        with_test_database(|database| async move {
+            let player = database
+                .get_or_create_player("shirou")
+                .await
+                .expect("player should be created");
+            let session = database
+                .create_game_session(player.id, "shirou1")
+                .await
+                .expect("session should be created");
+            assert!(session.end_time.is_none());
+            assert_eq!(session.score, 0);
+
+            database
+                .log_player_action(session.id, "jump", None, Some((10.0, 2.0)))
+                .await
+                .expect("single action should be logged");
+            let batch = [
+                (
+                    session.id,
+                    RecordedAction {
+                        action_type: "attack",
+                        action_data: None,
+                        position: None,
+                        timestamp: chrono::Utc::now(),
+                    },
+                ),
+                (
+                    session.id,
+                    RecordedAction {
+                        action_type: "checkpoint",
+                        action_data: Some(serde_json::json!({ "checkpoint_id": 1 })),
+                        position: Some((300.0, 0.0)),
+                        timestamp: chrono::Utc::now(),
+                    },
+                ),
+            ];
+            database
+                .log_player_actions(&batch)
+                .await
+                .expect("action batch should be logged");
+            database
+                .update_game_session(session.id, 150.0, 3, 42.5, 1650)
+                .await
+                .expect("session should be closed");
+
+            let row = sqlx::query(
+                "SELECT end_time IS NOT NULL AS closed, score, jump_count FROM game_sessions WHERE id = $1",
+            )
+            .bind(session.id)
+            .fetch_one(&database.pool)
+            .await
+            .expect("session should exist");
+            assert!(row.get::<bool, _>("closed"));
+            assert_eq!(row.get::<i32, _>("score"), 1650);
+            assert_eq!(row.get::<i32, _>("jump_count"), 3);
+
+            let action_types: Vec<String> = sqlx::query(
+                "SELECT action_type FROM player_actions WHERE session_id = $1 ORDER BY timestamp, action_type",
+            )
+            .bind(session.id)
+            .fetch_all(&database.pool)
+            .await
+            .expect("actions should be readable")
+            .into_iter()
+            .map(|row| row.get("action_type"))
+            .collect();
+            assert_eq!(action_types.len(), 3);
+            assert!(action_types.iter().any(|kind| kind == "checkpoint"));
+        });
+    }
+
+    #[test]
+    #[ignore = "requires TEST_DATABASE_URL"]
+    fn save_game_upserts_on_player_and_save_name() {
+        with_test_database(|database| async move {
+            let player = database
+                .get_or_create_player("saber")
+                .await
+                .expect("player should be created");
+
+            let first = database
+                .save_game(player.id, "slot1", &sample_game_data(10.0))
+                .await
+                .expect("first save should insert");
+            let second = database
+                .save_game(player.id, "slot1", &sample_game_data(250.0))
+                .await
+                .expect("second save should update");
+            assert_eq!(first, second);
+            database
+                .save_game(player.id, "slot2", &sample_game_data(5.0))
+                .await
+                .expect("other slot should insert");
+            assert_eq!(save_count(&database.pool, player.id).await, 2);
+
+            let loaded = database
+                .load_game(player.id, "slot1")
+                .await
+                .expect("updated save should load");
+            assert_eq!(loaded.game_stats.distance_traveled, 250.0);
+
+            let saves = database
+                .get_player_saves(player.id)
+                .await
+                .expect("saves should be listed");
+            assert_eq!(saves[0].save_name, "slot2");
+        });
+    }
+
+    #[test]
+    #[ignore = "requires TEST_DATABASE_URL"]
+    fn save_worker_upsert_overwrites_existing_slot() {
+        with_test_database(|database| async move {
+            let player = database
+                .get_or_create_player("archer")
+                .await
+                .expect("player should be created");
+            let first_saved_at = chrono::Utc::now();
+            let task = |checkpoint_id: i32, saved_at| SaveGameTask {
+                player_id: player.id,
+                save_name: "online_autosave".to_string(),
+                game_data: serde_json::json!({ "checkpoint_id": checkpoint_id }),
+                saved_at,
+            };
+            for (checkpoint_id, saved_at) in [
+                (1, first_saved_at),
+                (2, first_saved_at + chrono::Duration::seconds(5)),
+            ] {
+                let written = persist_save_task(&database.pool, &task(checkpoint_id, saved_at))
+                    .await
+                    .expect("save task should persist");
+                assert!(written);
+            }
+            assert_eq!(save_count(&database.pool, player.id).await, 1);
+
+            // 迟到的重试（产生时间更早）不会覆盖较新的存档
+            let written = persist_save_task(&database.pool, &task(1, first_saved_at))
+                .await
+                .expect("stale save task should be accepted");
+            assert!(!written);
+
+            let saves = database
+                .get_player_saves(player.id)
+                .await
+                .expect("saves should be listed");
+            assert_eq!(saves[0].game_data["checkpoint_id"], 2);
+
+            // 不存在的玩家违反外键，属于不可重试的错误
+            let orphan = SaveGameTask {
+                player_id: Uuid::new_v4(),
+                save_name: "online_autosave".to_string(),
+                game_data: serde_json::json!({}),
+                saved_at: chrono::Utc::now(),
+            };
+            let error = persist_save_task(&database.pool, &orphan)
+                .await
+                .expect_err("orphan save should be rejected");
+            assert!(matches!(error, sqlx::Error::Database(_)));
+        });
+    }
+
+    #[test]
+    #[ignore = "requires TEST_DATABASE_URL"]
+    fn save_keys_persist_and_keep_the_first_registration() {
+        use crate::systems::save_signing::SaveSigningKey;
+
+        with_test_database(|database| async move {
+            let player = database
+                .get_or_create_player("rin")
+                .await
+                .expect("player should be created");
+            let key = SaveSigningKey::from_bytes([9; 32]);
+            let other = SaveSigningKey::from_bytes([8; 32]);
+
+            assert_eq!(database.save_key(player.id).await.expect("lookup"), None);
+            assert!(
+                database
+                    .register_save_key(player.id, &key)
+                    .await
+                    .expect("register")
+            );
+            assert!(
+                database
+                    .register_save_key(player.id, &key)
+                    .await
+                    .expect("re-register")
+            );
+            assert!(
+                !database
+                    .register_save_key(player.id, &other)
+                    .await
+                    .expect("conflict")
+            );
+            assert_eq!(
+                database.save_key(player.id).await.expect("lookup"),
+                Some(key)
+            );
+        });
+    }
+
+    #[test]
+    #[ignore = "requires TEST_DATABASE_URL"]
+    fn load_game_reports_missing_and_undecodable_saves() {
+        with_test_database(|database| async move {
+            let player = database
+                .get_or_create_player("illya")
+                .await
+                .expect("player should be created");
+
+            let missing = database.load_game(player.id, "nope").await;
+            assert!(matches!(missing, Err(sqlx::Error::RowNotFound)));
+
+            sqlx::query(
+                "INSERT INTO save_games (player_id, save_name, game_data) VALUES ($1, 'broken', $2)",
+            )
+            .bind(player.id)
+            .bind(serde_json::json!({ "character_type": 7 }))
+            .execute(&database.pool)
+            .await
+            .expect("raw save should insert");
+            let broken = database.load_game(player.id, "broken").await;
+            assert!(matches!(broken, Err(sqlx::Error::Decode(_))));
+        });
+    }
+}
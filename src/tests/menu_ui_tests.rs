@@ -5,6 +5,9 @@
 use crate::components::*;
 use crate::resources::*;
 use crate::states::*;
+use crate::systems::leaderboard_ui::{
+    LeaderboardOverlayRoot, LeaderboardStatusText, MenuLeaderboardButton,
+};
 use crate::systems::menu::*;
 use crate::systems::settings_ui::{
     MenuSettingsButton, SettingsBackButton, SettingsOverlayRoot, SettingsPanelTitle, VolumeBarFill,
@@ -155,6 +158,53 @@ mod property_tests {
         assert_eq!(count, 1);
     }
 
+    #[test]
+    fn test_menu_leaderboard_button_opens_offline_overlay() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::state::app::StatesPlugin));
+        app.init_state::<GameState>();
+        app.insert_resource(create_mock_game_assets());
+        app.init_resource::<crate::systems::network::NetworkResource>();
+        app.init_resource::<crate::systems::network::LeaderboardState>();
+        app.add_systems(Startup, setup_menu);
+        app.add_systems(
+            Update,
+            (
+                crate::systems::leaderboard_ui::handle_menu_leaderboard_button,
+                crate::systems::leaderboard_ui::update_leaderboard_display,
+            )
+                .chain(),
+        );
+
+        app.update();
+        let button = app
+            .world_mut()
+            .query_filtered::<Entity, With<MenuLeaderboardButton>>()
+            .single(app.world())
+            .expect("leaderboard button");
+        app.world_mut()
+            .entity_mut(button)
+            .insert(Interaction::Pressed);
+        app.update();
+        app.update();
+
+        let count = app
+            .world_mut()
+            .query_filtered::<Entity, With<LeaderboardOverlayRoot>>()
+            .iter(app.world())
+            .count();
+        assert_eq!(count, 1);
+        let status = app
+            .world_mut()
+            .query_filtered::<&Text, With<LeaderboardStatusText>>()
+            .single(app.world())
+            .expect("leaderboard status text");
+        assert_eq!(
+            status.0,
+            crate::systems::text_constants::LeaderboardText::OFFLINE
+        );
+    }
+
     #[test]
     fn test_settings_overlay_contains_volume_controls() {
         let mut app = App::new();
@@ -935,7 +985,7 @@ mod button_interaction_tests {
 
         // Verify initial character selection
         let initial_selection = app.world().resource::<CharacterSelection>();
-        let initial_char = initial_selection.selected_character.clone();
+        let initial_char = initial_selection.selected_character;
 
         // Find character select buttons
         let mut char_button_query = app
@@ -943,7 +993,7 @@ mod button_interaction_tests {
             .query_filtered::<(Entity, &CharacterSelectButton), ()>();
         let char_buttons: Vec<(Entity, CharacterType)> = char_button_query
             .iter(app.world())
-            .map(|(e, btn)| (e, btn.character_type.clone()))
+            .map(|(e, btn)| (e, btn.character_type))
             .collect();
 
         assert!(
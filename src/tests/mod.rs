@@ -1,6 +1,8 @@
 pub mod animation_tests;
 pub mod camera_tests;
 pub mod components_tests;
+#[cfg(feature = "server")]
+pub mod database_tests;
 pub mod integration_tests;
 pub mod menu_ui_tests;
 pub mod reference_attack_assets_tests;
@@ -1,5 +1,5 @@
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     fs,
     path::{Path, PathBuf},
     time::Duration,
@@ -11,12 +11,15 @@ use serde_json::Value;
 
 use crate::{
     components::{
-        AttackAnimationState, Enemy, EnemyState, EnemyType, FacingDirection, Ground,
-        LedgeTraversal, LedgeTraversalPhase, Player, PlayerState, SkyCheckpoint, SkyClimbAnchor,
-        SkyCombatGate, SkyEncounterEnemy, SkyEncounterState, SkyEnemyKind, SkyEnemySpawn,
-        SkyGateVisual, SkyLevelRuntime, SkyPlayerStart, Velocity,
+        AttackAnimationState, Boss, BossAction, BossHazard, Enemy, EnemyBrain, EnemyState,
+        EnemyType, FacingDirection, Ground, LedgeTraversal, LedgeTraversalPhase, Player,
+        PlayerState, SkyBoss, SkyCheckpoint, SkyClimbAnchor, SkyCombatGate, SkyEncounterEnemy,
+        SkyEncounterState, SkyEnemyKind, SkyEnemySpawn, SkyGateVisual, SkyLevelRuntime,
+        SkyPlayerStart, Velocity,
     },
-    systems::{collision::CollisionBox, sky_level},
+    events::{ArenaCleared, CameraImpulseEvent, CheckpointActivated, DamageEvent},
+    resources::GameplayTuning,
+    systems::{boss, collision::CollisionBox, combat::EnemyProjectile, sky_level},
 };
 
 fn asset_path(relative: &str) -> PathBuf {
@@ -194,7 +197,8 @@ fn sky_city_gameplay_entities_obey_encounter_contract() {
     };
     assert_eq!(count("PlayerStart"), 1);
     assert_eq!(count("Checkpoint"), 5);
-    assert_eq!(count("EnemySpawn"), 32);
+    assert_eq!(count("EnemySpawn"), 31);
+    assert_eq!(count("SkyBoss"), 1);
     assert_eq!(count("CombatGate"), 6);
     assert_eq!(count("Goal"), 1);
     assert_eq!(count("ClimbAnchor"), 33);
@@ -222,12 +226,14 @@ fn sky_city_gameplay_entities_obey_encounter_contract() {
     assert_eq!(gates_per_arena, BTreeMap::from([(1, 2), (2, 2), (3, 2)]));
 
     let mut enemies_per_arena = BTreeMap::<i64, usize>::new();
+    let mut enemy_kinds = BTreeSet::new();
     for enemy in entities
         .iter()
         .filter(|entity| entity["__identifier"] == "EnemySpawn")
     {
         let arena = field_value(enemy, "arena").as_i64().expect("arena integer");
         *enemies_per_arena.entry(arena).or_default() += 1;
+        enemy_kinds.insert(field_value(enemy, "kind").as_str().expect("enemy kind"));
         assert!(
             field_value(enemy, "healthMultiplier")
                 .as_f64()
@@ -241,7 +247,31 @@ fn sky_city_gameplay_entities_obey_encounter_contract() {
     }
     assert_eq!(enemies_per_arena.get(&1), Some(&4));
     assert_eq!(enemies_per_arena.get(&2), Some(&4));
-    assert_eq!(enemies_per_arena.get(&3), Some(&5));
+    assert_eq!(enemies_per_arena.get(&3), Some(&4));
+    assert_eq!(
+        enemy_kinds.into_iter().collect::<Vec<_>>(),
+        vec!["BlackMud", "Familiar", "HeroicSpirit", "Shadow", "Slime"]
+    );
+
+    let boss = entities
+        .iter()
+        .find(|entity| entity["__identifier"] == "SkyBoss")
+        .expect("boss placement");
+    assert_eq!(field_value(boss, "arena").as_i64(), Some(3));
+    let arena_three_gates = entities
+        .iter()
+        .filter(|entity| {
+            entity["__identifier"] == "CombatGate"
+                && field_value(entity, "arena").as_i64() == Some(3)
+        })
+        .map(|gate| gate["__grid"][0].as_i64().expect("gate column"))
+        .collect::<Vec<_>>();
+    let boss_column = boss["__grid"][0].as_i64().expect("boss column");
+    assert!(
+        arena_three_gates.iter().any(|&gate| gate < boss_column)
+            && arena_three_gates.iter().any(|&gate| gate > boss_column),
+        "the boss must be fought inside the final arena, before the goal"
+    );
 
     let mut iids = HashSet::new();
     assert!(iids.insert(project["iid"].as_str().expect("project IID")));
@@ -352,6 +382,7 @@ fn gate_test_app(player_x: f32, active_arena: Option<i32>) -> App {
         active_arena,
         ..default()
     })
+    .add_message::<ArenaCleared>()
     .add_systems(Update, sky_level::update_combat_gates);
     app.world_mut()
         .spawn((Player, Transform::from_xyz(player_x, 100.0, 0.0)));
@@ -418,6 +449,224 @@ fn arena_gates_reopen_if_revive_moves_player_outside() {
     assert_eq!(open_gate_count, 2);
 }
 
+#[test]
+fn cleared_arena_reports_whether_player_took_damage() {
+    for (damaged, expected_no_damage) in [(false, true), (true, false)] {
+        let mut app = gate_test_app(500.0, Some(1));
+        if damaged {
+            app.world_mut()
+                .resource_mut::<SkyEncounterState>()
+                .damaged_arenas
+                .insert(1);
+        }
+        let spawn = app
+            .world_mut()
+            .query_filtered::<Entity, With<SkyEnemySpawn>>()
+            .single(app.world())
+            .expect("single spawn");
+        app.world_mut().despawn(spawn);
+        app.update();
+
+        assert!(
+            app.world()
+                .resource::<SkyEncounterState>()
+                .completed_arenas
+                .contains(&1)
+        );
+        let cleared: Vec<_> = app
+            .world_mut()
+            .resource_mut::<Messages<ArenaCleared>>()
+            .drain()
+            .map(|clear| (clear.arena, clear.no_damage))
+            .collect();
+        assert_eq!(cleared, vec![(1, expected_no_damage)]);
+    }
+}
+
+#[test]
+fn unspawned_boss_keeps_its_arena_closed() {
+    let mut app = gate_test_app(500.0, Some(1));
+    let spawn = app
+        .world_mut()
+        .query_filtered::<Entity, With<SkyEnemySpawn>>()
+        .single(app.world())
+        .expect("single spawn");
+    app.world_mut().despawn(spawn);
+    app.world_mut().spawn(SkyBoss {
+        arena: 1,
+        health_multiplier: 1.0,
+        patrol_range: 640.0,
+    });
+    app.update();
+
+    let encounters = app.world().resource::<SkyEncounterState>();
+    assert_eq!(encounters.active_arena, Some(1));
+    assert!(!encounters.completed_arenas.contains(&1));
+}
+
+fn boss_test_app(health_fraction: f32) -> (App, Entity) {
+    let mut app = App::new();
+    app.insert_resource(Time::<()>::default())
+        .insert_resource(SkyEncounterState {
+            active_arena: Some(3),
+            ..default()
+        })
+        .add_message::<CameraImpulseEvent>()
+        .add_systems(Update, boss::boss_ai);
+    app.world_mut()
+        .spawn((Player, Transform::from_xyz(900.0, 200.0, 0.0)));
+
+    let mut state = EnemyState::new(100, 800.0)
+        .with_spawn_origin(600.0)
+        .with_movement(150.0, 22.0, 0.0);
+    state.health = (100.0 * health_fraction) as i32;
+    let boss = app
+        .world_mut()
+        .spawn((
+            Enemy,
+            EnemyType::EnemyHeroicSpirit,
+            Boss::new(3),
+            state,
+            Transform::from_xyz(600.0, 200.0, 0.0),
+            Velocity { x: 0.0, y: 0.0 },
+            SkyEncounterEnemy {
+                arena: 3,
+                anchor_y: 200.0,
+            },
+        ))
+        .id();
+    (app, boss)
+}
+
+fn step(app: &mut App, secs: f32) {
+    app.world_mut()
+        .resource_mut::<Time<()>>()
+        .advance_by(Duration::from_secs_f32(secs));
+    app.update();
+}
+
+#[test]
+fn boss_phase_threshold_interrupts_and_swaps_behavior_tuning() {
+    let tuning = GameplayTuning::default().boss;
+    let (mut app, boss) = boss_test_app(0.5);
+    step(&mut app, 0.016);
+
+    let entity = app.world().entity(boss);
+    let runtime = entity.get::<Boss>().expect("boss");
+    assert_eq!(runtime.phase, 1);
+    assert_eq!(runtime.action, BossAction::Idle);
+    assert!(runtime.transition_timer > 0.0);
+    let state = entity.get::<EnemyState>().expect("boss state");
+    assert!(
+        (state.contact_damage - tuning.contact_damage * tuning.phases[1].damage_multiplier).abs()
+            < 0.001
+    );
+    assert_eq!(
+        app.world_mut()
+            .resource_mut::<Messages<CameraImpulseEvent>>()
+            .drain()
+            .count(),
+        1
+    );
+
+    // Phases never regress even if a later frame reads a higher health ratio.
+    app.world_mut()
+        .get_mut::<EnemyState>(boss)
+        .expect("boss state")
+        .health = 100;
+    step(&mut app, tuning.phase_transition_secs + 0.05);
+    assert_eq!(app.world().get::<Boss>(boss).expect("boss").phase, 1);
+}
+
+#[test]
+fn boss_barrage_fires_spread_volleys_from_phase_pattern_table() {
+    let tuning = GameplayTuning::default().boss;
+    let (mut app, boss) = boss_test_app(0.5);
+    step(&mut app, 0.016);
+    step(&mut app, tuning.phase_transition_secs + 0.05);
+
+    let runtime = app.world().get::<Boss>(boss).expect("boss").clone();
+    assert_eq!(runtime.action, BossAction::Barrage);
+    assert_eq!(runtime.active_pattern, 0);
+    assert_eq!(runtime.repeats_left, 3);
+
+    step(&mut app, 0.5);
+    let projectiles = app
+        .world_mut()
+        .query::<&EnemyProjectile>()
+        .iter(app.world())
+        .count();
+    assert_eq!(projectiles, 5);
+    assert_eq!(app.world().get::<Boss>(boss).expect("boss").repeats_left, 2);
+
+    let directions = boss::barrage_directions(Vec2::X, 5, 60.0);
+    assert_eq!(directions.len(), 5);
+    assert!(directions[2].abs_diff_eq(Vec2::X, 0.001));
+    assert!((directions[0].angle_to(directions[4]).to_degrees() - 60.0).abs() < 0.01);
+}
+
+#[test]
+fn boss_waits_until_the_player_enters_its_arena() {
+    let (mut app, boss) = boss_test_app(1.0);
+    app.world_mut()
+        .resource_mut::<SkyEncounterState>()
+        .active_arena = None;
+    step(&mut app, 2.0);
+
+    let runtime = app.world().get::<Boss>(boss).expect("boss");
+    assert_eq!(runtime.action, BossAction::Idle);
+    assert_eq!(
+        app.world()
+            .get::<Transform>(boss)
+            .expect("transform")
+            .translation
+            .x,
+        600.0
+    );
+}
+
+#[test]
+fn boss_hazard_telegraphs_before_hitting_player_once() {
+    let mut app = App::new();
+    app.insert_resource(Time::<()>::default())
+        .add_message::<DamageEvent>()
+        .add_systems(Update, boss::update_boss_hazards);
+    app.world_mut().spawn((
+        Player,
+        Transform::from_xyz(0.0, 40.0, 0.0),
+        CollisionBox::new(Vec2::new(40.0, 60.0)),
+    ));
+    let hazard = app
+        .world_mut()
+        .spawn((
+            BossHazard {
+                telegraph_timer: 0.25,
+                active_timer: 0.4,
+                damage: 26.0,
+                width: 56.0,
+                has_hit: false,
+            },
+            Transform::from_xyz(10.0, 110.0, 0.0),
+            Sprite::default(),
+        ))
+        .id();
+
+    let mut damage = Vec::new();
+    for _ in 0..4 {
+        step(&mut app, 0.1);
+        damage.extend(
+            app.world_mut()
+                .resource_mut::<Messages<DamageEvent>>()
+                .drain()
+                .map(|event| event.amount),
+        );
+    }
+    assert_eq!(damage, vec![26.0]);
+
+    step(&mut app, 0.4);
+    assert!(app.world().get_entity(hazard).is_err());
+}
+
 #[test]
 fn initialized_ldtk_player_does_not_overwrite_restored_save_position() {
     let saved_position = Vec3::new(3_400.0, 720.0, 1.0);
@@ -456,6 +705,7 @@ fn loaded_position_reconciles_to_highest_passed_checkpoint() {
         checkpoint_needs_reconciliation: true,
         ..default()
     })
+    .add_message::<CheckpointActivated>()
     .add_systems(Update, sky_level::activate_checkpoints);
     app.world_mut()
         .spawn((Player, Transform::from_translation(saved_position)));
@@ -471,12 +721,52 @@ fn loaded_position_reconciles_to_highest_passed_checkpoint() {
     assert!(runtime.checkpoint_position.x < saved_position.x);
 }
 
+#[derive(Resource, Default)]
+struct ActivatedCheckpoints(Vec<i32>);
+
+fn record_checkpoint_activations(
+    mut activated: MessageReader<CheckpointActivated>,
+    mut log: ResMut<ActivatedCheckpoints>,
+) {
+    log.0
+        .extend(activated.read().map(|activation| activation.checkpoint_id));
+}
+
+#[test]
+fn reaching_new_checkpoint_emits_activation_once() {
+    let coords = GridCoords::new(10, 10);
+    let mut checkpoint_position = sky_level::grid_translation(coords, 1.0);
+    checkpoint_position.y += 14.0;
+
+    let mut app = App::new();
+    app.init_resource::<SkyLevelRuntime>()
+        .init_resource::<ActivatedCheckpoints>()
+        .add_message::<CheckpointActivated>()
+        .add_systems(
+            Update,
+            (
+                sky_level::activate_checkpoints,
+                record_checkpoint_activations,
+            )
+                .chain(),
+        );
+    app.world_mut()
+        .spawn((Player, Transform::from_translation(checkpoint_position)));
+    app.world_mut().spawn((SkyCheckpoint { id: 1 }, coords));
+
+    app.update();
+    app.update();
+
+    assert_eq!(app.world().resource::<SkyLevelRuntime>().checkpoint_id, 1);
+    assert_eq!(app.world().resource::<ActivatedCheckpoints>().0, vec![1]);
+}
+
 #[test]
 fn authored_enemy_keeps_floating_island_anchor_and_fights_bidirectionally() {
     let anchor_y = 640.0;
     let mut app = App::new();
     app.insert_resource(Time::<()>::default())
-        .add_systems(Update, crate::systems::enemy::enemy_patrol_ai);
+        .add_systems(Update, crate::systems::enemy_behavior::enemy_behavior_ai);
     app.world_mut()
         .resource_mut::<Time<()>>()
         .advance_by(Duration::from_millis(16));
@@ -489,6 +779,7 @@ fn authored_enemy_keeps_floating_island_anchor_and_fights_bidirectionally() {
         EnemyState::new(3, 96.0)
             .with_spawn_origin(400.0)
             .with_movement(60.0, 12.0, 0.0),
+        EnemyBrain::default(),
         Velocity { x: 0.0, y: 0.0 },
         SkyEncounterEnemy { arena: 1, anchor_y },
     ));
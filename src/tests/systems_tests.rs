@@ -488,85 +488,106 @@ mod tests {
     }
 
     #[test]
-    fn test_save_manager_new() {
-        let save_manager = crate::resources::SaveManager::new();
+    fn test_next_autosave_path_fills_empty_slots_then_overwrites_oldest() {
+        let temp_dir = std::env::temp_dir().join(format!(
+            "emiyashiro-autosave-slots-{}",
+            uuid::Uuid::new_v4()
+        ));
+        fs::create_dir_all(&temp_dir).expect("create save dir");
+
+        assert_eq!(
+            save::next_autosave_path(&temp_dir, 2),
+            temp_dir.join("autosave_1.json")
+        );
+        fs::write(temp_dir.join("autosave_1.json"), b"{}").expect("write slot 1");
+        assert_eq!(
+            save::next_autosave_path(&temp_dir, 2),
+            temp_dir.join("autosave_2.json")
+        );
 
-        assert!(save_manager.current_save.is_none());
-        assert_eq!(save_manager.save_file_path, "save_data.json");
+        std::thread::sleep(Duration::from_millis(20));
+        fs::write(temp_dir.join("autosave_2.json"), b"{}").expect("write slot 2");
+        assert_eq!(
+            save::next_autosave_path(&temp_dir, 2),
+            temp_dir.join("autosave_1.json"),
+            "the oldest autosave should be reused once every slot exists"
+        );
+
+        let _ = fs::remove_dir_all(temp_dir);
     }
 
     #[test]
-    fn test_save_game_writes_v2_schema() {
-        let temp_path =
-            std::env::temp_dir().join(format!("emiyashiro-save-v2-{}.json", uuid::Uuid::new_v4()));
+    fn test_checkpoint_activation_writes_full_state_autosave_slot() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("emiyashiro-autosave-{}", uuid::Uuid::new_v4()));
+        let mut error_recovery = error_handling::ErrorRecoveryManager::new();
+        error_recovery.backup_directory = temp_dir.join("backup");
 
         let mut app = App::new();
         app.add_plugins(MinimalPlugins)
+            .init_resource::<GameplayTuning>()
             .init_resource::<GameStats>()
             .init_resource::<CharacterSelection>()
-            .init_resource::<SaveManager>()
-            .add_systems(Update, save::save_game);
-
-        app.world_mut().resource_mut::<SaveManager>().save_file_path =
-            temp_path.to_string_lossy().to_string();
+            .init_resource::<AudioStateManager>()
+            .init_resource::<save::AutosaveState>()
+            .init_resource::<async_file_ops::AsyncFileManager>()
+            .insert_resource(SaveFileManager {
+                save_directory: temp_dir.to_string_lossy().to_string(),
+                ..Default::default()
+            })
+            .insert_resource(error_recovery)
+            .add_message::<crate::events::CheckpointActivated>()
+            .add_systems(
+                Update,
+                (save::autosave_system, save::poll_autosave_tasks).chain(),
+            );
+        app.world_mut().spawn((
+            Player,
+            Transform::from_xyz(320.0, 48.0, 1.0),
+            Velocity { x: 0.0, y: 0.0 },
+            PlayerState::default(),
+        ));
         app.world_mut()
             .resource_mut::<GameStats>()
             .distance_traveled = 321.0;
         app.world_mut().resource_mut::<GameStats>().jump_count = 7;
-        app.world_mut().resource_mut::<GameStats>().play_time = 42.0;
-
-        app.update();
-
-        let json = fs::read_to_string(&temp_path).expect("save file should exist");
-        let v2_save: SaveFileData = serde_json::from_str(&json).expect("should be SaveFileData v2");
-
-        assert_eq!(v2_save.version, "2.0");
-        assert_eq!(v2_save.game_state.distance_traveled, 321.0);
-        assert_eq!(v2_save.game_state.jump_count, 7);
-        assert!(v2_save.verify_checksum());
-
-        let _ = fs::remove_file(temp_path);
-    }
-
-    #[test]
-    fn test_load_game_rejects_deprecated_save_schema() {
-        let temp_path = std::env::temp_dir().join(format!(
-            "emiyashiro-deprecated-save-{}.json",
-            uuid::Uuid::new_v4()
-        ));
-
-        let deprecated_json = serde_json::json!({
-            "player_name": "DeprecatedPlayer",
-            "selected_character": "Shirou2",
-            "best_distance": 888.0,
-            "total_jumps": 99,
-            "total_play_time": 123.0,
-            "save_time": chrono::Utc::now(),
-        });
-        let deprecated_json =
-            serde_json::to_string_pretty(&deprecated_json).expect("serialize json");
-        fs::write(&temp_path, deprecated_json.as_bytes()).expect("write deprecated save");
-
-        let mut app = App::new();
-        app.add_plugins(MinimalPlugins)
-            .init_resource::<CharacterSelection>()
-            .init_resource::<SaveManager>()
-            .add_systems(Update, save::load_game);
-
-        app.world_mut().resource_mut::<SaveManager>().save_file_path =
-            temp_path.to_string_lossy().to_string();
+        app.world_mut()
+            .resource_mut::<Messages<crate::events::CheckpointActivated>>()
+            .write(crate::events::CheckpointActivated { checkpoint_id: 1 });
 
-        app.update();
+        for _ in 0..200 {
+            app.update();
+            if app
+                .world()
+                .resource::<save::AutosaveState>()
+                .last_autosave_path
+                .is_some()
+            {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
 
-        let save_manager = app.world().resource::<SaveManager>();
+        let slot_path = temp_dir.join("autosave_1.json");
+        assert_eq!(
+            app.world()
+                .resource::<save::AutosaveState>()
+                .last_autosave_path
+                .as_deref(),
+            Some(slot_path.as_path())
+        );
+        let state = load_slot_file(&slot_path).expect("autosave should load as a regular slot");
+        assert_eq!(state.player_position, Vec3::new(320.0, 48.0, 1.0));
+        assert_eq!(state.distance_traveled, 321.0);
+        assert_eq!(state.jump_count, 7);
         assert!(
-            save_manager.current_save.is_none(),
-            "deprecated save schema must be rejected"
+            app.world()
+                .resource::<error_handling::ErrorRecoveryManager>()
+                .backup_exists("autosave_1"),
+            "autosave should rotate a backup like a manual save"
         );
-        let loaded_selection = app.world().resource::<CharacterSelection>();
-        assert_eq!(loaded_selection.selected_character, CharacterType::Shirou);
 
-        let _ = fs::remove_file(temp_path);
+        let _ = fs::remove_dir_all(temp_dir);
     }
 
     #[test]
@@ -576,6 +597,7 @@ mod tests {
             .add_plugins(bevy::state::app::StatesPlugin)
             .init_state::<GameState>()
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
             .add_systems(Update, crate::systems::combat::apply_damage_events);
 
@@ -1345,6 +1367,9 @@ mod tests {
             distance_traveled: 2500.0,
             jump_count: 25,
             play_time: 120.0,
+            combat: CombatRecord::default(),
+            player_status_effects: StatusEffects::default(),
+            untrusted_save: false,
             music_position: 45.5,
             music_playing: true,
             audio_volume: 0.8,
@@ -1437,6 +1462,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_every_corruption_error_offers_backup_restore() {
+        use error_handling::{RecoveryAction, SaveSystemError};
+
+        let temp_dir =
+            std::env::temp_dir().join(format!("emiyashiro-corrupt-{}", uuid::Uuid::new_v4()));
+        let mut error_manager = error_handling::ErrorRecoveryManager::new();
+        error_manager.backup_directory = temp_dir.clone();
+
+        let corruptions = [
+            SaveSystemError::FileCorrupted("slot".to_string()),
+            SaveSystemError::ChecksumMismatch,
+            SaveSystemError::SignatureMismatch,
+            SaveSystemError::DeserializationFailed("eof".to_string()),
+            SaveSystemError::DecompressionFailed("bad frame".to_string()),
+        ];
+        for error in corruptions.clone() {
+            assert!(error.is_corruption());
+            assert!(
+                !matches!(
+                    error_manager.handle_load_error(error, "slot"),
+                    RecoveryAction::UseBackup
+                ),
+                "no backup exists yet"
+            );
+        }
+
+        error_manager
+            .backup_policy()
+            .rotate("slot", b"{}")
+            .expect("write backup");
+        for error in corruptions {
+            assert!(matches!(
+                error_manager.handle_load_error(error, "slot"),
+                RecoveryAction::UseBackup
+            ));
+        }
+
+        let _ = fs::remove_dir_all(temp_dir);
+    }
+
     #[test]
     fn test_audio_state_management() {
         let mut audio_manager = AudioStateManager::default();
@@ -1468,6 +1534,9 @@ mod tests {
             distance_traveled: 1250.0,
             jump_count: 15,
             play_time: 75.0,
+            combat: CombatRecord::default(),
+            player_status_effects: StatusEffects::default(),
+            untrusted_save: false,
             music_position: 22.5,
             music_playing: true,
             audio_volume: 0.9,
@@ -1540,6 +1609,7 @@ mod tests {
             .insert_resource(ButtonInput::<KeyCode>::default())
             .init_resource::<GameStats>()
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
             .add_systems(
                 Update,
@@ -1734,7 +1804,9 @@ mod tests {
             .add_plugins(bevy::state::app::StatesPlugin)
             .init_state::<GameState>()
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
+            .add_message::<crate::events::StatusEffectEvent>()
             .add_systems(
                 Update,
                 (
@@ -1786,6 +1858,7 @@ mod tests {
             .add_plugins(bevy::state::app::StatesPlugin)
             .init_state::<GameState>()
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
             .add_systems(
                 Update,
@@ -1803,8 +1876,10 @@ mod tests {
                     damage: 6.0,
                     lifetime: Timer::from_seconds(0.2, TimerMode::Once),
                     combo_step: 1,
+                    combo_chain: 1,
                     knockback_x: 60.0,
                     knockback_y: 10.0,
+                    launch_y: 0.0,
                     hit_stop_secs: 0.02,
                 },
                 Transform::from_xyz(0.0, 0.0, 0.0),
@@ -1841,6 +1916,98 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_frame_driven_knife_hits_only_on_active_frames_and_once_per_enemy() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(bevy::state::app::StatesPlugin)
+            .init_state::<GameState>()
+            .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
+            .add_message::<crate::events::CameraImpulseEvent>()
+            .insert_resource(crate::systems::hitboxes::load_frame_data())
+            .add_systems(
+                Update,
+                (
+                    crate::systems::combat::knife_enemy_collision,
+                    crate::systems::combat::apply_damage_events,
+                )
+                    .chain(),
+            );
+
+        let mut attack_state = AttackAnimationState::default();
+        attack_state.trigger_with_style(0.5, AttackAnimationStyle::GroundLightRow(1));
+        let animation = crate::systems::sprite_animation::SpriteAnimation {
+            current_animation: AnimationType::Attacking,
+            current_frame: 0,
+            ..Default::default()
+        };
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_xyz(0.0, 0.0, 0.0),
+                animation,
+                crate::systems::hitboxes::FrameDrivenAttack {
+                    trigger_serial: attack_state.trigger_serial,
+                    combo_step: 1,
+                    combo_chain: 1,
+                    facing: 1.0,
+                    damage: 6.0,
+                    knockback_x: 90.0,
+                    knockback_y: 10.0,
+                    launch_y: 0.0,
+                    hit_stop_secs: 0.02,
+                    feedback: crate::systems::combat::KnifeSlashFeedback {
+                        camera_intensity: 2.0,
+                        camera_duration: 0.05,
+                        hit_stop_freeze_speed: 0.2,
+                        base_alpha: 0.2,
+                        visual_expand: 0.1,
+                        visual_spin: 0.0,
+                    },
+                    hit_targets: Vec::new(),
+                },
+                attack_state,
+            ))
+            .id();
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                EnemyState::new(50, 100.0),
+                Transform::from_xyz(60.0, 10.0, 0.0),
+                Velocity::default(),
+                crate::systems::collision::CollisionBox::new(Vec2::new(20.0, 20.0)),
+            ))
+            .id();
+        let enemy_health = |app: &App| {
+            app.world()
+                .entity(enemy)
+                .get::<EnemyState>()
+                .unwrap()
+                .health
+        };
+
+        app.update();
+        assert_eq!(enemy_health(&app), 50, "windup frame has no hitbox");
+
+        app.world_mut()
+            .get_mut::<crate::systems::sprite_animation::SpriteAnimation>(player)
+            .unwrap()
+            .current_frame = 2;
+        app.update();
+        app.update();
+        assert_eq!(enemy_health(&app), 44, "active frame hits exactly once");
+        assert!(
+            app.world()
+                .entity(enemy)
+                .get::<Velocity>()
+                .is_some_and(|velocity| velocity.x > 0.0),
+            "knockback follows the attacker's facing"
+        );
+    }
+
     #[test]
     fn test_knife_combo_buffer_spawns_followup_slash() {
         let mut app = App::new();
@@ -2166,6 +2333,7 @@ mod tests {
         app.add_plugins(MinimalPlugins)
             .insert_resource(ButtonInput::<KeyCode>::default())
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_systems(Update, crate::systems::shirou::handle_shroud_input);
 
         let player = app
@@ -3351,8 +3519,10 @@ mod tests {
                     damage: 6.0,
                     lifetime: Timer::from_seconds(0.20, TimerMode::Once),
                     combo_step: 1,
+                    combo_chain: 1,
                     knockback_x: 60.0,
                     knockback_y: 10.0,
+                    launch_y: 0.0,
                     hit_stop_secs: 0.02,
                 },
                 crate::systems::combat::KnifeSlashFeedback {
@@ -4109,7 +4279,7 @@ mod tests {
             .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
                 Duration::from_secs_f32(1.0 / 60.0),
             ))
-            .add_systems(Update, crate::systems::enemy::enemy_patrol_ai);
+            .add_systems(Update, crate::systems::enemy_behavior::enemy_behavior_ai);
 
         app.world_mut().spawn((
             Player,
@@ -4124,19 +4294,21 @@ mod tests {
                 EnemyState::new(14, 360.0)
                     .with_spawn_origin(380.0)
                     .with_movement(132.0, 19.0, 0.0),
+                EnemyBrain::default(),
                 Transform::from_xyz(380.0, GameConfig::GROUND_LEVEL + 30.0, 0.0),
                 Velocity::default(),
             ))
             .id();
 
         app.update();
-        let state_after_lock = app
+        let brain_after_lock = app
             .world()
             .entity(enemy)
-            .get::<EnemyState>()
-            .expect("heroic enemy state");
-        assert!(
-            state_after_lock.dash_charge_timer > 0.0,
+            .get::<EnemyBrain>()
+            .expect("heroic enemy brain");
+        assert_eq!(
+            brain_after_lock.node,
+            BehaviorNode::Telegraph,
             "heroic spirit should enter dash charge state"
         );
 
@@ -4144,12 +4316,13 @@ mod tests {
             app.update();
         }
         let entity = app.world().entity(enemy);
-        let state_after_dash = entity
-            .get::<EnemyState>()
-            .expect("heroic enemy state after dash");
+        let brain_after_dash = entity
+            .get::<EnemyBrain>()
+            .expect("heroic enemy brain after dash");
         let velocity = entity.get::<Velocity>().expect("heroic velocity");
-        assert!(
-            state_after_dash.dash_active_timer > 0.0,
+        assert_eq!(
+            brain_after_dash.node,
+            BehaviorNode::Dash,
             "dash charge should transition into active dash"
         );
         assert!(
@@ -4165,7 +4338,7 @@ mod tests {
             .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
                 Duration::from_secs_f32(1.0 / 60.0),
             ))
-            .add_systems(Update, crate::systems::enemy::enemy_patrol_ai);
+            .add_systems(Update, crate::systems::enemy_behavior::enemy_behavior_ai);
 
         app.world_mut().spawn((
             Player,
@@ -4180,6 +4353,7 @@ mod tests {
                 EnemyState::new(14, 360.0)
                     .with_spawn_origin(40.0)
                     .with_movement(132.0, 19.0, 0.0),
+                EnemyBrain::default(),
                 Transform::from_xyz(40.0, GameConfig::GROUND_LEVEL + 30.0, 0.0),
                 Velocity::default(),
             ))
@@ -4190,7 +4364,7 @@ mod tests {
 
         let entity = app.world().entity(enemy);
         let transform = entity.get::<Transform>().expect("enemy transform");
-        let state = entity.get::<EnemyState>().expect("enemy state");
+        let brain = entity.get::<EnemyBrain>().expect("enemy brain");
         let velocity = entity.get::<Velocity>().expect("enemy velocity");
 
         assert!(
@@ -4202,7 +4376,8 @@ mod tests {
             "passed enemy velocity should point away from the player"
         );
         assert_eq!(
-            state.dash_charge_timer, 0.0,
+            brain.node,
+            BehaviorNode::Retreat,
             "passed heroic enemy should not reacquire a dash attack"
         );
     }
@@ -4214,14 +4389,7 @@ mod tests {
             .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
                 Duration::from_secs_f32(1.0 / 60.0),
             ))
-            .add_systems(
-                Update,
-                (
-                    crate::systems::enemy::enemy_patrol_ai,
-                    crate::systems::enemy::enemy_ranged_attack,
-                )
-                    .chain(),
-            );
+            .add_systems(Update, crate::systems::enemy_behavior::enemy_behavior_ai);
 
         app.world_mut().spawn((
             Player,
@@ -4236,6 +4404,7 @@ mod tests {
                 EnemyState::new(6, 300.0)
                     .with_spawn_origin(40.0)
                     .with_movement(92.0, 13.0, 0.0),
+                EnemyBrain::default(),
                 Transform::from_xyz(40.0, GameConfig::GROUND_LEVEL + 124.0, 0.0),
                 Velocity::default(),
             ))
@@ -4244,7 +4413,7 @@ mod tests {
         app.update();
 
         let entity = app.world().entity(enemy);
-        let state = entity.get::<EnemyState>().expect("familiar state");
+        let brain = entity.get::<EnemyBrain>().expect("familiar brain");
         let velocity = entity.get::<Velocity>().expect("familiar velocity");
 
         assert!(
@@ -4252,7 +4421,7 @@ mod tests {
             "passed familiar should keep leaving instead of hovering back into range"
         );
         assert!(
-            !state.pending_ranged_shot,
+            !brain.is_attacking(),
             "passed familiar should not start a ranged windup behind the player"
         );
     }
@@ -4264,14 +4433,7 @@ mod tests {
             .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
                 Duration::from_secs_f32(1.0 / 60.0),
             ))
-            .add_systems(
-                Update,
-                (
-                    crate::systems::enemy::enemy_patrol_ai,
-                    crate::systems::enemy::enemy_ranged_attack,
-                )
-                    .chain(),
-            );
+            .add_systems(Update, crate::systems::enemy_behavior::enemy_behavior_ai);
 
         app.world_mut().spawn((
             Player,
@@ -4286,6 +4448,7 @@ mod tests {
                 EnemyState::new(6, 300.0)
                     .with_spawn_origin(220.0)
                     .with_movement(92.0, 13.0, 0.0),
+                EnemyBrain::default(),
                 Transform::from_xyz(220.0, GameConfig::GROUND_LEVEL + 124.0, 0.0),
                 Velocity::default(),
             ))
@@ -4293,20 +4456,21 @@ mod tests {
 
         app.update();
 
-        let (pending_after_start, _windup_after_start) = app
+        let node_after_start = app
             .world()
             .entity(familiar)
-            .get::<EnemyState>()
-            .map(|state| (state.pending_ranged_shot, state.ranged_windup_timer))
-            .expect("familiar state");
+            .get::<EnemyBrain>()
+            .map(|brain| brain.node)
+            .expect("familiar brain");
         let projectiles_after_start = {
             let mut query = app
                 .world_mut()
                 .query::<&crate::systems::combat::EnemyProjectile>();
             query.iter(app.world()).count()
         };
-        assert!(
-            pending_after_start,
+        assert_eq!(
+            node_after_start,
+            BehaviorNode::Telegraph,
             "familiar should enter windup before casting"
         );
         assert_eq!(
@@ -4318,20 +4482,21 @@ mod tests {
             app.update();
         }
 
-        let (pending_after_fire, cooldown_after_fire) = app
+        let (node_after_fire, cooldown_after_fire) = app
             .world()
             .entity(familiar)
-            .get::<EnemyState>()
-            .map(|state| (state.pending_ranged_shot, state.ranged_cooldown))
-            .expect("familiar state after fire");
+            .get::<EnemyBrain>()
+            .map(|brain| (brain.node, brain.attack_cooldown))
+            .expect("familiar brain after fire");
         let projectiles_after_fire = {
             let mut query = app
                 .world_mut()
                 .query::<&crate::systems::combat::EnemyProjectile>();
             query.iter(app.world()).count()
         };
-        assert!(
-            !pending_after_fire,
+        assert_ne!(
+            node_after_fire,
+            BehaviorNode::Telegraph,
             "familiar should clear pending shot after firing"
         );
         assert!(
@@ -4356,6 +4521,357 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_hit_stun_interrupts_telegraph_and_returns_to_idle_node() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                Duration::from_secs_f32(1.0 / 60.0),
+            ))
+            .add_systems(Update, crate::systems::enemy_behavior::enemy_behavior_ai);
+
+        app.world_mut().spawn((
+            Player,
+            Transform::from_xyz(0.0, GameConfig::GROUND_LEVEL, 0.0),
+        ));
+
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                EnemyType::EnemyHeroicSpirit,
+                EnemyState::new(14, 360.0)
+                    .with_spawn_origin(380.0)
+                    .with_movement(132.0, 19.0, 0.0),
+                EnemyBrain::default(),
+                Transform::from_xyz(380.0, GameConfig::GROUND_LEVEL + 30.0, 0.0),
+                Velocity::default(),
+            ))
+            .id();
+
+        app.update();
+        assert_eq!(
+            app.world().entity(enemy).get::<EnemyBrain>().unwrap().node,
+            BehaviorNode::Telegraph
+        );
+
+        app.world_mut()
+            .get_mut::<EnemyState>(enemy)
+            .unwrap()
+            .apply_hit_stun(0.1);
+        app.update();
+        let brain = app.world().entity(enemy).get::<EnemyBrain>().unwrap();
+        assert_eq!(
+            brain.node,
+            BehaviorNode::Stunned,
+            "hit stun should cancel the dash telegraph"
+        );
+        assert_eq!(brain.aim, Vec2::ZERO);
+
+        for _ in 0..8 {
+            app.update();
+        }
+        let brain = app.world().entity(enemy).get::<EnemyBrain>().unwrap();
+        assert_eq!(
+            brain.node,
+            BehaviorNode::Chase,
+            "the archetype's idle node resumes after stun while the dash stays on cooldown"
+        );
+    }
+
+    #[test]
+    fn test_shadow_lunge_grabs_player_then_releases_after_hold() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                Duration::from_secs_f32(1.0 / 60.0),
+            ))
+            .add_systems(Update, crate::systems::enemy_behavior::enemy_behavior_ai);
+
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::from_xyz(0.0, GameConfig::GROUND_LEVEL, 0.0),
+                crate::systems::collision::CollisionBox::new(GameConfig::PLAYER_SIZE),
+            ))
+            .id();
+        let shadow = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                EnemyType::Shadow,
+                EnemyState::new(9, 320.0)
+                    .with_spawn_origin(120.0)
+                    .with_movement(104.0, 15.0, 0.0),
+                EnemyBrain::default(),
+                Transform::from_xyz(120.0, GameConfig::GROUND_LEVEL, 0.0),
+                Velocity::default(),
+                crate::systems::collision::CollisionBox::new(Vec2::new(34.0, 70.0)),
+            ))
+            .id();
+
+        app.update();
+        assert_eq!(
+            app.world().entity(shadow).get::<EnemyBrain>().unwrap().node,
+            BehaviorNode::Telegraph
+        );
+
+        let mut grabbed = false;
+        for _ in 0..60 {
+            app.update();
+            if app.world().entity(player).contains::<Grabbed>() {
+                grabbed = true;
+                break;
+            }
+        }
+        assert!(grabbed, "the lunge should catch a player in range");
+        assert_eq!(
+            app.world().entity(player).get::<Grabbed>().unwrap().by,
+            shadow
+        );
+        assert_eq!(
+            app.world().entity(shadow).get::<EnemyBrain>().unwrap().node,
+            BehaviorNode::Grab
+        );
+
+        for _ in 0..90 {
+            app.update();
+        }
+        assert!(
+            !app.world().entity(player).contains::<Grabbed>(),
+            "the player should be released once the hold expires"
+        );
+        assert_ne!(
+            app.world().entity(shadow).get::<EnemyBrain>().unwrap().node,
+            BehaviorNode::Grab
+        );
+    }
+
+    #[test]
+    fn test_mud_drain_bypasses_guard_and_invulnerability() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(bevy::state::app::StatesPlugin)
+            .init_state::<GameState>()
+            .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
+            .add_message::<crate::events::CameraImpulseEvent>()
+            .insert_resource(SkyEncounterState {
+                active_arena: Some(1),
+                ..default()
+            })
+            .add_systems(Update, crate::systems::combat::apply_damage_events);
+
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Health::new(100.0),
+                DamageInvulnerability { remaining: 1.0 },
+                PlayerDefense {
+                    guarding: true,
+                    ..default()
+                },
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<Messages<crate::events::DamageEvent>>()
+            .write(crate::events::DamageEvent {
+                target: player,
+                amount: 5.0,
+                source: crate::events::DamageSource::MudDrain,
+            });
+        app.update();
+
+        let health = app.world().entity(player).get::<Health>().unwrap();
+        assert!(
+            (health.current - 95.0).abs() < 1e-4,
+            "mud drain should ignore guard chip and i-frames"
+        );
+        assert!(
+            (app.world()
+                .entity(player)
+                .get::<DamageInvulnerability>()
+                .unwrap()
+                .remaining
+                - 1.0)
+                .abs()
+                < f32::EPSILON,
+            "mud drain should not refresh i-frames"
+        );
+        assert!(
+            app.world()
+                .resource::<SkyEncounterState>()
+                .damaged_arenas
+                .contains(&1),
+            "mud drain still spoils the no-damage clear"
+        );
+    }
+
+    fn spawn_physics_test_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                Duration::from_secs_f32(1.0 / 60.0),
+            ))
+            .add_systems(Update, crate::systems::enemy_behavior::enemy_behavior_ai);
+        app.world_mut().spawn((
+            Player,
+            Transform::from_xyz(-1000.0, GameConfig::GROUND_LEVEL, 0.0),
+        ));
+        app
+    }
+
+    #[test]
+    fn test_launcher_juggles_ground_enemy_then_lands_on_sky_collider() {
+        let mut app = spawn_physics_test_app();
+        let physics = EnemyPhysicsTuning::default();
+        let anchor_y =
+            GameConfig::GROUND_LEVEL + GameplayTuning::default().enemies.slime.spawn_y_offset;
+        let half_height = 15.0;
+        let platform_top = anchor_y - half_height + 30.0;
+        app.world_mut().spawn((
+            Transform::from_xyz(0.0, platform_top - 20.0, 0.0),
+            crate::systems::collision::CollisionBox::new(Vec2::new(400.0, 40.0)),
+            SkyMergedCollider,
+        ));
+
+        let mut body = EnemyBody::default();
+        let mut velocity = Velocity::default();
+        body.take_hit(&mut velocity, Vec2::new(0.0, 10.0), 560.0, &physics);
+        assert!(
+            body.airborne,
+            "launcher should put a grounded enemy in the air"
+        );
+        assert_eq!(velocity.y, 560.0);
+        let mut state = EnemyState::new(5, 170.0);
+        state.apply_hit_stun(0.2);
+        let start_y = anchor_y + 30.0;
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                EnemyType::Slime,
+                state,
+                EnemyBrain::default(),
+                body,
+                velocity,
+                Transform::from_xyz(0.0, start_y, 0.0),
+                crate::systems::collision::CollisionBox::new(Vec2::new(40.0, half_height * 2.0)),
+            ))
+            .id();
+
+        let mut peak_y = start_y;
+        for _ in 0..30 {
+            app.update();
+            let y = app
+                .world()
+                .entity(enemy)
+                .get::<Transform>()
+                .unwrap()
+                .translation
+                .y;
+            peak_y = peak_y.max(y);
+        }
+        assert!(
+            peak_y > start_y + 80.0,
+            "launched enemy should rise, peak {peak_y}"
+        );
+        let entity = app.world().entity(enemy);
+        assert!(
+            entity.get::<EnemyBody>().unwrap().airborne,
+            "enemy should still be airborne"
+        );
+        assert_eq!(
+            entity.get::<EnemyBrain>().unwrap().node,
+            BehaviorNode::Stunned
+        );
+
+        // 浮空追击：空中命中再次托起，并按 juggle_decay 衰减
+        {
+            let mut entity = app.world_mut().entity_mut(enemy);
+            let mut velocity = entity.get::<Velocity>().unwrap().clone();
+            entity.get_mut::<EnemyBody>().unwrap().take_hit(
+                &mut velocity,
+                Vec2::new(0.0, 70.0),
+                0.0,
+                &physics,
+            );
+            assert!(velocity.y > 0.0 && velocity.y < 70.0 * physics.air_knockback_scale_y);
+            entity.insert(velocity);
+        }
+
+        for _ in 0..120 {
+            app.update();
+        }
+        let entity = app.world().entity(enemy);
+        let body = entity.get::<EnemyBody>().unwrap();
+        let y = entity.get::<Transform>().unwrap().translation.y;
+        assert!(!body.airborne, "enemy should land");
+        assert_eq!(body.juggle_hits, 0);
+        assert_eq!(body.rest_y, Some(platform_top + half_height));
+        assert!(
+            (y - (platform_top + half_height)).abs() < 4.0,
+            "enemy should rest on the collider, y {y}"
+        );
+    }
+
+    #[test]
+    fn test_knocked_back_enemy_bounces_off_sky_collider_wall() {
+        let mut app = spawn_physics_test_app();
+        let physics = EnemyPhysicsTuning::default();
+        let anchor_y =
+            GameConfig::GROUND_LEVEL + GameplayTuning::default().enemies.slime.spawn_y_offset;
+        app.world_mut().spawn((
+            Transform::from_xyz(50.0, anchor_y + 60.0, 0.0),
+            crate::systems::collision::CollisionBox::new(Vec2::new(40.0, 200.0)),
+            SkyMergedCollider,
+        ));
+
+        let mut body = EnemyBody::default();
+        let mut velocity = Velocity::default();
+        body.take_hit(&mut velocity, Vec2::new(220.0, 10.0), 0.0, &physics);
+        assert!(!body.airborne, "a plain ground hit should not launch");
+        assert_eq!(velocity.y, 0.0);
+        let mut state = EnemyState::new(5, 170.0);
+        state.apply_hit_stun(0.5);
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                EnemyType::Slime,
+                state,
+                EnemyBrain::default(),
+                body,
+                velocity,
+                Transform::from_xyz(0.0, anchor_y, 0.0),
+                crate::systems::collision::CollisionBox::new(Vec2::new(40.0, 30.0)),
+            ))
+            .id();
+
+        for _ in 0..12 {
+            app.update();
+        }
+        let entity = app.world().entity(enemy);
+        let body = entity.get::<EnemyBody>().unwrap();
+        let transform = entity.get::<Transform>().unwrap();
+        assert_eq!(
+            body.wall_bounces, 1,
+            "enemy should bounce off the wall once"
+        );
+        assert!(
+            entity.get::<Velocity>().unwrap().x < 0.0,
+            "bounce should reverse velocity"
+        );
+        assert!(
+            transform.translation.x <= 10.0,
+            "enemy must not pass through the wall"
+        );
+        assert_eq!(transform.translation.y, anchor_y);
+    }
+
     #[test]
     fn test_player_crouch_syncs_collision_box_size() {
         let mut app = App::new();
@@ -4417,6 +4933,104 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fireball_burns_enemy_and_burn_ticks_through_damage_pipeline() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(bevy::state::app::StatesPlugin)
+            .init_state::<GameState>()
+            .insert_resource(bevy::time::TimeUpdateStrategy::ManualDuration(
+                Duration::from_secs_f32(0.1),
+            ))
+            .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
+            .add_message::<crate::events::CameraImpulseEvent>()
+            .add_message::<crate::events::StatusEffectEvent>()
+            .add_systems(
+                Update,
+                (
+                    crate::systems::combat::projectile_enemy_collision,
+                    crate::systems::status_effects::apply_status_effect_events,
+                    crate::systems::status_effects::tick_status_effects,
+                    crate::systems::combat::apply_damage_events,
+                )
+                    .chain(),
+            );
+
+        app.world_mut().spawn((
+            Projectile,
+            ProjectileType::Fireball,
+            ProjectileData::new(5, 0.0, 10.0),
+            Transform::from_xyz(0.0, 0.0, 0.0),
+            crate::systems::collision::CollisionBox::new(Vec2::new(20.0, 20.0)),
+        ));
+        let enemy = app
+            .world_mut()
+            .spawn((
+                Enemy,
+                EnemyState::new(100, 100.0),
+                Transform::from_xyz(0.0, 0.0, 0.0),
+                Velocity::default(),
+                crate::systems::collision::CollisionBox::new(Vec2::new(20.0, 20.0)),
+            ))
+            .id();
+
+        app.update();
+        app.update();
+        let effects = app
+            .world()
+            .entity(enemy)
+            .get::<StatusEffects>()
+            .expect("fireball should attach status effects");
+        assert!(effects.has(StatusKind::Burn));
+        let health_after_hit = app
+            .world()
+            .entity(enemy)
+            .get::<EnemyState>()
+            .unwrap()
+            .health;
+        assert_eq!(health_after_hit, 95);
+
+        for _ in 0..10 {
+            app.update();
+        }
+        let health = app
+            .world()
+            .entity(enemy)
+            .get::<EnemyState>()
+            .unwrap()
+            .health;
+        assert!(
+            health < health_after_hit,
+            "burn ticks should keep damaging the enemy"
+        );
+    }
+
+    #[test]
+    fn test_player_status_effects_survive_save_round_trip() {
+        let tuning = StatusEffectTuning::default();
+        let mut effects = StatusEffects::default();
+        effects.apply(StatusKind::Poison, 3, &tuning.poison);
+
+        let state = CompleteGameState {
+            player_status_effects: effects.clone(),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(&state).expect("serialize state");
+        let restored: CompleteGameState =
+            serde_json::from_value(json.clone()).expect("deserialize state");
+        assert_eq!(restored.player_status_effects, effects);
+
+        let mut legacy = json;
+        legacy
+            .as_object_mut()
+            .expect("state object")
+            .remove("player_status_effects");
+        let legacy: CompleteGameState =
+            serde_json::from_value(legacy).expect("old saves have no status effects");
+        assert!(legacy.player_status_effects.is_empty());
+    }
+
     #[test]
     fn test_enemy_projectile_hit_player_applies_damage_and_despawns_projectile() {
         let mut app = App::new();
@@ -4424,7 +5038,9 @@ mod tests {
             .add_plugins(bevy::state::app::StatesPlugin)
             .init_state::<GameState>()
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
+            .add_message::<crate::events::StatusEffectEvent>()
             .add_systems(
                 Update,
                 (
@@ -4479,6 +5095,7 @@ mod tests {
             .add_plugins(bevy::state::app::StatesPlugin)
             .init_state::<GameState>()
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
             .add_systems(Update, crate::systems::combat::apply_damage_events);
 
@@ -4517,6 +5134,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_guarding_player_takes_only_chip_damage() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(bevy::state::app::StatesPlugin)
+            .init_state::<GameState>()
+            .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
+            .add_message::<crate::events::CameraImpulseEvent>()
+            .add_systems(Update, crate::systems::combat::apply_damage_events);
+
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Health::new(100.0),
+                DamageInvulnerability::default(),
+                PlayerDefense {
+                    guarding: true,
+                    ..default()
+                },
+            ))
+            .id();
+
+        app.world_mut()
+            .resource_mut::<Messages<crate::events::DamageEvent>>()
+            .write(crate::events::DamageEvent {
+                target: player,
+                amount: 20.0,
+                source: crate::events::DamageSource::EnemyContact,
+            });
+        app.update();
+
+        let chip = crate::resources::GameplayTuning::default()
+            .defense
+            .guard_chip_ratio;
+        let health = app
+            .world()
+            .entity(player)
+            .get::<Health>()
+            .expect("player health");
+        assert!(
+            (health.current - (100.0 - 20.0 * chip)).abs() < 1e-4,
+            "guard should reduce hostile damage to chip damage"
+        );
+    }
+
     #[test]
     fn test_player_enemy_contact_damage_updates_hud_health_text() {
         let mut app = App::new();
@@ -4525,6 +5189,7 @@ mod tests {
             .init_state::<GameState>()
             .init_resource::<GameStats>()
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
             .add_systems(
                 Update,
@@ -4587,6 +5252,7 @@ mod tests {
             .insert_resource(ButtonInput::<KeyCode>::default())
             .init_resource::<GameStats>()
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
             .add_systems(
                 Update,
@@ -4662,6 +5328,7 @@ mod tests {
             .init_state::<GameState>()
             .insert_resource(ButtonInput::<KeyCode>::default())
             .add_message::<crate::events::DamageEvent>()
+            .add_message::<crate::events::EnemyDefeated>()
             .add_message::<crate::events::CameraImpulseEvent>()
             .add_systems(
                 Update,
@@ -4819,6 +5486,17 @@ mod tests {
         );
     }
 
+    fn load_slot_file(
+        path: &std::path::Path,
+    ) -> Result<CompleteGameState, error_handling::SaveSystemError> {
+        futures_lite::future::block_on(server_file_ops::load_game_state_internal(
+            path.to_path_buf(),
+            false,
+            None,
+        ))
+        .map(|(state, _)| state)
+    }
+
     #[test]
     fn test_load_game_rejects_state_only_schema() {
         let temp_path = std::env::temp_dir().join(format!(
@@ -4837,23 +5515,13 @@ mod tests {
             serde_json::to_string_pretty(&state_only_schema).expect("serialize state-only schema");
         fs::write(&temp_path, state_only_json.as_bytes()).expect("write state-only schema");
 
-        let mut app = App::new();
-        app.add_plugins(MinimalPlugins)
-            .init_resource::<CharacterSelection>()
-            .init_resource::<SaveManager>()
-            .add_systems(Update, save::load_game);
-
-        app.world_mut().resource_mut::<SaveManager>().save_file_path =
-            temp_path.to_string_lossy().to_string();
-        app.update();
-
-        let save_manager = app.world().resource::<SaveManager>();
         assert!(
-            save_manager.current_save.is_none(),
+            matches!(
+                load_slot_file(&temp_path),
+                Err(error_handling::SaveSystemError::DeserializationFailed(_))
+            ),
             "state-only schema must be rejected"
         );
-        let loaded_selection = app.world().resource::<CharacterSelection>();
-        assert_eq!(loaded_selection.selected_character, CharacterType::Shirou);
 
         let _ = fs::remove_file(temp_path);
     }
@@ -4878,23 +5546,13 @@ mod tests {
         let json = serde_json::to_string_pretty(&save_data).expect("serialize save");
         fs::write(&temp_path, json).expect("write save");
 
-        let mut app = App::new();
-        app.add_plugins(MinimalPlugins)
-            .init_resource::<CharacterSelection>()
-            .init_resource::<SaveManager>()
-            .add_systems(Update, save::load_game);
-
-        app.world_mut().resource_mut::<SaveManager>().save_file_path =
-            temp_path.to_string_lossy().to_string();
-        app.update();
-
-        let save_manager = app.world().resource::<SaveManager>();
         assert!(
-            save_manager.current_save.is_none(),
+            matches!(
+                load_slot_file(&temp_path),
+                Err(error_handling::SaveSystemError::ChecksumMismatch)
+            ),
             "checksum mismatch must be rejected"
         );
-        let loaded_selection = app.world().resource::<CharacterSelection>();
-        assert_eq!(loaded_selection.selected_character, CharacterType::Shirou);
 
         let _ = fs::remove_file(temp_path);
     }